@@ -1,5 +1,5 @@
 var goldenLayout;
-let configVersion = 6;
+let configVersion = 7;
 
 function make_config() {
   let editor_width_pct = 100.0 - 61.8;
@@ -83,6 +83,13 @@ function make_config() {
                 isClosable: false,
                 id: "compiler_output",
               },
+              {
+                type: "component",
+                componentName: "Transcript",
+                componentState: {},
+                isClosable: false,
+                id: "transcript",
+              },
               {
                 type: "component",
                 componentName: "Leaderboard",
@@ -153,6 +160,13 @@ export function init() {
       container.getElement()[0].id = "compiler-output-window";
     }
   );
+  goldenLayout.registerComponent(
+    "Transcript",
+    function (container, componentState) {
+      container.getElement()[0].style.overflow = "auto";
+      container.getElement()[0].id = "transcript-window";
+    }
+  );
   goldenLayout.registerComponent(
     "Leaderboard",
     function (container, componentState) {
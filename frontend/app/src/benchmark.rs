@@ -69,6 +69,7 @@ impl Component for Benchmark {
             Msg::ReceivedSimAgentResponse(oort_simulation_worker::Response::Snapshot {
                 snapshot,
             }) => {
+                let snapshot = snapshot.into_latest();
                 if snapshot.status == oort_simulator::scenario::Status::Running {
                     self.time = snapshot.time;
                     if snapshot.timing.total() > oort_simulator::simulation::PHYSICS_TICK_LENGTH {
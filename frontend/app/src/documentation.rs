@@ -29,6 +29,7 @@ pub fn documentation(props: &DocumentationProps) -> Html {
                 <li>{ "F: Fast-forward." }</li>
                 <li>{ "M: Slow motion." }</li>
                 <li>{ "G: Show debug lines for all ships." }</li>
+                <li>{ "P: Step back one tick (while showing debug lines)." }</li>
                 <li>{ "V: Toggle NLIPS, which makes smaller ships more visible when zoomed out." }</li>
                 <li>{ "B: Toggle postprocessing (blur)." }</li>
                 <li>{ "Mouse wheel: Zoom." }</li>
@@ -67,24 +68,58 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               <li><code>{ "class() → Class" }</code>{ ": Returns the ship class." }</li>
               <li><code>{ "position() → Vec2" }</code>{ ": Get the current position in meters." }</li>
               <li><code>{ "velocity() → Vec2" }</code>{ ": Get the current velocity in m/s." }</li>
+              <li><code>{ "velocity_field() → Vec2" }</code>{ ": Get the background velocity field at the current position in m/s." }</li>
+              <li><code>{ "drag() → f64" }</code>{ ": Get the scenario's linear drag in 1/s. Zero in empty space; in a nebula, velocity decays by a factor of exp(-drag * t)." }</li>
               <li><code>{ "heading() → f64" }</code>{ ": Get the current heading in radians." }</li>
               <li><code>{ "angular_velocity() → f64" }</code>{ ": Get the current angular velocity in radians/s." }</li>
+              <li><code>{ "imu_acceleration() → Vec2" }</code>{ ": Get the acceleration measured over the last tick in m/s², after clamping and collisions." }</li>
+              <li><code>{ "imu_angular_acceleration() → f64" }</code>{ ": Get the angular acceleration measured over the last tick in radians/s²." }</li>
               <li><code>{ "health() → f64" }</code>{ ": Current health." }</li>
               <li><code>{ "fuel() → f64" }</code>{ ": Current fuel (delta-v)." }</li>
               <li><code>{ "accelerate(acceleration: Vec2)" }</code>{ ": Accelerate the ship. Units are m/s²." }</li>
               <li><code>{ "turn(speed: f64)" }</code>{ ": Rotate the ship. Unit is radians/s." }</li>
               <li><code>{ "torque(acceleration: f64)" }</code>{ ": Angular acceleration. Unit is radians/s²." }</li>
+              <li><code>{ "mass() -> f64" }</code>{ ": Mass in kg, from the area of the ship's hull and its class density." }</li>
               <li><code>{ "max_forward_acceleration() -> f64" }</code>{ ": Maximum forward acceleration." }</li>
               <li><code>{ "max_backward_acceleration() -> f64" }</code>{ ": Maximum backward acceleration." }</li>
               <li><code>{ "max_lateral_acceleration() -> f64" }</code>{ ": Maximum lateral acceleration." }</li>
               <li><code>{ "max_angular_acceleration() -> f64" }</code>{ ": Maximum angular acceleration." }</li>
+              <li><code>{ "burn_time_remaining() -> f64" }</code>{ ": Seconds until a missile's motor burns out. Infinite for ships without a burn profile." }</li>
+              <li><code>{ "moment_of_inertia() -> f64" }</code>{ ": Moment of inertia in kg·m²." }</li>
+              <li><code>{ "max_turn_rate_at_speed(speed: f64) -> f64" }</code>{ ": Fastest the ship can turn its velocity vector at a given speed, limited by lateral acceleration. Unit is radians/s." }</li>
+              <li><code>{ "min_turn_radius_at_speed(speed: f64) -> f64" }</code>{ ": Radius of the tightest turn possible at a given speed. Unit is meters." }</li>
+              <li><code>{ "instruction_budget() -> u64" }</code>{ ": Instructions the ship's AI may execute each tick. Missiles and torpedoes get much less than other ships." }</li>
+              <li><code>{ "set_collision_assist(enabled: bool)" }</code>{ ": Let the simulator override your acceleration at the last moment to avoid hitting friendly ships and asteroids." }</li>
+              <li><code>{ "collision_assist() -> bool" }</code>{ ": Get whether collision assist is enabled." }</li>
+              <li><code>{ "set_rotation_stabilizer(enabled: bool)" }</code>{ ": Let the simulator stop your ship spinning on ticks you don't command a torque." }</li>
+              <li><code>{ "rotation_stabilizer() -> bool" }</code>{ ": Get whether the rotation stabilizer is enabled." }</li>
+              <li><code>{ "set_flak_mode(enabled: bool)" }</code>{ ": Let the simulator aim and fire your flak guns at incoming missiles and torpedoes. Only the cruiser's main gun is a flak gun." }</li>
+              <li><code>{ "flak_mode() -> bool" }</code>{ ": Get whether flak mode is enabled." }</li>
+              <li><code>{ "set_home_on_jam(enabled: bool)" }</code>{ ": Let the simulator fly your torpedo straight at a jammer that hides every contact from its radar." }</li>
+              <li><code>{ "home_on_jam() -> bool" }</code>{ ": Get whether home-on-jam is enabled." }</li>
+              <li><code>{ "orbit(contact: &ScanResult, radius: f64, direction: OrbitDirection)" }</code>{ ": Let the simulator fly a circle around a radar contact. You keep control of turning and aiming." }</li>
+              <li><code>{ "stop_orbit()" }</code>{ ": Disable the orbit autopilot." }</li>
             </ul>
 
             <h2>{ "Weapons" }</h2>
             <ul>
               <li><code>{ "fire(index: usize)" }</code>{ ": Fire a weapon (gun or missile launcher)." }</li>
+              <li><code>{ "launch_at(index: usize, contact: &ScanResult)" }</code>{ ": Launch a missile or torpedo with its radar pointed at the contact. Its first scan returns the contact if the radar finds nothing." }</li>
               <li><code>{ "aim(index: usize, angle: f64)" }</code>{ ": Aim a weapon (for weapons on a turret)." }</li>
+              <li><code>{ "fire_spread(index: usize, headings: &[f64])" }</code>{ ": Fire a burst gun like the cruiser's main gun with one shot along each heading instead of a random spread." }</li>
+              <li><code>{ "fire_at(index: usize, point: Vec2)" }</code>{ ": Fire a turreted gun so the bullet passes through a point, aimed using the muzzle's exact state when it fires." }</li>
+              <li><code>{ "aim_all_at(point: Vec2)" }</code>{ ": Aim every turreted weapon at a point, accounting for where each is mounted." }</li>
+              <li><code>{ "aim_all(angle: f64)" }</code>{ ": Aim every turreted weapon along a heading, converging at the distance set by set_gun_convergence." }</li>
+              <li><code>{ "set_gun_convergence(distance: f64)" }</code>{ ": Set the convergence distance used by aim_all. Zero means parallel." }</li>
+              <li><code>{ "reload_ticks(index: usize) -> u32" }</code>{ ": Ticks until weapons 0-3 are ready to fire." }</li>
+              <li><code>{ "weapon_ready(index: usize) -> bool" }</code>{ ": Whether a weapon is ready to fire." }</li>
+              <li><code>{ "ammo(index: usize) -> f64" }</code>{ ": Rounds left for a weapon, or infinity if unlimited. The frigate's main gun carries a limited supply." }</li>
+              <li><code>{ "weapon_reloaded(index: usize) -> bool" }</code>{ ": Whether a missile launcher finished reloading since the previous tick." }</li>
+              <li><code>{ "gun_heat(index: usize) -> f64" }</code>{ ": Heat of a gun from 0 to 1. Hot guns are less accurate and overheat at 1." }</li>
+              <li><code>{ "gun_arc(index: usize) -> (f64, f64)" }</code>{ ": Firing arc of a gun relative to the ship's heading. Turrets can't aim outside it." }</li>
               <li><code>{ "explode()" }</code>{ ": Self-destruct." }</li>
+              <li><code>{ "disarm()" }</code>{ ": Make this ship's warhead safe so it won't release fragments when destroyed." }</li>
+              <li><code>{ "set_launch_ttl(seconds: f64)" }</code>{ ": Set the lifetime of missiles and torpedoes launched from now on, between one second and the class default." }</li>
             </ul>
 
             <h2>{ "Radar" }</h2>
@@ -94,7 +129,12 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               <li><code>{ "set_radar_width(width: f64)" }</code>{ ": Adjust the width of the radar beam (in radians)." }</li>
               <li><code>{ "radar_width() -> f64" }</code>{ ": Get current radar width." }</li>
               <li><code>{ "scan() → Option<ScanResult>" }</code>{ ": Find an enemy ship illuminated by the radar." }</li>
-              <li><code>{ "struct ScanResult { position: Vec2, velocity: Vec2 }" }</code></li>
+              <li><code>{ "struct ScanResult { position: Vec2, velocity: Vec2, age: f64 }" }</code></li>
+              <li><code>{ "set_radar_memory(seconds: f64)" }</code>{ ": Keep reporting a lost contact for up to this long, extrapolated from where it was last seen. Its age tells how stale it is." }</li>
+              <li><code>{ "radar_memory() -> f64" }</code>{ ": Get how long lost contacts are remembered." }</li>
+              <li><code>{ "ScanResult::predict(dt: f64) → Vec2" }</code>{ ": Where the contact will be after dt seconds at its current velocity." }</li>
+              <li><code>{ "ScanResult::capabilities() → Option<Capabilities>" }</code>{ ": The contact's class stats and weapons, including max_gun_range(). Also available for any class with constants::capabilities." }</li>
+              <li><code>{ "Track::update(contact: &ScanResult), Track::predict(dt: f64) → Option<Vec2>" }</code>{ ": Follow a contact across ticks to also account for its acceleration." }</li>
             </ul>
 
             <h2>{ "Advanced Radar" }</h2>
@@ -103,9 +143,23 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               <li><code>{ "radar_min_distance() -> f64" }</code>{ ": Get current minimum distance filter." }</li>
               <li><code>{ "set_radar_max_distance(dist: f64)" }</code>{ ": Set the maximum distance filter." }</li>
               <li><code>{ "radar_max_distance() -> f64" }</code>{ ": Get current maximum distance filter." }</li>
+              <li><code>{ "radar_max_range() -> f64" }</code>{ ": Get the maximum range of the radar's instruments, which depends on the ship class." }</li>
+              <li><code>{ "seeker_doppler_notch() → bool" }</code>{ ": Returns true if missile and torpedo seekers in this scenario lose targets that aren't closing with them. See constants::DOPPLER_NOTCH_GAIN and ClassStats::doppler_notch." }</li>
+              <li><code>{ "set_radar_min_closing_speed(speed: f64)" }</code>{ ": Ignore contacts approaching slower than this. Receding contacts have a negative closing speed." }</li>
+              <li><code>{ "radar_min_closing_speed() -> f64" }</code>{ ": Get current minimum closing speed filter." }</li>
+              <li><code>{ "set_radar_max_closing_speed(speed: f64)" }</code>{ ": Ignore contacts approaching faster than this." }</li>
+              <li><code>{ "radar_max_closing_speed() -> f64" }</code>{ ": Get current maximum closing speed filter." }</li>
               <li><code>{ "set_radar_ecm_mode(mode: EcmMode)" }</code>{ ": Set the Electronic Counter Measures (ECM) mode." }</li>
               <li><code>{ "EcmMode::None" }</code>{ ": No ECM, radar will operate normally." }</li>
               <li><code>{ "EcmMode::Noise" }</code>{ ": Decrease the enemy radar's signal to noise ratio, making it more difficult to detect targets and reducing accuracy of returned contacts." }</li>
+              <li><code>{ "set_radar_power(fraction: f64)" }</code>{ ": Turn the radar down to between 0.01 and 1 of full power. Detection range falls slower than the range at which enemies notice the radar." }</li>
+              <li><code>{ "radar_power() -> f64" }</code>{ ": Get the current radar power fraction." }</li>
+              <li><code>{ "radar_warning() → Option<RadarWarning>" }</code>{ ": Get the bearing and signal strength of the strongest enemy radar illuminating this ship." }</li>
+              <li><code>{ "jam_bearing() → Option<f64>" }</code>{ ": Get the bearing to a jammer that hid every contact from the radar last tick. Torpedoes only." }</li>
+              <li><code>{ "set_radar_passive(enabled: bool)" }</code>{ ": Stop emitting and listen for enemy radars pointed at this ship. Passive radars can't be detected but don't measure range." }</li>
+              <li><code>{ "passive_contact() → Option<PassiveContact>" }</code>{ ": Get the bearing and signal strength of the strongest enemy radar heard within the beam while passive." }</li>
+              <li><code>{ "set_transponder(enabled: bool)" }</code>{ ": Enable or disable the IFF transponder. Friendly radars will see ships with their transponder disabled." }</li>
+              <li><code>{ "transponder() -> bool" }</code>{ ": Get whether the IFF transponder is enabled." }</li>
             </ul>
 
             <h2>{ "Radio" }</h2>
@@ -133,6 +187,12 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               </li>
             </ul>
 
+            <h2>{ "Reinforcements" }</h2>
+            <ul>
+              <li><code>{ "team_resources() -> f64" }</code>{ ": Get the resources available to your team." }</li>
+              <li><code>{ "request_reinforcement(class: Class)" }</code>{ ": Spawn a ship at a scenario-defined spawn point if your team can afford it. Fighters cost 100, frigates 400, and cruisers 1000." }</li>
+            </ul>
+
             <h2>{ "Scalar Math" }</h2>
             <ul>
               <li><code>{ "PI, TAU" }</code>{ ": Constants."}</li>
@@ -163,9 +223,20 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               <li><code>{ "v1.distance(v2: Vec2) → f64" }</code>{ ": Distance between two points." }</li>
             </ul>
 
+            <h2>{ "Geometry" }</h2>
+            <ul>
+              <li><code>{ "in_arc(origin: Vec2, heading: f64, width: f64, point: Vec2) → bool" }</code>{ ": Returns true if the point is within the arc seen from origin." }</li>
+              <li><code>{ "arcs_overlap(heading0: f64, width0: f64, heading1: f64, width1: f64) → bool" }</code>{ ": Returns true if two arcs share any direction." }</li>
+              <li><code>{ "ray_circle_intersection(origin: Vec2, direction: Vec2, center: Vec2, radius: f64) → Option<f64>" }</code>{ ": Returns the distance along a ray to a circle." }</li>
+              <li><code>{ "segment_intersects_circle(start: Vec2, end: Vec2, center: Vec2, radius: f64) → bool" }</code>{ ": Returns true if a line segment touches a circle." }</li>
+              <li><code>{ "tangent_points(point: Vec2, center: Vec2, radius: f64) → Option<(Vec2, Vec2)>" }</code>{ ": Returns where lines from a point touch a circle." }</li>
+              <li><code>{ "Sector::new(center: Vec2, heading: f64, width: f64, radius: f64)" }</code>{ ": A pie slice, such as a firing arc or radar beam. Has " }<code>{ "contains(point)" }</code>{ " and " }<code>{ "overlaps(&other)" }</code>{ " methods." }</li>
+            </ul>
+
             <h2>{ "Debugging" }</h2>
             <ul>
               <li><code>{ "debug!(...)" }</code>{ ": Add text to be displayed when the ship is selected by clicking on it. Works just like " }<code>{ "println!" }</code>{ "." }</li>
+              <li><code>{ "team_log!(...)" }</code>{ ": Add a line to the team's battle transcript, shown in the Transcript tab. Works just like " }<code>{ "println!" }</code>{ ". Limited to about 5 lines per second per team." }</li>
               <li><code>{ "draw_line(v0: Vec2, v1: Vec2, color: u32)" }</code>{ ": Draw a line visible when the ship is selected. Color is 24-bit RGB." }</li>
               <li><code>{ "draw_triangle(center: Vec2, radius: f64, color: u32)" }</code>{ ": Draw a triangle visible when the ship is selected." }</li>
               <li><code>{ "draw_square(center: Vec2, radius: f64, color: u32)" }</code>{ ": Draw a square visible when the ship is selected." }</li>
@@ -179,12 +250,43 @@ pub fn documentation(props: &DocumentationProps) -> Html {
               <li><code>{ "current_tick() → u32" }</code>{ ": Returns the number of ticks elapsed since the simulation started." }</li>
               <li><code>{ "current_time() → f64" }</code>{ ": Returns the number of seconds elapsed since the simulation started." }</li>
               <li><code>{ "angle_diff(a: f64, b: f64) → f64" }</code>{ ": Returns the shortest (possibly negative) distance between two angles." }</li>
+              <li><code>{ "wrap_angle(a: f64) → f64" }</code>{ ": Returns the equivalent angle between -π and π." }</li>
+              <li><code>{ "normalize_angle(a: f64) → f64" }</code>{ ": Returns the equivalent angle between 0 and 2π." }</li>
+              <li><code>{ "lerp_angle(a: f64, b: f64, t: f64) → f64" }</code>{ ": Interpolates between two angles the short way around." }</li>
+              <li><code>{ "bearing(from: Vec2, to: Vec2) → f64" }</code>{ ": Returns the direction from one point to another." }</li>
+              <li><code>{ "relative_bearing(from: Vec2, heading: f64, to: Vec2) → f64" }</code>{ ": Returns how far to turn from a heading to face a point." }</li>
+              <li><code>{ "from_polar(radius: f64, angle: f64) → Vec2" }</code>{ ": Returns the vector with the given length and angle." }</li>
+              <li><code>{ "to_polar(v: Vec2) → (f64, f64)" }</code>{ ": Returns the length and angle of a vector." }</li>
+              <li><code>{ "pro_nav(contact: &ScanResult, n: f64) → Vec2" }</code>{ ": Returns the lateral acceleration for proportional navigation towards a radar contact." }</li>
               <li><code>{ "rand(low: f64, high: f64) → f64" }</code>{ ": Get a random number." }</li>
+              <li><code>{ "rand_int(low: i64, high: i64) → i64" }</code>{ ": Get a random integer in low..high." }</li>
+              <li><code>{ "rand_bool(probability: f64) → bool" }</code>{ ": Returns true with the given probability." }</li>
               <li><code>{ "target() → Vec2" }</code>{ ": Used in some scenarios, returns the position of the target." }</li>
               <li><code>{ "target_velocity() → Vec2" }</code>{ ": Used in some scenarios, returns the velocity of the target." }</li>
               <li><code>{ "seed() → u128" }</code>{ ": Returns a seed useful for initializing a random number generator." }</li>
+              <li><code>{ "world_bounds() → (Vec2, Vec2)" }</code>{ ": Returns the minimum and maximum corners of the world. Ships touching the boundary are destroyed." }</li>
+              <li><code>{ "asteroids_destructible() → bool" }</code>{ ": Returns true if bullets damage asteroids in this scenario." }</li>
+              <li><code>{ "asteroids_split() → bool" }</code>{ ": Returns true if destroyed asteroids break into smaller ones." }</li>
+              <li><code>{ "asteroids_block_bullets() → bool" }</code>{ ": Returns true if asteroids stop bullets. Otherwise bullets pass through them." }</li>
+              <li><code>{ "distance_to_boundary(heading: f64) → f64" }</code>{ ": Returns the distance from your ship to the world boundary along a heading." }</li>
             </ul>
 
+            <h2>{ "Behavior Trees" }</h2>
+            <p>{ "The " }<code>{ "oort_api::behavior" }</code>{ " module contains a small behavior tree runtime. Build trees from " }<code>{ "condition" }</code>{ ", " }<code>{ "action" }</code>{ ", " }<code>{ "sequence" }</code>{ ", " }<code>{ "fallback" }</code>{ ", and " }<code>{ "invert" }</code>{ " nodes and tick them once per game tick." }</p>
+
+            <h2>{ "Events" }</h2>
+            <p>{ "Implement " }<code>{ "oort_api::events::Events" }</code>{ " for your Ship to have these callbacks called before tick:" }</p>
+            <ul>
+                <li><code>{ "on_radar_contact(&mut self, contact: &ScanResult)" }</code>{ ": The radar has a contact." }</li>
+                <li><code>{ "on_damage(&mut self, amount: f64)" }</code>{ ": The ship took damage since the previous tick." }</li>
+                <li><code>{ "on_message(&mut self, radio: usize, message: Message)" }</code>{ ": A radio received a message." }</li>
+                <li><code>{ "on_launch(&mut self, index: usize)" }</code>{ ": A weapon launched a missile or torpedo." }</li>
+                <li><code>{ "on_reload(&mut self, index: usize)" }</code>{ ": A missile launcher finished reloading." }</li>
+            </ul>
+
+            <h2>{ "Team Setup" }</h2>
+            <p>{ "Implement " }<code>{ "oort_api::team::ScenarioStart" }</code>{ " for your Ship to have " }<code>{ "on_scenario_start(metadata: &ScenarioMetadata) -> BTreeMap<u32, Message>" }</code>{ " called once before the first tick. It receives the ID, class, position and heading of each of the team's ships and returns orders keyed by ship ID. Each ship reads its orders with " }<code>{ "orders() -> Option<Message>" }</code>{ "." }</p>
+
             <h2>{ "Extra Crates" }</h2>
             <p>{ "The following crates are available for use in your code:" }</p>
             <ul>
@@ -196,10 +298,13 @@ pub fn documentation(props: &DocumentationProps) -> Html {
             <h2>{ "Ship Classes" }</h2>
             <ul>
               <li>{ "Fighter: Small, fast, and lightly armored. One forward-facing gun and one missile launcher. "}</li>
+              <li>{ "Corvette: Between a fighter and a frigate. One forward-facing gun and two angled missile launchers. "}</li>
               <li>{ "Frigate: Medium size with heavy armor. One forward-facing high-velocity gun, two turreted guns, and one missile launcher. "}</li>
               <li>{ "Cruiser: Large, slow, and heavily armored. One turreted flak gun, two missile launchers, and one torpedo launcher. "}</li>
               <li>{ "Missile: Highly maneuverable but unarmored. Explodes on contact or after an " }<code>{ "explode()" }</code>{ " call." }</li>
               <li>{ "Torpedo: Better armor, larger warhead, but less maneuverable than a missile. Explodes on contact or after an " }<code>{ "explode()" }</code>{ " call." }</li>
+              <li>{ "Station: A large immobile structure placed by some scenarios. "}</li>
+              <li>{ "Turret: An immobile gun platform with one turreted gun, placed by some scenarios. "}</li>
             </ul>
         </div>
     };
@@ -423,6 +423,8 @@ impl Component for EditorWindow {
 
                 add_action("oort-replay-paused", "Replay paused", None);
 
+                add_action("oort-quick-test", "Quick test against reference AI", None);
+
                 add_action("oort-restore-initial-code", "Restore initial code", None);
 
                 add_action("oort-load-solution", "Load solution", None);
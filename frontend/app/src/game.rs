@@ -48,6 +48,7 @@ pub enum Msg {
     RegisterSimulationWindowLink(Scope<SimulationWindow>),
     Start,
     SimulationFinished(Snapshot),
+    QuickTestFinished(Snapshot),
     ReceivedBackgroundSimAgentResponse(oort_simulation_worker::Response, u32),
     EditorAction { team: usize, action: String },
     ShowFeedback,
@@ -78,6 +79,9 @@ pub enum ExecutionMode {
     Initial,
     Run,
     Replay { paused: bool },
+    // Runs the player's code against the reference AI in the background,
+    // leaving the simulation on screen alone.
+    QuickTest,
 }
 
 pub struct Game {
@@ -196,6 +200,11 @@ impl Component for Game {
                 true
             }
             Msg::SimulationFinished(snapshot) => self.on_simulation_finished(context, snapshot),
+            Msg::QuickTestFinished(snapshot) => {
+                self.compiler_errors = Some(quick_test_summary(&snapshot));
+                js::golden_layout::select_tab("compiler_output");
+                true
+            }
             Msg::EditorAction {
                 team: _,
                 ref action,
@@ -229,6 +238,16 @@ impl Component for Game {
                 self.start_compile(context, ExecutionMode::Replay { paused: true });
                 true
             }
+            Msg::EditorAction {
+                team: _,
+                ref action,
+            } if action == "oort-quick-test" => {
+                self.save_current_code(context, &context.props().scenario, None);
+                let team = self.team_mut(0);
+                team.running_source_code = team.get_editor_code();
+                self.start_compile(context, ExecutionMode::QuickTest);
+                true
+            }
             Msg::EditorAction { team, ref action } if action == "oort-restore-initial-code" => {
                 let mut code = scenario::load(&context.props().scenario)
                     .initial_code()
@@ -284,6 +303,7 @@ impl Component for Game {
                 oort_simulation_worker::Response::Snapshot { snapshot },
                 seed,
             ) => {
+                let snapshot = snapshot.into_latest();
                 if snapshot.nonce == self.background_nonce {
                     if snapshot.status == Status::Running
                         && snapshot.time < (MAX_TICKS as f64 * PHYSICS_TICK_LENGTH)
@@ -374,7 +394,9 @@ impl Component for Game {
                     .filter_map(|x| x.as_ref().err())
                     .cloned()
                     .collect();
-                if errors.is_empty() {
+                if errors.is_empty() && execution_mode == ExecutionMode::QuickTest {
+                    self.start_quick_test(context);
+                } else if errors.is_empty() {
                     services::send_telemetry(Telemetry::StartScenario {
                         scenario_name: context.props().scenario.clone(),
                         code: code_to_string(&self.player_team().running_source_code),
@@ -504,6 +526,7 @@ impl Component for Game {
             .get_element_by_id("simulation-window")
             .expect("a #simulation-window element");
         let on_simulation_finished = context.link().callback(Msg::SimulationFinished);
+        let on_quick_test_finished = context.link().callback(Msg::QuickTestFinished);
         let register_link = context.link().callback(Msg::RegisterSimulationWindowLink);
         let version = context.props().version.clone();
 
@@ -524,6 +547,11 @@ impl Component for Game {
             .get_element_by_id("documentation-window")
             .expect("a #documentation-window element");
 
+        // For the transcript rendered by SimulationWindow.
+        let transcript_window_host = gloo_utils::document()
+            .get_element_by_id("transcript-window")
+            .expect("a #transcript-window element");
+
         // For CompilerOutput.
         let compiler_output_window_host = gloo_utils::document()
             .get_element_by_id("compiler-output-window")
@@ -587,7 +615,7 @@ impl Component for Game {
             <Welcome host={welcome_window_host} show_feedback_cb={show_feedback_cb.clone()} select_scenario_cb={select_scenario_cb2} />
             <EditorWindow host={editor_window0_host} editor_link={editor0_link} on_editor_action={on_editor0_action} team=0 />
             <EditorWindow host={editor_window1_host} editor_link={editor1_link} on_editor_action={on_editor1_action} team=1 />
-            <SimulationWindow host={simulation_window_host} {on_simulation_finished} {register_link} {version} canvas_ref={self.simulation_canvas_ref.clone()} />
+            <SimulationWindow host={simulation_window_host} transcript_host={transcript_window_host} {on_simulation_finished} {on_quick_test_finished} {register_link} {version} canvas_ref={self.simulation_canvas_ref.clone()} />
             <Documentation host={documentation_window_host} {show_feedback_cb} />
             <CompilerOutputWindow host={compiler_output_window_host} {compiler_errors} />
             <LeaderboardWindow host={leaderboard_window_host} scenario_name={context.props().scenario.clone()} {play_cb} />
@@ -639,7 +667,8 @@ impl Game {
         let status = snapshot.status;
 
         if !snapshot.errors.is_empty() {
-            self.compiler_errors = Some(format!("Simulation errors: {:?}", snapshot.errors));
+            let errors: Vec<String> = snapshot.errors.iter().map(|e| e.to_string()).collect();
+            self.compiler_errors = Some(format!("Simulation errors:\n{}", errors.join("\n")));
             return true;
         }
 
@@ -1083,7 +1112,7 @@ impl Game {
             .collect();
         let rand_seed = rand::thread_rng().gen();
         let seed = match execution_mode {
-            ExecutionMode::Initial | ExecutionMode::Run => {
+            ExecutionMode::Initial | ExecutionMode::Run | ExecutionMode::QuickTest => {
                 self.configured_seed(context).unwrap_or(rand_seed)
             }
             ExecutionMode::Replay { .. } => self
@@ -1109,6 +1138,25 @@ impl Game {
         self.background_nonce = 0;
     }
 
+    fn start_quick_test(&mut self, context: &Context<Self>) {
+        let seed = self
+            .configured_seed(context)
+            .unwrap_or(self.previous_seed.unwrap_or(0));
+        let codes = vec![
+            self.player_team().running_compiled_code.clone(),
+            Code::Builtin("reference".to_string()),
+        ];
+        if let Some(link) = self.simulation_window_link.as_ref() {
+            link.send_message(crate::simulation_window::Msg::StartQuickTest {
+                scenario_name: context.props().scenario.clone(),
+                seed,
+                codes,
+            });
+        } else {
+            log::error!("Missing SimulationWindow");
+        }
+    }
+
     pub fn change_scenario(&mut self, context: &Context<Self>, scenario_name: &str, run: bool) {
         let codes = crate::codestorage::load(&context.props().scenario);
         let scenario = oort_simulator::scenario::load(&context.props().scenario);
@@ -1324,6 +1372,21 @@ impl Team {
     }
 }
 
+fn quick_test_summary(snapshot: &Snapshot) -> String {
+    let result = if !snapshot.errors.is_empty() {
+        let errors: Vec<String> = snapshot.errors.iter().map(|e| e.to_string()).collect();
+        format!("errors:\n{}", errors.join("\n"))
+    } else {
+        match snapshot.status {
+            Status::Victory { team: 0 } => format!("won in {:.3} seconds", snapshot.score_time),
+            Status::Victory { .. } | Status::Failed => "lost".to_string(),
+            Status::Draw => "draw".to_string(),
+            Status::Running => "ran out of time".to_string(),
+        }
+    };
+    format!("Quick test against the reference AI: {result}")
+}
+
 pub fn code_to_string(code: &Code) -> String {
     match code {
         Code::None => "".to_string(),
@@ -1,6 +1,8 @@
 use crate::ui::UI;
 use gloo_render::{request_animation_frame, AnimationFrame};
 use oort_simulation_worker::SimAgent;
+use oort_simulator::scenario::MAX_TICKS;
+use oort_simulator::simulation::PHYSICS_TICK_LENGTH;
 use oort_simulator::{scenario, simulation::Code, snapshot::Snapshot};
 use rand::Rng;
 use std::rc::Rc;
@@ -21,14 +23,24 @@ pub enum Msg {
     WheelEvent(web_sys::WheelEvent),
     PointerEvent(web_sys::PointerEvent),
     BlurEvent(web_sys::FocusEvent),
+    // Runs a second simulation in the worker while the main one is paused.
+    StartQuickTest {
+        scenario_name: String,
+        seed: u32,
+        codes: Vec<Code>,
+    },
     RequestSnapshot,
+    SetCheckpoints(bool),
+    Rewind(u32),
     ReceivedSimAgentResponse(oort_simulation_worker::Response),
 }
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct SimulationWindowProps {
     pub host: web_sys::Element,
+    pub transcript_host: web_sys::Element,
     pub on_simulation_finished: Callback<Snapshot>,
+    pub on_quick_test_finished: Callback<Snapshot>,
     pub register_link: Callback<Scope<SimulationWindow>>,
     pub version: String,
     pub canvas_ref: NodeRef,
@@ -39,10 +51,15 @@ pub struct SimulationWindow {
     render_handle: Option<AnimationFrame>,
     nonce: u32,
     sim_agent: Box<dyn Bridge<SimAgent>>,
+    quick_test_nonce: Option<u32>,
+    // Set when a quick test snapshot arrived while the main simulation was
+    // running, so the next one is requested once it pauses.
+    quick_test_waiting: bool,
     last_status: scenario::Status,
     canvas_ref: NodeRef,
     status_ref: NodeRef,
     picked_ref: NodeRef,
+    transcript_ref: NodeRef,
 }
 
 impl Component for SimulationWindow {
@@ -67,10 +84,13 @@ impl Component for SimulationWindow {
             render_handle,
             nonce: 0,
             sim_agent,
+            quick_test_nonce: None,
+            quick_test_waiting: false,
             last_status: scenario::Status::Running,
             canvas_ref: context.props().canvas_ref.clone(),
             status_ref: NodeRef::default(),
             picked_ref: NodeRef::default(),
+            transcript_ref: NodeRef::default(),
         }
     }
 
@@ -85,12 +105,15 @@ impl Component for SimulationWindow {
                 self.nonce = rand::thread_rng().gen();
                 self.ui = Some(Box::new(UI::new(
                     context.link().callback(|_| Msg::RequestSnapshot),
+                    context.link().callback(Msg::SetCheckpoints),
+                    context.link().callback(Msg::Rewind),
                     seed,
                     self.nonce,
                     context.props().version.clone(),
                     self.canvas_ref.clone(),
                     self.status_ref.clone(),
                     self.picked_ref.clone(),
+                    self.transcript_ref.clone(),
                     start_paused,
                 )));
                 self.sim_agent
@@ -108,6 +131,27 @@ impl Component for SimulationWindow {
                 }
                 self.check_status(context)
             }
+            Msg::StartQuickTest {
+                scenario_name,
+                seed,
+                codes,
+            } => {
+                if let Some(nonce) = self.quick_test_nonce.take() {
+                    self.sim_agent
+                        .send(oort_simulation_worker::Request::CloseWorkspace { nonce });
+                }
+                let nonce = rand::thread_rng().gen();
+                self.quick_test_nonce = Some(nonce);
+                self.quick_test_waiting = false;
+                self.sim_agent
+                    .send(oort_simulation_worker::Request::StartWorkspace {
+                        scenario_name,
+                        seed,
+                        codes,
+                        nonce,
+                    });
+                false
+            }
             Msg::RequestSnapshot => {
                 self.sim_agent
                     .send(oort_simulation_worker::Request::Snapshot {
@@ -116,6 +160,23 @@ impl Component for SimulationWindow {
                     });
                 false
             }
+            Msg::SetCheckpoints(enabled) => {
+                self.sim_agent
+                    .send(oort_simulation_worker::Request::SetCheckpoints { enabled });
+                false
+            }
+            Msg::Rewind(tick) => {
+                self.nonce = rand::thread_rng().gen();
+                if let Some(ui) = self.ui.as_mut() {
+                    ui.on_rewind(tick, self.nonce);
+                }
+                self.sim_agent
+                    .send(oort_simulation_worker::Request::Rewind {
+                        tick,
+                        nonce: self.nonce,
+                    });
+                false
+            }
             Msg::KeyEvent(e) => {
                 if let Some(ui) = self.ui.as_mut() {
                     ui.on_key_event(e);
@@ -143,13 +204,21 @@ impl Component for SimulationWindow {
             Msg::ReceivedSimAgentResponse(oort_simulation_worker::Response::Snapshot {
                 snapshot,
             }) => {
-                if let Some(ui) = self.ui.as_mut() {
+                let snapshot = snapshot.into_latest();
+                if Some(snapshot.nonce) == self.quick_test_nonce {
+                    self.on_quick_test_snapshot(context, snapshot);
+                } else if let Some(ui) = self.ui.as_mut() {
                     ui.on_snapshot(snapshot);
                 }
                 false
             }
         };
 
+        if self.quick_test_waiting && self.ui.as_ref().map_or(true, |ui| ui.idle()) {
+            self.quick_test_waiting = false;
+            self.request_quick_test_snapshot();
+        }
+
         if let Some(ui) = self.ui.as_ref() {
             if ui.needs_render() {
                 self.render_handle = {
@@ -170,7 +239,18 @@ impl Component for SimulationWindow {
         let pointer_event_cb = context.link().callback(Msg::PointerEvent);
         let blur_event_cb = context.link().callback(Msg::BlurEvent);
 
-        create_portal(
+        let transcript = create_portal(
+            html! {
+                <div class="transcript">
+                    <h1>{ "Transcript" }</h1>
+                    <p>{ "Lines logged with " }<code>{ "team_log!" }</code>{ " appear here." }</p>
+                    <pre ref={self.transcript_ref.clone()}></pre>
+                </div>
+            },
+            context.props().transcript_host.clone(),
+        );
+
+        let simulation = create_portal(
             html! {
                 <>
                     <canvas id="simcanvas" class="glcanvas"
@@ -190,11 +270,44 @@ impl Component for SimulationWindow {
                 </>
             },
             context.props().host.clone(),
-        )
+        );
+
+        html! {
+            <>
+                { simulation }
+                { transcript }
+            </>
+        }
     }
 }
 
 impl SimulationWindow {
+    fn on_quick_test_snapshot(&mut self, context: &Context<Self>, snapshot: Snapshot) {
+        if snapshot.status == scenario::Status::Running
+            && snapshot.errors.is_empty()
+            && snapshot.time < (MAX_TICKS as f64 * PHYSICS_TICK_LENGTH)
+        {
+            if self.ui.as_ref().map_or(true, |ui| ui.idle()) {
+                self.request_quick_test_snapshot();
+            } else {
+                self.quick_test_waiting = true;
+            }
+        } else {
+            if let Some(nonce) = self.quick_test_nonce.take() {
+                self.sim_agent
+                    .send(oort_simulation_worker::Request::CloseWorkspace { nonce });
+            }
+            context.props().on_quick_test_finished.emit(snapshot);
+        }
+    }
+
+    fn request_quick_test_snapshot(&mut self) {
+        if let Some(nonce) = self.quick_test_nonce {
+            self.sim_agent
+                .send(oort_simulation_worker::Request::WorkspaceSnapshot { ticks: 100, nonce });
+        }
+    }
+
     fn check_status(&mut self, context: &Context<Self>) -> bool {
         if let Some(ui) = self.ui.as_ref() {
             let status = ui.status();
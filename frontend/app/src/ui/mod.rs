@@ -3,12 +3,12 @@ pub mod frame_timer;
 pub mod setting;
 
 use log::{debug, info};
-use nalgebra::{point, vector, Point2};
+use nalgebra::{point, vector, Point2, Vector2};
 use oort_renderer::Renderer;
 use oort_simulator::model;
 use oort_simulator::scenario::Status;
 use oort_simulator::simulation::{self, PHYSICS_TICK_LENGTH};
-use oort_simulator::snapshot::{self, ShipSnapshot, Snapshot};
+use oort_simulator::snapshot::{self, ShipSnapshot, Snapshot, TeamLogEntry};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::Duration;
 use web_sys::{Element, HtmlCanvasElement};
@@ -20,6 +20,7 @@ const MAX_ZOOM: f32 = 5e-3;
 const INITIAL_ZOOM: f32 = 1e-3;
 const SNAPSHOT_PRELOAD: usize = 5;
 const MAX_SNAPSHOT_REQUESTS_IN_FLIGHT: usize = 10;
+const MAX_PICKED_API_CALLS: usize = 8;
 
 pub struct UI {
     version: String,
@@ -30,6 +31,8 @@ pub struct UI {
     canvas: HtmlCanvasElement,
     zoom: f32,
     camera_target: Point2<f32>,
+    // Floating origin of the snapshot on screen.
+    origin: Vector2<f64>,
     frame_timer: frame_timer::FrameTimer,
     status: Status,
     quit: bool,
@@ -47,9 +50,20 @@ pub struct UI {
     snapshot_requests_in_flight: usize,
     nonce: u32,
     request_snapshot: yew::Callback<()>,
+    // Checkpoints are only kept while the debugger is on, since stepping back
+    // needs them.
+    set_checkpoints: yew::Callback<bool>,
+    request_rewind: yew::Callback<u32>,
+    // Set until the snapshot answering a rewind arrives.
+    rewinding: bool,
     picked_ship_id: Option<u64>,
     status_ref: NodeRef,
     picked_ref: NodeRef,
+    transcript_ref: NodeRef,
+    // Every team log line received so far, including from preloaded
+    // snapshots that haven't been displayed yet.
+    team_log: Vec<TeamLogEntry>,
+    transcript_len: usize,
     touches: HashMap<i32, Touch>,
     drag_start: Option<Point2<i32>>,
     needs_render: bool,
@@ -61,17 +75,23 @@ impl UI {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         request_snapshot: yew::Callback<()>,
+        set_checkpoints: yew::Callback<bool>,
+        request_rewind: yew::Callback<u32>,
         seed: u32,
         nonce: u32,
         version: String,
         canvas_ref: NodeRef,
         status_ref: NodeRef,
         picked_ref: NodeRef,
+        transcript_ref: NodeRef,
         paused: bool,
     ) -> Self {
         if let Some(elem) = status_ref.cast::<Element>() {
             elem.set_text_content(Some("LOADING..."));
         }
+        if let Some(elem) = transcript_ref.cast::<Element>() {
+            elem.set_text_content(Some(""));
+        }
 
         let canvas = canvas_ref
             .cast::<HtmlCanvasElement>()
@@ -88,6 +108,7 @@ impl UI {
 
         let debug = setting::read("debug", false);
         renderer.set_debug(debug);
+        set_checkpoints.emit(debug);
         renderer.set_blur(setting::read("blur", true));
         renderer.set_nlips(setting::read("nlips", false));
 
@@ -100,6 +121,7 @@ impl UI {
             canvas,
             zoom,
             camera_target,
+            origin: vector![0.0, 0.0],
             frame_timer,
             status: Status::Running,
             quit: false,
@@ -117,9 +139,15 @@ impl UI {
             snapshot_requests_in_flight: 0,
             nonce,
             request_snapshot,
+            set_checkpoints,
+            request_rewind,
+            rewinding: false,
             picked_ship_id: None,
             status_ref,
             picked_ref,
+            transcript_ref,
+            team_log: Vec::new(),
+            transcript_len: 0,
             touches: HashMap::new(),
             drag_start: None,
             needs_render: true,
@@ -178,8 +206,20 @@ impl UI {
             self.keys_ignored.insert("g".to_string());
             self.debug = !self.debug;
             self.renderer.set_debug(self.debug);
+            self.set_checkpoints.emit(self.debug);
             setting::write("debug", &self.debug);
         }
+        if self.debug && self.keys_down.contains("p") && !self.keys_ignored.contains("p") {
+            self.keys_ignored.insert("p".to_string());
+            if let Some(snapshot) = self.snapshot.as_ref() {
+                let tick = (snapshot.time / PHYSICS_TICK_LENGTH).round() as u32;
+                if tick > 0 && !self.rewinding {
+                    self.paused = true;
+                    self.single_steps = 0;
+                    self.request_rewind.emit(tick - 1);
+                }
+            }
+        }
         if self.keys_down.contains("q") {
             self.set_status_message("EXITED");
             self.quit = true;
@@ -259,6 +299,14 @@ impl UI {
             _ => {}
         }
 
+        if !matches!(self.status, Status::Running | Status::Victory { team: 0 }) {
+            if let Some(snapshot) = self.snapshot.as_ref() {
+                for event in snapshot.hints.iter() {
+                    status_msgs.push(format!("HINT: {}", event.hint));
+                }
+            }
+        }
+
         if self.pending_snapshots.len() <= 1 && !fast_forward {
             status_msgs.push("SLOW SIM".to_owned());
         }
@@ -293,6 +341,7 @@ impl UI {
             }
 
             self.update_picked();
+            self.update_transcript();
         }
 
         if self.frame == 600 {
@@ -314,6 +363,21 @@ impl UI {
             return;
         }
 
+        if self.rewinding {
+            // Replaces the snapshot on screen even while paused.
+            self.rewinding = false;
+            self.physics_time = std::time::Duration::from_secs_f64(snapshot.time);
+            self.status = snapshot.status;
+            self.follow_origin(snapshot.origin);
+            self.renderer.update(&snapshot);
+            self.snapshot = Some(snapshot);
+            self.needs_render = true;
+            return;
+        }
+
+        // Collected here rather than when displayed because fast forward
+        // skips snapshots.
+        self.team_log.extend(snapshot.team_log.iter().cloned());
         self.pending_snapshots.push_back(snapshot);
         if self.snapshot_requests_in_flight > 0 {
             self.snapshot_requests_in_flight -= 1;
@@ -322,6 +386,18 @@ impl UI {
         self.needs_render = true;
     }
 
+    // Called when the window has asked the worker to rewind to `tick`.
+    // Responses with the old nonce, which were simulated past that point, are
+    // dropped.
+    pub fn on_rewind(&mut self, tick: u32, nonce: u32) {
+        self.nonce = nonce;
+        self.rewinding = true;
+        self.pending_snapshots.clear();
+        self.snapshot_requests_in_flight = 0;
+        self.team_log.retain(|entry| entry.tick <= tick);
+        self.transcript_len = usize::MAX;
+    }
+
     pub fn update_snapshot(&mut self) {
         while self.pending_snapshots.len() > SNAPSHOT_PRELOAD / 2
             && std::time::Duration::from_secs_f64(self.pending_snapshots[1].time)
@@ -345,6 +421,8 @@ impl UI {
             let first_snapshot = self.snapshot.is_none();
 
             self.snapshot = self.pending_snapshots.pop_front();
+            let origin = self.snapshot.as_ref().unwrap().origin;
+            self.follow_origin(origin);
             let snapshot = self.snapshot.as_mut().unwrap();
 
             if first_snapshot {
@@ -404,6 +482,22 @@ impl UI {
         }
     }
 
+    // When the simulation recenters it moves everything by the change in
+    // origin, so the camera and anything kept from earlier snapshots move with
+    // it.
+    fn follow_origin(&mut self, origin: Vector2<f64>) {
+        if origin == self.origin {
+            return;
+        }
+        let offset = self.origin - origin;
+        self.camera_target += offset.cast::<f32>();
+        for touch in self.touches.values_mut() {
+            touch.world_position += offset;
+        }
+        self.renderer.translate(offset.cast());
+        self.origin = origin;
+    }
+
     pub fn on_key_event(&mut self, e: web_sys::KeyboardEvent) {
         if e.type_() == "keydown" {
             self.keys_down.insert(e.key());
@@ -564,8 +658,52 @@ impl UI {
                 } else {
                     "".to_string()
                 };
+                let launchers_text: String = self
+                    .snapshot
+                    .as_ref()
+                    .map(|s| {
+                        s.launchers
+                            .iter()
+                            .filter(|launcher| launcher.ship == ship.id)
+                            .map(|launcher| {
+                                const BAR_WIDTH: usize = 10;
+                                let filled = (launcher.reload_fraction() * BAR_WIDTH as f64).floor()
+                                    as usize;
+                                let status = if launcher.ready() {
+                                    "ready".to_string()
+                                } else {
+                                    format!(
+                                        "{:.1}s",
+                                        launcher.reload_ticks_remaining as f64
+                                            * PHYSICS_TICK_LENGTH
+                                    )
+                                };
+                                format!(
+                                    "Launcher {}: [{}{}] {}\n",
+                                    launcher.index,
+                                    "#".repeat(filled),
+                                    "-".repeat(BAR_WIDTH - filled),
+                                    status
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // Most recent calls first.
+                let api_calls_text: String = self
+                    .snapshot
+                    .as_ref()
+                    .and_then(|s| s.api_calls.get(&ship.id))
+                    .map(|records| {
+                        let mut text = "API calls:\n".to_string();
+                        for record in records.iter().rev().take(MAX_PICKED_API_CALLS) {
+                            text.push_str(&format!("  {record}\n"));
+                        }
+                        text
+                    })
+                    .unwrap_or_default();
                 elem.set_text_content(Some(&format!(
-                    "{class:?}\nTeam: {team:?}\nHealth: {health:.0}\n{fuel_text}{active_abilities_text}{debug_text}"
+                    "{class:?}\nTeam: {team:?}\nHealth: {health:.0}\n{fuel_text}{launchers_text}{active_abilities_text}{api_calls_text}{debug_text}"
                 )));
             }
         } else if let Some(elem) = self.picked_ref.cast::<Element>() {
@@ -574,6 +712,32 @@ impl UI {
         self.renderer.set_picked_ship(self.picked_ship_id);
     }
 
+    pub fn update_transcript(&mut self) {
+        let Some(snapshot) = self.snapshot.as_ref() else {
+            return;
+        };
+        let tick = (snapshot.time / PHYSICS_TICK_LENGTH).round() as u32;
+        let len = self.team_log.partition_point(|entry| entry.tick <= tick);
+        if len == self.transcript_len {
+            return;
+        }
+        self.transcript_len = len;
+        if let Some(elem) = self.transcript_ref.cast::<Element>() {
+            let text = self.team_log[..len]
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{:.2}s [Team {}] {}\n",
+                        entry.tick as f64 * PHYSICS_TICK_LENGTH,
+                        entry.team,
+                        entry.text
+                    )
+                })
+                .collect::<String>();
+            elem.set_text_content(Some(&text));
+        }
+    }
+
     pub fn set_status_message(&self, text: &str) {
         if let Some(elem) = self.status_ref.cast::<Element>() {
             elem.set_text_content(Some(text));
@@ -584,6 +748,12 @@ impl UI {
         self.frame
     }
 
+    // True when the main simulation isn't asking the worker for snapshots, so
+    // background work there won't slow it down.
+    pub fn idle(&self) -> bool {
+        self.paused || self.status != Status::Running
+    }
+
     pub fn needs_render(&self) -> bool {
         self.needs_render
             || !(self.paused || self.status != Status::Running)
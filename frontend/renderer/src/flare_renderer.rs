@@ -42,6 +42,31 @@ fn flare_positions(class: ShipClass) -> Vec<FlarePosition> {
                 },
             ]
         }
+        ShipClass::Corvette => {
+            let x = -21.0;
+            vec![
+                FlarePosition {
+                    offset: vector![x, 0.0],
+                    angle: TAU / 2.0,
+                    scale: vector![60.0, 30.0],
+                },
+                FlarePosition {
+                    offset: vector![x, 0.0],
+                    angle: 0.0,
+                    scale: vector![40.0, 24.0],
+                },
+                FlarePosition {
+                    offset: vector![0.0, 11.0],
+                    angle: TAU / 4.0,
+                    scale: vector![10.0, 8.0],
+                },
+                FlarePosition {
+                    offset: vector![0.0, -11.0],
+                    angle: -TAU / 4.0,
+                    scale: vector![10.0, 8.0],
+                },
+            ]
+        }
         ShipClass::Frigate => {
             let x = -48.0;
             vec![
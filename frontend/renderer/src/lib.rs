@@ -19,7 +19,7 @@ use bullet_renderer::BulletRenderer;
 use flare_renderer::FlareRenderer;
 use grid_renderer::GridRenderer;
 use line_renderer::LineRenderer;
-use nalgebra::{point, vector, Matrix4, Point2};
+use nalgebra::{point, vector, Matrix4, Point2, Vector2};
 use oort_api::Text;
 use oort_simulator::simulation::Line;
 use oort_simulator::snapshot::Snapshot;
@@ -265,6 +265,13 @@ impl Renderer {
         self.trail_renderer.update(snapshot);
     }
 
+    // Moves what's kept from earlier snapshots, after the simulation moved its
+    // floating origin.
+    pub fn translate(&mut self, offset: Vector2<f32>) {
+        self.particle_renderer.translate(offset);
+        self.trail_renderer.translate(offset);
+    }
+
     pub fn set_blur(&mut self, blur: bool) {
         self.blur_enabled = blur;
     }
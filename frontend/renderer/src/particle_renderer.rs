@@ -156,6 +156,12 @@ void main() {
         }
     }
 
+    pub fn translate(&mut self, offset: Vector2<f32>) {
+        for particle in self.particles.iter_mut() {
+            particle.position += offset;
+        }
+    }
+
     pub fn upload(&mut self, projection_matrix: &Matrix4<f32>, snapshot: &Snapshot) -> DrawSet {
         let current_time = snapshot.time as f32;
 
@@ -123,12 +123,8 @@ void main() {
         let zoom_factor = 2e-3 / zoom;
 
         for (&class, ships) in ships_by_class.iter() {
-            let model = model::load(class);
-            let radius: f32 = model
-                .iter()
-                .max_by_key(|v| v.norm_squared() as i32)
-                .unwrap()
-                .norm();
+            let model = model::get(class);
+            let radius = model.radius;
             let min_nlips_scale = 4.0f32.max(radius / 20.0);
             let nlips_scale = (2.0 * zoom_factor / radius.log2()).min(50.0);
             for nlips_draw in [false, true] {
@@ -141,7 +137,7 @@ void main() {
                 }
                 let scale = if nlips_draw { nlips_scale } else { 1.0 };
                 let vertices =
-                    geometry::line_loop_mesh(&model::scale(scale, &model), base_line_width);
+                    geometry::line_loop_mesh(&model::scale(scale, &model.mesh), base_line_width);
                 let vertices_token = self.buffer_arena.write(&vertices);
                 let num_vertices = vertices.len();
 
@@ -150,7 +146,12 @@ void main() {
                 for ship in ships.iter() {
                     let p = ship.position.coords.cast::<f32>();
                     let shielded = ship.active_abilities.contains(&oort_api::Ability::Shield);
-                    let mut team_color = Self::team_color(ship.team);
+                    let mut team_color = if ship.hulk {
+                        // Wrecks are drawn dim regardless of which team they belonged to.
+                        vector![0.25, 0.25, 0.25, 0.6]
+                    } else {
+                        Self::team_color(ship.team)
+                    };
                     if nlips_draw {
                         team_color.w *= (nlips_scale / min_nlips_scale - 1.0)
                             .clamp(0.0, 1.0)
@@ -24,6 +24,9 @@ pub struct TrailRenderer {
     buffer: WebGlBuffer,
     index: i32,
     last_positions: HashMap<u64, Point2<f32>>,
+    // Vertices already in the buffer are drawn shifted by this, so they can
+    // follow the floating origin without being rewritten.
+    offset: Vector2<f32>,
 }
 
 impl TrailRenderer {
@@ -88,6 +91,7 @@ void main() {
             buffer,
             index: 0,
             last_positions: HashMap::new(),
+            offset: vector![0.0, 0.0],
         })
     }
 
@@ -95,6 +99,13 @@ void main() {
         self.projection_matrix = *m;
     }
 
+    pub fn translate(&mut self, offset: Vector2<f32>) {
+        self.offset += offset;
+        for position in self.last_positions.values_mut() {
+            *position += offset;
+        }
+    }
+
     pub fn update(&mut self, snapshot: &Snapshot) {
         let mut data = vec![];
         data.reserve(snapshot.ships.len() * 2 * FLOATS_PER_VERTEX as usize);
@@ -124,7 +135,8 @@ void main() {
                 use std::collections::hash_map::Entry;
                 match self.last_positions.entry(ship.id) {
                     Entry::Occupied(mut e) => {
-                        let last_position = e.insert(current_position);
+                        let last_position = e.insert(current_position) - self.offset;
+                        let current_position = current_position - self.offset;
                         data.push(last_position.x);
                         data.push(last_position.y);
                         data.push(color.x);
@@ -248,7 +260,9 @@ void main() {
         self.context.uniform_matrix4fv_with_f32_array(
             Some(&self.transform_loc),
             false,
-            self.projection_matrix.data.as_slice(),
+            (self.projection_matrix * Matrix4::new_translation(&self.offset.push(0.0)))
+                .data
+                .as_slice(),
         );
 
         self.context
@@ -267,6 +281,7 @@ void main() {
 fn trail_offset(class: ShipClass) -> Vector2<f32> {
     match class {
         ShipClass::Fighter => vector![-7.0, 0.0],
+        ShipClass::Corvette => vector![-21.0, 0.0],
         ShipClass::Frigate => vector![-48.0, 0.0],
         ShipClass::Cruiser => vector![-96.0, 0.0],
         ShipClass::Missile => vector![-2.1, 0.0],
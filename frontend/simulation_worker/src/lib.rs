@@ -1,8 +1,9 @@
-use oort_simulator::scenario::{Status, MAX_TICKS};
+use oort_simulator::simulation::checkpoint::CheckpointConfig;
+use oort_simulator::simulation::pool::{SimulationPool, WorkspaceId};
 use oort_simulator::simulation::Code;
-use oort_simulator::simulation::Simulation;
-use oort_simulator::snapshot::Snapshot;
+use oort_simulator::snapshot::VersionedSnapshot;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use yew_agent::{HandlerId, Private, WorkerLink};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -17,17 +18,47 @@ pub enum Request {
         ticks: u32,
         nonce: u32,
     },
+    // Turns checkpoints for the main simulation on or off, including for
+    // later scenarios. Off by default since they cost memory and time.
+    SetCheckpoints {
+        enabled: bool,
+    },
+    // Rewinds the main simulation to an earlier tick, if it's still covered
+    // by a checkpoint, and responds with a snapshot.
+    Rewind {
+        tick: u32,
+        nonce: u32,
+    },
+    // Starts a simulation alongside the main one, e.g. a quick test of the
+    // code being edited. Later requests with the same nonce address it.
+    StartWorkspace {
+        scenario_name: String,
+        seed: u32,
+        codes: Vec<Code>,
+        nonce: u32,
+    },
+    WorkspaceSnapshot {
+        ticks: u32,
+        nonce: u32,
+    },
+    CloseWorkspace {
+        nonce: u32,
+    },
 }
 
+// Snapshots carry a version header, so a page left open across a deploy can
+// still read what a freshly loaded worker sends.
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Response {
-    Snapshot { snapshot: Snapshot },
+    Snapshot { snapshot: VersionedSnapshot },
 }
 
 pub struct SimAgent {
     link: WorkerLink<Self>,
-    sim: Option<Box<Simulation>>,
-    errored: bool,
+    pool: SimulationPool,
+    main: Option<WorkspaceId>,
+    workspaces: HashMap<u32, WorkspaceId>,
+    checkpoints: bool,
 }
 
 impl yew_agent::Worker for SimAgent {
@@ -39,8 +70,10 @@ impl yew_agent::Worker for SimAgent {
     fn create(link: WorkerLink<Self>) -> Self {
         Self {
             link,
-            sim: None,
-            errored: false,
+            pool: SimulationPool::new(),
+            main: None,
+            workspaces: HashMap::new(),
+            checkpoints: false,
         }
     }
 
@@ -54,23 +87,57 @@ impl yew_agent::Worker for SimAgent {
                 codes,
                 nonce,
             } => {
-                self.sim = Some(Simulation::new(&scenario_name, seed, &codes));
-                let snapshot = self.sim().snapshot(nonce);
-                self.errored = !snapshot.errors.is_empty();
-                self.link.respond(who, Response::Snapshot { snapshot });
+                if let Some(id) = self.main.take() {
+                    self.pool.remove(id);
+                }
+                let id = self.create(&scenario_name, seed, &codes);
+                self.main = Some(id);
+                self.update_checkpoints();
+                self.respond_snapshot(who, id, nonce);
+            }
+            Request::SetCheckpoints { enabled } => {
+                if self.checkpoints != enabled {
+                    self.checkpoints = enabled;
+                    self.update_checkpoints();
+                }
             }
             Request::Snapshot { ticks, nonce } => {
-                if self.errored {
-                    return;
+                if let Some(id) = self.main {
+                    self.step(who, id, ticks, nonce);
                 }
-                for _ in 0..ticks {
-                    if self.sim().status() == Status::Running && self.sim().tick() < MAX_TICKS {
-                        self.sim().step();
+            }
+            Request::Rewind { tick, nonce } => {
+                if let Some(id) = self.main {
+                    if let Some(sim) = self.pool.get_mut(id) {
+                        let ticks = sim.tick().saturating_sub(tick);
+                        sim.step_back(ticks);
+                        // Restoring a checkpoint doesn't publish.
+                        sim.set_publishing(true);
                     }
+                    self.respond_snapshot(who, id, nonce);
+                }
+            }
+            Request::StartWorkspace {
+                scenario_name,
+                seed,
+                codes,
+                nonce,
+            } => {
+                let id = self.create(&scenario_name, seed, &codes);
+                if let Some(old_id) = self.workspaces.insert(nonce, id) {
+                    self.pool.remove(old_id);
+                }
+                self.respond_snapshot(who, id, nonce);
+            }
+            Request::WorkspaceSnapshot { ticks, nonce } => {
+                if let Some(&id) = self.workspaces.get(&nonce) {
+                    self.step(who, id, ticks, nonce);
+                }
+            }
+            Request::CloseWorkspace { nonce } => {
+                if let Some(id) = self.workspaces.remove(&nonce) {
+                    self.pool.remove(id);
                 }
-                let snapshot = self.sim().snapshot(nonce);
-                self.errored = !snapshot.errors.is_empty();
-                self.link.respond(who, Response::Snapshot { snapshot });
             }
         };
     }
@@ -81,7 +148,43 @@ impl yew_agent::Worker for SimAgent {
 }
 
 impl SimAgent {
-    fn sim(&mut self) -> &mut Simulation {
-        self.sim.as_mut().unwrap()
+    fn create(&mut self, scenario_name: &str, seed: u32, codes: &[Code]) -> WorkspaceId {
+        let id = self.pool.create(scenario_name, seed, codes);
+        if let Some(sim) = self.pool.get_mut(id) {
+            sim.set_publishing(true);
+        }
+        id
+    }
+
+    fn update_checkpoints(&mut self) {
+        let config = self.checkpoints.then(CheckpointConfig::default);
+        if let Some(sim) = self.main.and_then(|id| self.pool.get_mut(id)) {
+            sim.set_checkpoints(config);
+        }
+    }
+
+    fn step(&mut self, who: HandlerId, id: WorkspaceId, ticks: u32, nonce: u32) {
+        // A simulation that has reported errors stops responding.
+        if self.pool.errored(id) {
+            return;
+        }
+        self.pool.step(id, ticks);
+        self.respond_snapshot(who, id, nonce);
+    }
+
+    fn respond_snapshot(&mut self, who: HandlerId, id: WorkspaceId, nonce: u32) {
+        if let Some(sim) = self.pool.get(id) {
+            let mut snapshot = match sim.published() {
+                Some(published) => (*published).clone(),
+                None => sim.snapshot(0),
+            };
+            snapshot.nonce = nonce;
+            self.link.respond(
+                who,
+                Response::Snapshot {
+                    snapshot: snapshot.into(),
+                },
+            );
+        }
     }
 }
@@ -36,12 +36,31 @@ pub unsafe fn export_tick_ship(key: i32) {
             }
         });
         oort_api::rng_state::set(ship.rng.clone());
+        {
+            #[allow(unused_imports)]
+            use oort_api::events::{DispatchEvents, DispatchNone, Dispatcher};
+            (&mut Dispatcher(&mut ship.user_ship)).dispatch();
+        }
         ship.user_ship.tick();
         oort_api::dbg::update();
         ship.rng = oort_api::rng_state::get().clone();
     }
 }
 
+#[doc(hidden)]
+#[no_mangle]
+pub unsafe fn export_scenario_start(count: i32) {
+    oort_api::panic::reset();
+    unsafe {
+        oort_api::rng_state::set(oort_api::rng_state::RngState::new());
+    }
+    #[allow(unused_imports)]
+    use oort_api::team::{DispatchScenarioStart, DispatchStartNone, StartDispatcher};
+    let metadata = oort_api::team::read_metadata(count as usize);
+    let orders = (&StartDispatcher::<user::Ship>(std::marker::PhantomData)).start(&metadata);
+    oort_api::team::set_orders(orders);
+}
+
 #[doc(hidden)]
 #[no_mangle]
 pub unsafe fn export_delete_ship(key: i32) {
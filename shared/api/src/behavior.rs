@@ -0,0 +1,164 @@
+//! A small behavior tree runtime.
+//!
+//! Trees are built from closures and ticked once per game tick with a
+//! user-defined context, usually the ship's own struct:
+//!
+//! ```
+//! use oort_api::behavior::*;
+//!
+//! struct Context {
+//!     enemy_visible: bool,
+//!     shots: u32,
+//! }
+//!
+//! let mut tree = fallback(vec![
+//!     sequence(vec![
+//!         condition(|ctx: &mut Context| ctx.enemy_visible),
+//!         action(|ctx: &mut Context| {
+//!             ctx.shots += 1;
+//!             Status::Success
+//!         }),
+//!     ]),
+//!     action(|_: &mut Context| Status::Running),
+//! ]);
+//!
+//! let mut ctx = Context { enemy_visible: true, shots: 0 };
+//! assert_eq!(tree.tick(&mut ctx), Status::Success);
+//! assert_eq!(ctx.shots, 1);
+//! ```
+//!
+//! Composite nodes remember which child was running, so a long-running action
+//! is resumed on the next tick instead of re-evaluating the earlier children.
+
+/// Result of ticking a node.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Status {
+    /// The node finished successfully.
+    Success,
+    /// The node failed.
+    Failure,
+    /// The node needs more ticks to finish.
+    Running,
+}
+
+impl From<bool> for Status {
+    fn from(b: bool) -> Status {
+        if b {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+/// A node in a behavior tree.
+pub trait Node<C> {
+    /// Runs the node for one tick.
+    fn tick(&mut self, ctx: &mut C) -> Status;
+
+    /// Clears any state remembered between ticks.
+    fn reset(&mut self) {}
+}
+
+/// A boxed node, as returned by the constructor functions in this module.
+pub type BoxNode<C> = Box<dyn Node<C>>;
+
+struct Condition<F>(F);
+
+impl<C, F: FnMut(&mut C) -> bool> Node<C> for Condition<F> {
+    fn tick(&mut self, ctx: &mut C) -> Status {
+        (self.0)(ctx).into()
+    }
+}
+
+struct Action<F>(F);
+
+impl<C, F: FnMut(&mut C) -> Status> Node<C> for Action<F> {
+    fn tick(&mut self, ctx: &mut C) -> Status {
+        (self.0)(ctx)
+    }
+}
+
+struct Composite<C> {
+    children: Vec<BoxNode<C>>,
+    current: usize,
+    // The status that moves on to the next child.
+    continue_on: Status,
+}
+
+impl<C> Node<C> for Composite<C> {
+    fn tick(&mut self, ctx: &mut C) -> Status {
+        while let Some(child) = self.children.get_mut(self.current) {
+            match child.tick(ctx) {
+                Status::Running => return Status::Running,
+                status if status == self.continue_on => self.current += 1,
+                status => {
+                    self.reset();
+                    return status;
+                }
+            }
+        }
+        self.reset();
+        self.continue_on
+    }
+
+    fn reset(&mut self) {
+        self.current = 0;
+        for child in self.children.iter_mut() {
+            child.reset();
+        }
+    }
+}
+
+struct Invert<C>(BoxNode<C>);
+
+impl<C> Node<C> for Invert<C> {
+    fn tick(&mut self, ctx: &mut C) -> Status {
+        match self.0.tick(ctx) {
+            Status::Success => Status::Failure,
+            Status::Failure => Status::Success,
+            Status::Running => Status::Running,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Returns a node that succeeds if `f` returns true and fails otherwise.
+pub fn condition<C: 'static>(f: impl FnMut(&mut C) -> bool + 'static) -> BoxNode<C> {
+    Box::new(Condition(f))
+}
+
+/// Returns a node that runs `f` and returns its status.
+pub fn action<C: 'static>(f: impl FnMut(&mut C) -> Status + 'static) -> BoxNode<C> {
+    Box::new(Action(f))
+}
+
+/// Returns a node that runs its children in order until one fails.
+///
+/// Succeeds if all children succeed.
+pub fn sequence<C: 'static>(children: Vec<BoxNode<C>>) -> BoxNode<C> {
+    Box::new(Composite {
+        children,
+        current: 0,
+        continue_on: Status::Success,
+    })
+}
+
+/// Returns a node that runs its children in order until one succeeds.
+///
+/// Fails if all children fail.
+pub fn fallback<C: 'static>(children: Vec<BoxNode<C>>) -> BoxNode<C> {
+    Box::new(Composite {
+        children,
+        current: 0,
+        continue_on: Status::Failure,
+    })
+}
+
+/// Returns a node that swaps the success and failure of its child.
+pub fn invert<C: 'static>(child: BoxNode<C>) -> BoxNode<C> {
+    Box::new(Invert(child))
+}
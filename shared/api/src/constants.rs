@@ -0,0 +1,449 @@
+//! Gameplay numbers shared by the game and this documentation.
+//!
+//! The simulator builds its ships from these values, so they're exactly what
+//! the game uses. Times are in ticks unless noted, see [`TICK_LENGTH`].
+//!
+//! ```
+//! use oort_api::constants::{self, class_stats};
+//! use oort_api::prelude::*;
+//!
+//! let cruiser = class_stats(Class::Cruiser).unwrap();
+//! assert!(cruiser.max_forward_acceleration < constants::FIGHTER.max_forward_acceleration);
+//! ```
+
+use crate::prelude::ScanResult;
+use crate::Class;
+
+/// The time between each simulation tick (in seconds).
+pub const TICK_LENGTH: f64 = 1.0 / 60.0;
+
+/// Largest width or height of the world (in meters).
+pub const MAX_WORLD_SIZE: f64 = 200000.0;
+
+/// Lowest fraction of full power a radar can be set to.
+pub const MIN_RADAR_POWER: f64 = 0.01;
+
+/// Longest time (in seconds) a radar can remember a lost contact.
+pub const MAX_RADAR_MEMORY: f64 = 5.0;
+
+/// Gain of a radar's sidelobes relative to the center of its beam (-30 dB).
+/// See [`radar_gain`].
+pub const RADAR_SIDELOBE_GAIN: f64 = 1e-3;
+
+/// Gain of a seeker's returns inside its Doppler notch (-30 dB), in scenarios
+/// with seeker Doppler notches.
+///
+/// The notch rejects returns with no Doppler shift, so it hides anything whose
+/// closing speed with the seeker is slower than the seeker's notch width (see
+/// [`ClassStats::doppler_notch`]). A ship matching an incoming missile's
+/// speed along the line of sight, or flying perpendicular to a slow one
+/// ("beaming"), can drop out of its seeker.
+pub const DOPPLER_NOTCH_GAIN: f64 = 1e-3;
+
+/// Weakest one-way signal (in watts, -20 dBm) picked up by a radar warning
+/// receiver. See [`radar_warning`](crate::prelude::radar_warning).
+pub const RWR_MIN_RSSI: f64 = 1e-5;
+
+/// Transmit power of every ship's radios (in watts).
+pub const RADIO_POWER: f64 = 20e3;
+
+/// Receiving cross section of every ship's radios (in m²).
+pub const RADIO_RX_CROSS_SECTION: f64 = 5.0;
+
+/// Weakest signal (in watts, -20 dBm) a radio can receive.
+pub const RADIO_MIN_RSSI: f64 = 1e-5;
+
+/// Shortest lifetime (in ticks) [`set_launch_ttl`](crate::prelude::set_launch_ttl)
+/// can give a missile or torpedo.
+pub const MIN_LAUNCH_TTL: u32 = 60;
+
+/// Lifetime (in seconds) of the fragments thrown out when a missile or
+/// torpedo explodes.
+pub const WARHEAD_FRAGMENT_TTL: f32 = 0.2;
+
+/// Fraction of full reload speed left to a ship with no health, in scenarios
+/// where damage slows reloads. Reload speed falls linearly with health.
+pub const DAMAGED_RELOAD_SPEED: f64 = 0.5;
+
+/// Returns the gain of a radar beam `width` radians wide in a direction
+/// `offset` radians from its heading, relative to the center of the beam.
+///
+/// The gain is 1 within the beam. Outside it the mainlobe rolls off, 20 dB
+/// down half a beam width past the edge, until it reaches
+/// [`RADAR_SIDELOBE_GAIN`]. Transmitted power and received echoes are both
+/// scaled by the gain, so a target's signal is scaled by its square, while
+/// jamming and radar warning receivers only see it once.
+///
+/// ```
+/// use oort_api::constants::{radar_gain, RADAR_SIDELOBE_GAIN};
+/// use std::f64::consts::PI;
+///
+/// let width = PI / 8.0;
+/// assert_eq!(radar_gain(width / 2.0, width), 1.0);
+/// assert!((radar_gain(width, width) - 0.01).abs() < 1e-9);
+/// assert_eq!(radar_gain(PI, width), RADAR_SIDELOBE_GAIN);
+/// ```
+pub fn radar_gain(offset: f64, width: f64) -> f64 {
+    let excess = (offset.abs() - width / 2.0) / (width / 2.0);
+    if excess <= 0.0 {
+        return 1.0;
+    }
+    10.0f64
+        .powf(-2.0 * excess * excess)
+        .max(RADAR_SIDELOBE_GAIN)
+}
+
+/// Durability and maneuverability of a ship class.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassStats {
+    /// Health when the ship is created.
+    pub health: f64,
+    /// Damage absorbed from each hit.
+    pub armor: f64,
+    /// Maximum forward acceleration (in m/s²).
+    pub max_forward_acceleration: f64,
+    /// Maximum backward acceleration (in m/s²).
+    pub max_backward_acceleration: f64,
+    /// Maximum lateral acceleration (in m/s²).
+    pub max_lateral_acceleration: f64,
+    /// Maximum angular acceleration (in radians/s²).
+    pub max_angular_acceleration: f64,
+    /// Delta-v available (in m/s), or `None` for unlimited fuel.
+    pub fuel: Option<f64>,
+    /// Doppler notch width of the ship's radar (in m/s), or zero for none.
+    /// Only seekers have a notch, see [`DOPPLER_NOTCH_GAIN`].
+    pub doppler_notch: f64,
+    /// Transmit power of the ship's radar at full power (in watts).
+    pub radar_power: f64,
+    /// Receiving cross section of the ship's radar (in m²). Larger antennas
+    /// pick up weaker echoes.
+    pub rx_cross_section: f64,
+    /// Farthest distance the ship's radar can detect anything at (in m).
+    pub radar_max_range: f64,
+    /// How much radar energy the ship reflects (in m²).
+    pub radar_cross_section: f64,
+    /// Ticks until the ship expires, or `None` if it doesn't.
+    pub ttl: Option<u32>,
+}
+
+/// Stats of the fighter.
+pub const FIGHTER: ClassStats = ClassStats {
+    health: 100.0,
+    armor: 0.0,
+    max_forward_acceleration: 60.0,
+    max_backward_acceleration: 30.0,
+    max_lateral_acceleration: 30.0,
+    max_angular_acceleration: std::f64::consts::TAU,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 20e3,
+    rx_cross_section: 5.0,
+    radar_max_range: 100e3,
+    radar_cross_section: 10.0,
+    ttl: None,
+};
+
+/// Stats of the corvette.
+pub const CORVETTE: ClassStats = ClassStats {
+    health: 1500.0,
+    armor: 0.0,
+    max_forward_acceleration: 30.0,
+    max_backward_acceleration: 15.0,
+    max_lateral_acceleration: 15.0,
+    max_angular_acceleration: std::f64::consts::PI,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 40e3,
+    rx_cross_section: 7.0,
+    radar_max_range: 120e3,
+    radar_cross_section: 20.0,
+    ttl: None,
+};
+
+/// Stats of the frigate.
+pub const FRIGATE: ClassStats = ClassStats {
+    health: 10000.0,
+    armor: 2.0,
+    max_forward_acceleration: 10.0,
+    max_backward_acceleration: 5.0,
+    max_lateral_acceleration: 5.0,
+    max_angular_acceleration: std::f64::consts::FRAC_PI_4,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 100e3,
+    rx_cross_section: 10.0,
+    radar_max_range: 150e3,
+    radar_cross_section: 30.0,
+    ttl: None,
+};
+
+/// Stats of the cruiser.
+pub const CRUISER: ClassStats = ClassStats {
+    health: 20000.0,
+    armor: 5.0,
+    max_forward_acceleration: 5.0,
+    max_backward_acceleration: 2.5,
+    max_lateral_acceleration: 2.5,
+    max_angular_acceleration: std::f64::consts::FRAC_PI_8,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 200e3,
+    rx_cross_section: 20.0,
+    radar_max_range: 200e3,
+    radar_cross_section: 40.0,
+    ttl: None,
+};
+
+/// Stats of the missile.
+pub const MISSILE: ClassStats = ClassStats {
+    health: 20.0,
+    armor: 0.0,
+    max_forward_acceleration: 300.0,
+    max_backward_acceleration: 0.0,
+    max_lateral_acceleration: 100.0,
+    max_angular_acceleration: 8.0 * std::f64::consts::PI,
+    fuel: Some(2000.0),
+    doppler_notch: 40.0,
+    radar_power: 1e3,
+    rx_cross_section: 3.0,
+    radar_max_range: 40e3,
+    radar_cross_section: 0.1,
+    ttl: Some(60 * 60),
+};
+
+/// Stats of the torpedo.
+pub const TORPEDO: ClassStats = ClassStats {
+    health: 100.0,
+    armor: 0.0,
+    max_forward_acceleration: 70.0,
+    max_backward_acceleration: 0.0,
+    max_lateral_acceleration: 20.0,
+    max_angular_acceleration: 4.0 * std::f64::consts::PI,
+    fuel: Some(3000.0),
+    doppler_notch: 20.0,
+    radar_power: 10e3,
+    rx_cross_section: 3.0,
+    radar_max_range: 60e3,
+    radar_cross_section: 0.3,
+    ttl: Some(60 * 60),
+};
+
+/// Stats of the station, which can't move.
+pub const STATION: ClassStats = ClassStats {
+    health: 50000.0,
+    armor: 5.0,
+    max_forward_acceleration: 0.0,
+    max_backward_acceleration: 0.0,
+    max_lateral_acceleration: 0.0,
+    max_angular_acceleration: 0.0,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 200e3,
+    rx_cross_section: 20.0,
+    radar_max_range: 200e3,
+    radar_cross_section: 100.0,
+    ttl: None,
+};
+
+/// Stats of the turret, which can't move.
+pub const TURRET: ClassStats = ClassStats {
+    health: 2000.0,
+    armor: 2.0,
+    max_forward_acceleration: 0.0,
+    max_backward_acceleration: 0.0,
+    max_lateral_acceleration: 0.0,
+    max_angular_acceleration: 0.0,
+    fuel: None,
+    doppler_notch: 0.0,
+    radar_power: 50e3,
+    rx_cross_section: 10.0,
+    radar_max_range: f64::INFINITY,
+    radar_cross_section: 20.0,
+    ttl: None,
+};
+
+/// Returns the stats of a ship class, or `None` for classes that aren't
+/// ships, such as asteroids.
+pub fn class_stats(class: Class) -> Option<ClassStats> {
+    match class {
+        Class::Fighter => Some(FIGHTER),
+        Class::Corvette => Some(CORVETTE),
+        Class::Frigate => Some(FRIGATE),
+        Class::Cruiser => Some(CRUISER),
+        Class::Missile => Some(MISSILE),
+        Class::Torpedo => Some(TORPEDO),
+        Class::Station => Some(STATION),
+        Class::Turret => Some(TURRET),
+        _ => None,
+    }
+}
+
+/// Performance of a gun.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GunStats {
+    /// Muzzle velocity (in m/s).
+    pub speed: f64,
+    /// Ticks between shots.
+    pub reload_ticks: u32,
+    /// Bullets fired per shot.
+    pub burst_size: i32,
+    /// Lifetime of each bullet (in seconds).
+    pub bullet_ttl: f32,
+}
+
+impl GunStats {
+    /// Distance a bullet travels relative to the ship that fired it before it
+    /// expires (in meters).
+    pub fn range(&self) -> f64 {
+        self.speed * self.bullet_ttl as f64
+    }
+}
+
+/// The rapid-fire gun on fighters and corvettes, and the frigate's turrets.
+pub const VULCAN: GunStats = GunStats {
+    speed: 1000.0,
+    reload_ticks: 4,
+    burst_size: 1,
+    bullet_ttl: 10.0,
+};
+
+/// The frigate's main gun.
+pub const FRIGATE_MAIN_GUN: GunStats = GunStats {
+    speed: 4000.0,
+    reload_ticks: 120,
+    burst_size: 1,
+    bullet_ttl: 60.0,
+};
+
+/// The cruiser's flak turret.
+pub const CRUISER_FLAK: GunStats = GunStats {
+    speed: 1000.0,
+    reload_ticks: 24,
+    burst_size: 6,
+    bullet_ttl: 1.0,
+};
+
+/// The turret's gun.
+pub const TURRET_GUN: GunStats = GunStats {
+    speed: 3000.0,
+    reload_ticks: 60,
+    burst_size: 1,
+    bullet_ttl: 10.0,
+};
+
+/// Ticks for a fighter's missile launcher to reload.
+pub const FIGHTER_MISSILE_RELOAD_TICKS: u32 = 300;
+
+/// Ticks for each of a corvette's missile launchers to reload.
+pub const CORVETTE_MISSILE_RELOAD_TICKS: u32 = 240;
+
+/// Ticks for a frigate's missile launcher to reload.
+pub const FRIGATE_MISSILE_RELOAD_TICKS: u32 = 120;
+
+/// Ticks for each of a cruiser's missile launchers to reload.
+pub const CRUISER_MISSILE_RELOAD_TICKS: u32 = 72;
+
+/// Ticks for a cruiser's torpedo launcher to reload.
+pub const CRUISER_TORPEDO_RELOAD_TICKS: u32 = 180;
+
+/// Speed (in m/s) at which missiles and torpedoes leave their launcher,
+/// relative to the launching ship.
+pub const LAUNCH_SPEED: f64 = 100.0;
+
+/// Timing of a ship ability.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AbilityStats {
+    /// How long the ability stays active once activated (in seconds).
+    pub active_time: f64,
+    /// Time from activation until the ability can be used again (in seconds).
+    pub reload_time: f64,
+}
+
+/// The fighter's and missile's boost.
+pub const BOOST: AbilityStats = AbilityStats {
+    active_time: 2.0,
+    reload_time: 10.0,
+};
+
+/// Forward acceleration added while boosting (in m/s²).
+pub const BOOST_ACCELERATION: f64 = 100.0;
+
+/// The torpedo's decoy.
+pub const DECOY: AbilityStats = AbilityStats {
+    active_time: 0.5,
+    reload_time: 10.0,
+};
+
+/// The cruiser's shield.
+pub const SHIELD: AbilityStats = AbilityStats {
+    active_time: 1.0,
+    reload_time: 5.0,
+};
+
+/// Performance and armament of a ship class, for judging the threat posed by
+/// a radar contact.
+///
+/// ```
+/// use oort_api::constants;
+/// use oort_api::prelude::*;
+///
+/// let frigate = constants::capabilities(Class::Frigate).unwrap();
+/// assert_eq!(frigate.max_gun_range(), 240000.0);
+/// assert!(constants::capabilities(Class::Asteroid).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capabilities {
+    /// Durability and maneuverability.
+    pub stats: ClassStats,
+    /// Guns carried, in weapon order.
+    pub guns: &'static [GunStats],
+    /// Number of missile launchers.
+    pub missile_launchers: u32,
+    /// Number of torpedo launchers.
+    pub torpedo_launchers: u32,
+}
+
+impl Capabilities {
+    /// Returns the longest range of any gun, or zero if the class has none.
+    pub fn max_gun_range(&self) -> f64 {
+        self.guns.iter().map(GunStats::range).fold(0.0, f64::max)
+    }
+
+    /// Returns true if the class can launch missiles or torpedoes.
+    pub fn has_launchers(&self) -> bool {
+        self.missile_launchers + self.torpedo_launchers > 0
+    }
+}
+
+/// Returns the capabilities of a ship class, or `None` for classes that
+/// aren't ships.
+pub fn capabilities(class: Class) -> Option<Capabilities> {
+    let stats = class_stats(class)?;
+    let guns: &'static [GunStats] = match class {
+        Class::Fighter | Class::Corvette => &[VULCAN],
+        Class::Frigate => &[FRIGATE_MAIN_GUN, VULCAN, VULCAN],
+        Class::Cruiser => &[CRUISER_FLAK],
+        Class::Turret => &[TURRET_GUN],
+        _ => &[],
+    };
+    let (missile_launchers, torpedo_launchers) = match class {
+        Class::Fighter | Class::Frigate => (1, 0),
+        Class::Corvette => (2, 0),
+        Class::Cruiser => (2, 1),
+        _ => (0, 0),
+    };
+    Some(Capabilities {
+        stats,
+        guns,
+        missile_launchers,
+        torpedo_launchers,
+    })
+}
+
+impl ScanResult {
+    /// Returns the capabilities of the contact's class. See
+    /// [`capabilities`].
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        capabilities(self.class)
+    }
+}
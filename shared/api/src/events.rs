@@ -0,0 +1,108 @@
+//! Event callbacks called before `tick`.
+//!
+//! Implement [`Events`] for your `Ship` to be notified of events instead of
+//! checking for them every tick:
+//!
+//! ```
+//! use oort_api::events::Events;
+//! use oort_api::prelude::*;
+//!
+//! pub struct Ship {
+//!     target: Option<Vec2>,
+//! }
+//!
+//! impl Events for Ship {
+//!     fn on_radar_contact(&mut self, contact: &ScanResult) {
+//!         self.target = Some(contact.position);
+//!     }
+//!
+//!     fn on_damage(&mut self, amount: f64) {
+//!         debug!("took {} damage", amount);
+//!     }
+//! }
+//! ```
+//!
+//! All callbacks have empty default implementations, and ships that don't
+//! implement `Events` are only ticked.
+
+use crate::prelude::radio_internal::{radio_indices, MAX_RADIOS};
+use crate::prelude::{scan, Message, ScanResult};
+use crate::sys::{read_system_state, read_system_state_u64};
+use crate::SystemState;
+
+/// Callbacks for events that happened since the previous tick.
+pub trait Events {
+    /// Called when the radar has a contact.
+    fn on_radar_contact(&mut self, _contact: &ScanResult) {}
+
+    /// Called when the ship took damage.
+    fn on_damage(&mut self, _amount: f64) {}
+
+    /// Called when radio `radio` received a message.
+    fn on_message(&mut self, _radio: usize, _message: Message) {}
+
+    /// Called when weapon `index` launched a missile or torpedo.
+    fn on_launch(&mut self, _index: usize) {}
+
+    /// Called when the missile launcher at weapon `index` finished reloading.
+    fn on_reload(&mut self, _index: usize) {}
+}
+
+/// Calls the callbacks for any events that happened since the previous tick.
+pub fn dispatch<T: Events>(ship: &mut T) {
+    let damage = read_system_state(SystemState::DamageTaken);
+    if damage > 0.0 {
+        ship.on_damage(damage);
+    }
+
+    if let Some(contact) = scan() {
+        ship.on_radar_contact(&contact);
+    }
+
+    for radio in 0..MAX_RADIOS {
+        let idxs = radio_indices(radio);
+        if read_system_state(idxs.receive) != 0.0 {
+            let message = idxs.data.map(read_system_state);
+            ship.on_message(radio, message);
+        }
+    }
+
+    let launched = read_system_state_u64(SystemState::LaunchedWeapons);
+    for index in 0..64 {
+        if launched & (1 << index) != 0 {
+            ship.on_launch(index);
+        }
+    }
+
+    let reloaded = read_system_state_u64(SystemState::ReloadedWeapons);
+    for index in 0..64 {
+        if reloaded & (1 << index) != 0 {
+            ship.on_reload(index);
+        }
+    }
+}
+
+// Lets the AI wrapper call `dispatch` only if the user's ship implements
+// `Events`, using autoref-based specialization.
+#[doc(hidden)]
+pub struct Dispatcher<'a, T>(pub &'a mut T);
+
+#[doc(hidden)]
+pub trait DispatchEvents {
+    fn dispatch(&mut self);
+}
+
+impl<T: Events> DispatchEvents for Dispatcher<'_, T> {
+    fn dispatch(&mut self) {
+        dispatch(self.0);
+    }
+}
+
+#[doc(hidden)]
+pub trait DispatchNone {
+    fn dispatch(&mut self);
+}
+
+impl<T> DispatchNone for &mut Dispatcher<'_, T> {
+    fn dispatch(&mut self) {}
+}
@@ -0,0 +1,245 @@
+//! Geometry for firing arcs, radar beams and obstacles.
+//!
+//! Angles are in radians, counter-clockwise from the positive x axis, like
+//! [`heading`](crate::prelude::heading). Arcs and sectors are given by the
+//! heading of their centerline and their total angular width.
+//!
+//! ```
+//! use oort_api::prelude::*;
+//!
+//! // A turret at the origin facing up, able to cover 90 degrees.
+//! let arc = Sector::new(vec2(0.0, 0.0), PI / 2.0, PI / 2.0, 1000.0);
+//! assert!(arc.contains(vec2(100.0, 500.0)));
+//! assert!(!arc.contains(vec2(500.0, 100.0)));
+//!
+//! // Does a bullet fired along the x axis pass through an asteroid?
+//! let hit = ray_circle_intersection(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(500.0, 10.0), 50.0);
+//! assert!(hit.is_some());
+//! ```
+
+use crate::prelude::{angle_diff, vec2, Vec2, Vec2Extras, TAU};
+
+fn is_clockwise(v0: Vec2, v1: Vec2) -> bool {
+    -v0.x * v1.y + v0.y * v1.x > 0.0
+}
+
+/// Returns true if `point` lies within `width / 2` radians of `heading` as
+/// seen from `origin`, at any distance.
+pub fn in_arc(origin: Vec2, heading: f64, width: f64, point: Vec2) -> bool {
+    if width >= TAU {
+        return true;
+    }
+    let ray0 = vec2(1.0, 0.0).rotate(heading - width * 0.5);
+    let ray1 = vec2(1.0, 0.0).rotate(heading + width * 0.5);
+    let dp = point - origin;
+    if is_clockwise(ray1, ray0) {
+        !is_clockwise(ray0, dp) && is_clockwise(ray1, dp)
+    } else {
+        is_clockwise(ray1, dp) || !is_clockwise(ray0, dp)
+    }
+}
+
+/// Returns true if the arcs centered on `heading0` and `heading1` with the
+/// given widths share any direction.
+pub fn arcs_overlap(heading0: f64, width0: f64, heading1: f64, width1: f64) -> bool {
+    angle_diff(heading0, heading1).abs() <= (width0 + width1) * 0.5
+}
+
+/// Returns the distance along the ray from `origin` in `direction` to where
+/// it first touches the circle, or `None` if it misses. Returns zero if
+/// `origin` is inside the circle.
+pub fn ray_circle_intersection(
+    origin: Vec2,
+    direction: Vec2,
+    center: Vec2,
+    radius: f64,
+) -> Option<f64> {
+    let direction = direction.normalize();
+    let dp = center - origin;
+    if dp.dot(dp) <= radius * radius {
+        return Some(0.0);
+    }
+    let along = dp.dot(direction);
+    if along < 0.0 {
+        return None;
+    }
+    let across_squared = dp.dot(dp) - along * along;
+    if across_squared > radius * radius {
+        return None;
+    }
+    Some(along - (radius * radius - across_squared).sqrt())
+}
+
+/// Returns true if the line segment from `start` to `end` touches the circle.
+pub fn segment_intersects_circle(start: Vec2, end: Vec2, center: Vec2, radius: f64) -> bool {
+    let d = end - start;
+    if d.dot(d) == 0.0 {
+        return start.distance(center) <= radius;
+    }
+    let t = ((center - start).dot(d) / d.dot(d)).clamp(0.0, 1.0);
+    (start + d * t).distance(center) <= radius
+}
+
+/// Returns the points where lines from `point` touch the circle, or `None`
+/// if `point` is inside it.
+///
+/// The first point is counter-clockwise of the line from `point` to `center`
+/// and the second is clockwise. Steering for either one passes the circle at
+/// a distance of exactly `radius`.
+pub fn tangent_points(point: Vec2, center: Vec2, radius: f64) -> Option<(Vec2, Vec2)> {
+    let dp = center - point;
+    let distance = dp.length();
+    if distance < radius {
+        return None;
+    }
+    // Angle at the center between `point` and each tangent point.
+    let angle = (radius / distance).acos();
+    let back = (point - center).normalize() * radius;
+    Some((center + back.rotate(-angle), center + back.rotate(angle)))
+}
+
+/// A circular sector: the points within `radius` of `center` and within
+/// `width / 2` radians of `heading`.
+///
+/// Useful for weapon firing arcs and radar beams.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sector {
+    /// The apex of the sector.
+    pub center: Vec2,
+    /// Direction of the sector's centerline (in radians).
+    pub heading: f64,
+    /// Total angle covered (in radians).
+    pub width: f64,
+    /// Distance from the apex to the curved edge (in meters).
+    pub radius: f64,
+}
+
+impl Sector {
+    /// Returns a sector with the given apex, centerline, width and radius.
+    pub fn new(center: Vec2, heading: f64, width: f64, radius: f64) -> Self {
+        Self {
+            center,
+            heading,
+            width,
+            radius,
+        }
+    }
+
+    /// Returns true if `point` is inside the sector, including its edges.
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.distance(self.center) <= self.radius
+            && (point == self.center || in_arc(self.center, self.heading, self.width, point))
+    }
+
+    /// Returns true if this sector and `other` share any point.
+    pub fn overlaps(&self, other: &Sector) -> bool {
+        // Without crossing boundaries, overlapping sectors must contain one
+        // another, and then the inner one's center is inside the outer one.
+        if self.contains(other.center) || other.contains(self.center) {
+            return true;
+        }
+        let edges0 = self.edges();
+        let edges1 = other.edges();
+        for &(a, b) in edges0.iter() {
+            for &(c, d) in edges1.iter() {
+                if segments_intersect(a, b, c, d) {
+                    return true;
+                }
+            }
+            if other.arc_crosses_segment(a, b) {
+                return true;
+            }
+        }
+        for &(c, d) in edges1.iter() {
+            if self.arc_crosses_segment(c, d) {
+                return true;
+            }
+        }
+        self.arc_crosses_arc(other)
+    }
+
+    // The straight edges, empty for a full circle.
+    fn edges(&self) -> Vec<(Vec2, Vec2)> {
+        if self.width >= TAU {
+            return vec![];
+        }
+        [-0.5, 0.5]
+            .iter()
+            .map(|side| {
+                let edge = vec2(self.radius, 0.0).rotate(self.heading + self.width * side);
+                (self.center, self.center + edge)
+            })
+            .collect()
+    }
+
+    fn on_arc(&self, point: Vec2) -> bool {
+        in_arc(self.center, self.heading, self.width, point)
+    }
+
+    fn arc_crosses_segment(&self, start: Vec2, end: Vec2) -> bool {
+        let d = end - start;
+        let f = start - self.center;
+        let a = d.dot(d);
+        if a == 0.0 {
+            return false;
+        }
+        let b = 2.0 * f.dot(d);
+        let c = f.dot(f) - self.radius * self.radius;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return false;
+        }
+        let root = discriminant.sqrt();
+        [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+            .iter()
+            .any(|&t| (0.0..=1.0).contains(&t) && self.on_arc(start + d * t))
+    }
+
+    fn arc_crosses_arc(&self, other: &Sector) -> bool {
+        let dp = other.center - self.center;
+        let distance = dp.length();
+        if distance == 0.0
+            || distance > self.radius + other.radius
+            || distance < (self.radius - other.radius).abs()
+        {
+            return false;
+        }
+        // Distance from this center, along `dp`, to the chord joining the
+        // two intersection points.
+        let along = (distance * distance + self.radius * self.radius - other.radius * other.radius)
+            / (2.0 * distance);
+        let across = (self.radius * self.radius - along * along).max(0.0).sqrt();
+        let u = dp / distance;
+        let mid = self.center + u * along;
+        let perpendicular = vec2(-u.y, u.x) * across;
+        [mid + perpendicular, mid - perpendicular]
+            .iter()
+            .any(|&p| self.on_arc(p) && other.on_arc(p))
+    }
+}
+
+fn cross(v0: Vec2, v1: Vec2) -> f64 {
+    v0.x * v1.y - v0.y * v1.x
+}
+
+fn segments_intersect(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let r = b - a;
+    let s = d - c;
+    let denominator = cross(r, s);
+    if denominator == 0.0 {
+        // Parallel. Only collinear overlapping segments touch.
+        if cross(c - a, r) != 0.0 {
+            return false;
+        }
+        let rr = r.dot(r);
+        if rr == 0.0 {
+            return a == c;
+        }
+        let t0 = (c - a).dot(r) / rr;
+        let t1 = (d - a).dot(r) / rr;
+        return t0.min(t1) <= 1.0 && t0.max(t1) >= 0.0;
+    }
+    let t = cross(c - a, s) / denominator;
+    let u = cross(c - a, r) / denominator;
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
@@ -1,8 +1,14 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+pub mod behavior;
+pub mod constants;
+pub mod events;
+pub mod geometry;
 #[doc(hidden)]
 pub mod panic;
+pub mod team;
+pub mod testing;
 mod vec;
 
 #[allow(missing_docs)]
@@ -145,13 +151,163 @@ pub enum SystemState {
 
     Id,
 
+    VelocityFieldX,
+    VelocityFieldY,
+
+    TransponderDisabled,
+
+    // Heat of guns 0-3, packed as 16-bit fixed point.
+    GunHeat,
+
+    TeamResources,
+    // Class of the requested reinforcement plus one, or zero for none.
+    ReinforcementRequest,
+
+    CollisionAssist,
+
+    // 0 = disabled, 1 = enabled.
+    RadarClosingSpeedFilter,
+    RadarMinClosingSpeed,
+    RadarMaxClosingSpeed,
+
+    // Damage taken since the previous tick.
+    DamageTaken,
+    // Bitmask of weapon indices that launched a missile or torpedo since the previous tick.
+    LaunchedWeapons,
+
+    FlakMode,
+
+    // Signed orbit radius: positive is counterclockwise, negative is
+    // clockwise, zero disables the orbit autopilot.
+    OrbitRadius,
+    // Packed with `pack_f32_pair`.
+    OrbitContactPosition,
+    OrbitContactVelocity,
+
+    // Point all turreted guns should aim at, packed with `pack_f32_pair`. Zero
+    // means no request.
+    AimAllTarget,
+    GunConvergence,
+
+    // Acceleration and angular acceleration measured over the previous tick.
+    ImuAccelerationX,
+    ImuAccelerationY,
+    ImuAngularAcceleration,
+
+    // Lifetime in seconds of launched missiles and torpedoes, or zero for the
+    // class default.
+    LaunchTtl,
+    Disarm,
+
+    // Points to fire guns 0-3 at, packed with `pack_f32_pair`. Zero means no
+    // request.
+    FireAt0,
+    FireAt1,
+    FireAt2,
+    FireAt3,
+
+    // Fraction of full radar power.
+    RadarPower,
+    // Strongest enemy radar illuminating this ship.
+    RadarWarningFound,
+    RadarWarningBearing,
+    RadarWarningRssi,
+
+    TeamLogPointer,
+    TeamLogLength,
+
+    // Bitmask of weapon indices ready to fire.
+    ReadyWeapons,
+    // Bitmask of weapon indices whose missile launcher finished reloading
+    // since the previous tick.
+    ReloadedWeapons,
+
+    // Instructions this ship may execute per tick.
+    InstructionBudget,
+
+    // Mass in kg.
+    Mass,
+    // Moment of inertia about the center of mass in kg·m².
+    MomentOfInertia,
+
+    // Target designated for missiles and torpedoes launched this tick.
+    DesignationPosition,
+    DesignationVelocity,
+    DesignationClass,
+
+    // Rounds left for each weapon.
+    Ammo0,
+    Ammo1,
+    Ammo2,
+    Ammo3,
+
+    // Hard limit on radar contact distance.
+    RadarMaxRange,
+
+    // Headings for each shot of a burst, set by `fire_spread`. A count of
+    // zero means no request.
+    FireSpreadWeapon,
+    FireSpreadCount,
+    FireSpread0,
+    FireSpread1,
+    FireSpread2,
+    FireSpread3,
+    FireSpread4,
+    FireSpread5,
+    FireSpread6,
+    FireSpread7,
+
+    // 0 = disabled, 1 = enabled.
+    HomeOnJam,
+    // Strongest jammer hiding every radar contact.
+    JamFound,
+    JamBearing,
+
+    // Seconds until the motor stops thrusting forward. Infinite without a
+    // burn profile.
+    BurnTimeRemaining,
+
+    // Firing arc of guns 0-3 relative to the ship's heading, packed with
+    // `pack_f32_pair`.
+    GunArc0,
+    GunArc1,
+    GunArc2,
+    GunArc3,
+
+    // Seconds since the radar contact was last seen, zero if seen this tick.
+    RadarContactAge,
+    // Seconds a lost radar contact keeps being reported.
+    RadarMemory,
+
+    // 0 = active, 1 = passive.
+    RadarPassive,
+    // Strongest enemy radar heard by the passive radar.
+    PassiveContactFound,
+    PassiveContactBearing,
+    PassiveContactRssi,
+
+    // 0 = disabled, 1 = enabled.
+    RotationStabilizer,
+
     Size,
-    MaxSize = 128,
+    MaxSize = 192,
 }
 
 #[allow(missing_docs)]
 pub const MAX_ENVIRONMENT_SIZE: usize = 1024;
 
+#[doc(hidden)]
+pub const FIRE_SPREAD_SLOTS: [SystemState; api::MAX_FIRE_SPREAD] = [
+    SystemState::FireSpread0,
+    SystemState::FireSpread1,
+    SystemState::FireSpread2,
+    SystemState::FireSpread3,
+    SystemState::FireSpread4,
+    SystemState::FireSpread5,
+    SystemState::FireSpread6,
+    SystemState::FireSpread7,
+];
+
 /// Identifiers for each class of ship.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -163,6 +319,9 @@ pub enum Class {
     Target,
     Missile,
     Torpedo,
+    Station,
+    Turret,
+    Corvette,
     Unknown,
 }
 
@@ -177,6 +336,9 @@ impl Class {
             4 => Class::Target,
             5 => Class::Missile,
             6 => Class::Torpedo,
+            7 => Class::Station,
+            8 => Class::Turret,
+            9 => Class::Corvette,
             _ => Class::Unknown,
         }
     }
@@ -261,6 +423,16 @@ impl From<f64> for EcmMode {
     }
 }
 
+/// Direction of travel for the orbit autopilot.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrbitDirection {
+    /// Counterclockwise around the contact.
+    Counterclockwise,
+    /// Clockwise around the contact.
+    Clockwise,
+}
+
 #[doc(hidden)]
 #[derive(Default, Clone)]
 pub struct Line {
@@ -297,6 +469,11 @@ pub mod sys {
     pub static mut SYSTEM_STATE: [u64; SystemState::MaxSize as usize] =
         [0; SystemState::MaxSize as usize];
 
+    // Read by the simulator so it doesn't overrun SYSTEM_STATE in modules
+    // built against an older API with a smaller array.
+    #[no_mangle]
+    pub static SYSTEM_STATE_SIZE: u32 = SystemState::MaxSize as u32;
+
     pub fn read_system_state_u64(index: SystemState) -> u64 {
         let system_state = unsafe { &SYSTEM_STATE };
         system_state[index as usize]
@@ -315,6 +492,18 @@ pub mod sys {
         write_system_state_u64(index, value.to_bits())
     }
 
+    // Stores two values in one slot at f32 precision.
+    pub fn pack_f32_pair(x: f64, y: f64) -> u64 {
+        (x as f32).to_bits() as u64 | ((y as f32).to_bits() as u64) << 32
+    }
+
+    pub fn unpack_f32_pair(packed: u64) -> (f64, f64) {
+        (
+            f32::from_bits(packed as u32) as f64,
+            f32::from_bits((packed >> 32) as u32) as f64,
+        )
+    }
+
     #[no_mangle]
     pub static mut ENVIRONMENT: [u8; MAX_ENVIRONMENT_SIZE] = [0; MAX_ENVIRONMENT_SIZE];
 
@@ -343,6 +532,7 @@ pub mod sys {
 }
 
 mod math {
+    use crate::vec::{vec2, Vec2, Vec2Extras};
     pub use std::f64::consts::{PI, TAU};
 
     /// Returns the smallest rotation between angles `a` and `b`.
@@ -356,6 +546,185 @@ mod math {
             c
         }
     }
+
+    /// Returns the angle equivalent to `a` in the range (-π, π].
+    ///
+    /// ```
+    /// use oort_api::prelude::*;
+    /// assert!((wrap_angle(1.5 * PI) + 0.5 * PI).abs() < 1e-9);
+    /// assert_eq!(wrap_angle(-PI), PI);
+    /// ```
+    pub fn wrap_angle(a: f64) -> f64 {
+        angle_diff(0.0, a)
+    }
+
+    /// Returns the angle equivalent to `a` in the range [0, 2π), the same
+    /// range as [`heading`](crate::prelude::heading).
+    pub fn normalize_angle(a: f64) -> f64 {
+        let c = a.rem_euclid(TAU);
+        // rem_euclid can round up to exactly TAU for tiny negative inputs.
+        if c >= TAU {
+            0.0
+        } else {
+            c
+        }
+    }
+
+    /// Interpolates from angle `a` to angle `b` the short way around.
+    ///
+    /// Returns `a` when `t` is 0 and `b` when `t` is 1, normalized to [0, 2π).
+    pub fn lerp_angle(a: f64, b: f64, t: f64) -> f64 {
+        normalize_angle(a + angle_diff(a, b) * t)
+    }
+
+    /// Returns the direction from `from` to `to`, in the range [0, 2π).
+    pub fn bearing(from: Vec2, to: Vec2) -> f64 {
+        normalize_angle((to - from).angle())
+    }
+
+    /// Returns how far to turn from `heading` to face `to` from `from`, in the
+    /// range (-π, π]. Positive is counter-clockwise.
+    ///
+    /// ```
+    /// use oort_api::prelude::*;
+    /// // Facing along +x, a point straight above is a quarter turn to the left.
+    /// let turn = relative_bearing(vec2(0.0, 0.0), 0.0, vec2(0.0, 100.0));
+    /// assert!((turn - PI / 2.0).abs() < 1e-9);
+    /// ```
+    pub fn relative_bearing(from: Vec2, heading: f64, to: Vec2) -> f64 {
+        angle_diff(heading, bearing(from, to))
+    }
+
+    /// Returns the vector with the given length and angle.
+    pub fn from_polar(radius: f64, angle: f64) -> Vec2 {
+        vec2(radius * angle.cos(), radius * angle.sin())
+    }
+
+    /// Returns the length and angle of `v`, with the angle in [0, 2π).
+    pub fn to_polar(v: Vec2) -> (f64, f64) {
+        (v.length(), normalize_angle(v.angle()))
+    }
+}
+
+mod guidance {
+    use super::api::{position, velocity, ScanResult};
+    use crate::vec::*;
+
+    /// Returns the lateral acceleration commanded by proportional navigation.
+    ///
+    /// The result is perpendicular to the line of sight to `contact`, with a
+    /// magnitude of `n` times the closing speed times the rotation rate of the
+    /// line of sight. Navigation constants between 3 and 5 work well. Add some
+    /// thrust along the line of sight to close the distance.
+    pub fn pro_nav(contact: &ScanResult, n: f64) -> Vec2 {
+        pro_nav_relative(
+            contact.position - position(),
+            contact.velocity - velocity(),
+            n,
+        )
+    }
+
+    /// Like [`pro_nav`], given the target's position and velocity relative to the ship.
+    pub fn pro_nav_relative(dp: Vec2, dv: Vec2, n: f64) -> Vec2 {
+        let distance_squared = dp.dot(dp);
+        if distance_squared == 0.0 {
+            return vec2(0.0, 0.0);
+        }
+        let distance = distance_squared.sqrt();
+        let closing_speed = -dp.dot(dv) / distance;
+        let los_rate = (dp.x * dv.y - dp.y * dv.x) / distance_squared;
+        let los_normal = vec2(-dp.y, dp.x) / distance;
+        los_normal * (n * closing_speed * los_rate)
+    }
+}
+
+mod prediction {
+    use super::api::{current_time, ScanResult};
+    use crate::vec::*;
+
+    impl ScanResult {
+        /// Returns true if this is a remembered contact the radar didn't see
+        /// this tick. See [`set_radar_memory`](crate::prelude::set_radar_memory).
+        pub fn is_stale(&self) -> bool {
+            self.age > 0.0
+        }
+
+        /// Returns where the contact will be after `dt` seconds, assuming it
+        /// keeps its current velocity.
+        pub fn predict(&self, dt: f64) -> Vec2 {
+            self.position + self.velocity * dt
+        }
+
+        /// Like [`predict`](ScanResult::predict), but assuming the contact
+        /// also keeps accelerating at `acceleration` (in m/s²), e.g. as
+        /// estimated by a [`Track`].
+        pub fn predict_with_acceleration(&self, acceleration: Vec2, dt: f64) -> Vec2 {
+            self.position + self.velocity * dt + acceleration * (0.5 * dt * dt)
+        }
+    }
+
+    /// Follows one contact across ticks to estimate its acceleration.
+    ///
+    /// Call [`update`](Track::update) each tick the radar sees the contact.
+    #[derive(Clone, Debug, Default)]
+    pub struct Track {
+        last: Option<(f64, ScanResult)>,
+        acceleration: Vec2,
+    }
+
+    impl Track {
+        /// Weight given to each new acceleration sample. Radar velocities are
+        /// noisy, so the estimate is smoothed over several ticks.
+        pub const SMOOTHING: f64 = 0.2;
+
+        /// Gaps longer than this (in seconds) start a new track.
+        pub const MAX_GAP: f64 = 1.0;
+
+        /// Returns a track with no history.
+        pub fn new() -> Track {
+            Default::default()
+        }
+
+        /// Adds a new observation of the contact made this tick.
+        pub fn update(&mut self, contact: &ScanResult) {
+            self.update_at(current_time(), contact);
+        }
+
+        /// Adds a new observation of the contact made at `time` (in seconds).
+        pub fn update_at(&mut self, time: f64, contact: &ScanResult) {
+            match self.last.as_ref() {
+                Some((last_time, last))
+                    if time > *last_time
+                        && time - last_time <= Self::MAX_GAP
+                        && last.class == contact.class =>
+                {
+                    let sample = (contact.velocity - last.velocity) / (time - last_time);
+                    self.acceleration =
+                        self.acceleration + (sample - self.acceleration) * Self::SMOOTHING;
+                }
+                Some((last_time, _)) if time == *last_time => return,
+                _ => self.acceleration = vec2(0.0, 0.0),
+            }
+            self.last = Some((time, contact.clone()));
+        }
+
+        /// Returns the most recent observation.
+        pub fn contact(&self) -> Option<&ScanResult> {
+            self.last.as_ref().map(|(_, contact)| contact)
+        }
+
+        /// Returns the estimated acceleration of the contact (in m/s²).
+        pub fn acceleration(&self) -> Vec2 {
+            self.acceleration
+        }
+
+        /// Returns where the contact will be `dt` seconds after the most
+        /// recent observation, or None if there is none.
+        pub fn predict(&self, dt: f64) -> Option<Vec2> {
+            self.contact()
+                .map(|contact| contact.predict_with_acceleration(self.acceleration, dt))
+        }
+    }
 }
 
 mod rng {
@@ -365,9 +734,27 @@ mod rng {
     }
 
     /// Returns a random number between `low` and `high`.
+    ///
+    /// Each ship has its own generator, seeded from the scenario seed and the
+    /// ship, so a match replayed with the same seed gets the same numbers.
     pub fn rand(low: f64, high: f64) -> f64 {
         rng().rand_float() * (high - low) + low
     }
+
+    /// Returns a random integer at least `low` and less than `high`, or
+    /// `low` if the range is empty.
+    pub fn rand_int(low: i64, high: i64) -> i64 {
+        if high <= low {
+            return low;
+        }
+        let span = high.wrapping_sub(low) as u64;
+        low.wrapping_add(rng().rand_range(0..span) as i64)
+    }
+
+    /// Returns true with the given probability.
+    pub fn rand_bool(probability: f64) -> bool {
+        rng().rand_float() < probability
+    }
 }
 
 #[doc(hidden)]
@@ -399,18 +786,30 @@ pub mod rng_state {
 
 mod api {
     use super::sys::{read_system_state, write_system_state};
-    use super::{Ability, Class, EcmMode, SystemState};
-    use crate::sys::{read_system_state_u64, write_system_state_u64};
+    use super::{Ability, Class, EcmMode, OrbitDirection, SystemState};
+    use crate::sys::{
+        pack_f32_pair, read_system_state_u64, unpack_f32_pair, write_system_state_u64,
+    };
     use crate::{vec::*, ActiveAbilities, Message};
 
     /// The time between each simulation tick.
-    pub const TICK_LENGTH: f64 = 1.0 / 60.0;
+    pub const TICK_LENGTH: f64 = crate::constants::TICK_LENGTH;
+
+    /// Maximum number of headings used by [`fire_spread`].
+    pub const MAX_FIRE_SPREAD: usize = 8;
 
     /// Returns a per-ship ID that is unique within a team.
     pub fn id() -> u32 {
         read_system_state(SystemState::Id) as u32
     }
 
+    /// Returns the orders given to this ship by the team's
+    /// [`on_scenario_start`](crate::team::ScenarioStart::on_scenario_start)
+    /// hook, if any.
+    pub fn orders() -> Option<Message> {
+        crate::team::orders_for(id())
+    }
+
     /// Returns the ship [`Class`] (Fighter, Cruiser, etc).
     pub fn class() -> Class {
         Class::from_f64(read_system_state(SystemState::Class))
@@ -434,6 +833,70 @@ mod api {
             .unwrap_or(0.0)
     }
 
+    /// Returns true if bullets damage asteroids in this scenario.
+    pub fn asteroids_destructible() -> bool {
+        matches!(
+            super::sys::getenv("ASTEROID_TERRAIN").unwrap_or("destructible"),
+            "destructible" | "splitting"
+        )
+    }
+
+    /// Returns true if destroyed asteroids break into smaller ones in this
+    /// scenario. The fragments share the asteroid's momentum.
+    pub fn asteroids_split() -> bool {
+        super::sys::getenv("ASTEROID_TERRAIN").unwrap_or("destructible") == "splitting"
+    }
+
+    /// Returns true if missile and torpedo seekers in this scenario have a
+    /// Doppler notch, which hides targets that aren't closing with the seeker.
+    /// See [`DOPPLER_NOTCH_GAIN`](crate::constants::DOPPLER_NOTCH_GAIN).
+    pub fn seeker_doppler_notch() -> bool {
+        super::sys::getenv("SEEKER_DOPPLER_NOTCH").unwrap_or("false") == "true"
+    }
+
+    /// Returns true if asteroids stop bullets in this scenario, whether or
+    /// not they take damage. Otherwise bullets pass through them.
+    pub fn asteroids_block_bullets() -> bool {
+        super::sys::getenv("ASTEROID_TERRAIN").unwrap_or("destructible") != "intangible"
+    }
+
+    /// Returns the minimum and maximum corners of the world (in meters).
+    ///
+    /// Ships that touch the boundary are destroyed.
+    pub fn world_bounds() -> (Vec2, Vec2) {
+        let half_size = world_size() / 2.0;
+        (vec2(-half_size, -half_size), vec2(half_size, half_size))
+    }
+
+    /// Returns the distance (in meters) from the current position to the
+    /// world boundary along the given heading.
+    pub fn distance_to_boundary(heading: f64) -> f64 {
+        if world_size() <= 0.0 {
+            return f64::INFINITY;
+        }
+        distance_to_bounds(position(), heading, world_bounds())
+    }
+
+    /// Returns the distance from `position` to the edge of `bounds` along the
+    /// given heading.
+    pub fn distance_to_bounds(position: Vec2, heading: f64, bounds: (Vec2, Vec2)) -> f64 {
+        let (min, max) = bounds;
+        let axis_distance = |p: f64, d: f64, lo: f64, hi: f64| {
+            if d > 1e-12 {
+                (hi - p) / d
+            } else if d < -1e-12 {
+                (lo - p) / d
+            } else {
+                f64::INFINITY
+            }
+        };
+        let dx = heading.cos();
+        let dy = heading.sin();
+        axis_distance(position.x, dx, min.x, max.x)
+            .min(axis_distance(position.y, dy, min.y, max.y))
+            .max(0.0)
+    }
+
     /// Returns the current position (in meters).
     pub fn position() -> Vec2 {
         vec2(
@@ -450,6 +913,28 @@ mod api {
         )
     }
 
+    /// Returns the background velocity field at the ship's position (in m/s).
+    ///
+    /// Some scenarios move asteroids along this field, e.g. orbiting a central body.
+    pub fn velocity_field() -> Vec2 {
+        vec2(
+            read_system_state(SystemState::VelocityFieldX),
+            read_system_state(SystemState::VelocityFieldY),
+        )
+    }
+
+    /// Returns the scenario's linear drag (in 1/s).
+    ///
+    /// In empty space this is zero. In a nebula every moving object, including
+    /// bullets, slows down: with no acceleration, velocity decays by a factor
+    /// of `(-drag() * t).exp()` over `t` seconds.
+    pub fn drag() -> f64 {
+        super::sys::getenv("DRAG")
+            .unwrap_or("0.0")
+            .parse()
+            .unwrap_or(0.0)
+    }
+
     /// Returns the current heading (in radians).
     pub fn heading() -> f64 {
         read_system_state(SystemState::Heading)
@@ -460,6 +945,24 @@ mod api {
         read_system_state(SystemState::AngularVelocity)
     }
 
+    /// Returns the acceleration measured over the previous tick (in m/s²).
+    ///
+    /// Unlike the value passed to `accelerate` this is what actually
+    /// happened, after clamping, running out of fuel and collisions. It may be
+    /// noisy.
+    pub fn imu_acceleration() -> Vec2 {
+        vec2(
+            read_system_state(SystemState::ImuAccelerationX),
+            read_system_state(SystemState::ImuAccelerationY),
+        )
+    }
+
+    /// Returns the angular acceleration measured over the previous tick (in
+    /// radians/s²). It may be noisy.
+    pub fn imu_angular_acceleration() -> f64 {
+        read_system_state(SystemState::ImuAngularAcceleration)
+    }
+
     /// Sets the linear acceleration for the next tick (in m/s²).
     pub fn accelerate(mut acceleration: Vec2) {
         acceleration = acceleration.rotate(-heading());
@@ -494,7 +997,8 @@ mod api {
     /// Aims a turreted weapon.
     ///
     /// `index` selects the weapon.
-    /// `heading` is in radians.
+    /// `heading` is in radians. A turret can't turn past the edges of its
+    /// [`gun_arc`] and fires along the closest edge instead.
     pub fn aim(index: usize, heading: f64) {
         let state_index = match index {
             0 => SystemState::Aim0,
@@ -506,6 +1010,50 @@ mod api {
         write_system_state(state_index, heading);
     }
 
+    /// Aims all turreted weapons at a point.
+    ///
+    /// The simulator accounts for where each gun is mounted on the ship, so
+    /// the shots converge on `point` (a world position) rather than flying
+    /// parallel. Call `fire` afterwards on the guns you want to shoot.
+    pub fn aim_all_at(point: Vec2) {
+        write_system_state_u64(SystemState::AimAllTarget, pack_point(point));
+    }
+
+    fn pack_point(point: Vec2) -> u64 {
+        match pack_f32_pair(point.x, point.y) {
+            // Zero is reserved for no request.
+            0 => pack_f32_pair(-0.0, 0.0),
+            packed => packed,
+        }
+    }
+
+    /// Aims all turreted weapons along `heading`.
+    ///
+    /// The shots converge at the distance set with `set_gun_convergence`, or
+    /// fly parallel if it is zero.
+    pub fn aim_all(heading: f64) {
+        let convergence = gun_convergence();
+        if convergence > 0.0 {
+            aim_all_at(position() + vec2(convergence, 0.0).rotate(heading));
+        } else {
+            for index in 0..4 {
+                aim(index, heading);
+            }
+        }
+    }
+
+    /// Returns the distance at which `aim_all` makes shots converge.
+    pub fn gun_convergence() -> f64 {
+        read_system_state(SystemState::GunConvergence)
+    }
+
+    /// Sets the distance (in meters) at which `aim_all` makes shots converge.
+    ///
+    /// Zero, the default, disables convergence.
+    pub fn set_gun_convergence(distance: f64) {
+        write_system_state(SystemState::GunConvergence, distance.max(0.0));
+    }
+
     /// Fires a weapon.
     ///
     /// `index` selects the weapon.
@@ -520,6 +1068,65 @@ mod api {
         write_system_state(state_index, 1.0);
     }
 
+    /// Fires a burst weapon with one shot along each of `headings` (in
+    /// radians), instead of scattering the burst randomly around its aim.
+    ///
+    /// Only guns that fire several bullets at once, like the cruiser's main
+    /// gun, use the headings; other weapons fire as if `fire` was called.
+    /// Each heading is limited to the gun's firing arc and still gets the
+    /// gun's usual inaccuracy. Shots without a heading use the gun's aim and
+    /// extra headings are ignored. Only one weapon per tick can fire a
+    /// spread: a later call replaces an earlier one.
+    pub fn fire_spread(index: usize, headings: &[f64]) {
+        if index > 3 {
+            return;
+        }
+        let headings = &headings[..headings.len().min(MAX_FIRE_SPREAD)];
+        for (slot, heading) in crate::FIRE_SPREAD_SLOTS.iter().zip(headings.iter()) {
+            write_system_state(*slot, *heading);
+        }
+        write_system_state(SystemState::FireSpreadWeapon, index as f64);
+        write_system_state(SystemState::FireSpreadCount, headings.len() as f64);
+    }
+
+    /// Fires a gun so that its bullet passes through `point`.
+    ///
+    /// The simulator picks the gun's angle when the shot is fired, using the
+    /// exact position and velocity of the muzzle at that moment, so there's no
+    /// error from aiming with last tick's state. `point` should be where the
+    /// target will be when the bullet arrives. Only turreted guns can be
+    /// aimed; other weapons fire as if `fire` was called.
+    pub fn fire_at(index: usize, point: Vec2) {
+        let state_index = match index {
+            0 => SystemState::FireAt0,
+            1 => SystemState::FireAt1,
+            2 => SystemState::FireAt2,
+            3 => SystemState::FireAt3,
+            _ => return,
+        };
+        write_system_state_u64(state_index, pack_point(point));
+    }
+
+    /// Launches the missile or torpedo at weapon `index`, designating
+    /// `contact` as its target.
+    ///
+    /// The missile's radar starts out pointed at the contact, and its first
+    /// call to `scan` returns the contact (extrapolated by one tick) if the
+    /// radar doesn't find anything itself. This avoids flying blind for the
+    /// first few ticks after launch.
+    pub fn launch_at(index: usize, contact: &ScanResult) {
+        write_system_state_u64(
+            SystemState::DesignationPosition,
+            pack_point(contact.position),
+        );
+        write_system_state_u64(
+            SystemState::DesignationVelocity,
+            pack_f32_pair(contact.velocity.x, contact.velocity.y),
+        );
+        write_system_state(SystemState::DesignationClass, contact.class as u32 as f64);
+        fire(index);
+    }
+
     /// Returns the number of ticks until a weapon is ready to fire.
     ///
     /// `index` selects the weapon. Returns 0 if the weapon is ready.
@@ -534,6 +1141,65 @@ mod api {
         read_system_state(state_index) as u32
     }
 
+    /// Returns true if a weapon is ready to fire.
+    ///
+    /// Unlike `reload_ticks` this works for any weapon index, and also
+    /// accounts for overheated guns.
+    pub fn weapon_ready(index: usize) -> bool {
+        index < 64 && read_system_state_u64(SystemState::ReadyWeapons) & (1 << index) != 0
+    }
+
+    /// Returns true if the missile launcher at weapon `index` finished
+    /// reloading since the previous tick.
+    pub fn weapon_reloaded(index: usize) -> bool {
+        index < 64 && read_system_state_u64(SystemState::ReloadedWeapons) & (1 << index) != 0
+    }
+
+    /// Returns the number of rounds left for a weapon.
+    ///
+    /// Weapons with unlimited ammunition, including missile launchers, return
+    /// infinity. Some scenarios let ships near a friendly station resupply.
+    pub fn ammo(index: usize) -> f64 {
+        let state_index = match index {
+            0 => SystemState::Ammo0,
+            1 => SystemState::Ammo1,
+            2 => SystemState::Ammo2,
+            3 => SystemState::Ammo3,
+            _ => return 0.0,
+        };
+        read_system_state(state_index)
+    }
+
+    /// Returns the heat of a gun, from 0 to 1.
+    ///
+    /// Each shot heats the gun and it cools down over time. Hotter guns are
+    /// less accurate. A gun reaching full heat overheats and can't fire until
+    /// it has cooled down to 0.25.
+    pub fn gun_heat(index: usize) -> f64 {
+        if index >= 4 {
+            return 0.0;
+        }
+        let packed = read_system_state_u64(SystemState::GunHeat);
+        ((packed >> (16 * index)) & 0xffff) as f64 / u16::MAX as f64
+    }
+
+    /// Returns the firing arc of a gun relative to the ship's heading, as the
+    /// angles (in radians) of its clockwise and counter-clockwise edges.
+    ///
+    /// Fixed guns return the same angle twice. Turrets covering every
+    /// direction return `(0, 2π)`. Returns `(0, 0)` if there is no gun at
+    /// `index`.
+    pub fn gun_arc(index: usize) -> (f64, f64) {
+        let state_index = match index {
+            0 => SystemState::GunArc0,
+            1 => SystemState::GunArc1,
+            2 => SystemState::GunArc2,
+            3 => SystemState::GunArc3,
+            _ => return (0.0, 0.0),
+        };
+        unpack_f32_pair(read_system_state_u64(state_index))
+    }
+
     /// Self-destructs, producing a damaging explosion.
     ///
     /// This is commonly used by missiles.
@@ -541,6 +1207,30 @@ mod api {
         write_system_state(SystemState::Explode, 1.0);
     }
 
+    /// Makes this ship's warhead safe.
+    ///
+    /// A disarmed missile or torpedo can still `explode` or run out of time,
+    /// but it won't release any fragments, so it can't hurt anyone nearby.
+    /// This can't be undone.
+    pub fn disarm() {
+        write_system_state(SystemState::Disarm, 1.0);
+    }
+
+    /// Returns the lifetime (in seconds) given to missiles and torpedoes this
+    /// ship launches, or zero for the class default.
+    pub fn launch_ttl() -> f64 {
+        read_system_state(SystemState::LaunchTtl)
+    }
+
+    /// Sets the lifetime (in seconds) of missiles and torpedoes launched from
+    /// now on.
+    ///
+    /// The lifetime is clamped between one second and the class default of
+    /// 60 seconds. Zero restores the default.
+    pub fn set_launch_ttl(seconds: f64) {
+        write_system_state(SystemState::LaunchTtl, seconds.max(0.0));
+    }
+
     /// Returns the current health.
     pub fn health() -> f64 {
         read_system_state(SystemState::Health)
@@ -602,6 +1292,60 @@ mod api {
         write_system_state(SystemState::RadarMaxDistance, dist);
     }
 
+    /// Returns the maximum range of the radar's instruments (in meters).
+    ///
+    /// Contacts beyond this range are never reported, regardless of radar
+    /// power or the distance filter. It depends on the ship class.
+    pub fn radar_max_range() -> f64 {
+        read_system_state(SystemState::RadarMaxRange)
+    }
+
+    /// Gets the current minimum closing speed filter of the radar (in m/s).
+    pub fn radar_min_closing_speed() -> f64 {
+        if read_system_state(SystemState::RadarClosingSpeedFilter) == 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        read_system_state(SystemState::RadarMinClosingSpeed)
+    }
+
+    /// Sets the minimum closing speed filter of the radar (in m/s).
+    ///
+    /// Contacts approaching slower than this are ignored. Receding contacts
+    /// have a negative closing speed. The default is negative infinity.
+    ///
+    /// It takes effect next tick.
+    pub fn set_radar_min_closing_speed(speed: f64) {
+        enable_radar_closing_speed_filter();
+        write_system_state(SystemState::RadarMinClosingSpeed, speed);
+    }
+
+    /// Gets the current maximum closing speed filter of the radar (in m/s).
+    pub fn radar_max_closing_speed() -> f64 {
+        if read_system_state(SystemState::RadarClosingSpeedFilter) == 0.0 {
+            return f64::INFINITY;
+        }
+        read_system_state(SystemState::RadarMaxClosingSpeed)
+    }
+
+    /// Sets the maximum closing speed filter of the radar (in m/s).
+    ///
+    /// Contacts approaching faster than this are ignored. The default is
+    /// infinity.
+    ///
+    /// It takes effect next tick.
+    pub fn set_radar_max_closing_speed(speed: f64) {
+        enable_radar_closing_speed_filter();
+        write_system_state(SystemState::RadarMaxClosingSpeed, speed);
+    }
+
+    fn enable_radar_closing_speed_filter() {
+        if read_system_state(SystemState::RadarClosingSpeedFilter) == 0.0 {
+            write_system_state(SystemState::RadarClosingSpeedFilter, 1.0);
+            write_system_state(SystemState::RadarMinClosingSpeed, f64::NEG_INFINITY);
+            write_system_state(SystemState::RadarMaxClosingSpeed, f64::INFINITY);
+        }
+    }
+
     /// Gets the Electronic Counter Measures (ECM) mode.
     pub fn radar_ecm_mode() -> EcmMode {
         read_system_state(SystemState::RadarEcmMode).into()
@@ -612,6 +1356,75 @@ mod api {
         write_system_state(SystemState::RadarEcmMode, mode as u32 as f64);
     }
 
+    /// Returns the fraction of full power the radar is emitting.
+    pub fn radar_power() -> f64 {
+        read_system_state(SystemState::RadarPower)
+    }
+
+    /// Sets the fraction of full power the radar emits, between
+    /// [`MIN_RADAR_POWER`](crate::constants::MIN_RADAR_POWER) and 1.
+    ///
+    /// Detection range scales with the fourth root of power, but the range at
+    /// which enemy warning receivers notice the radar (see [`radar_warning`])
+    /// scales with the square root. A radar at low power can find targets
+    /// before they find it. Power also affects ECM jamming strength.
+    ///
+    /// It takes effect next tick.
+    pub fn set_radar_power(fraction: f64) {
+        write_system_state(SystemState::RadarPower, fraction);
+    }
+
+    /// An enemy radar detected by the radar warning receiver.
+    #[derive(Clone, Debug)]
+    pub struct RadarWarning {
+        /// Direction from this ship to the emitter.
+        pub bearing: f64,
+        /// The received signal strength measured in dBm.
+        pub rssi: f64,
+    }
+
+    /// Returns the strongest enemy radar beam illuminating this ship.
+    ///
+    /// Only radars that are scanning are detected, not ECM jammers.
+    pub fn radar_warning() -> Option<RadarWarning> {
+        if read_system_state(SystemState::RadarWarningFound) == 0.0 {
+            return None;
+        }
+        Some(RadarWarning {
+            bearing: read_system_state(SystemState::RadarWarningBearing),
+            rssi: read_system_state(SystemState::RadarWarningRssi),
+        })
+    }
+
+    /// Returns the bearing (in radians) to the strongest jammer if it drowned
+    /// out every contact in the radar beam on the previous tick.
+    ///
+    /// Always `None` for ships other than torpedoes.
+    pub fn jam_bearing() -> Option<f64> {
+        if read_system_state(SystemState::JamFound) != 0.0 {
+            Some(read_system_state(SystemState::JamBearing))
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the IFF transponder is enabled.
+    pub fn transponder() -> bool {
+        read_system_state(SystemState::TransponderDisabled) == 0.0
+    }
+
+    /// Enables or disables the IFF transponder.
+    ///
+    /// Friendly radars ignore ships broadcasting their team's IFF code. A ship
+    /// with its transponder disabled is invisible to this filtering and will
+    /// show up on its own team's radars like any other contact.
+    pub fn set_transponder(enabled: bool) {
+        write_system_state(
+            SystemState::TransponderDisabled,
+            if enabled { 0.0 } else { 1.0 },
+        );
+    }
+
     /// A radar contact.
     #[derive(Clone, Debug)]
     pub struct ScanResult {
@@ -625,6 +1438,12 @@ mod api {
         pub rssi: f64,
         /// The signal-to-noise ratio measured in dB.
         pub snr: f64,
+        /// Seconds since the radar last saw the contact.
+        ///
+        /// Always zero unless [`set_radar_memory`] is used. Then a lost
+        /// contact keeps being reported for a while, moved along its last
+        /// known velocity, with its age counting up.
+        pub age: f64,
     }
 
     /// Returns the radar contact with the highest signal strength.
@@ -644,6 +1463,64 @@ mod api {
             ),
             rssi: read_system_state(SystemState::RadarContactRssi),
             snr: read_system_state(SystemState::RadarContactSnr),
+            age: read_system_state(SystemState::RadarContactAge),
+        })
+    }
+
+    /// Returns how long (in seconds) a lost radar contact keeps being
+    /// reported by [`scan`].
+    pub fn radar_memory() -> f64 {
+        read_system_state(SystemState::RadarMemory)
+    }
+
+    /// Sets how long (in seconds) [`scan`] keeps reporting a contact after
+    /// the radar loses it, up to
+    /// [`MAX_RADAR_MEMORY`](crate::constants::MAX_RADAR_MEMORY).
+    ///
+    /// The remembered contact is moved along its last known velocity and has
+    /// a non-zero [`age`](ScanResult::age). Zero, the default, reports only
+    /// contacts seen this tick. It takes effect next tick.
+    pub fn set_radar_memory(seconds: f64) {
+        write_system_state(SystemState::RadarMemory, seconds);
+    }
+
+    /// Returns whether the radar is in passive mode.
+    pub fn radar_passive() -> bool {
+        read_system_state(SystemState::RadarPassive) != 0.0
+    }
+
+    /// Switches the radar between passive and active modes.
+    ///
+    /// A passive radar stops emitting and listens instead. Enemy warning
+    /// receivers can't detect it and it burns almost no fuel, but [`scan`]
+    /// finds nothing. [`passive_contact`] reports the direction to the
+    /// strongest enemy radar within the beam whose own beam is illuminating
+    /// this ship, without its range. Narrower beams give more accurate
+    /// bearings. It takes effect next tick.
+    pub fn set_radar_passive(enabled: bool) {
+        write_system_state(SystemState::RadarPassive, if enabled { 1.0 } else { 0.0 });
+    }
+
+    /// An enemy radar heard by a passive radar.
+    #[derive(Clone, Debug)]
+    pub struct PassiveContact {
+        /// Direction from this ship to the emitter.
+        pub bearing: f64,
+        /// The received signal strength measured in dBm.
+        pub rssi: f64,
+    }
+
+    /// Returns the strongest enemy radar heard within the beam on the
+    /// previous tick.
+    ///
+    /// Always `None` unless the radar is passive, see [`set_radar_passive`].
+    pub fn passive_contact() -> Option<PassiveContact> {
+        if read_system_state(SystemState::PassiveContactFound) == 0.0 {
+            return None;
+        }
+        Some(PassiveContact {
+            bearing: read_system_state(SystemState::PassiveContactBearing),
+            rssi: read_system_state(SystemState::PassiveContactRssi),
         })
     }
 
@@ -786,7 +1663,31 @@ mod api {
         )
     }
 
+    /// Returns the number of instructions this ship's AI may execute each
+    /// tick. Exceeding it crashes the ship.
+    ///
+    /// Missiles (50k) and torpedoes (100k) get a much smaller budget than
+    /// other ships (1M).
+    pub fn instruction_budget() -> u64 {
+        read_system_state_u64(SystemState::InstructionBudget)
+    }
+
+    /// Returns the mass of this ship (in kg).
+    pub fn mass() -> f64 {
+        read_system_state(SystemState::Mass)
+    }
+
+    /// Returns the moment of inertia of this ship about its center of mass
+    /// (in kg·m²).
+    pub fn moment_of_inertia() -> f64 {
+        read_system_state(SystemState::MomentOfInertia)
+    }
+
     /// Returns the maximum forward acceleration (in m/s²).
+    ///
+    /// For missiles with a burn profile this changes over time: high during
+    /// the boost, lower during the sustain and zero once coasting. See
+    /// [`burn_time_remaining`].
     pub fn max_forward_acceleration() -> f64 {
         read_system_state(SystemState::MaxForwardAcceleration)
     }
@@ -806,6 +1707,32 @@ mod api {
         read_system_state(SystemState::MaxAngularAcceleration)
     }
 
+    /// Returns the number of seconds until this ship's motor burns out and it
+    /// can no longer accelerate forward.
+    ///
+    /// Only missiles launched with a burn profile have a limited burn; this
+    /// returns infinity for every other ship.
+    pub fn burn_time_remaining() -> f64 {
+        read_system_state(SystemState::BurnTimeRemaining)
+    }
+
+    /// Returns the fastest rate (in radians/s) at which this ship can turn its
+    /// velocity vector while moving at `speed` (in m/s), using its maximum
+    /// lateral acceleration.
+    ///
+    /// A missile can compare this to the rotation of the line of sight to its
+    /// target to decide whether an intercept is still possible. Returns
+    /// infinity for a speed of zero.
+    pub fn max_turn_rate_at_speed(speed: f64) -> f64 {
+        max_lateral_acceleration() / speed.abs()
+    }
+
+    /// Returns the radius (in meters) of the tightest circle this ship can fly
+    /// at `speed` (in m/s), using its maximum lateral acceleration.
+    pub fn min_turn_radius_at_speed(speed: f64) -> f64 {
+        speed * speed / max_lateral_acceleration()
+    }
+
     /// Returns the number of ticks elapsed since the simulation began.
     pub fn current_tick() -> u32 {
         read_system_state(SystemState::CurrentTick) as u32
@@ -837,6 +1764,110 @@ mod api {
         ActiveAbilities(read_system_state_u64(SystemState::ActivateAbility))
     }
 
+    /// Returns whether collision avoidance assist is enabled.
+    pub fn collision_assist() -> bool {
+        read_system_state(SystemState::CollisionAssist) != 0.0
+    }
+
+    /// Enables or disables collision avoidance assist.
+    ///
+    /// When enabled the simulator will override this ship's acceleration
+    /// shortly before it would hit a friendly ship or an asteroid.
+    pub fn set_collision_assist(enabled: bool) {
+        write_system_state(
+            SystemState::CollisionAssist,
+            if enabled { 1.0 } else { 0.0 },
+        );
+    }
+
+    /// Returns whether the rotation stabilizer is enabled.
+    pub fn rotation_stabilizer() -> bool {
+        read_system_state(SystemState::RotationStabilizer) != 0.0
+    }
+
+    /// Enables or disables the rotation stabilizer.
+    ///
+    /// When enabled, on any tick where this ship commands no torque (see
+    /// [`torque`] and [`turn`]) the simulator applies torque to bring its
+    /// angular velocity to zero, within the usual angular acceleration limit.
+    pub fn set_rotation_stabilizer(enabled: bool) {
+        write_system_state(
+            SystemState::RotationStabilizer,
+            if enabled { 1.0 } else { 0.0 },
+        );
+    }
+
+    /// Returns whether flak mode is enabled.
+    pub fn flak_mode() -> bool {
+        read_system_state(SystemState::FlakMode) != 0.0
+    }
+
+    /// Enables or disables flak mode.
+    ///
+    /// When enabled the simulator will aim and fire this ship's flak guns at
+    /// the incoming missile or torpedo with the shortest time to impact. Only
+    /// the cruiser's main gun is a flak gun.
+    pub fn set_flak_mode(enabled: bool) {
+        write_system_state(SystemState::FlakMode, if enabled { 1.0 } else { 0.0 });
+    }
+
+    /// Returns whether home-on-jam is enabled.
+    pub fn home_on_jam() -> bool {
+        read_system_state(SystemState::HomeOnJam) != 0.0
+    }
+
+    /// Enables or disables home-on-jam.
+    ///
+    /// When enabled and [`jam_bearing`] returns a bearing, the simulator
+    /// overrides this ship's acceleration and rotation to fly straight at the
+    /// jammer. Your code is back in control as soon as the radar can see
+    /// again. Only torpedoes have the receiver this needs.
+    pub fn set_home_on_jam(enabled: bool) {
+        write_system_state(SystemState::HomeOnJam, if enabled { 1.0 } else { 0.0 });
+    }
+
+    /// Circles `contact` at `radius` meters using the orbit autopilot.
+    ///
+    /// While enabled the simulator sets this ship's acceleration every tick,
+    /// leaving turning and aiming to your code. The contact's position is
+    /// extrapolated using its velocity, so call this again with fresh radar
+    /// data to follow a maneuvering target. Collision assist still takes
+    /// priority.
+    pub fn orbit(contact: &ScanResult, radius: f64, direction: OrbitDirection) {
+        let radius = match direction {
+            OrbitDirection::Counterclockwise => radius.abs(),
+            OrbitDirection::Clockwise => -radius.abs(),
+        };
+        write_system_state(SystemState::OrbitRadius, radius);
+        write_system_state_u64(
+            SystemState::OrbitContactPosition,
+            pack_f32_pair(contact.position.x, contact.position.y),
+        );
+        write_system_state_u64(
+            SystemState::OrbitContactVelocity,
+            pack_f32_pair(contact.velocity.x, contact.velocity.y),
+        );
+    }
+
+    /// Disables the orbit autopilot.
+    pub fn stop_orbit() {
+        write_system_state(SystemState::OrbitRadius, 0.0);
+    }
+
+    /// Returns the resources available to this ship's team.
+    pub fn team_resources() -> f64 {
+        read_system_state(SystemState::TeamResources)
+    }
+
+    /// Requests a reinforcement of the given class.
+    ///
+    /// If the team has enough resources and the scenario defines a spawn point
+    /// the ship will be created at the end of this tick and its cost deducted.
+    /// Fighters cost 100, frigates 400, and cruisers 1000.
+    pub fn request_reinforcement(class: Class) {
+        write_system_state(SystemState::ReinforcementRequest, class as u32 as f64 + 1.0);
+    }
+
     /// Returns the position of the target set by the scenario.
     /// Only used in tutorials.
     pub fn target() -> Vec2 {
@@ -867,6 +1898,7 @@ pub mod dbg {
     static mut TEXT_BUFFER: String = String::new();
     static mut LINE_BUFFER: Vec<Line> = Vec::new();
     static mut DRAWN_TEXT_BUFFER: Vec<Text> = Vec::new();
+    static mut TEAM_LOG_BUFFER: String = String::new();
 
     /// Adds text to be displayed when the ship is selected by clicking on it.
     ///
@@ -887,6 +1919,28 @@ pub mod dbg {
         buf.push('\n');
     }
 
+    /// Adds a line to the team's battle transcript.
+    ///
+    /// Works just like [println!]. Unlike [debug!], lines are kept for the
+    /// whole match and shown in the Transcript tab, which makes it useful for
+    /// narrating what a fleet is doing. Each team can log about 5 lines per
+    /// second, with bursts of up to 20. Lines over the limit are dropped and
+    /// long lines are truncated to 200 bytes.
+    #[macro_export]
+    macro_rules! team_log {
+        ($($arg:tt)*) => {
+            $crate::dbg::write_team_log(std::format_args!($($arg)*))
+        };
+    }
+
+    #[allow(unused)]
+    #[doc(hidden)]
+    pub fn write_team_log(args: std::fmt::Arguments) {
+        let buf = unsafe { &mut TEAM_LOG_BUFFER };
+        let _ = std::fmt::write(buf, args);
+        buf.push('\n');
+    }
+
     /// Creates a 24-bit RGB color from the arguments.
     pub fn rgb(r: u8, g: u8, b: u8) -> u32 {
         let r = r as u32;
@@ -1051,6 +2105,14 @@ pub mod dbg {
                 slice.len() as u32 as f64,
             );
         }
+        {
+            let slice = unsafe { &mut TEAM_LOG_BUFFER }.as_bytes();
+            write_system_state(
+                super::SystemState::TeamLogPointer,
+                slice.as_ptr() as u32 as f64,
+            );
+            write_system_state(super::SystemState::TeamLogLength, slice.len() as u32 as f64);
+        }
     }
 
     #[doc(hidden)]
@@ -1059,6 +2121,7 @@ pub mod dbg {
             TEXT_BUFFER.clear();
             LINE_BUFFER.clear();
             DRAWN_TEXT_BUFFER.clear();
+            TEAM_LOG_BUFFER.clear();
         }
     }
 }
@@ -1097,12 +2160,6 @@ mod deprecated {
         };
         write_system_state(state_index, 1.0);
     }
-
-    /// TODO Remove this.
-    #[deprecated]
-    pub fn orders() -> f64 {
-        0.0
-    }
 }
 
 /// All APIs.
@@ -1114,15 +2171,21 @@ pub mod prelude {
     #[doc(hidden)]
     pub use super::deprecated::*;
     #[doc(inline)]
+    pub use super::geometry::*;
+    #[doc(inline)]
+    pub use super::guidance::*;
+    #[doc(inline)]
     pub use super::math::*;
     #[doc(inline)]
+    pub use super::prediction::*;
+    #[doc(inline)]
     pub use super::rng::*;
     #[doc(inline)]
     pub use super::vec::*;
     #[doc(inline)]
-    pub use super::{Ability, Class, EcmMode, Message};
+    pub use super::{Ability, Class, EcmMode, Message, OrbitDirection};
     #[doc(inline)]
-    pub use crate::{debug, draw_text};
+    pub use crate::{debug, draw_text, team_log};
 
     pub use byteorder;
     pub use maths_rs;
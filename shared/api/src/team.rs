@@ -0,0 +1,154 @@
+//! Team-wide setup before the first tick.
+//!
+//! Implement [`ScenarioStart`] for your `Ship` to look at the whole team once,
+//! before any ship ticks, and give each ship its orders:
+//!
+//! ```
+//! use oort_api::prelude::*;
+//! use oort_api::team::{ScenarioMetadata, ScenarioStart};
+//! use std::collections::BTreeMap;
+//!
+//! pub struct Ship {}
+//!
+//! impl ScenarioStart for Ship {
+//!     fn on_scenario_start(metadata: &ScenarioMetadata) -> BTreeMap<u32, Message> {
+//!         // The two fighters furthest forward are scouts, the rest escorts.
+//!         let mut fighters: Vec<_> = metadata
+//!             .ships
+//!             .iter()
+//!             .filter(|ship| ship.class == Class::Fighter)
+//!             .collect();
+//!         fighters.sort_by(|a, b| b.position.x.total_cmp(&a.position.x));
+//!         fighters
+//!             .iter()
+//!             .enumerate()
+//!             .map(|(i, ship)| (ship.id, [if i < 2 { 1.0 } else { 2.0 }, 0.0, 0.0, 0.0]))
+//!             .collect()
+//!     }
+//! }
+//! ```
+//!
+//! Each ship then reads its orders with [`orders`](crate::prelude::orders).
+//! Ships created later in the scenario have no orders.
+
+use crate::prelude::{
+    asteroids_block_bullets, asteroids_destructible, asteroids_split, scenario_name, vec2,
+    world_size, Class, Message, Vec2,
+};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+/// Maximum number of ships described in [`ScenarioMetadata`].
+pub const MAX_ROSTER_SIZE: usize = 256;
+
+/// Number of values per ship in `ROSTER`: id, class, x, y and heading.
+#[doc(hidden)]
+pub const ROSTER_ENTRY_SIZE: usize = 5;
+
+// Written by the simulator before calling `on_scenario_start`.
+#[doc(hidden)]
+#[no_mangle]
+pub static mut ROSTER: [f64; MAX_ROSTER_SIZE * ROSTER_ENTRY_SIZE] =
+    [0.0; MAX_ROSTER_SIZE * ROSTER_ENTRY_SIZE];
+
+static mut ORDERS: BTreeMap<u32, Message> = BTreeMap::new();
+
+/// A ship on this team at the start of the scenario.
+#[derive(Clone, Debug)]
+pub struct ShipInfo {
+    /// The ship's [`id`](crate::prelude::id).
+    pub id: u32,
+    /// The ship's class.
+    pub class: Class,
+    /// The ship's starting position (in meters).
+    pub position: Vec2,
+    /// The ship's starting heading (in radians).
+    pub heading: f64,
+}
+
+/// What a team knows about the scenario before the first tick.
+#[derive(Clone, Debug)]
+pub struct ScenarioMetadata {
+    /// Same as [`scenario_name`](crate::prelude::scenario_name).
+    pub scenario_name: String,
+    /// Same as [`world_size`](crate::prelude::world_size).
+    pub world_size: f64,
+    /// Same as [`asteroids_destructible`](crate::prelude::asteroids_destructible).
+    pub asteroids_destructible: bool,
+    /// Same as [`asteroids_split`](crate::prelude::asteroids_split).
+    pub asteroids_split: bool,
+    /// Same as [`asteroids_block_bullets`](crate::prelude::asteroids_block_bullets).
+    pub asteroids_block_bullets: bool,
+    /// The team's ships, in order of ID.
+    pub ships: Vec<ShipInfo>,
+}
+
+/// Hook called once per team before the first tick.
+pub trait ScenarioStart {
+    /// Returns orders for the team's ships, keyed by ship ID.
+    fn on_scenario_start(metadata: &ScenarioMetadata) -> BTreeMap<u32, Message>;
+}
+
+/// Returns the orders given to the ship with `id` by
+/// [`ScenarioStart::on_scenario_start`].
+#[doc(hidden)]
+pub fn orders_for(id: u32) -> Option<Message> {
+    unsafe { ORDERS.get(&id).copied() }
+}
+
+#[doc(hidden)]
+pub fn read_metadata(count: usize) -> ScenarioMetadata {
+    let roster = unsafe { &ROSTER };
+    let ships = roster
+        .chunks_exact(ROSTER_ENTRY_SIZE)
+        .take(count.min(MAX_ROSTER_SIZE))
+        .map(|entry| ShipInfo {
+            id: entry[0] as u32,
+            class: Class::from_f64(entry[1]),
+            position: vec2(entry[2], entry[3]),
+            heading: entry[4],
+        })
+        .collect();
+    ScenarioMetadata {
+        scenario_name: scenario_name().to_string(),
+        world_size: world_size(),
+        asteroids_destructible: asteroids_destructible(),
+        asteroids_split: asteroids_split(),
+        asteroids_block_bullets: asteroids_block_bullets(),
+        ships,
+    }
+}
+
+#[doc(hidden)]
+pub fn set_orders(orders: BTreeMap<u32, Message>) {
+    unsafe {
+        ORDERS = orders;
+    }
+}
+
+// Lets the AI wrapper call `on_scenario_start` only if the user's ship
+// implements `ScenarioStart`, using autoref-based specialization.
+#[doc(hidden)]
+pub struct StartDispatcher<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait DispatchScenarioStart {
+    fn start(&self, metadata: &ScenarioMetadata) -> BTreeMap<u32, Message>;
+}
+
+impl<T: ScenarioStart> DispatchScenarioStart for StartDispatcher<T> {
+    fn start(&self, metadata: &ScenarioMetadata) -> BTreeMap<u32, Message> {
+        T::on_scenario_start(metadata)
+    }
+}
+
+#[doc(hidden)]
+pub trait DispatchStartNone {
+    fn start(&self, metadata: &ScenarioMetadata) -> BTreeMap<u32, Message>;
+}
+
+impl<T> DispatchStartNone for &StartDispatcher<T> {
+    fn start(&self, _metadata: &ScenarioMetadata) -> BTreeMap<u32, Message> {
+        BTreeMap::new()
+    }
+}
@@ -0,0 +1,282 @@
+//! Unit testing for ship AIs without the simulator.
+//!
+//! A [`MockSim`] feeds scripted sensor readings to the API and records the
+//! commands your code issues in response:
+//!
+//! ```
+//! use oort_api::prelude::*;
+//! use oort_api::testing::{Inputs, MockSim};
+//!
+//! struct Ship {}
+//!
+//! impl Ship {
+//!     fn tick(&mut self) {
+//!         if let Some(contact) = scan() {
+//!             turn(angle_diff(heading(), (contact.position - position()).angle()));
+//!             fire(0);
+//!         }
+//!         accelerate(vec2(10.0, 0.0));
+//!     }
+//! }
+//!
+//! let mut sim = MockSim::new();
+//! let mut ship = Ship {};
+//!
+//! let commands = sim.tick(&Inputs::default(), || ship.tick());
+//! assert!(!commands.fire[0]);
+//! assert_eq!(commands.acceleration, vec2(10.0, 0.0));
+//!
+//! let inputs = Inputs {
+//!     contact: Some(ScanResult {
+//!         class: Class::Fighter,
+//!         position: vec2(0.0, 1000.0),
+//!         velocity: vec2(0.0, 0.0),
+//!         rssi: -80.0,
+//!         snr: 20.0,
+//!         age: 0.0,
+//!     }),
+//!     ..Default::default()
+//! };
+//! let commands = sim.tick(&inputs, || ship.tick());
+//! assert!(commands.fire[0]);
+//! assert!(commands.torque > 0.0);
+//! ```
+//!
+//! The API uses global state, so only one `MockSim` exists at a time. Others
+//! wait for it to be dropped, which lets tests run in parallel.
+
+use crate::prelude::radio_internal::{radio_indices, MAX_RADIOS};
+use crate::prelude::{vec2, Class, Message, ScanResult, Vec2, Vec2Extras, TAU};
+use crate::sys::{
+    read_system_state, read_system_state_u64, unpack_f32_pair, write_system_state,
+    write_system_state_u64, SYSTEM_STATE,
+};
+use crate::{SystemState, FIRE_SPREAD_SLOTS};
+use std::sync::{Mutex, MutexGuard};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Sensor readings and ship status seen by the AI on one tick.
+#[derive(Clone, Debug)]
+pub struct Inputs {
+    /// Ticks since the simulation began.
+    pub tick: u32,
+    /// The ship's class.
+    pub class: Class,
+    /// The ship's position (in meters).
+    pub position: Vec2,
+    /// The ship's velocity (in m/s).
+    pub velocity: Vec2,
+    /// The ship's heading (in radians).
+    pub heading: f64,
+    /// The ship's angular velocity (in radians/s).
+    pub angular_velocity: f64,
+    /// The ship's health.
+    pub health: f64,
+    /// The ship's fuel (delta-v).
+    pub fuel: f64,
+    /// Maximum forward, backward and lateral acceleration (in m/s²).
+    pub max_acceleration: [f64; 3],
+    /// Maximum angular acceleration (in radians/s²).
+    pub max_angular_acceleration: f64,
+    /// Returned by `scan`.
+    pub contact: Option<ScanResult>,
+    /// Messages received, as pairs of radio index and message.
+    pub received: Vec<(usize, Message)>,
+}
+
+impl Default for Inputs {
+    /// A stationary fighter at the origin with nothing on radar.
+    fn default() -> Self {
+        Inputs {
+            tick: 0,
+            class: Class::Fighter,
+            position: vec2(0.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+            heading: 0.0,
+            angular_velocity: 0.0,
+            health: 100.0,
+            fuel: f64::INFINITY,
+            max_acceleration: [60.0, 30.0, 30.0],
+            max_angular_acceleration: TAU,
+            contact: None,
+            received: vec![],
+        }
+    }
+}
+
+/// Commands issued by the AI during one tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Commands {
+    /// Requested acceleration in world coordinates (in m/s²), after `accelerate`
+    /// has limited it to the ship's maximum.
+    pub acceleration: Vec2,
+    /// Requested angular acceleration (in radians/s²), set by `turn` or
+    /// `torque`.
+    pub torque: f64,
+    /// Weapons fired with `fire`.
+    pub fire: [bool; 4],
+    /// Points passed to `fire_at`.
+    pub fire_at: [Option<Vec2>; 4],
+    /// Weapon index and headings passed to `fire_spread`.
+    pub fire_spread: Option<(usize, Vec<f64>)>,
+    /// Headings of each turreted weapon, set by `aim`.
+    pub aim: [f64; 4],
+    /// Set if `explode` was called.
+    pub explode: bool,
+    /// Radar heading (in radians) for the next tick.
+    pub radar_heading: f64,
+    /// Radar width (in radians) for the next tick.
+    pub radar_width: f64,
+    /// Messages sent, as pairs of radio index and message.
+    pub sent: Vec<(usize, Message)>,
+}
+
+/// Stands in for the simulator when testing AI code.
+pub struct MockSim {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl MockSim {
+    /// Resets the API state and waits for any other `MockSim` to be dropped.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            SYSTEM_STATE = [0; SystemState::MaxSize as usize];
+        }
+        write_system_state(SystemState::RadarWidth, TAU / 16.0);
+        write_system_state(SystemState::RadarMaxDistance, 1e9);
+        write_system_state(SystemState::RadarPower, 1.0);
+        unsafe {
+            crate::rng_state::set(crate::rng_state::RngState::new());
+        }
+        crate::dbg::reset();
+        crate::team::set_orders(Default::default());
+        MockSim { _lock: lock }
+    }
+
+    /// Sets the readings returned by the API until the next call.
+    pub fn set_inputs(&mut self, inputs: &Inputs) {
+        use SystemState::*;
+        write_system_state(CurrentTick, inputs.tick as f64);
+        write_system_state(SystemState::Class, inputs.class as u32 as f64);
+        write_system_state(PositionX, inputs.position.x);
+        write_system_state(PositionY, inputs.position.y);
+        write_system_state(VelocityX, inputs.velocity.x);
+        write_system_state(VelocityY, inputs.velocity.y);
+        write_system_state(Heading, inputs.heading);
+        write_system_state(AngularVelocity, inputs.angular_velocity);
+        write_system_state(Health, inputs.health);
+        write_system_state(Fuel, inputs.fuel);
+        write_system_state(MaxForwardAcceleration, inputs.max_acceleration[0]);
+        write_system_state(MaxBackwardAcceleration, inputs.max_acceleration[1]);
+        write_system_state(MaxLateralAcceleration, inputs.max_acceleration[2]);
+        write_system_state(MaxAngularAcceleration, inputs.max_angular_acceleration);
+
+        if let Some(contact) = inputs.contact.as_ref() {
+            write_system_state(RadarContactFound, 1.0);
+            write_system_state(RadarContactClass, contact.class as u32 as f64);
+            write_system_state(RadarContactPositionX, contact.position.x);
+            write_system_state(RadarContactPositionY, contact.position.y);
+            write_system_state(RadarContactVelocityX, contact.velocity.x);
+            write_system_state(RadarContactVelocityY, contact.velocity.y);
+            write_system_state(RadarContactRssi, contact.rssi);
+            write_system_state(RadarContactSnr, contact.snr);
+            write_system_state(RadarContactAge, contact.age);
+        } else {
+            write_system_state(RadarContactFound, 0.0);
+        }
+
+        for radio in 0..MAX_RADIOS {
+            write_system_state(radio_indices(radio).receive, 0.0);
+        }
+        for (radio, message) in inputs.received.iter() {
+            let idxs = radio_indices(*radio);
+            write_system_state(idxs.receive, 1.0);
+            for (slot, value) in idxs.data.iter().zip(message.iter()) {
+                write_system_state(*slot, *value);
+            }
+        }
+    }
+
+    /// Returns the commands issued since the previous call and clears them,
+    /// like the simulator does at the end of each tick.
+    pub fn take_commands(&mut self) -> Commands {
+        use SystemState::*;
+        let heading = read_system_state(Heading);
+        let mut commands = Commands {
+            acceleration: vec2(
+                read_system_state(AccelerateX),
+                read_system_state(AccelerateY),
+            )
+            .rotate(heading),
+            torque: read_system_state(Torque),
+            fire: [false; 4],
+            fire_at: [None; 4],
+            fire_spread: None,
+            aim: [0.0; 4],
+            explode: read_system_state(Explode) != 0.0,
+            radar_heading: read_system_state(RadarHeading),
+            radar_width: read_system_state(RadarWidth),
+            sent: vec![],
+        };
+        for (i, (fire, fire_at)) in [
+            (Fire0, FireAt0),
+            (Fire1, FireAt1),
+            (Fire2, FireAt2),
+            (Fire3, FireAt3),
+        ]
+        .iter()
+        .enumerate()
+        {
+            commands.fire[i] = read_system_state(*fire) != 0.0;
+            let packed = read_system_state_u64(*fire_at);
+            if packed != 0 {
+                let (x, y) = unpack_f32_pair(packed);
+                commands.fire_at[i] = Some(vec2(x, y));
+            }
+            write_system_state(*fire, 0.0);
+            write_system_state_u64(*fire_at, 0);
+        }
+        let spread_count = read_system_state(FireSpreadCount) as usize;
+        if spread_count > 0 {
+            let headings = FIRE_SPREAD_SLOTS
+                .iter()
+                .take(spread_count)
+                .map(|slot| read_system_state(*slot))
+                .collect();
+            commands.fire_spread = Some((read_system_state(FireSpreadWeapon) as usize, headings));
+            write_system_state(FireSpreadCount, 0.0);
+        }
+        for (i, aim) in [Aim0, Aim1, Aim2, Aim3].iter().enumerate() {
+            commands.aim[i] = read_system_state(*aim);
+        }
+        for radio in 0..MAX_RADIOS {
+            let idxs = radio_indices(radio);
+            if read_system_state(idxs.send) != 0.0 {
+                let mut message: Message = [0.0; 4];
+                for (value, slot) in message.iter_mut().zip(idxs.data.iter()) {
+                    *value = read_system_state(*slot);
+                }
+                commands.sent.push((radio, message));
+                write_system_state(idxs.send, 0.0);
+            }
+        }
+
+        write_system_state(AccelerateX, 0.0);
+        write_system_state(AccelerateY, 0.0);
+        write_system_state(Torque, 0.0);
+        write_system_state(Explode, 0.0);
+        crate::dbg::reset();
+        commands
+    }
+
+    /// Runs one tick: sets `inputs`, calls `tick` and returns the commands it
+    /// issued.
+    pub fn tick(&mut self, inputs: &Inputs, tick: impl FnOnce()) -> Commands {
+        self.set_inputs(inputs);
+        tick();
+        self.take_commands()
+    }
+}
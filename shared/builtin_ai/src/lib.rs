@@ -3,6 +3,7 @@ pub mod empty;
 pub mod fuzz;
 pub mod gunnery;
 pub mod missile;
+pub mod opponent;
 pub mod planetary_defense_enemy;
 pub mod radar_test;
 pub mod radar_test_enemy;
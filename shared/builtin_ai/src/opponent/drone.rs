@@ -0,0 +1,30 @@
+// Opponent tier 0: wanders between random waypoints and never fires.
+use oort_api::prelude::*;
+
+pub struct Ship {
+    waypoint: Vec2,
+}
+
+impl Ship {
+    pub fn new() -> Ship {
+        Ship {
+            waypoint: random_waypoint(),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if position().distance(self.waypoint) < 500.0 {
+            self.waypoint = random_waypoint();
+        }
+        let dp = self.waypoint - position();
+        let desired_velocity = dp.normalize() * 200.0;
+        accelerate((desired_velocity - velocity()) * 2.0);
+        turn(5.0 * angle_diff(heading(), dp.angle()));
+        draw_line(position(), self.waypoint, 0x444444);
+    }
+}
+
+fn random_waypoint() -> Vec2 {
+    let r = world_size() * 0.3;
+    vec2(rand(-r, r), rand(-r, r))
+}
@@ -0,0 +1,84 @@
+// Opponent tier 2: tracks a single target, leads its shots, jinks to spoil
+// the enemy's aim and launches missiles when they are ready.
+use oort_api::prelude::*;
+
+const BULLET_SPEED: f64 = 1000.0;
+const SEARCH_RADAR_WIDTH: f64 = TAU / 32.0;
+const TRACK_RADAR_WIDTH: f64 = TAU / 360.0;
+
+pub struct Ship {
+    target: Option<ScanResult>,
+    lost_ticks: u32,
+    jink: Vec2,
+}
+
+impl Ship {
+    pub fn new() -> Ship {
+        set_radar_width(SEARCH_RADAR_WIDTH);
+        Ship {
+            target: None,
+            lost_ticks: 0,
+            jink: vec2(0.0, 0.0),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.update_track();
+        let Some(target) = self.target.clone() else {
+            accelerate(-velocity());
+            return;
+        };
+
+        // Predict where the target will be when our bullets arrive.
+        let dp = target.position - position();
+        let dv = target.velocity - velocity();
+        let mut predicted_dp = dp;
+        for _ in 0..10 {
+            let t = predicted_dp.length() / BULLET_SPEED;
+            predicted_dp = dp + dv * t;
+        }
+        let aim_heading = predicted_dp.angle();
+        turn(10.0 * angle_diff(heading(), aim_heading) - angular_velocity() * 0.5);
+        if angle_diff(heading(), aim_heading).abs() < 0.02 {
+            fire(0);
+        }
+        if reload_ticks(1) == 0 {
+            fire(1);
+        }
+
+        // Hold a fighting distance, changing the sideways jink every second.
+        if current_tick() % 60 == 0 {
+            self.jink = vec2(0.0, rand(-1.0, 1.0)).rotate(dp.angle());
+        }
+        let range_error = dp.length() - 2500.0;
+        let closing = dp.normalize() * range_error.clamp(-1.0, 1.0);
+        accelerate(
+            (closing + self.jink) * max_forward_acceleration()
+                - dv.dot(dp.normalize()) * dp.normalize(),
+        );
+    }
+
+    fn update_track(&mut self) {
+        if let Some(contact) = scan() {
+            self.lost_ticks = 0;
+            let predicted = contact.position + contact.velocity * TICK_LENGTH;
+            set_radar_heading((predicted - position()).angle());
+            set_radar_width(TRACK_RADAR_WIDTH);
+            self.target = Some(contact);
+        } else {
+            self.lost_ticks += 1;
+            if let Some(target) = self.target.as_mut() {
+                target.position += target.velocity * TICK_LENGTH;
+            }
+            if self.lost_ticks > 30 {
+                self.target = None;
+            }
+            if self.target.is_none() {
+                set_radar_width(SEARCH_RADAR_WIDTH);
+                set_radar_heading(radar_heading() + radar_width());
+            } else {
+                set_radar_width(TRACK_RADAR_WIDTH * 4.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Opponent tier 1: closes on the nearest contact and fires straight at it,
+// without leading the target.
+use oort_api::prelude::*;
+
+pub struct Ship {}
+
+impl Ship {
+    pub fn new() -> Ship {
+        set_radar_width(TAU / 32.0);
+        Ship {}
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(contact) = scan() {
+            let dp = contact.position - position();
+            set_radar_heading(dp.angle());
+            set_radar_width(TAU / 360.0);
+            turn(5.0 * angle_diff(heading(), dp.angle()));
+            if dp.length() > 2000.0 {
+                accelerate(dp.normalize() * max_forward_acceleration());
+            } else {
+                accelerate(-velocity());
+            }
+            if angle_diff(heading(), dp.angle()).abs() < 0.05 {
+                fire(0);
+            }
+        } else {
+            set_radar_width(TAU / 32.0);
+            set_radar_heading(radar_heading() + radar_width());
+            accelerate(-velocity());
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod drone;
+pub mod duelist;
+pub mod gunner;
@@ -6,16 +6,23 @@ pub enum Ship {
     Frigate(Frigate),
     Cruiser(Cruiser),
     Missile(Missile), // Also used for torpedos.
+    Station,
+    Turret(Turret),
 }
 
 impl Ship {
     pub fn new() -> Ship {
         match class() {
             Class::Fighter => Ship::Fighter(Fighter::new()),
+            // Corvettes fly like heavy fighters.
+            Class::Corvette => Ship::Fighter(Fighter::new()),
             Class::Frigate => Ship::Frigate(Frigate::new()),
             Class::Cruiser => Ship::Cruiser(Cruiser::new()),
             Class::Missile => Ship::Missile(Missile::new()),
             Class::Torpedo => Ship::Missile(Missile::new()),
+            // Stations have no weapons or engines to control.
+            Class::Station => Ship::Station,
+            Class::Turret => Ship::Turret(Turret::new()),
             _ => unreachable!(),
         }
     }
@@ -26,6 +33,8 @@ impl Ship {
             Ship::Frigate(frigate) => frigate.tick(),
             Ship::Cruiser(cruiser) => cruiser.tick(),
             Ship::Missile(missile) => missile.tick(),
+            Ship::Station => {}
+            Ship::Turret(turret) => turret.tick(),
         }
     }
 }
@@ -46,6 +55,7 @@ impl Fighter {
         if let Some(contact) = scan().filter(|c| {
             [
                 Class::Fighter,
+                Class::Corvette,
                 Class::Frigate,
                 Class::Cruiser,
                 Class::Torpedo,
@@ -120,6 +130,7 @@ impl Frigate {
             if let Some(contact) = scan().filter(|c| {
                 [
                     Class::Fighter,
+                    Class::Corvette,
                     Class::Frigate,
                     Class::Cruiser,
                     Class::Asteroid,
@@ -163,6 +174,7 @@ impl Frigate {
             if let Some(contact) = scan().filter(|c| {
                 [
                     Class::Fighter,
+                    Class::Corvette,
                     Class::Missile,
                     Class::Torpedo,
                     Class::Asteroid,
@@ -190,6 +202,38 @@ impl Frigate {
     }
 }
 
+// Turrets
+pub struct Turret {}
+
+impl Turret {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(contact) = scan().filter(|c| {
+            [
+                Class::Fighter,
+                Class::Corvette,
+                Class::Frigate,
+                Class::Cruiser,
+                Class::Torpedo,
+            ]
+            .contains(&c.class)
+        }) {
+            set_radar_heading((contact.position - position()).angle());
+            set_radar_width(radar_width() * 0.5);
+            if let Some(angle) = lead_target(contact.position, contact.velocity, 3e3, 10.0) {
+                aim(0, angle);
+                fire(0);
+            }
+        } else {
+            set_radar_heading(radar_heading() + radar_width());
+            set_radar_width(TAU / 60.0);
+        }
+    }
+}
+
 // Cruisers
 pub struct Cruiser {
     pub move_target: Vec2,
@@ -246,6 +290,7 @@ impl Cruiser {
             if let Some(contact) = scan().filter(|c| {
                 [
                     Class::Fighter,
+                    Class::Corvette,
                     Class::Frigate,
                     Class::Cruiser,
                     Class::Torpedo,
@@ -318,6 +363,7 @@ impl Missile {
         // Don't let torpedos get distracted by smaller ships.
         let missile_target_classes = [
             Class::Fighter,
+            Class::Corvette,
             Class::Frigate,
             Class::Cruiser,
             Class::Torpedo,
@@ -1,8 +1,11 @@
 pub mod analyzer;
+pub mod ranked;
+pub mod stats;
 
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TelemetryMsg {
@@ -107,3 +110,66 @@ pub struct TournamentCompetitor {
     pub shortcode: String,
     pub rating: f64,
 }
+
+// Chosen when a scenario is started, for scenarios that support it.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+pub const SCENARIO_RESULT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScenarioResult {
+    pub version: u32,
+    pub scenario_name: String,
+    pub seed: u32,
+    // Hex-encoded SHA-256 of the AI source code.
+    pub code_hash: String,
+    pub ticks: u32,
+    // Value of `Simulation::hash` at the end of the scenario.
+    pub replay_hash: u64,
+    pub simulator_version: String,
+    // Instructions executed by the player's AI over the whole run.
+    #[serde(default)]
+    pub instructions: u64,
+    // Present for ranked runs, whose seed must come from the backend.
+    #[serde(default)]
+    pub seed_grant: Option<ranked::SeedGrant>,
+    // Results at different difficulties go on separate leaderboards.
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub stats: stats::BattleStats,
+}
+
+impl Eq for ScenarioResult {}
+
+// Hex-encoded SHA-256 of AI source code, as stored in `ScenarioResult`.
+pub fn code_hash(source_code: &str) -> String {
+    format!("{:x}", Sha256::digest(source_code.as_bytes()))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScenarioResultSubmission {
+    pub userid: String,
+    pub username: String,
+    #[serde(default)]
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub result: ScenarioResult,
+    pub code: String,
+}
@@ -0,0 +1,205 @@
+//! Seeds for ranked runs are chosen by the backend rather than the client, so
+//! a leaderboard time can't come from trying many seeds and submitting the
+//! luckiest one. The backend signs each grant with a secret key and checks the
+//! signature when the result embedding it is submitted.
+
+use chrono::serde::ts_milliseconds;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// How long a client has to submit a result after receiving a seed.
+pub const SEED_GRANT_LIFETIME_HOURS: i64 = 24;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SeedGrant {
+    pub scenario_name: String,
+    pub userid: String,
+    pub seed: u32,
+    #[serde(with = "ts_milliseconds")]
+    pub issued_at: DateTime<Utc>,
+    // Hex-encoded HMAC-SHA256 of the other fields.
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedGrantError {
+    BadSignature,
+    Expired,
+    WrongUser,
+    WrongScenario,
+    WrongSeed,
+}
+
+impl std::fmt::Display for SeedGrantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedGrantError::BadSignature => write!(f, "Seed grant signature is invalid"),
+            SeedGrantError::Expired => write!(f, "Seed grant has expired"),
+            SeedGrantError::WrongUser => write!(f, "Seed grant was issued to another user"),
+            SeedGrantError::WrongScenario => {
+                write!(f, "Seed grant was issued for another scenario")
+            }
+            SeedGrantError::WrongSeed => write!(f, "Result does not use the granted seed"),
+        }
+    }
+}
+
+impl std::error::Error for SeedGrantError {}
+
+impl SeedGrant {
+    pub fn new(
+        scenario_name: &str,
+        userid: &str,
+        seed: u32,
+        issued_at: DateTime<Utc>,
+        secret: &[u8],
+    ) -> Self {
+        let mut grant = SeedGrant {
+            scenario_name: scenario_name.to_string(),
+            userid: userid.to_string(),
+            seed,
+            issued_at,
+            signature: String::new(),
+        };
+        grant.signature = grant.compute_signature(secret);
+        grant
+    }
+
+    fn compute_signature(&self, secret: &[u8]) -> String {
+        let message = format!(
+            "{}\n{}\n{}\n{}",
+            self.scenario_name,
+            self.userid,
+            self.seed,
+            self.issued_at.timestamp_millis()
+        );
+        to_hex(&hmac_sha256(secret, message.as_bytes()))
+    }
+
+    // Checks that the grant was issued by the holder of `secret` to `userid`
+    // and is still valid at `now`.
+    pub fn verify(
+        &self,
+        secret: &[u8],
+        userid: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), SeedGrantError> {
+        let expected = self.compute_signature(secret);
+        let matches = expected.len() == self.signature.len()
+            && expected
+                .bytes()
+                .zip(self.signature.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+        if !matches {
+            return Err(SeedGrantError::BadSignature);
+        }
+        if self.userid != userid {
+            return Err(SeedGrantError::WrongUser);
+        }
+        if now - self.issued_at > Duration::hours(SEED_GRANT_LIFETIME_HOURS) {
+            return Err(SeedGrantError::Expired);
+        }
+        Ok(())
+    }
+
+    // Checks that a result was produced from this grant.
+    pub fn check_run(&self, scenario_name: &str, seed: u32) -> Result<(), SeedGrantError> {
+        if self.scenario_name != scenario_name {
+            return Err(SeedGrantError::WrongScenario);
+        }
+        if self.seed != seed {
+            return Err(SeedGrantError::WrongSeed);
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(message);
+    let inner = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&outer.finalize());
+    digest
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SECRET: &[u8] = b"test secret";
+
+    fn grant() -> SeedGrant {
+        SeedGrant::new("gunnery", "user", 1234, Utc::now(), SECRET)
+    }
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            to_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let grant = grant();
+        assert_eq!(grant.verify(SECRET, "user", Utc::now()), Ok(()));
+        assert_eq!(
+            grant.verify(b"other secret", "user", Utc::now()),
+            Err(SeedGrantError::BadSignature)
+        );
+        assert_eq!(
+            grant.verify(SECRET, "other", Utc::now()),
+            Err(SeedGrantError::WrongUser)
+        );
+        assert_eq!(
+            grant.verify(SECRET, "user", Utc::now() + Duration::days(2)),
+            Err(SeedGrantError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_tampered_seed() {
+        let mut grant = grant();
+        grant.seed += 1;
+        assert_eq!(
+            grant.verify(SECRET, "user", Utc::now()),
+            Err(SeedGrantError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_check_run() {
+        let grant = grant();
+        assert_eq!(grant.check_run("gunnery", 1234), Ok(()));
+        assert_eq!(
+            grant.check_run("gunnery", 1),
+            Err(SeedGrantError::WrongSeed)
+        );
+        assert_eq!(
+            grant.check_run("fighter_duel", 1234),
+            Err(SeedGrantError::WrongScenario)
+        );
+    }
+}
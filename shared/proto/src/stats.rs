@@ -0,0 +1,114 @@
+//! Battle statistics for post-match analysis.
+//!
+//! The simulator records these as a match plays out, and they're included in
+//! scenario results so the tournament runner and the UI can show them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct WeaponStats {
+    // Bullets or fragments released.
+    pub shots: u64,
+    pub hits: u64,
+    pub damage: f64,
+    // Damage beyond what was needed to destroy the target.
+    #[serde(default)]
+    pub overkill: f64,
+}
+
+impl WeaponStats {
+    pub fn accuracy(&self) -> f64 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.shots as f64
+        }
+    }
+
+    // Fraction of damage wasted on targets that were already finished.
+    pub fn overkill_fraction(&self) -> f64 {
+        if self.damage == 0.0 {
+            0.0
+        } else {
+            self.overkill / self.damage
+        }
+    }
+}
+
+// Stats for each gun on a single ship.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShipGunStats {
+    pub team: i32,
+    // Class name, e.g. "frigate".
+    pub class: String,
+    // Indexed by gun index.
+    pub guns: BTreeMap<usize, WeaponStats>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct MissileStats {
+    // Missiles and torpedoes launched.
+    pub launched: u64,
+    // Launched missiles and torpedoes destroyed by enemy fire.
+    pub intercepted: u64,
+}
+
+// Summary of a battle for post-match analysis.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BattleStats {
+    // Damage dealt, indexed by attacking team and then target team.
+    pub team_damage: BTreeMap<i32, BTreeMap<i32, f64>>,
+    // Damage dealt, indexed by attacking and then target class name.
+    pub class_damage: BTreeMap<String, BTreeMap<String, f64>>,
+    // Indexed by weapon name, e.g. "frigate gun 0" or "missile warhead".
+    pub weapons: BTreeMap<String, WeaponStats>,
+    // Indexed by the launching team.
+    pub missiles: BTreeMap<i32, MissileStats>,
+    // Indexed by the firing ship's ID.
+    #[serde(default)]
+    pub ship_guns: BTreeMap<u64, ShipGunStats>,
+}
+
+impl BattleStats {
+    pub fn team_damage(&self, attacker_team: i32, target_team: i32) -> f64 {
+        self.team_damage
+            .get(&attacker_team)
+            .and_then(|row| row.get(&target_team))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    // Total damage taken by a team from every other team.
+    pub fn damage_taken(&self, target_team: i32) -> f64 {
+        self.team_damage
+            .iter()
+            .filter(|(attacker_team, _)| **attacker_team != target_team)
+            .filter_map(|(_, row)| row.get(&target_team))
+            .sum()
+    }
+
+    // Shots and hits of all of a team's guns combined.
+    pub fn team_guns(&self, team: i32) -> WeaponStats {
+        let mut total = WeaponStats::default();
+        for gun in self
+            .ship_guns
+            .values()
+            .filter(|ship| ship.team == team)
+            .flat_map(|ship| ship.guns.values())
+        {
+            total.shots += gun.shots;
+            total.hits += gun.hits;
+            total.damage += gun.damage;
+            total.overkill += gun.overkill;
+        }
+        total
+    }
+
+    // Stats for one gun on one ship, e.g. a frigate's turret.
+    pub fn ship_gun(&self, ship: u64, gun: usize) -> Option<&WeaponStats> {
+        self.ship_guns
+            .get(&ship)
+            .and_then(|ship| ship.guns.get(&gun))
+    }
+}
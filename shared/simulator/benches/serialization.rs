@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use oort_simulator::scenario;
 use oort_simulator::simulation;
-use oort_simulator::snapshot::Snapshot;
+use oort_simulator::snapshot::{self, Snapshot, VersionedSnapshot};
 
 fn make_snapshot() -> Snapshot {
     let scenario_name = "tutorial_squadron";
@@ -14,20 +14,19 @@ fn make_snapshot() -> Snapshot {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let snapshot = make_snapshot();
+    let versioned = VersionedSnapshot::from(make_snapshot());
     c.bench_function("json", |b| {
         b.iter(|| {
-            black_box(serde_json::from_str::<Snapshot>(
-                &serde_json::to_string(&snapshot).unwrap(),
-            ))
+            black_box(
+                serde_json::from_str::<VersionedSnapshot>(
+                    &serde_json::to_string(&versioned).unwrap(),
+                )
+                .map(VersionedSnapshot::into_latest),
+            )
         })
     });
     c.bench_function("bincode", |b| {
-        b.iter(|| {
-            black_box(bincode::deserialize::<Snapshot>(
-                &bincode::serialize(&snapshot).unwrap(),
-            ))
-        })
+        b.iter(|| black_box(snapshot::decode(&bincode::serialize(&versioned).unwrap())))
     });
 }
 
@@ -0,0 +1,113 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nalgebra::vector;
+use oort_simulator::scenario;
+use oort_simulator::ship::{self, ShipHandle};
+use oort_simulator::simulation::{self, Code};
+use rapier2d_f64::data::Coarena;
+use std::collections::HashMap;
+
+const NUM_SHIPS: usize = 500;
+const STATE_SIZE: usize = oort_api::SystemState::Size as usize;
+
+// Stand-in for a team controller's per-ship system state.
+#[derive(Clone)]
+struct State([u64; STATE_SIZE]);
+
+impl Default for State {
+    fn default() -> Self {
+        State([0; STATE_SIZE])
+    }
+}
+
+fn make_sim(codes: &[Code]) -> Box<simulation::Simulation> {
+    let mut sim = simulation::Simulation::new("test", 0, codes);
+    for i in 0..NUM_SHIPS {
+        let team = (i % 2) as i32;
+        ship::create(
+            &mut sim,
+            vector![(i / 2) as f64 * 100.0, team as f64 * 1000.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(team),
+        );
+    }
+    sim
+}
+
+fn team_ships(sim: &simulation::Simulation, team: i32) -> Vec<ShipHandle> {
+    sim.ships
+        .iter()
+        .copied()
+        .filter(|&handle| sim.ship(handle).data().team == team)
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("ship_data_access", |b| {
+        let sim = make_sim(&[Code::None, Code::None]);
+        b.iter(|| {
+            let mut health = 0.0;
+            for &handle in sim.ships.iter() {
+                health += sim.ship(handle).data().health;
+            }
+            black_box(health)
+        })
+    });
+    // Team controller state lookups, keyed by body index as the controller
+    // does and with the HashMap it used before as a baseline.
+    c.bench_function("controller_state_coarena", |b| {
+        let sim = make_sim(&[Code::None, Code::None]);
+        let handles = team_ships(&sim, 0);
+        let mut states = Coarena::new();
+        for handle in handles.iter() {
+            states.insert(handle.0, State::default());
+        }
+        b.iter(|| {
+            for handle in handles.iter() {
+                states.get_mut(handle.0).unwrap().0[0] += 1;
+            }
+            black_box(&states);
+        })
+    });
+    c.bench_function("controller_state_hashmap", |b| {
+        let sim = make_sim(&[Code::None, Code::None]);
+        let handles = team_ships(&sim, 0);
+        let mut states: HashMap<ShipHandle, State> = handles
+            .iter()
+            .map(|&handle| (handle, State::default()))
+            .collect();
+        b.iter(|| {
+            for handle in handles.iter() {
+                states.get_mut(handle).unwrap().0[0] += 1;
+            }
+            black_box(&states);
+        })
+    });
+    c.bench_function("step_500_ships", |b| {
+        let mut sim = make_sim(&[scenario::reference_ai(), scenario::reference_ai()]);
+        b.iter(|| sim.step())
+    });
+    // Same as above with a single worker thread, to measure what running team
+    // controllers in parallel saves.
+    c.bench_function("step_500_ships_serial", |b| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let mut sim = make_sim(&[scenario::reference_ai(), scenario::reference_ai()]);
+            b.iter(|| sim.step())
+        })
+    });
+}
+
+pub fn criterion_config() -> Criterion {
+    Criterion::default()
+        .sample_size(10)
+        .measurement_time(core::time::Duration::from_secs(10))
+}
+
+criterion_group!(name = benches;
+                 config = criterion_config();
+                 targets = criterion_benchmark);
+criterion_main!(benches);
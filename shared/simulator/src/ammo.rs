@@ -0,0 +1,72 @@
+use crate::ship::{ShipClass, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::Vector2;
+
+// Lets ships near a friendly depot regain ammunition. Scenarios opt in with
+// `Scenario::resupply`.
+#[derive(Clone, Debug)]
+pub struct Resupply {
+    // Classes that resupply other ships of their team.
+    pub depots: Vec<ShipClass>,
+    // Distance in meters from a depot within which ships are resupplied.
+    pub radius: f64,
+    // Ticks between each round restored to a gun.
+    pub interval: u32,
+}
+
+impl Default for Resupply {
+    fn default() -> Self {
+        Resupply {
+            depots: vec![ShipClass::Station],
+            radius: 1000.0,
+            interval: 30,
+        }
+    }
+}
+
+// Restores one round to each gun with limited ammunition on ships in range of
+// a friendly depot.
+pub fn tick(sim: &mut Simulation) {
+    let Some(resupply) = sim.resupply().cloned() else {
+        return;
+    };
+    if resupply.interval == 0 || sim.tick() % resupply.interval != 0 {
+        return;
+    }
+
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    let depots: Vec<(ShipHandle, i32, Vector2<f64>)> = handle_snapshot
+        .iter()
+        .filter_map(|&handle| {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            if !resupply.depots.contains(&data.class) || data.destroyed {
+                return None;
+            }
+            Some((handle, data.team, ship.position().vector))
+        })
+        .collect();
+    if depots.is_empty() {
+        return;
+    }
+
+    for handle in handle_snapshot {
+        let (team, position) = {
+            let ship = sim.ship(handle);
+            (ship.data().team, ship.position().vector)
+        };
+        let in_range = depots.iter().any(|&(depot, depot_team, depot_position)| {
+            depot != handle
+                && depot_team == team
+                && (depot_position - position).norm() <= resupply.radius
+        });
+        if !in_range {
+            continue;
+        }
+        for gun in sim.ship_mut(handle).data_mut().guns.iter_mut() {
+            if let Some(capacity) = gun.ammo_capacity {
+                gun.ammo = (gun.ammo + 1).min(capacity);
+            }
+        }
+    }
+}
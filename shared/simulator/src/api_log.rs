@@ -0,0 +1,147 @@
+use crate::ship::{ShipAccessor, ShipClass};
+use crate::simulation::PHYSICS_TICK_LENGTH;
+use oort_api::Ability;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+
+// Number of calls remembered for each ship.
+pub const API_CALL_LOG_LENGTH: usize = 32;
+
+// An action requested by a ship's AI. Calls that set state every tick, like
+// `accelerate`, aren't recorded.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ApiCall {
+    Fire { index: usize },
+    FireAt { index: usize },
+    FireSpread { index: usize },
+    AimAllAt,
+    ActivateAbility(Ability),
+    RequestReinforcement(ShipClass),
+    SendMessage { radio: usize },
+    Disarm,
+    Explode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiCallOutcome {
+    Ok,
+    Reloading { ticks: u32 },
+    Overheated,
+    // There is no weapon, ability or radio with that index.
+    Missing,
+    // The launch was blocked by the ship's own hull.
+    Blocked,
+    // The simulator refused the request, e.g. not enough resources.
+    Denied,
+    OutOfAmmo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiCallRecord {
+    pub tick: u32,
+    pub call: ApiCall,
+    pub outcome: ApiCallOutcome,
+}
+
+// The most recent calls made by one ship.
+#[derive(Clone, Debug, Default)]
+pub struct ApiCallLog {
+    records: VecDeque<ApiCallRecord>,
+}
+
+impl ApiCallLog {
+    pub fn push(&mut self, tick: u32, call: ApiCall, outcome: ApiCallOutcome) {
+        if self.records.len() == API_CALL_LOG_LENGTH {
+            self.records.pop_front();
+        }
+        self.records.push_back(ApiCallRecord {
+            tick,
+            call,
+            outcome,
+        });
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &ApiCallRecord> {
+        self.records.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+// Predicts the outcome of firing weapon `index`. Launches blocked by the hull
+// are only known after trying.
+pub fn fire_outcome(ship: &ShipAccessor, index: usize) -> ApiCallOutcome {
+    let data = ship.data();
+    if let Some(gun) = data.guns.get(index) {
+        if !gun.has_ammo() {
+            ApiCallOutcome::OutOfAmmo
+        } else if gun.overheated {
+            ApiCallOutcome::Overheated
+        } else if gun.reload_ticks_remaining > 0 {
+            ApiCallOutcome::Reloading {
+                ticks: gun.reload_ticks_remaining,
+            }
+        } else {
+            ApiCallOutcome::Ok
+        }
+    } else if let Some(launcher) = data.missile_launchers.get(index - data.guns.len()) {
+        if launcher.reload_ticks_remaining > 0 {
+            ApiCallOutcome::Reloading {
+                ticks: launcher.reload_ticks_remaining,
+            }
+        } else {
+            ApiCallOutcome::Ok
+        }
+    } else {
+        ApiCallOutcome::Missing
+    }
+}
+
+pub fn ability_outcome(ship: &ShipAccessor, ability: Ability) -> ApiCallOutcome {
+    match ship.data().abilities.iter().find(|x| x.ability == ability) {
+        None => ApiCallOutcome::Missing,
+        Some(x) if x.reload_time_remaining > 0.0 => ApiCallOutcome::Reloading {
+            ticks: (x.reload_time_remaining / PHYSICS_TICK_LENGTH).ceil() as u32,
+        },
+        Some(_) => ApiCallOutcome::Ok,
+    }
+}
+
+impl fmt::Display for ApiCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiCall::Fire { index } => write!(f, "fire({index})"),
+            ApiCall::FireAt { index } => write!(f, "fire_at({index}, ..)"),
+            ApiCall::FireSpread { index } => write!(f, "fire_spread({index}, ..)"),
+            ApiCall::AimAllAt => write!(f, "aim_all_at(..)"),
+            ApiCall::ActivateAbility(ability) => write!(f, "activate_ability({ability:?})"),
+            ApiCall::RequestReinforcement(class) => write!(f, "request_reinforcement({class:?})"),
+            ApiCall::SendMessage { radio } => write!(f, "send (radio {radio})"),
+            ApiCall::Disarm => write!(f, "disarm()"),
+            ApiCall::Explode => write!(f, "explode()"),
+        }
+    }
+}
+
+impl fmt::Display for ApiCallOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiCallOutcome::Ok => write!(f, "ok"),
+            ApiCallOutcome::Reloading { ticks } => write!(f, "reloading for {ticks} ticks"),
+            ApiCallOutcome::Overheated => write!(f, "overheated"),
+            ApiCallOutcome::Missing => write!(f, "no such index"),
+            ApiCallOutcome::Blocked => write!(f, "blocked by hull"),
+            ApiCallOutcome::Denied => write!(f, "denied"),
+            ApiCallOutcome::OutOfAmmo => write!(f, "out of ammo"),
+        }
+    }
+}
+
+impl fmt::Display for ApiCallRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.tick, self.call, self.outcome)
+    }
+}
@@ -0,0 +1,118 @@
+//! Asteroids that break apart.
+//!
+//! With `AsteroidTerrain::Splitting`, an asteroid shot down to zero health
+//! breaks into two or three smaller ones instead of disappearing. The size of
+//! an asteroid is encoded in its variant: `variant / TIER_STRIDE` is its tier,
+//! and each tier is half the size of the one before. The rest of the variant
+//! picks the shape, so the renderer and the physics engine build fragments the
+//! same way as any other asteroid. Asteroids of the smallest tier vaporize.
+//!
+//! Fragments share the parent's mass in proportion to their area and fly
+//! apart from its center of mass. The dispersion is balanced so the fragments'
+//! combined momentum is exactly the parent's, and their center of mass is
+//! where the parent's was.
+
+use crate::model;
+use crate::ship::{self, ShipClass, ShipData};
+use nalgebra::{vector, Rotation2, Vector2};
+use rand::Rng;
+use std::f64::consts::TAU;
+
+// Variants per tier.
+pub const TIER_STRIDE: i32 = 1000;
+
+// Tier of the smallest asteroids, which vaporize instead of splitting.
+pub const MAX_TIER: i32 = 2;
+
+// Number of distinct asteroid shapes in each tier.
+pub const SHAPES: i32 = 30;
+
+// Typical speed (in m/s) fragments fly apart at.
+pub const DISPERSION_SPEED: f64 = 20.0;
+
+// Distance of each fragment from the parent's center, as a fraction of the
+// parent's radius.
+const FRAGMENT_OFFSET: f64 = 0.6;
+
+pub fn tier(variant: i32) -> i32 {
+    variant / TIER_STRIDE
+}
+
+// Size relative to a full-sized asteroid.
+pub fn scale(variant: i32) -> f64 {
+    0.5f64.powi(tier(variant))
+}
+
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    pub position: Vector2<f64>,
+    pub velocity: Vector2<f64>,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub data: ShipData,
+}
+
+// Breaks up an asteroid with the given state. Returns no fragments if it's
+// too small to split, or isn't an asteroid at all.
+pub fn split<R: Rng>(
+    rng: &mut R,
+    parent: &ShipData,
+    position: Vector2<f64>,
+    velocity: Vector2<f64>,
+    angular_velocity: f64,
+) -> Vec<Fragment> {
+    let ShipClass::Asteroid { variant } = parent.class else {
+        return vec![];
+    };
+    if tier(variant) >= MAX_TIER {
+        return vec![];
+    }
+    let fragment_tier = tier(variant) + 1;
+    let n = rng.gen_range(2..=3);
+    let base_angle = rng.gen_range(0.0..TAU);
+    let offset_distance = model::radius(parent.class) as f64 * FRAGMENT_OFFSET;
+
+    let mut fragments: Vec<Fragment> = (0..n)
+        .map(|i| {
+            let direction = Rotation2::new(base_angle + i as f64 * TAU / n as f64)
+                .transform_vector(&vector![1.0, 0.0]);
+            let offset = direction * offset_distance;
+            let spin = vector![-offset.y, offset.x] * angular_velocity;
+            let dispersion = direction * DISPERSION_SPEED * rng.gen_range(0.5..1.5);
+            let variant = fragment_tier * TIER_STRIDE + rng.gen_range(0..SHAPES);
+            Fragment {
+                position: offset,
+                velocity: spin + dispersion,
+                heading: rng.gen_range(0.0..TAU),
+                angular_velocity,
+                data: ship::asteroid(variant),
+            }
+        })
+        .collect();
+
+    let total_area: f64 = fragments
+        .iter()
+        .map(|fragment| model::area(fragment.data.class))
+        .sum();
+    for fragment in fragments.iter_mut() {
+        fragment.data.mass = parent.mass * model::area(fragment.data.class) / total_area;
+    }
+
+    // Fragment shapes and dispersion speeds differ, so re-center the offsets
+    // and velocity changes on the parent's center of mass.
+    let mean_offset = fragments
+        .iter()
+        .map(|fragment| fragment.position * fragment.data.mass)
+        .sum::<Vector2<f64>>()
+        / parent.mass;
+    let mean_dv = fragments
+        .iter()
+        .map(|fragment| fragment.velocity * fragment.data.mass)
+        .sum::<Vector2<f64>>()
+        / parent.mass;
+    for fragment in fragments.iter_mut() {
+        fragment.position = position + fragment.position - mean_offset;
+        fragment.velocity = velocity + fragment.velocity - mean_dv;
+    }
+    fragments
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use super::index_set::{HasIndex, Index};
+use crate::ship::WeaponClass;
 use crate::simulation::{Simulation, MAX_WORLD_SIZE, PHYSICS_TICK_LENGTH};
+use crate::stats::ShotSource;
 use crate::{collision, simulation};
 use bitvec::vec::BitVec;
 use nalgebra::Vector2;
@@ -31,6 +33,37 @@ pub struct BulletData {
     pub team: i32,
     pub ttl: f32,
     pub color: u32,
+    // Seconds since the bullet was fired.
+    pub age: f32,
+    pub damage_falloff: Option<DamageFalloff>,
+    // Released by an exploding warhead.
+    pub fragment: bool,
+    pub source: Option<ShotSource>,
+    pub weapon_class: WeaponClass,
+}
+
+impl BulletData {
+    // Multiplier applied to the damage of a hit.
+    pub fn damage_factor(&self) -> f64 {
+        self.damage_falloff
+            .map(|falloff| falloff.factor(self.age) as f64)
+            .unwrap_or(1.0)
+    }
+}
+
+// Reduces damage linearly once a bullet is older than `start` seconds, losing
+// `rate` of the full damage per second down to a floor of `min`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DamageFalloff {
+    pub start: f32,
+    pub rate: f32,
+    pub min: f32,
+}
+
+impl DamageFalloff {
+    pub fn factor(&self, age: f32) -> f32 {
+        (1.0 - self.rate * (age - self.start).max(0.0)).max(self.min)
+    }
 }
 
 pub fn body(sim: &Simulation, handle: BulletHandle) -> &RigidBody {
@@ -95,6 +128,7 @@ pub fn tick(sim: &mut Simulation) {
         let team = {
             let data = data_mut(sim, handle);
             data.ttl -= dt as f32;
+            data.age += dt as f32;
             if data.ttl <= 0.0 {
                 destroy(sim, handle);
                 continue;
@@ -107,14 +141,16 @@ pub fn tick(sim: &mut Simulation) {
         let mut needs_collider = false;
         {
             let world_size = sim.world_size();
+            let origin = sim.origin();
             let body = sim.bodies.get_mut(RigidBodyHandle(handle.index())).unwrap();
             has_collider = !body.colliders().is_empty();
 
             let position = *body.translation();
-            if position.x < -world_size / 2.0
-                || position.x > world_size / 2.0
-                || position.y < -world_size / 2.0
-                || position.y > world_size / 2.0
+            let world_position = position + origin;
+            if world_position.x < -world_size / 2.0
+                || world_position.x > world_size / 2.0
+                || world_position.y < -world_size / 2.0
+                || world_position.y > world_size / 2.0
             {
                 destroy(sim, handle);
                 continue;
@@ -2,16 +2,21 @@ use crate::bullet::{self, BulletHandle};
 use crate::index_set::HasIndex;
 use crate::ship::{ShipClass, ShipHandle};
 use crate::simulation::{Particle, Simulation, PHYSICS_TICK_LENGTH};
-use nalgebra::{Rotation2, UnitComplex};
+use crate::snapshot::Effect;
+use crate::stats::RecordStats;
+use nalgebra::{Point2, Rotation2, UnitComplex, Vector2};
 use oort_api::Ability;
 use rand::Rng;
+use rapier2d_f64::parry::query::PointQuery;
 use rapier2d_f64::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::f64::consts::TAU;
 
 const DAMAGE_FACTOR: f64 = 0.00014;
 const WALL_COLLISION_GROUP: Group = Group::GROUP_1;
 const SHIP_COLLISION_GROUP: Group = Group::GROUP_2;
 const PLANET_COLLISION_GROUP: Group = Group::GROUP_3;
+const ASTEROID_COLLISION_GROUP: Group = Group::GROUP_14;
 const BULLET_GROUPS: &[Group] = &[
     Group::GROUP_4,
     Group::GROUP_5,
@@ -25,6 +30,34 @@ const BULLET_GROUPS: &[Group] = &[
     Group::GROUP_13,
 ];
 
+// How bullets interact with asteroids. Scenarios choose with
+// `Scenario::asteroid_terrain`.
+#[derive(PartialEq, Eq, Hash, Debug, Serialize, Deserialize, Copy, Clone, Default)]
+pub enum AsteroidTerrain {
+    // Bullets damage and eventually destroy asteroids.
+    #[default]
+    Destructible,
+    // Asteroids stop bullets but take no damage.
+    Shielded,
+    // Bullets pass through asteroids.
+    Intangible,
+    // Like Destructible, but destroyed asteroids break into smaller ones. See
+    // the `asteroid` module.
+    Splitting,
+}
+
+impl AsteroidTerrain {
+    // Name passed to ship code in the ASTEROID_TERRAIN environment variable.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AsteroidTerrain::Destructible => "destructible",
+            AsteroidTerrain::Shielded => "shielded",
+            AsteroidTerrain::Intangible => "intangible",
+            AsteroidTerrain::Splitting => "splitting",
+        }
+    }
+}
+
 fn bullet_group(team: i32) -> Group {
     BULLET_GROUPS[team as usize]
 }
@@ -38,14 +71,17 @@ fn all_bullet_groups() -> Group {
 pub fn bullet_interaction_groups(team: i32) -> InteractionGroups {
     InteractionGroups::new(
         bullet_group(team),
-        WALL_COLLISION_GROUP | SHIP_COLLISION_GROUP | PLANET_COLLISION_GROUP,
+        WALL_COLLISION_GROUP
+            | SHIP_COLLISION_GROUP
+            | PLANET_COLLISION_GROUP
+            | ASTEROID_COLLISION_GROUP,
     )
 }
 
 pub fn wall_interaction_groups() -> InteractionGroups {
     InteractionGroups::new(
         WALL_COLLISION_GROUP,
-        SHIP_COLLISION_GROUP | all_bullet_groups(),
+        SHIP_COLLISION_GROUP | ASTEROID_COLLISION_GROUP | all_bullet_groups(),
     )
 }
 
@@ -53,15 +89,41 @@ pub fn ship_interaction_groups(team: i32) -> InteractionGroups {
     let bullet_groups = all_bullet_groups() ^ bullet_group(team);
     InteractionGroups::new(
         SHIP_COLLISION_GROUP,
-        WALL_COLLISION_GROUP | SHIP_COLLISION_GROUP | PLANET_COLLISION_GROUP | bullet_groups,
+        WALL_COLLISION_GROUP
+            | SHIP_COLLISION_GROUP
+            | PLANET_COLLISION_GROUP
+            | ASTEROID_COLLISION_GROUP
+            | bullet_groups,
     )
 }
 
+// Bullets of every team are filtered out of intangible asteroids, since
+// rapier only reports contacts that both colliders' groups allow.
+pub fn asteroid_interaction_groups(team: i32, terrain: AsteroidTerrain) -> InteractionGroups {
+    let bullet_groups = match terrain {
+        AsteroidTerrain::Intangible => Group::empty(),
+        _ => all_bullet_groups() ^ bullet_group(team),
+    };
+    InteractionGroups::new(
+        ASTEROID_COLLISION_GROUP,
+        WALL_COLLISION_GROUP
+            | SHIP_COLLISION_GROUP
+            | PLANET_COLLISION_GROUP
+            | ASTEROID_COLLISION_GROUP
+            | bullet_groups,
+    )
+}
+
+// Ships in their spawn grace period only collide with the world edge.
+pub fn spawning_interaction_groups() -> InteractionGroups {
+    InteractionGroups::new(SHIP_COLLISION_GROUP, WALL_COLLISION_GROUP)
+}
+
 pub fn planet_interaction_groups() -> InteractionGroups {
     let bullet_groups = all_bullet_groups();
     InteractionGroups::new(
         PLANET_COLLISION_GROUP,
-        SHIP_COLLISION_GROUP | PLANET_COLLISION_GROUP | bullet_groups,
+        SHIP_COLLISION_GROUP | PLANET_COLLISION_GROUP | ASTEROID_COLLISION_GROUP | bullet_groups,
     )
 }
 
@@ -93,9 +155,19 @@ pub fn handle_collisions(sim: &mut Simulation, events: &[CollisionEvent]) {
                     bullet::destroy(sim, bullet);
                     return;
                 }
+                if sim.asteroid_terrain() == AsteroidTerrain::Shielded
+                    && matches!(sim.ship(ship).data().class, ShipClass::Asteroid { .. })
+                {
+                    bullet::destroy(sim, bullet);
+                    return;
+                }
                 let dv = bullet_velocity - sim.ship(ship).velocity();
                 let energy = 0.5 * bullet::data(sim, bullet).mass as f64 * dv.magnitude_squared();
-                let damage = energy * DAMAGE_FACTOR;
+                let damage = sim
+                    .ship(ship)
+                    .data()
+                    .armor
+                    .apply(energy * DAMAGE_FACTOR * bullet::data(sim, bullet).damage_factor());
                 for _ in 0..((damage as i32 / 10).clamp(1, 20)) {
                     let rot = Rotation2::new(sim.rng.gen_range(0.0..TAU));
                     let v = rot.transform_vector(&vector![sim.rng.gen_range(0.0..1000.0), 0.0]);
@@ -107,12 +179,40 @@ pub fn handle_collisions(sim: &mut Simulation, events: &[CollisionEvent]) {
                         lifetime: (PHYSICS_TICK_LENGTH * 30.0) as f32,
                     });
                 }
+                let attacker = bullet::data(sim, bullet).team;
+                *sim.damage_dealt.entry(attacker).or_default() += damage;
+                let (target_team, target_class) = {
+                    let data = sim.ship(ship).data();
+                    (data.team, data.class)
+                };
+                let overkill = (damage - sim.ship(ship).data().health.max(0.0)).max(0.0);
+                let source = bullet::data(sim, bullet).source;
+                sim.stats.record_hit(
+                    attacker,
+                    source,
+                    target_team,
+                    target_class,
+                    damage,
+                    overkill,
+                );
+                let normal = impact_normal(sim, ship, bullet_position);
+                sim.events.effects.push(Effect::Hit {
+                    ship: ship.into(),
+                    position: Point2::from(bullet_position),
+                    normal,
+                    damage,
+                    overkill,
+                });
                 let ship_destroyed = {
                     let ship_data = sim.ship_data.get_mut(ship.index()).unwrap();
                     ship_data.health -= damage;
+                    ship_data.damage_taken += damage;
                     ship_data.health <= 0.0
                 };
                 if ship_destroyed {
+                    if matches!(target_class, ShipClass::Missile | ShipClass::Torpedo) {
+                        sim.stats.record_interception(target_team);
+                    }
                     for _ in 0..10 {
                         let rot = Rotation2::new(sim.rng.gen_range(0.0..TAU));
                         let v = rot.transform_vector(&vector![sim.rng.gen_range(0.0..200.0), 0.0]);
@@ -127,7 +227,10 @@ pub fn handle_collisions(sim: &mut Simulation, events: &[CollisionEvent]) {
                             lifetime,
                         });
                     }
-                    sim.ship_mut(ship).data_mut().destroyed = true;
+                    let fragment = bullet::data(sim, bullet).fragment;
+                    let ship_data = sim.ship_data.get_mut(ship.index()).unwrap();
+                    ship_data.destroyed = true;
+                    ship_data.chain_reaction |= fragment;
                     bullet::data_mut(sim, bullet).mass *= 0.5;
                     let rotation = UnitComplex::new(sim.rng.gen_range(-0.1..0.1));
                     let new_bullet_velocity = rotation.transform_vector(&bullet_velocity);
@@ -201,3 +304,26 @@ pub fn add_walls(sim: &mut Simulation) {
     make_edge(world_size / 2.0, 0.0, std::f64::consts::PI / 2.0);
     make_edge(-world_size / 2.0, 0.0, 3.0 * std::f64::consts::PI / 2.0);
 }
+
+// Outward normal of the ship's surface closest to `point`, falling back to the
+// direction from the ship's center.
+fn impact_normal(sim: &Simulation, ship: ShipHandle, point: Vector2<f64>) -> Vector2<f64> {
+    let body = sim.ship(ship).body();
+    let fallback = (point - body.position().translation.vector)
+        .try_normalize(1e-9)
+        .unwrap_or(vector![1.0, 0.0]);
+    let Some(collider) = body.colliders().first().and_then(|&h| sim.colliders.get(h)) else {
+        return fallback;
+    };
+    let projection =
+        collider
+            .shape()
+            .project_point(collider.position(), &Point2::from(point), false);
+    let normal = point - projection.point.coords;
+    let normal = if projection.is_inside {
+        -normal
+    } else {
+        normal
+    };
+    normal.try_normalize(1e-9).unwrap_or(fallback)
+}
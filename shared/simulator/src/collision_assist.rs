@@ -0,0 +1,121 @@
+use crate::model;
+use crate::ship::{ShipClass, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::{vector, Rotation2, Vector2};
+
+// How far ahead to look for collisions, in seconds.
+pub const LOOKAHEAD: f64 = 2.0;
+// Extra clearance added to the sum of the two ships' radii.
+pub const SAFETY_MARGIN: f64 = 10.0;
+
+struct Obstacle {
+    handle: ShipHandle,
+    team: i32,
+    asteroid: bool,
+    position: Vector2<f64>,
+    velocity: Vector2<f64>,
+    radius: f64,
+}
+
+// Overrides the acceleration of ships with collision assist enabled when they
+// are about to hit a friendly ship or an asteroid.
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    if !handle_snapshot
+        .iter()
+        .any(|&handle| sim.ship(handle).data().collision_assist)
+    {
+        return;
+    }
+
+    let obstacles: Vec<Obstacle> = handle_snapshot
+        .iter()
+        .filter_map(|&handle| {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            if matches!(data.class, ShipClass::Missile | ShipClass::Torpedo) {
+                return None;
+            }
+            Some(Obstacle {
+                handle,
+                team: data.team,
+                asteroid: matches!(data.class, ShipClass::Asteroid { .. }),
+                position: ship.position().vector,
+                velocity: ship.velocity(),
+                radius: model::radius(data.class) as f64,
+            })
+        })
+        .collect();
+
+    for handle in handle_snapshot {
+        if !sim.ship(handle).data().collision_assist {
+            continue;
+        }
+        let (team, radius, position, velocity, heading) = {
+            let ship = sim.ship(handle);
+            (
+                ship.data().team,
+                model::radius(ship.data().class) as f64,
+                ship.position().vector,
+                ship.velocity(),
+                ship.heading(),
+            )
+        };
+
+        let mut best: Option<(f64, Vector2<f64>)> = None;
+        for obstacle in obstacles.iter() {
+            if obstacle.handle == handle || !(obstacle.asteroid || obstacle.team == team) {
+                continue;
+            }
+            let dp = obstacle.position - position;
+            let dv = obstacle.velocity - velocity;
+            let dv_squared = dv.norm_squared();
+            if dv_squared < 1e-9 {
+                continue;
+            }
+            let t = -dp.dot(&dv) / dv_squared;
+            if !(0.0..LOOKAHEAD).contains(&t) {
+                continue;
+            }
+            let miss = dp + dv * t;
+            if miss.norm() >= radius + obstacle.radius + SAFETY_MARGIN {
+                continue;
+            }
+            if best.map(|(best_t, _)| t < best_t).unwrap_or(true) {
+                // Steer away from the obstacle's position at closest approach.
+                let away = if miss.norm() > 1e-6 {
+                    -miss.normalize()
+                } else {
+                    vector![dv.y, -dv.x].normalize()
+                };
+                best = Some((t, away));
+            }
+        }
+
+        if let Some((_, away)) = best {
+            let local = Rotation2::new(-heading) * away;
+            let acceleration = {
+                let ship = sim.ship(handle);
+                let data = ship.data();
+                let max_x = if local.x >= 0.0 {
+                    data.max_forward_acceleration
+                } else {
+                    data.max_backward_acceleration
+                };
+                let max_y = data.max_lateral_acceleration;
+                let scale_x = if local.x.abs() > 1e-9 {
+                    max_x / local.x.abs()
+                } else {
+                    f64::INFINITY
+                };
+                let scale_y = if local.y.abs() > 1e-9 {
+                    max_y / local.y.abs()
+                } else {
+                    f64::INFINITY
+                };
+                local * scale_x.min(scale_y)
+            };
+            sim.ship_mut(handle).accelerate(acceleration);
+        }
+    }
+}
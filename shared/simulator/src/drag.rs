@@ -0,0 +1,19 @@
+use crate::simulation::{Simulation, PHYSICS_TICK_LENGTH};
+
+// Slows every moving body, ships and bullets alike, as if flying through a
+// nebula. `drag` is the fraction of velocity lost per second for small values;
+// velocity decays by a factor of `exp(-drag * t)` over `t` seconds.
+pub fn tick(sim: &mut Simulation) {
+    let drag = sim.drag();
+    if drag <= 0.0 {
+        return;
+    }
+    let factor = (-drag * PHYSICS_TICK_LENGTH).exp();
+    for (_, body) in sim.bodies.iter_mut() {
+        if !body.is_dynamic() {
+            continue;
+        }
+        let velocity = *body.linvel() * factor;
+        body.set_linvel(velocity, false);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::ship::{ShipClass, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::Vector2;
+
+struct Threat {
+    team: i32,
+    position: Vector2<f64>,
+    velocity: Vector2<f64>,
+}
+
+// Aims and fires the flak guns of ships with flak mode enabled at the most
+// threatening incoming missile or torpedo in range.
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    if !handle_snapshot
+        .iter()
+        .any(|&handle| sim.ship(handle).data().flak_mode)
+    {
+        return;
+    }
+
+    let threats: Vec<Threat> = handle_snapshot
+        .iter()
+        .filter_map(|&handle| {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            if !matches!(data.class, ShipClass::Missile | ShipClass::Torpedo) || data.destroyed {
+                return None;
+            }
+            Some(Threat {
+                team: data.team,
+                position: ship.position().vector,
+                velocity: ship.velocity(),
+            })
+        })
+        .collect();
+
+    for handle in handle_snapshot {
+        if !sim.ship(handle).data().flak_mode {
+            continue;
+        }
+        let (team, position, velocity, heading) = {
+            let ship = sim.ship(handle);
+            (
+                ship.data().team,
+                ship.position().vector,
+                ship.velocity(),
+                ship.heading(),
+            )
+        };
+        let num_guns = sim.ship(handle).data().guns.len();
+        for index in 0..num_guns {
+            let (speed, range, gun) = {
+                let ship = sim.ship(handle);
+                let gun = &ship.data().guns[index];
+                if !gun.flak {
+                    continue;
+                }
+                (gun.speed, gun.speed * gun.ttl as f64, gun.clone())
+            };
+
+            let mut best: Option<(f64, f64)> = None;
+            for threat in threats.iter().filter(|threat| threat.team != team) {
+                let dp = threat.position - position;
+                let dv = threat.velocity - velocity;
+                let distance = dp.norm();
+                let closing_speed = -dp.dot(&dv) / distance.max(1e-9);
+                if distance > range || closing_speed <= 0.0 {
+                    continue;
+                }
+                let Some(t) = intercept_time(dp, dv, speed) else {
+                    continue;
+                };
+                let aim = dp + dv * t;
+                let aim_heading = aim.y.atan2(aim.x);
+                if !gun.in_arc(aim_heading - heading) {
+                    continue;
+                }
+                let time_to_impact = distance / closing_speed;
+                if best.map_or(true, |(best_time, _)| time_to_impact < best_time) {
+                    best = Some((time_to_impact, aim_heading));
+                }
+            }
+
+            if let Some((_, aim_heading)) = best {
+                let mut ship = sim.ship_mut(handle);
+                ship.aim(index as i64, aim_heading);
+                ship.fire_gun(index as i64);
+            }
+        }
+    }
+}
+
+// Time for a bullet fired at `speed` to reach a target at relative position
+// `dp` moving with relative velocity `dv`.
+fn intercept_time(dp: Vector2<f64>, dv: Vector2<f64>, speed: f64) -> Option<f64> {
+    let a = dv.dot(&dv) - speed * speed;
+    let b = 2.0 * dp.dot(&dv);
+    let c = dp.dot(&dp);
+    if a.abs() < 1e-9 {
+        let t = -c / b;
+        return (t > 0.0).then_some(t);
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt = discriminant.sqrt();
+    [(-b - sqrt) / (2.0 * a), (-b + sqrt) / (2.0 * a)]
+        .into_iter()
+        .filter(|&t| t > 0.0)
+        .min_by(|a, b| a.total_cmp(b))
+}
@@ -0,0 +1,157 @@
+use crate::scenario::Status;
+use crate::ship::{ShipClass, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::{Rotation2, Vector2};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+
+const PLAYER_TEAM: i32 = 0;
+// Shots further than this from any enemy's bearing count as unaimed.
+const AIM_TOLERANCE: f64 = TAU / 24.0;
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Hint {
+    NeverAccelerated,
+    NeverRotated,
+    NeverFired,
+    FiredBeforeAiming,
+}
+
+impl Hint {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Hint::NeverAccelerated => "Your ship never accelerated. Try calling accelerate().",
+            Hint::NeverRotated => "Your ship never turned. Try calling turn() or torque().",
+            Hint::NeverFired => "Your ship never fired. Try calling fire(0).",
+            Hint::FiredBeforeAiming => {
+                "Your ship fired before aiming at the target. Try turning towards the target before calling fire(0)."
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HintEvent {
+    pub tick: u32,
+    pub hint: Hint,
+}
+
+// Watches the player's ships in tutorials and explains why they failed.
+#[derive(Clone)]
+pub struct Grader {
+    enabled_hints: Vec<Hint>,
+    accelerated: bool,
+    rotated: bool,
+    fired: bool,
+    finished: bool,
+    events: Vec<HintEvent>,
+}
+
+impl Grader {
+    pub fn new(enabled_hints: Vec<Hint>) -> Self {
+        Self {
+            enabled_hints,
+            accelerated: false,
+            rotated: false,
+            fired: false,
+            finished: false,
+            events: vec![],
+        }
+    }
+
+    pub fn events(&self) -> &[HintEvent] {
+        &self.events
+    }
+
+    // Called after the ship controllers run, before their commands are applied.
+    pub fn observe(&mut self, sim: &Simulation) {
+        for &handle in sim.ships.iter() {
+            if !is_player_ship(sim, handle) {
+                continue;
+            }
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            self.accelerated |= data.acceleration != Vector2::zeros();
+            self.rotated |= data.angular_acceleration != 0.0;
+        }
+
+        for &(handle, gun_index) in sim.events.gun_shots.iter() {
+            if !is_player_ship(sim, handle) {
+                continue;
+            }
+            if !self.fired && !is_aimed(sim, handle, gun_index) {
+                self.emit(sim, Hint::FiredBeforeAiming);
+            }
+            self.fired = true;
+        }
+    }
+
+    // Called after the scenario has updated its status.
+    pub fn finish(&mut self, sim: &Simulation) {
+        if self.finished {
+            return;
+        }
+        match sim.status() {
+            Status::Running | Status::Victory { team: PLAYER_TEAM } => return,
+            _ => {}
+        }
+        self.finished = true;
+        if !self.accelerated {
+            self.emit(sim, Hint::NeverAccelerated);
+        }
+        if !self.rotated {
+            self.emit(sim, Hint::NeverRotated);
+        }
+        if !self.fired {
+            self.emit(sim, Hint::NeverFired);
+        }
+    }
+
+    fn emit(&mut self, sim: &Simulation, hint: Hint) {
+        if self.enabled_hints.contains(&hint) {
+            self.events.push(HintEvent {
+                tick: sim.tick(),
+                hint,
+            });
+        }
+    }
+}
+
+fn is_player_ship(sim: &Simulation, handle: ShipHandle) -> bool {
+    let ship = sim.ship(handle);
+    let data = ship.data();
+    data.team == PLAYER_TEAM
+        && [
+            ShipClass::Fighter,
+            ShipClass::Corvette,
+            ShipClass::Frigate,
+            ShipClass::Cruiser,
+        ]
+        .contains(&data.class)
+}
+
+fn is_aimed(sim: &Simulation, handle: ShipHandle, gun_index: usize) -> bool {
+    let ship = sim.ship(handle);
+    let relative_heading = ship
+        .data()
+        .guns
+        .get(gun_index)
+        .map(|gun| gun.clamp_to_arc(gun.heading - ship.heading()))
+        .unwrap_or(0.0);
+    let gun_rot = Rotation2::new(ship.heading() + relative_heading);
+    sim.ships.iter().any(|&other| {
+        let other = sim.ship(other);
+        if other.data().team == PLAYER_TEAM || other.data().hulk {
+            return false;
+        }
+        let dp = other.position().vector - ship.position().vector;
+        let bearing = Rotation2::rotation_between(&Vector2::x(), &dp);
+        gun_rot.angle_to(&bearing).abs() < AIM_TOLERANCE
+    })
+}
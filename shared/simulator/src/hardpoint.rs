@@ -0,0 +1,372 @@
+use crate::ship::{self, Gun, MissileLauncher, ShipClass, ShipData, WeaponClass};
+use nalgebra::{vector, Vector2};
+use std::f64::consts::TAU;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MountSize {
+    Small,
+    Large,
+}
+
+// A place on a hull where a weapon can be mounted.
+#[derive(Clone, Debug)]
+pub struct Hardpoint {
+    pub offset: Vector2<f64>,
+    // Direction relative to the ship's heading. For turrets, the center of
+    // the firing arc.
+    pub angle: f64,
+    pub turret: bool,
+    // Total width of a turret's firing arc. TAU covers every direction.
+    pub arc: f64,
+    // Largest weapon that fits.
+    pub size: MountSize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hull {
+    pub hardpoints: Vec<Hardpoint>,
+    // Total mass (kg) and power of the mounted weapons.
+    pub max_mass: f64,
+    pub max_power: f64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Weapon {
+    Vulcan,
+    Railgun,
+    Flak,
+    MissileLauncher,
+    TorpedoLauncher,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RefitError {
+    NoHardpoints,
+    TooManyWeapons,
+    TooLarge { index: usize },
+    OverMass,
+    OverPower,
+}
+
+impl std::fmt::Display for RefitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefitError::NoHardpoints => write!(f, "ship class has no hardpoints"),
+            RefitError::TooManyWeapons => write!(f, "more weapons than hardpoints"),
+            RefitError::TooLarge { index } => {
+                write!(f, "weapon too large for hardpoint {}", index)
+            }
+            RefitError::OverMass => write!(f, "weapons exceed the hull's mass budget"),
+            RefitError::OverPower => write!(f, "weapons exceed the hull's power budget"),
+        }
+    }
+}
+
+impl std::error::Error for RefitError {}
+
+impl Weapon {
+    pub fn size(self) -> MountSize {
+        match self {
+            Weapon::Vulcan | Weapon::MissileLauncher => MountSize::Small,
+            Weapon::Railgun | Weapon::Flak | Weapon::TorpedoLauncher => MountSize::Large,
+        }
+    }
+
+    pub fn mass(self) -> f64 {
+        match self {
+            Weapon::Vulcan => 500.0,
+            Weapon::Railgun => 50e3,
+            Weapon::Flak => 40e3,
+            Weapon::MissileLauncher => 1000.0,
+            Weapon::TorpedoLauncher => 20e3,
+        }
+    }
+
+    pub fn power(self) -> f64 {
+        match self {
+            Weapon::Vulcan => 1.0,
+            Weapon::Railgun => 5.0,
+            Weapon::Flak => 4.0,
+            Weapon::MissileLauncher => 0.5,
+            Weapon::TorpedoLauncher => 2.0,
+        }
+    }
+
+    fn gun(self, hardpoint: &Hardpoint) -> Option<Gun> {
+        let gun = match self {
+            Weapon::Vulcan => ship::vulcan_gun(),
+            Weapon::Railgun => Gun {
+                magazine_size: 1,
+                magazine_reload_ticks: 0,
+                reload_ticks: 2 * 60,
+                speed: 4000.0,
+                bullet_mass: 1.0,
+                ttl: 60.0,
+                weapon_class: WeaponClass::HeavySlug,
+                ..Default::default()
+            },
+            Weapon::Flak => Gun {
+                magazine_size: 30,
+                magazine_reload_ticks: 60,
+                reload_ticks: 24,
+                speed: 1000.0,
+                speed_error: 50.0,
+                inaccuracy: 0.02,
+                burst_size: 6,
+                ttl: 1.0,
+                bullet_mass: 0.1,
+                flak: true,
+                weapon_class: WeaponClass::Flak,
+                ..Default::default()
+            },
+            Weapon::MissileLauncher | Weapon::TorpedoLauncher => return None,
+        };
+        let (min_angle, max_angle) = if hardpoint.turret && hardpoint.arc >= TAU {
+            (0.0, TAU)
+        } else if hardpoint.turret {
+            (
+                hardpoint.angle - hardpoint.arc / 2.0,
+                hardpoint.angle + hardpoint.arc / 2.0,
+            )
+        } else {
+            let angle = hardpoint.angle.rem_euclid(TAU);
+            (angle, angle)
+        };
+        Some(Gun {
+            offset: hardpoint.offset,
+            min_angle,
+            max_angle,
+            magazine_remaining: gun.magazine_size,
+            weapon_class: match (self, hardpoint.turret) {
+                (Weapon::Vulcan, true) => WeaponClass::PointDefense,
+                _ => gun.weapon_class,
+            },
+            ..gun
+        })
+    }
+
+    fn missile_launcher(self, hardpoint: &Hardpoint) -> Option<MissileLauncher> {
+        let (class, reload_ticks) = match self {
+            Weapon::MissileLauncher => (ShipClass::Missile, 2 * 60),
+            Weapon::TorpedoLauncher => (ShipClass::Torpedo, 180),
+            _ => return None,
+        };
+        Some(MissileLauncher {
+            class,
+            reload_ticks,
+            reload_ticks_remaining: 0,
+            initial_speed: 100.0,
+            offset: hardpoint.offset,
+            angle: hardpoint.angle,
+            ignition_ticks: 0,
+            burn_profile: None,
+            lost: false,
+        })
+    }
+}
+
+// Width of the firing arc of the main battery on a refitted cruiser, centered
+// on the bow. Unlike the stock flak turret it can't fire aft.
+pub const CRUISER_MAIN_BATTERY_ARC: f64 = TAU * 0.75;
+
+pub fn hull(class: ShipClass) -> Option<Hull> {
+    let hardpoint = |x: f64, y: f64, angle: f64, turret: bool, size: MountSize| Hardpoint {
+        offset: vector![x, y],
+        angle,
+        turret,
+        arc: TAU,
+        size,
+    };
+    match class {
+        ShipClass::Fighter => Some(Hull {
+            hardpoints: vec![
+                hardpoint(20.0, 0.0, 0.0, false, MountSize::Small),
+                hardpoint(20.0, 0.0, 0.0, false, MountSize::Small),
+            ],
+            max_mass: 2000.0,
+            max_power: 1.5,
+        }),
+        ShipClass::Corvette => Some(Hull {
+            hardpoints: vec![
+                hardpoint(35.0, 0.0, 0.0, false, MountSize::Small),
+                hardpoint(0.0, 15.0, TAU / 8.0, false, MountSize::Small),
+                hardpoint(0.0, -15.0, -TAU / 8.0, false, MountSize::Small),
+            ],
+            max_mass: 4000.0,
+            max_power: 2.5,
+        }),
+        ShipClass::Frigate => Some(Hull {
+            hardpoints: vec![
+                hardpoint(40.0, 0.0, 0.0, false, MountSize::Large),
+                hardpoint(0.0, 15.0, 0.0, true, MountSize::Small),
+                hardpoint(0.0, -15.0, 0.0, true, MountSize::Small),
+                hardpoint(60.0, 0.0, 0.0, false, MountSize::Small),
+            ],
+            max_mass: 60e3,
+            max_power: 8.0,
+        }),
+        ShipClass::Cruiser => Some(Hull {
+            hardpoints: vec![
+                // The superstructure blocks the main battery from firing aft.
+                Hardpoint {
+                    arc: CRUISER_MAIN_BATTERY_ARC,
+                    ..hardpoint(0.0, 0.0, 0.0, true, MountSize::Large)
+                },
+                hardpoint(0.0, 50.0, TAU / 4.0, false, MountSize::Small),
+                hardpoint(0.0, -50.0, -TAU / 4.0, false, MountSize::Small),
+                hardpoint(140.0, 0.0, 0.0, false, MountSize::Large),
+            ],
+            max_mass: 70e3,
+            max_power: 8.0,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks that `loadout` fits the hull of `class`.
+///
+/// Each entry of the loadout is mounted on the hardpoint with the same index.
+pub fn validate(class: ShipClass, loadout: &[Option<Weapon>]) -> Result<Hull, RefitError> {
+    let hull = hull(class).ok_or(RefitError::NoHardpoints)?;
+    if loadout.len() > hull.hardpoints.len() {
+        return Err(RefitError::TooManyWeapons);
+    }
+    let mut mass = 0.0;
+    let mut power = 0.0;
+    for (index, (weapon, hardpoint)) in loadout.iter().zip(hull.hardpoints.iter()).enumerate() {
+        let Some(weapon) = weapon else {
+            continue;
+        };
+        if weapon.size() > hardpoint.size {
+            return Err(RefitError::TooLarge { index });
+        }
+        mass += weapon.mass();
+        power += weapon.power();
+    }
+    if mass > hull.max_mass {
+        return Err(RefitError::OverMass);
+    }
+    if power > hull.max_power {
+        return Err(RefitError::OverPower);
+    }
+    Ok(hull)
+}
+
+/// Replaces the guns and missile launchers of a ship with `loadout`.
+///
+/// Guns and launchers are numbered in hardpoint order. The ship is left
+/// unchanged if the loadout doesn't fit.
+pub fn refit(data: &mut ShipData, loadout: &[Option<Weapon>]) -> Result<(), RefitError> {
+    let hull = validate(data.class, loadout)?;
+    let mounted = || {
+        loadout
+            .iter()
+            .zip(hull.hardpoints.iter())
+            .filter_map(|(weapon, hardpoint)| weapon.map(|weapon| (weapon, hardpoint)))
+    };
+    data.guns = mounted()
+        .filter_map(|(weapon, hardpoint)| weapon.gun(hardpoint))
+        .collect();
+    data.missile_launchers = mounted()
+        .filter_map(|(weapon, hardpoint)| weapon.missile_launcher(hardpoint))
+        .collect();
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ship;
+
+    #[test]
+    fn test_default_loadouts_fit() {
+        use Weapon::*;
+        assert!(validate(ShipClass::Fighter, &[Some(Vulcan), Some(MissileLauncher)]).is_ok());
+        assert!(validate(
+            ShipClass::Corvette,
+            &[Some(Vulcan), Some(MissileLauncher), Some(MissileLauncher)]
+        )
+        .is_ok());
+        assert!(validate(
+            ShipClass::Frigate,
+            &[
+                Some(Railgun),
+                Some(Vulcan),
+                Some(Vulcan),
+                Some(MissileLauncher)
+            ]
+        )
+        .is_ok());
+        assert!(validate(
+            ShipClass::Cruiser,
+            &[
+                Some(Flak),
+                Some(MissileLauncher),
+                Some(MissileLauncher),
+                Some(TorpedoLauncher)
+            ]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_refit() {
+        use Weapon::*;
+        let mut data = ship::fighter(0);
+        refit(&mut data, &[Some(MissileLauncher), Some(MissileLauncher)]).unwrap();
+        assert_eq!(data.guns.len(), 0);
+        assert_eq!(data.missile_launchers.len(), 2);
+
+        let mut data = ship::cruiser(0);
+        refit(&mut data, &[None, None, None, Some(Railgun)]).unwrap();
+        assert_eq!(data.guns.len(), 1);
+        assert_eq!(data.guns[0].offset, vector![140.0, 0.0]);
+        assert_eq!(data.guns[0].magazine_remaining, data.guns[0].magazine_size);
+        assert_eq!(data.missile_launchers.len(), 0);
+
+        let mut data = ship::cruiser(0);
+        refit(&mut data, &[Some(Flak)]).unwrap();
+        let gun = &data.guns[0];
+        assert!(gun.in_arc(0.0));
+        assert!(gun.in_arc(TAU / 4.0));
+        assert!(gun.in_arc(-TAU / 4.0));
+        assert!(!gun.in_arc(TAU / 2.0));
+        assert_eq!(gun.clamp_to_arc(TAU * 0.4), CRUISER_MAIN_BATTERY_ARC / 2.0);
+        assert_eq!(
+            gun.clamp_to_arc(-TAU * 0.4),
+            -CRUISER_MAIN_BATTERY_ARC / 2.0
+        );
+    }
+
+    #[test]
+    fn test_refit_limits() {
+        use Weapon::*;
+        let mut data = ship::fighter(0);
+        assert_eq!(
+            refit(&mut data, &[Some(Railgun)]),
+            Err(RefitError::TooLarge { index: 0 })
+        );
+        assert_eq!(
+            refit(&mut data, &[Some(Vulcan), Some(Vulcan), Some(Vulcan)]),
+            Err(RefitError::TooManyWeapons)
+        );
+        assert_eq!(
+            refit(&mut data, &[Some(Vulcan), Some(Vulcan)]),
+            Err(RefitError::OverPower)
+        );
+        assert_eq!(
+            refit(
+                &mut ship::cruiser(0),
+                &[Some(Flak), None, None, Some(Railgun)]
+            ),
+            Err(RefitError::OverMass)
+        );
+        assert_eq!(
+            refit(&mut ship::asteroid(0), &[]),
+            Err(RefitError::NoHardpoints)
+        );
+        // Failed refits leave the ship unchanged.
+        assert_eq!(data.guns.len(), 1);
+        assert_eq!(data.missile_launchers.len(), 1);
+    }
+}
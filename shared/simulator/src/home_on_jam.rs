@@ -0,0 +1,42 @@
+use crate::ship::ShipHandle;
+use crate::simulation::Simulation;
+use crate::waypoint::{ANGULAR_VELOCITY_GAIN, HEADING_GAIN, VELOCITY_GAIN};
+use nalgebra::{vector, Rotation2};
+use oort_api::prelude::angle_diff;
+
+// Flies ships with home-on-jam enabled at the jammer drowning out their radar.
+// Runs after the ships' AIs and overrides them only while jammed, so the AI
+// takes over again as soon as the radar can see.
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for handle in handle_snapshot {
+        let (bearing, velocity, heading, angular_velocity, max_acceleration) = {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            if !data.home_on_jam {
+                continue;
+            }
+            let Some(bearing) = data.radar.as_ref().and_then(|radar| radar.jam_bearing) else {
+                continue;
+            };
+            (
+                bearing,
+                ship.velocity(),
+                ship.heading(),
+                ship.angular_velocity(),
+                data.max_forward_acceleration,
+            )
+        };
+
+        // Full thrust along the bearing while cancelling velocity across it.
+        let direction = vector![bearing.cos(), bearing.sin()];
+        let across = velocity - direction * velocity.dot(&direction);
+        let acceleration = direction * max_acceleration - across * VELOCITY_GAIN;
+
+        let mut ship = sim.ship_mut(handle);
+        ship.accelerate(Rotation2::new(-heading) * acceleration);
+        ship.torque(
+            HEADING_GAIN * angle_diff(heading, bearing) - ANGULAR_VELOCITY_GAIN * angular_velocity,
+        );
+    }
+}
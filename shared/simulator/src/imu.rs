@@ -0,0 +1,53 @@
+use crate::rng;
+use crate::ship::ShipHandle;
+use crate::simulation::{Simulation, PHYSICS_TICK_LENGTH};
+use nalgebra::{vector, Vector2};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+// Inertial measurement unit. Readings come from the change in velocity over
+// the last physics step, so they include clamping, fuel starvation and
+// collisions rather than echoing the commanded acceleration.
+#[derive(Debug, Clone, Default)]
+pub struct Imu {
+    // Standard deviation of the noise added to each reading.
+    pub acceleration_noise: f64,
+    pub angular_acceleration_noise: f64,
+    // World frame, in m/s².
+    pub acceleration: Vector2<f64>,
+    pub angular_acceleration: f64,
+    last_velocity: Option<(Vector2<f64>, f64)>,
+}
+
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for handle in handle_snapshot {
+        let mut rng = sim.noise_rng(handle, rng::Noise::Imu);
+        let (velocity, angular_velocity) = {
+            let ship = sim.ship(handle);
+            (ship.velocity(), ship.angular_velocity())
+        };
+        let mut ship = sim.ship_mut(handle);
+        let imu = &mut ship.data_mut().imu;
+        let (acceleration, angular_acceleration) = match imu.last_velocity {
+            Some((last_velocity, last_angular_velocity)) => (
+                (velocity - last_velocity) / PHYSICS_TICK_LENGTH,
+                (angular_velocity - last_angular_velocity) / PHYSICS_TICK_LENGTH,
+            ),
+            None => (vector![0.0, 0.0], 0.0),
+        };
+        imu.last_velocity = Some((velocity, angular_velocity));
+        imu.acceleration = acceleration;
+        imu.angular_acceleration = angular_acceleration;
+        if imu.acceleration_noise > 0.0 {
+            imu.acceleration += vector![
+                rng.sample::<f64, _>(StandardNormal),
+                rng.sample::<f64, _>(StandardNormal)
+            ] * imu.acceleration_noise;
+        }
+        if imu.angular_acceleration_noise > 0.0 {
+            imu.angular_acceleration +=
+                rng.sample::<f64, _>(StandardNormal) * imu.angular_acceleration_noise;
+        }
+    }
+}
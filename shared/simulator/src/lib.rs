@@ -1,14 +1,35 @@
+pub mod ammo;
+pub mod api_log;
+pub mod asteroid;
 pub mod bullet;
 pub mod collision;
+pub mod collision_assist;
 pub mod color;
 pub mod debug;
+pub mod drag;
+pub mod flak;
+pub mod grader;
+pub mod hardpoint;
+pub mod home_on_jam;
+pub mod imu;
 pub mod index_set;
 pub mod model;
+pub mod observer;
+pub mod orbit;
 pub mod radar;
 pub mod radio;
+pub mod replay_diff;
+pub mod replay_export;
+pub mod resources;
+pub mod result;
 pub mod rng;
 pub mod scenario;
+pub mod section;
 pub mod ship;
 pub mod simulation;
 pub mod snapshot;
+pub mod stats;
+pub mod team_log;
+pub mod velocity_field;
 pub mod vm;
+pub mod waypoint;
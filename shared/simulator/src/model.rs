@@ -1,6 +1,78 @@
 use crate::ship::ShipClass;
-use nalgebra::{vector, Rotation2, Vector2};
+use nalgebra::{point, vector, Rotation2, Vector2};
 use oorandom::Rand32;
+use rapier2d_f64::parry;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Everything needed to draw and simulate a class. The collider is built from
+// `hull` rather than `mesh`, so render meshes can be detailed or concave
+// without costing anything in the physics engine.
+#[derive(Clone, Debug)]
+pub struct Model {
+    pub mesh: Vec<Vector2<f32>>,
+    // Outline for the collider. The physics engine uses its convex hull.
+    pub hull: Vec<Vector2<f32>>,
+    // Distance from the origin to the furthest vertex of either outline.
+    pub radius: f32,
+    // Area in m² of the collider, which with the class density gives the mass.
+    pub area: f64,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<ShipClass, Rc<Model>>> = RefCell::new(HashMap::new());
+}
+
+// Returns the model for `class`, building it on first use.
+pub fn get(class: ShipClass) -> Rc<Model> {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow_mut()
+            .entry(class)
+            .or_insert_with(|| Rc::new(build(class)))
+            .clone()
+    })
+}
+
+fn build(class: ShipClass) -> Model {
+    let mesh = load(class);
+    // Classes whose art is too detailed for physics get a separate hull here.
+    let hull = mesh.clone();
+    let radius = mesh
+        .iter()
+        .chain(hull.iter())
+        .map(|v| v.norm())
+        .fold(0.0, f32::max);
+    let area = hull_area(&hull, radius);
+    Model {
+        mesh,
+        hull,
+        radius,
+        area,
+    }
+}
+
+// Area of the convex hull of `hull`. Matches the collider built by
+// `ship::create`, which falls back to a ball for degenerate hulls.
+fn hull_area(hull: &[Vector2<f32>], radius: f32) -> f64 {
+    let points: Vec<_> = hull
+        .iter()
+        .map(|v| point![v.x as f64, v.y as f64])
+        .collect();
+    let convex = parry::transformation::convex_hull(&points);
+    if convex.len() < 3 {
+        return std::f64::consts::PI * (radius as f64).powi(2);
+    }
+    let twice_area: f64 = (0..convex.len())
+        .map(|i| {
+            let a = convex[i];
+            let b = convex[(i + 1) % convex.len()];
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    twice_area.abs() / 2.0
+}
 
 pub fn scale(scale: f32, vertices: &[Vector2<f32>]) -> Vec<Vector2<f32>> {
     vertices
@@ -16,6 +88,19 @@ pub fn fighter() -> Vec<Vector2<f32>> {
     )
 }
 
+pub fn corvette() -> Vec<Vector2<f32>> {
+    scale(
+        30.0,
+        &[
+            vector![-0.7, -0.35],
+            vector![0.3, -0.35],
+            vector![1.0, 0.0],
+            vector![0.3, 0.35],
+            vector![-0.7, 0.35],
+        ],
+    )
+}
+
 pub fn frigate() -> Vec<Vector2<f32>> {
     scale(
         60.0,
@@ -63,14 +148,14 @@ pub fn cruiser() -> Vec<Vector2<f32>> {
 
 pub fn asteroid(variant: i32) -> Vec<Vector2<f32>> {
     let n = 7;
-    let mut rng = Rand32::new(variant as u64);
+    let mut rng = Rand32::new((variant % crate::asteroid::TIER_STRIDE) as u64);
     let mut vertices = vec![];
     for i in 0..n {
         let r = rng.rand_float();
         let rotation = Rotation2::new(i as f32 * 2.0 * std::f32::consts::PI / n as f32);
         vertices.push(rotation.transform_vector(&vector![r, 0.0]));
     }
-    scale(50.0, &vertices)
+    scale(50.0 * crate::asteroid::scale(variant) as f32, &vertices)
 }
 
 pub fn target() -> Vec<Vector2<f32>> {
@@ -135,6 +220,41 @@ pub fn big_planet() -> Vec<Vector2<f32>> {
     scale(10000.0, &vertices)
 }
 
+pub fn station() -> Vec<Vector2<f32>> {
+    let n = 8;
+    let mut vertices = vec![];
+    for i in 0..n {
+        let rotation = Rotation2::new((i as f32 + 0.5) * 2.0 * std::f32::consts::PI / n as f32);
+        vertices.push(rotation.transform_vector(&vector![1.0, 0.0]));
+    }
+    scale(150.0, &vertices)
+}
+
+pub fn turret() -> Vec<Vector2<f32>> {
+    scale(
+        20.0,
+        &[
+            vector![-1.0, -1.0],
+            vector![1.0, -1.0],
+            vector![1.0, 1.0],
+            vector![-1.0, 1.0],
+        ],
+    )
+}
+
+// Matches the missile batteries on the cruiser model.
+pub fn pod() -> Vec<Vector2<f32>> {
+    scale(
+        60.0,
+        &[
+            vector![-1.0, -0.1],
+            vector![1.0, -0.1],
+            vector![1.0, 0.1],
+            vector![-1.0, 0.1],
+        ],
+    )
+}
+
 pub fn load(class: ShipClass) -> Vec<Vector2<f32>> {
     match class {
         ShipClass::Fighter => fighter(),
@@ -145,13 +265,17 @@ pub fn load(class: ShipClass) -> Vec<Vector2<f32>> {
         ShipClass::Missile => missile(),
         ShipClass::Torpedo => torpedo(),
         ShipClass::Planet => big_planet(),
+        ShipClass::Station => station(),
+        ShipClass::Turret => turret(),
+        ShipClass::Corvette => corvette(),
+        ShipClass::Pod => pod(),
     }
 }
 
 pub fn radius(class: ShipClass) -> f32 {
-    load(class)
-        .iter()
-        .map(|&v| v.norm())
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap()
+    get(class).radius
+}
+
+pub fn area(class: ShipClass) -> f64 {
+    get(class).area
 }
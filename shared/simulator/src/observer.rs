@@ -0,0 +1,115 @@
+use crate::ship::ShipClass;
+use crate::simulation::Simulation;
+use nalgebra::Vector2;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// A non-participating controller for casting tools and tutorial narration. It
+// has no ships and can't issue commands; each tick it records the union of
+// what every team knows (its own ships plus its radar contacts) and hands it
+// out after a fixed delay.
+#[derive(Clone)]
+pub struct Observer {
+    delay_ticks: u32,
+    history: VecDeque<ObserverPicture>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ObserverPicture {
+    pub time: f64,
+    pub ships: Vec<ObservedShip>,
+    pub contacts: Vec<ObservedContact>,
+}
+
+// A ship as known to its own team. Positions are in world coordinates.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObservedShip {
+    pub id: u64,
+    pub team: i32,
+    pub class: ShipClass,
+    pub position: Vector2<f64>,
+    pub velocity: Vector2<f64>,
+    pub heading: f64,
+    pub health: f64,
+}
+
+// A radar contact held by one of the ships of `team`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObservedContact {
+    pub team: i32,
+    pub tracker: u64,
+    pub class: ShipClass,
+    pub position: Vector2<f64>,
+    pub velocity: Vector2<f64>,
+}
+
+impl Observer {
+    pub fn new(delay_ticks: u32) -> Self {
+        Self {
+            delay_ticks,
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn delay_ticks(&self) -> u32 {
+        self.delay_ticks
+    }
+
+    // The picture from `delay_ticks` ago, or None early in the match.
+    pub fn picture(&self) -> Option<&ObserverPicture> {
+        if self.history.len() > self.delay_ticks as usize {
+            self.history.front()
+        } else {
+            None
+        }
+    }
+
+    fn record(&mut self, picture: ObserverPicture) {
+        self.history.push_back(picture);
+        while self.history.len() > self.delay_ticks as usize + 1 {
+            self.history.pop_front();
+        }
+    }
+}
+
+fn build_picture(sim: &Simulation) -> ObserverPicture {
+    let origin = sim.origin();
+    let mut picture = ObserverPicture {
+        time: sim.time(),
+        ..Default::default()
+    };
+    for &handle in sim.ships.iter() {
+        let ship = sim.ship(handle);
+        let data = ship.data();
+        if data.destroyed {
+            continue;
+        }
+        picture.ships.push(ObservedShip {
+            id: handle.into(),
+            team: data.team,
+            class: data.class,
+            position: ship.position().vector + origin,
+            velocity: ship.velocity(),
+            heading: ship.heading(),
+            health: data.health,
+        });
+        if let Some(contact) = ship.radar().and_then(|radar| radar.result) {
+            picture.contacts.push(ObservedContact {
+                team: data.team,
+                tracker: handle.into(),
+                class: contact.class,
+                position: contact.position + origin,
+                velocity: contact.velocity,
+            });
+        }
+    }
+    picture
+}
+
+pub fn tick(sim: &mut Simulation) {
+    if sim.observer.is_none() {
+        return;
+    }
+    let picture = build_picture(sim);
+    sim.observer.as_mut().unwrap().record(picture);
+}
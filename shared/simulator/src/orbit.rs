@@ -0,0 +1,100 @@
+use crate::ship::ShipHandle;
+use crate::simulation::{Simulation, PHYSICS_TICK_LENGTH};
+use nalgebra::{vector, Rotation2, Vector2};
+pub use oort_api::OrbitDirection;
+
+// Fraction of the ship's lateral acceleration spent on centripetal
+// acceleration. The rest is left for correcting radius and speed errors.
+pub const CENTRIPETAL_FRACTION: f64 = 0.5;
+// Closing speed commanded per meter of radius error, in 1/s.
+pub const RADIAL_GAIN: f64 = 0.5;
+// Acceleration commanded per m/s of velocity error, in 1/s.
+pub const VELOCITY_GAIN: f64 = 2.0;
+
+// Autopilot that flies a circle of fixed radius around a contact. Only the
+// ship's linear acceleration is controlled, so its AI stays free to turn and
+// aim.
+#[derive(Debug, Clone)]
+pub struct Orbit {
+    pub radius: f64,
+    pub direction: OrbitDirection,
+    // The contact as given when the orbit was set.
+    pub contact_position: Vector2<f64>,
+    pub contact_velocity: Vector2<f64>,
+    // The contact's position extrapolated to the current tick.
+    pub center: Vector2<f64>,
+}
+
+impl Orbit {
+    pub fn new(
+        contact_position: Vector2<f64>,
+        contact_velocity: Vector2<f64>,
+        radius: f64,
+        direction: OrbitDirection,
+    ) -> Self {
+        Self {
+            radius,
+            direction,
+            contact_position,
+            contact_velocity,
+            center: contact_position,
+        }
+    }
+
+    // Speed relative to the contact at which the centripetal acceleration
+    // uses CENTRIPETAL_FRACTION of `max_acceleration`.
+    pub fn speed(&self, max_acceleration: f64) -> f64 {
+        (CENTRIPETAL_FRACTION * max_acceleration * self.radius).sqrt()
+    }
+
+    // World-frame acceleration to hold the orbit.
+    pub fn acceleration(
+        &self,
+        position: Vector2<f64>,
+        velocity: Vector2<f64>,
+        max_acceleration: f64,
+    ) -> Vector2<f64> {
+        let dp = position - self.center;
+        let distance = dp.norm();
+        let outward = if distance > 1e-6 {
+            dp / distance
+        } else {
+            vector![1.0, 0.0]
+        };
+        let tangent = match self.direction {
+            OrbitDirection::Counterclockwise => vector![-outward.y, outward.x],
+            OrbitDirection::Clockwise => vector![outward.y, -outward.x],
+        };
+
+        let speed = self.speed(max_acceleration);
+        let closing_speed = (RADIAL_GAIN * (distance - self.radius)).clamp(-speed, speed);
+        let target_velocity = self.contact_velocity + tangent * speed - outward * closing_speed;
+        let centripetal = -outward * speed * speed / distance.max(self.radius);
+        (target_velocity - velocity) * VELOCITY_GAIN + centripetal
+    }
+}
+
+// Sets the acceleration of ships with an orbit. Runs before collision assist
+// so that can still override it.
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for handle in handle_snapshot {
+        let acceleration = {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            let Some(orbit) = data.orbit.as_ref() else {
+                continue;
+            };
+            let acceleration = orbit.acceleration(
+                ship.position().vector,
+                ship.velocity(),
+                data.max_lateral_acceleration,
+            );
+            Rotation2::new(-ship.heading()) * acceleration
+        };
+        sim.ship_mut(handle).accelerate(acceleration);
+
+        let orbit = sim.ship_mut(handle).data_mut().orbit.as_mut().unwrap();
+        orbit.center += orbit.contact_velocity * PHYSICS_TICK_LENGTH;
+    }
+}
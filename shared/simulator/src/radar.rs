@@ -1,24 +1,40 @@
-use crate::ship::{self, ShipClass, ShipHandle};
+use crate::ship::{ShipClass, ShipHandle};
 use crate::simulation::{Line, Simulation};
+use crate::snapshot::RadarBeamSnapshot;
 use crate::{model, rng, simulation};
 use nalgebra::Rotation2;
 use nalgebra::{vector, Point2, Vector2};
+use oort_api::constants;
+pub use oort_api::constants::{
+    radar_gain, DOPPLER_NOTCH_GAIN, MAX_RADAR_MEMORY, MIN_RADAR_POWER, RADAR_SIDELOBE_GAIN,
+    RWR_MIN_RSSI,
+};
+use oort_api::geometry;
+use oort_api::prelude::{angle_diff, vec2};
 use oort_api::{Ability, EcmMode};
 use rand::Rng;
 use rand_distr::StandardNormal;
 use rapier2d_f64::parry;
 use rapier2d_f64::prelude::*;
 use std::collections::HashMap;
-use std::f64::consts::TAU;
+use std::f64::consts::{PI, TAU};
 use std::ops::Range;
-use wide::{f32x4, CmpGt, CmpLt};
+use wide::{f32x4, CmpGe, CmpLe};
 
 const DEBUG: bool = false;
 const BACKGROUND_NOISE: f64 = 1e-13; // -100 dBm
 const JAMMER_COEFF: f64 = 1e-9; // Account for frequency hopping and pulse length
+const NEGLIGIBLE_NOISE: f64 = 1e-2; // Fraction of the background noise
 const BEARING_NOISE_FACTOR: f64 = 1e1 * (TAU / 360.0);
 const DISTANCE_NOISE_FACTOR: f64 = 1e4;
 const VELOCITY_NOISE_FACTOR: f64 = 1e2;
+// Reflectors without a working transponder are grouped under this IFF code.
+const UNKNOWN_IFF: i32 = 10;
+// A contact must be this much stronger than the asteroid clutter in the beam.
+const CLUTTER_MARGIN: f64 = 2.0; // 3 dB
+
+// Standard deviation of a passive bearing as a fraction of the beam width.
+const PASSIVE_BEARING_NOISE: f64 = 0.05;
 
 #[derive(Clone, Debug)]
 pub struct Radar {
@@ -28,12 +44,46 @@ pub struct Radar {
     pub max_width: f64,
     pub min_distance: f64,
     pub max_distance: f64,
+    // Hard limit of the radar's instruments. Unlike `max_distance` the AI
+    // can't change it, and unlike detection range it doesn't depend on power.
+    pub max_range: f64,
+    // Contacts closing slower or faster than this are ignored.
+    pub min_closing_speed: f64,
+    pub max_closing_speed: f64,
     pub power: f64,
+    // Fraction of `power` actually emitted. Detection range scales with its
+    // fourth root but intercept range with its square root, so a quieter
+    // radar can see a target before the target's warning receiver sees it.
+    pub power_fraction: f64,
+    // Fuel burned per second at full power, for ships with a fuel tank.
+    pub fuel_cost: f64,
     pub rx_cross_section: f64,
     pub reliable_rssi: f64,
     pub min_rssi: f64,
     pub ecm_mode: EcmMode,
     pub result: Option<ScanResult>,
+    // Strongest enemy radar illuminating this ship last tick.
+    pub warning: Option<RadarWarning>,
+    // Whether the radar can find the bearing of a jammer that drowns it out.
+    // Only torpedoes have one, so fighters can still jam missiles.
+    pub home_on_jam: bool,
+    // Direction to the strongest jammer if it hid every contact last tick.
+    pub jam_bearing: Option<f64>,
+    // Ticks a lost contact keeps being reported, extrapolated from where it
+    // was last seen. Zero reports only contacts seen this tick.
+    pub memory_ticks: u32,
+    // The last contact seen and the number of ticks since. Only kept while
+    // `memory_ticks` is non-zero.
+    pub last_contact: Option<(ScanResult, u32)>,
+    // Listen for enemy radars instead of emitting. The beam only sets the
+    // direction listened in, and the radar reveals nothing about this ship.
+    pub passive: bool,
+    // Strongest enemy radar heard within the beam last tick while passive.
+    pub passive_contact: Option<RadarWarning>,
+    // Reflectors closing slower than this (in m/s) are attenuated by
+    // `DOPPLER_NOTCH_GAIN`, in scenarios with seeker Doppler notches. Zero for
+    // no notch.
+    pub doppler_notch: f64,
 }
 
 impl Default for Radar {
@@ -45,12 +95,25 @@ impl Default for Radar {
             max_width: TAU / 4.0,
             min_distance: 0.0,
             max_distance: 1e9,
+            max_range: f64::INFINITY,
+            min_closing_speed: f64::NEG_INFINITY,
+            max_closing_speed: f64::INFINITY,
             power: 100e3,
+            power_fraction: 1.0,
+            fuel_cost: 0.0,
             rx_cross_section: 10.0,
             reliable_rssi: from_dbm(-90.0),
             min_rssi: from_dbm(-100.0),
             ecm_mode: EcmMode::None,
             result: None,
+            warning: None,
+            home_on_jam: false,
+            jam_bearing: None,
+            memory_ticks: 0,
+            last_contact: None,
+            passive: false,
+            passive_contact: None,
+            doppler_notch: 0.0,
         }
     }
 }
@@ -88,18 +151,109 @@ impl Radar {
         self.max_distance = dist.clamp(0.0, simulation::MAX_WORLD_SIZE * 2.0);
     }
 
+    pub fn get_max_range(&self) -> f64 {
+        self.max_range
+    }
+
+    pub fn get_min_closing_speed(&self) -> f64 {
+        self.min_closing_speed
+    }
+
+    pub fn set_min_closing_speed(&mut self, speed: f64) {
+        if !speed.is_nan() {
+            self.min_closing_speed = speed;
+        }
+    }
+
+    pub fn get_max_closing_speed(&self) -> f64 {
+        self.max_closing_speed
+    }
+
+    pub fn set_max_closing_speed(&mut self, speed: f64) {
+        if !speed.is_nan() {
+            self.max_closing_speed = speed;
+        }
+    }
+
     pub fn set_ecm_mode(&mut self, mode: EcmMode) {
         self.ecm_mode = mode;
     }
 
+    pub fn get_power_fraction(&self) -> f64 {
+        self.power_fraction
+    }
+
+    pub fn set_power_fraction(&mut self, fraction: f64) {
+        if !fraction.is_nan() {
+            self.power_fraction = fraction.clamp(MIN_RADAR_POWER, 1.0);
+        }
+    }
+
+    pub fn emitted_power(&self) -> f64 {
+        self.power * self.power_fraction
+    }
+
     pub fn scan(&self) -> Option<ScanResult> {
         self.result
     }
+
+    // In seconds.
+    pub fn get_memory(&self) -> f64 {
+        self.memory_ticks as f64 * simulation::PHYSICS_TICK_LENGTH
+    }
+
+    pub fn set_memory(&mut self, seconds: f64) {
+        if !seconds.is_nan() {
+            let seconds = seconds.clamp(0.0, MAX_RADAR_MEMORY);
+            self.memory_ticks = (seconds / simulation::PHYSICS_TICK_LENGTH).round() as u32;
+            if self.memory_ticks == 0 {
+                self.last_contact = None;
+            }
+        }
+    }
+
+    // The contact seen this tick, or else a remembered one moved along its
+    // last known velocity, with the number of ticks since it was seen.
+    pub fn contact(&self) -> Option<(ScanResult, u32)> {
+        if let Some(result) = self.result {
+            return Some((result, 0));
+        }
+        let (contact, age) = self.last_contact?;
+        let dt = age as f64 * simulation::PHYSICS_TICK_LENGTH;
+        Some((
+            ScanResult {
+                position: contact.position + contact.velocity * dt,
+                ..contact
+            },
+            age,
+        ))
+    }
+
+    // Called after each scan to age or replace the remembered contact.
+    fn remember(&mut self) {
+        if self.memory_ticks == 0 {
+            return;
+        }
+        self.last_contact = match (self.result, self.last_contact) {
+            (Some(result), _) => Some((result, 0)),
+            (None, Some((contact, age))) if age < self.memory_ticks => Some((contact, age + 1)),
+            (None, _) => None,
+        };
+    }
+
+    // Keeps the remembered contact in place when the simulation's origin
+    // moves by `offset`.
+    pub fn recenter(&mut self, offset: Vector2<f64>) {
+        if let Some((contact, _)) = self.last_contact.as_mut() {
+            contact.position -= offset;
+        }
+    }
 }
 
 struct RadarEmitter {
     handle: ShipHandle,
     center: Point2<f64>,
+    velocity: Vector2<f64>,
     width: f64,
     start_bearing: f64,
     bearing: f64,
@@ -107,21 +261,38 @@ struct RadarEmitter {
     min_distance: f64,
     max_distance: f64,
     square_distance_range: Range<f64>,
+    min_closing_speed: f64,
+    max_closing_speed: f64,
     power: f64,
     rx_cross_section: f64,
     reliable_rssi: f64,
     min_rssi: f64,
     team: i32,
-    rays: [Vector2<f64>; 2],
+    // Beyond this range reflectors in the sidelobes are too weak to detect,
+    // trigger radar warning receivers, or add noise.
+    sidelobe_range: f64,
+    // Zero unless the scenario has seeker Doppler notches.
+    doppler_notch: f64,
+}
+
+impl RadarEmitter {
+    // Antenna gain towards `position`, see `radar_gain`.
+    fn gain(&self, position: Point2<f64>) -> f64 {
+        let dp = position - self.center;
+        radar_gain(angle_diff(self.bearing, dp.y.atan2(dp.x)), self.width)
+    }
 }
 
 #[derive(Clone)]
 struct RadarReflector {
+    handle: ShipHandle,
     position: Point2<f64>,
     velocity: Vector2<f64>,
     radar_cross_section: f64,
     class: ShipClass,
     jammer: Option<RadarJammer>,
+    // Only set for asteroids, which block line of sight.
+    occluder_radius: f64,
 }
 
 #[derive(Clone)]
@@ -132,6 +303,14 @@ struct RadarJammer {
     ecm_mode: EcmMode,
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct RadarWarning {
+    // Direction from the receiver to the emitter.
+    pub bearing: f64,
+    // In dBm.
+    pub rssi: f64,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct ScanResult {
     pub class: ShipClass,
@@ -158,6 +337,8 @@ fn from_dbm(x: f64) -> f64 {
 
 #[inline(never)]
 fn build_reflector_team(sim: &Simulation) -> Vec<ReflectorTeam> {
+    // Reflectors are grouped by the IFF code they broadcast rather than their
+    // actual team, so radars ignore anything that identifies as friendly.
     let mut reflectors_by_team: HashMap<i32, Vec<RadarReflector>> = HashMap::new();
 
     for handle in sim.ships.iter() {
@@ -168,7 +349,7 @@ fn build_reflector_team(sim: &Simulation) -> Vec<ReflectorTeam> {
         let mut radar_cross_section = ship_data.radar_cross_section;
         if ship.is_ability_active(Ability::Decoy) {
             class = ShipClass::Cruiser;
-            radar_cross_section = ship::CRUISER_RADAR_CROSS_SECTION / 2.0;
+            radar_cross_section = constants::CRUISER.radar_cross_section / 2.0;
         }
         if class == ShipClass::Planet {
             continue;
@@ -181,25 +362,37 @@ fn build_reflector_team(sim: &Simulation) -> Vec<ReflectorTeam> {
                 _ => Some(RadarJammer {
                     width: radar.width,
                     bearing: radar.heading,
-                    power: radar.power,
+                    power: radar.emitted_power(),
                     ecm_mode: radar.ecm_mode,
                 }),
             });
+        let iff = if ship_data.transponder.enabled {
+            ship_data.transponder.code.unwrap_or(ship_data.team)
+        } else {
+            UNKNOWN_IFF
+        };
+        let occluder_radius = if is_asteroid(class) {
+            model::radius(class) as f64
+        } else {
+            0.0
+        };
         reflectors_by_team
-            .entry(ship_data.team)
+            .entry(iff)
             .or_default()
             .push(RadarReflector {
+                handle: *handle,
                 position: ship.position().vector.into(),
                 velocity: ship.velocity(),
                 radar_cross_section,
                 class,
                 jammer,
+                occluder_radius,
             });
     }
 
     let mut result: Vec<ReflectorTeam> = Vec::new();
     result.resize(
-        10,
+        UNKNOWN_IFF as usize + 1,
         ReflectorTeam {
             xs: Vec::new(),
             ys: Vec::new(),
@@ -241,40 +434,72 @@ fn build_reflector_team(sim: &Simulation) -> Vec<ReflectorTeam> {
 pub fn tick(sim: &mut Simulation) {
     let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
     let reflector_teams = build_reflector_team(sim);
+    let max_jammer_intensity = reflector_teams
+        .iter()
+        .flat_map(|team| team.reflectors.iter())
+        .filter_map(|reflector| reflector.jammer.as_ref())
+        .map(|jammer| jammer.power / jammer.width)
+        .fold(0.0, f64::max);
+    let seeker_doppler_notch = sim.seeker_doppler_notch();
     let mut candidates: Vec<(i32, usize)> = Vec::new();
+    let mut occluders: Vec<&RadarReflector> = Vec::new();
     let planets = sim
         .ships
         .iter()
         .filter(|handle| sim.ship(**handle).data().class == ShipClass::Planet)
         .cloned()
         .collect::<Vec<_>>();
+    let mut warnings: HashMap<ShipHandle, RadarWarning> = HashMap::new();
+    // Heading and width of each passive radar's beam.
+    let listeners: HashMap<ShipHandle, (f64, f64)> = handle_snapshot
+        .iter()
+        .filter_map(|&handle| {
+            let ship = sim.ship(handle);
+            let radar = ship.data().radar.as_ref()?;
+            if radar.passive {
+                Some((handle, (radar.heading, radar.width)))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut heard: HashMap<ShipHandle, RadarWarning> = HashMap::new();
 
     for handle in handle_snapshot.iter().cloned() {
         let ship = sim.ship(handle);
         let ship_data = ship.data();
 
         if let Some(radar) = ship_data.radar.as_ref() {
+            if radar.passive {
+                let mut ship = sim.ship_mut(handle);
+                let radar = ship.data_mut().radar.as_mut().unwrap();
+                radar.result = None;
+                radar.jam_bearing = None;
+                radar.remember();
+                continue;
+            }
+
             let h = radar.heading;
             let w = radar.width;
             assert!(w < TAU / 2.0);
             let max_distance = compute_max_detection_range(radar, 40.0 /*cruiser*/)
                 .min(radar.max_distance)
+                .min(radar.max_range)
                 .min(simulation::MAX_WORLD_SIZE);
             let reliable_distance = compute_reliable_detection_range(radar, 10.0 /*fighter*/)
                 .min(radar.max_distance)
+                .min(radar.max_range)
                 .min(simulation::MAX_WORLD_SIZE);
 
             let start_bearing = h - 0.5 * w;
             let end_bearing = h + 0.5 * w;
-            let ray0 = Rotation2::new(start_bearing).transform_vector(&vector![1.0, 0.0]);
-            let ray1 = Rotation2::new(end_bearing).transform_vector(&vector![1.0, 0.0]);
-            assert!(is_clockwise(ray1, ray0));
-            let rays = [ray0, ray1];
+            let sidelobe_range = compute_sidelobe_range(radar, max_distance, max_jammer_intensity);
             let mut emitter = RadarEmitter {
                 handle,
                 team: ship_data.team,
                 center: ship.position().vector.into(),
-                power: radar.power,
+                velocity: ship.velocity(),
+                power: radar.emitted_power(),
                 reliable_rssi: radar.reliable_rssi,
                 min_rssi: radar.min_rssi,
                 rx_cross_section: radar.rx_cross_section,
@@ -285,7 +510,14 @@ pub fn tick(sim: &mut Simulation) {
                 min_distance: radar.min_distance,
                 max_distance,
                 square_distance_range: radar.min_distance.powi(2)..max_distance.powi(2),
-                rays,
+                min_closing_speed: radar.min_closing_speed,
+                max_closing_speed: radar.max_closing_speed,
+                sidelobe_range,
+                doppler_notch: if seeker_doppler_notch {
+                    radar.doppler_notch
+                } else {
+                    0.0
+                },
             };
 
             if radar.ecm_mode != EcmMode::None {
@@ -294,16 +526,22 @@ pub fn tick(sim: &mut Simulation) {
                     let ship_data = ship.data_mut();
                     let radar = ship_data.radar.as_mut().unwrap();
                     radar.result = None;
+                    radar.jam_bearing = None;
+                    radar.remember();
                 }
                 draw_emitter(sim, &emitter, reliable_distance);
                 continue;
             }
 
-            let mut rng = rng::new_rng(sim.tick());
+            let mut rng = sim.noise_rng(handle, rng::Noise::Radar);
 
             let mut best_rssi = emitter.min_rssi;
             let mut best_reflector: Option<&RadarReflector> = None;
+            let mut best_asteroid_rssi = emitter.min_rssi;
+            let mut best_asteroid: Option<&RadarReflector> = None;
+            let mut clutter = 0.0;
             let mut received_noise = BACKGROUND_NOISE * 2.0f64.powf(rng.gen_range(-1.0..1.0));
+            let mut strongest_jammer: Option<(f64, Point2<f64>)> = None;
             candidates.clear();
 
             let planet_contact = check_planet_contact(sim, &emitter, &planets);
@@ -314,6 +552,29 @@ pub fn tick(sim: &mut Simulation) {
             }
 
             find_candidates(&emitter, &reflector_teams, &mut candidates);
+            find_intercepts(
+                &emitter,
+                &reflector_teams,
+                &candidates,
+                &listeners,
+                &mut warnings,
+                &mut heard,
+            );
+
+            occluders.clear();
+            for (team, reflector_index) in candidates.iter() {
+                let reflector = &reflector_teams[*team as usize].reflectors[*reflector_index];
+                if reflector.occluder_radius > 0.0
+                    && emitter
+                        .square_distance_range
+                        .contains(&nalgebra::distance_squared(
+                            &emitter.center,
+                            &reflector.position,
+                        ))
+                {
+                    occluders.push(reflector);
+                }
+            }
 
             for (team, reflector_index) in candidates.iter() {
                 let reflector = &reflector_teams[*team as usize].reflectors[*reflector_index];
@@ -321,19 +582,26 @@ pub fn tick(sim: &mut Simulation) {
                     match jammer.ecm_mode {
                         EcmMode::None => {}
                         EcmMode::Noise => {
-                            if check_inside_beam_raw(
-                                &reflector.position,
+                            if geometry::in_arc(
+                                vec2(reflector.position.x, reflector.position.y),
                                 jammer.bearing,
                                 jammer.width,
-                                &emitter.center,
+                                vec2(emitter.center.x, emitter.center.y),
                             ) {
                                 let r_sq = nalgebra::distance_squared(
                                     &emitter.center,
                                     &reflector.position,
                                 );
-                                received_noise +=
-                                    JAMMER_COEFF * jammer.power * emitter.rx_cross_section
-                                        / (TAU * jammer.width * r_sq);
+                                let noise = JAMMER_COEFF
+                                    * jammer.power
+                                    * emitter.rx_cross_section
+                                    * emitter.gain(reflector.position)
+                                    / (TAU * jammer.width * r_sq);
+                                received_noise += noise;
+                                match strongest_jammer {
+                                    Some((strongest, _)) if strongest >= noise => {}
+                                    _ => strongest_jammer = Some((noise, reflector.position)),
+                                }
                             }
                         }
                     }
@@ -346,15 +614,34 @@ pub fn tick(sim: &mut Simulation) {
                         &reflector.position,
                     ))
                 {
+                    if is_occluded(&emitter, reflector, &occluders)
+                        || !check_closing_speed(&emitter, reflector)
+                    {
+                        continue;
+                    }
                     let rssi =
                         compute_rssi(&emitter, reflector) * 1.2f64.powf(rng.gen_range(-1.0..1.0));
-                    if rssi > best_rssi {
+                    if is_asteroid(reflector.class) {
+                        clutter += rssi;
+                        if rssi > best_asteroid_rssi {
+                            best_asteroid = Some(reflector);
+                            best_asteroid_rssi = rssi;
+                        }
+                    } else if rssi > best_rssi {
                         best_reflector = Some(reflector);
                         best_rssi = rssi;
                     }
                 }
             }
 
+            // Contacts hidden in asteroid clutter are lost behind the strongest asteroid.
+            if best_asteroid.is_some()
+                && (best_reflector.is_none() || best_rssi < clutter * CLUTTER_MARGIN)
+            {
+                best_reflector = best_asteroid;
+                best_rssi = best_asteroid_rssi;
+            }
+
             let best_rssi_dbm = into_dbm(best_rssi);
             let received_noise_dbm = into_dbm(received_noise);
             let signal_db = best_rssi_dbm - received_noise_dbm;
@@ -397,6 +684,16 @@ pub fn tick(sim: &mut Simulation) {
                 let ship_data = ship.data_mut();
                 let radar = ship_data.radar.as_mut().unwrap();
                 radar.result = result;
+                radar.remember();
+                radar.jam_bearing = match strongest_jammer {
+                    Some((noise, position))
+                        if radar.home_on_jam && result.is_none() && noise > BACKGROUND_NOISE =>
+                    {
+                        let dp = position - emitter.center;
+                        Some(dp.y.atan2(dp.x).rem_euclid(TAU))
+                    }
+                    _ => None,
+                };
             }
 
             draw_emitter(sim, &emitter, reliable_distance);
@@ -405,23 +702,114 @@ pub fn tick(sim: &mut Simulation) {
             }
         }
     }
+
+    for handle in handle_snapshot.iter().cloned() {
+        let passive_contact = heard.get(&handle).map(|&contact| {
+            let (_, width) = listeners[&handle];
+            let mut rng = sim.noise_rng(handle, rng::Noise::Radar);
+            let noise = rng.sample::<f64, _>(StandardNormal) * PASSIVE_BEARING_NOISE * width;
+            RadarWarning {
+                bearing: (contact.bearing + noise).rem_euclid(TAU),
+                ..contact
+            }
+        });
+        let mut ship = sim.ship_mut(handle);
+        let ship_data = ship.data_mut();
+        let has_fuel = ship_data.fuel.is_some();
+        if let Some(radar) = ship_data.radar.as_mut() {
+            radar.warning = warnings.get(&handle).copied();
+            radar.passive_contact = passive_contact;
+            let power_fraction = if radar.passive {
+                MIN_RADAR_POWER
+            } else {
+                radar.power_fraction
+            };
+            let fuel_consumption =
+                radar.fuel_cost * power_fraction * simulation::PHYSICS_TICK_LENGTH;
+            if has_fuel && fuel_consumption > 0.0 {
+                let fuel = ship_data.fuel.as_mut().unwrap();
+                *fuel = (*fuel - fuel_consumption).max(0.0);
+            }
+        }
+    }
+}
+
+// Records the emitter on the warning receivers of the ships in its beam, and
+// on the passive radars of those listening in its direction.
+fn find_intercepts(
+    emitter: &RadarEmitter,
+    reflector_teams: &[ReflectorTeam],
+    candidates: &[(i32, usize)],
+    listeners: &HashMap<ShipHandle, (f64, f64)>,
+    warnings: &mut HashMap<ShipHandle, RadarWarning>,
+    heard: &mut HashMap<ShipHandle, RadarWarning>,
+) {
+    for (team, reflector_index) in candidates.iter() {
+        let reflector = &reflector_teams[*team as usize].reflectors[*reflector_index];
+        if is_asteroid(reflector.class) {
+            continue;
+        }
+        let dp = emitter.center - reflector.position;
+        let rssi = emitter.power * emitter.gain(reflector.position)
+            / (TAU * emitter.width * dp.norm_squared());
+        if rssi < RWR_MIN_RSSI {
+            continue;
+        }
+        let warning = RadarWarning {
+            bearing: dp.y.atan2(dp.x).rem_euclid(TAU),
+            rssi: into_dbm(rssi),
+        };
+        keep_strongest(warnings, reflector.handle, warning);
+        if let Some(&(heading, width)) = listeners.get(&reflector.handle) {
+            if geometry::in_arc(
+                vec2(reflector.position.x, reflector.position.y),
+                heading,
+                width,
+                vec2(emitter.center.x, emitter.center.y),
+            ) {
+                keep_strongest(heard, reflector.handle, warning);
+            }
+        }
+    }
 }
 
+fn keep_strongest(
+    warnings: &mut HashMap<ShipHandle, RadarWarning>,
+    handle: ShipHandle,
+    warning: RadarWarning,
+) {
+    warnings
+        .entry(handle)
+        .and_modify(|w| {
+            if warning.rssi > w.rssi {
+                *w = warning;
+            }
+        })
+        .or_insert(warning);
+}
+
+// Reflectors in the mainlobe, including its rolloff past the edge of the
+// beam, are candidates at any range. Elsewhere the gain is down to
+// `RADAR_SIDELOBE_GAIN`, so only reflectors within `sidelobe_range` are
+// candidates.
 #[inline(never)]
 fn find_candidates(
     emitter: &RadarEmitter,
     reflector_teams: &[ReflectorTeam],
     candidates: &mut Vec<(i32, usize)>,
 ) {
-    let rays = [emitter.rays[0].cast::<f32>(), emitter.rays[1].cast::<f32>()];
     let emitter_position = emitter.center.cast::<f32>();
+    let axis = Rotation2::new(emitter.bearing)
+        .transform_vector(&vector![1.0, 0.0])
+        .cast::<f32>();
+    let mainlobe_half_width = mainlobe_width(emitter.width) / 2.0;
 
     let wex = f32x4::splat(emitter_position.x);
     let wey = f32x4::splat(emitter_position.y);
-    let wrx0 = f32x4::splat(rays[0].x);
-    let wry0 = f32x4::splat(rays[0].y);
-    let wrx1 = f32x4::splat(rays[1].x);
-    let wry1 = f32x4::splat(rays[1].y);
+    let wax = f32x4::splat(axis.x);
+    let way = f32x4::splat(axis.y);
+    let wcos = f32x4::splat(mainlobe_half_width.min(PI).cos() as f32);
+    let wrange_sq = f32x4::splat(emitter.sidelobe_range.powi(2) as f32);
 
     for (team, reflector_team) in reflector_teams.iter().enumerate() {
         let team = team as i32;
@@ -433,18 +821,17 @@ fn find_candidates(
         for (i, (&wx, &wy)) in reflector_team.xs.iter().zip(&reflector_team.ys).enumerate() {
             let wdx = wx - wex;
             let wdy = wy - wey;
+            let wd_sq = wdx * wdx + wdy * wdy;
 
-            // Positive if true.
-            fn is_clockwise(wx0: f32x4, wy0: f32x4, wx1: f32x4, wy1: f32x4) -> f32x4 {
-                -wx0 * wy1 + wy0 * wx1
-            }
-
-            let mask = is_clockwise(wrx0, wry0, wdx, wdy).cmp_lt(f32x4::ZERO)
-                & is_clockwise(wrx1, wry1, wdx, wdy).cmp_gt(f32x4::ZERO);
+            let mask =
+                (wdx * wax + wdy * way).cmp_ge(wd_sq.sqrt() * wcos) | wd_sq.cmp_le(wrange_sq);
             if mask.any() {
                 for (j, &v) in mask.to_array().iter().enumerate() {
                     let reflector_index = i * 4 + j;
-                    if v != 0.0 && reflector_index < n {
+                    if v != 0.0
+                        && reflector_index < n
+                        && reflector_team.reflectors[reflector_index].handle != emitter.handle
+                    {
                         candidates.push((team, reflector_index));
                     }
                 }
@@ -496,45 +883,110 @@ fn make_scan_result(
     }
 }
 
-fn decide_unreliable_rssi(rng: &mut impl Rng, rssi: f64, reliable_rssi: f64) -> bool {
-    rng.gen_bool(1.0 / (2.0 * reliable_rssi / rssi).log2())
+fn check_closing_speed(emitter: &RadarEmitter, reflector: &RadarReflector) -> bool {
+    if emitter.min_closing_speed == f64::NEG_INFINITY && emitter.max_closing_speed == f64::INFINITY
+    {
+        return true;
+    }
+    let dp = reflector.position - emitter.center;
+    let dv = reflector.velocity - emitter.velocity;
+    let distance = dp.norm();
+    let closing_speed = if distance > 0.0 {
+        -dp.dot(&dv) / distance
+    } else {
+        0.0
+    };
+    closing_speed >= emitter.min_closing_speed && closing_speed <= emitter.max_closing_speed
 }
 
-fn is_clockwise(v0: Vector2<f64>, v1: Vector2<f64>) -> bool {
-    -v0.x * v1.y + v0.y * v1.x > 0.0
+fn is_asteroid(class: ShipClass) -> bool {
+    matches!(class, ShipClass::Asteroid { .. })
 }
 
-fn check_inside_beam_raw(
-    src_position: &Point2<f64>,
-    bearing: f64,
-    width: f64,
-    dst_position: &Point2<f64>,
+// Returns true if an asteroid lies between the emitter and the reflector.
+fn is_occluded(
+    emitter: &RadarEmitter,
+    reflector: &RadarReflector,
+    occluders: &[&RadarReflector],
 ) -> bool {
-    let ray0 = Rotation2::new(bearing - width * 0.5).transform_vector(&vector![1.0, 0.0]);
-    let ray1 = Rotation2::new(bearing + width * 0.5).transform_vector(&vector![1.0, 0.0]);
-    let dp = dst_position - src_position;
-    let is_clockwise = |v0: Vector2<f64>, v1: Vector2<f64>| -v0.x * v1.y + v0.y * v1.x > 0.0;
-    if is_clockwise(ray1, ray0) {
-        !is_clockwise(ray0, dp) && is_clockwise(ray1, dp)
-    } else {
-        is_clockwise(ray1, dp) || !is_clockwise(ray0, dp)
+    let d = reflector.position - emitter.center;
+    let length_squared = d.norm_squared();
+    if length_squared == 0.0 {
+        return false;
     }
+    occluders.iter().any(|occluder| {
+        let dp = occluder.position - emitter.center;
+        let t = dp.dot(&d) / length_squared;
+        if t <= 0.0 || t >= 1.0 {
+            return false;
+        }
+        (dp - d * t).norm_squared() < occluder.occluder_radius.powi(2)
+    })
 }
 
+fn decide_unreliable_rssi(rng: &mut impl Rng, rssi: f64, reliable_rssi: f64) -> bool {
+    rng.gen_bool(1.0 / (2.0 * reliable_rssi / rssi).log2())
+}
+
+// The gain applies twice, on the way out and on the echo.
 fn compute_rssi(emitter: &RadarEmitter, reflector: &RadarReflector) -> f64 {
     let r_sq = nalgebra::distance_squared(&emitter.center, &reflector.position);
-    emitter.power * reflector.radar_cross_section * emitter.rx_cross_section
+    emitter.power
+        * reflector.radar_cross_section
+        * emitter.rx_cross_section
+        * emitter.gain(reflector.position).powi(2)
+        * doppler_gain(emitter, reflector)
         / (TAU * emitter.width * r_sq * r_sq)
 }
 
+// The notch sits at zero Doppler shift, so it catches reflectors by their
+// closing speed with the emitter.
+fn doppler_gain(emitter: &RadarEmitter, reflector: &RadarReflector) -> f64 {
+    if emitter.doppler_notch <= 0.0 {
+        return 1.0;
+    }
+    let dp = reflector.position - emitter.center;
+    let distance = dp.norm();
+    if distance == 0.0 {
+        return 1.0;
+    }
+    let closing_speed = dp.dot(&(reflector.velocity - emitter.velocity)) / distance;
+    if closing_speed.abs() < emitter.doppler_notch {
+        DOPPLER_NOTCH_GAIN
+    } else {
+        1.0
+    }
+}
+
 fn compute_max_detection_range(radar: &Radar, target_cross_section: f64) -> f64 {
-    (radar.power * target_cross_section * radar.rx_cross_section
+    (radar.emitted_power() * target_cross_section * radar.rx_cross_section
         / (TAU * radar.width * radar.min_rssi))
         .powf(0.25)
 }
 
+// Width of the mainlobe out to where its rolloff reaches the sidelobes.
+fn mainlobe_width(width: f64) -> f64 {
+    width * (1.0 + (-RADAR_SIDELOBE_GAIN.log10() / 2.0).sqrt())
+}
+
+// Range beyond which nothing in the sidelobes matters: cruiser-sized targets
+// are too weak to detect, warning receivers don't hear the emitter, and the
+// strongest jammer adds much less noise than the background.
+fn compute_sidelobe_range(radar: &Radar, max_distance: f64, max_jammer_intensity: f64) -> f64 {
+    let detection_range = (compute_max_detection_range(radar, 40.0 /*cruiser*/)
+        * RADAR_SIDELOBE_GAIN.sqrt())
+    .min(max_distance);
+    let warning_range =
+        (radar.emitted_power() * RADAR_SIDELOBE_GAIN / (TAU * radar.width * RWR_MIN_RSSI)).sqrt();
+    let jamming_range =
+        (JAMMER_COEFF * max_jammer_intensity * radar.rx_cross_section * RADAR_SIDELOBE_GAIN
+            / (TAU * BACKGROUND_NOISE * NEGLIGIBLE_NOISE))
+            .sqrt();
+    detection_range.max(warning_range).max(jamming_range)
+}
+
 fn compute_reliable_detection_range(radar: &Radar, target_cross_section: f64) -> f64 {
-    (radar.power * target_cross_section * radar.rx_cross_section
+    (radar.emitted_power() * target_cross_section * radar.rx_cross_section
         / (TAU * radar.width * radar.reliable_rssi))
         .powf(0.25)
 }
@@ -616,6 +1068,15 @@ fn draw_emitter(sim: &mut Simulation, emitter: &RadarEmitter, reliable_distance:
         color,
     });
     sim.emit_debug_lines(emitter.handle, lines);
+    sim.events.radar_beams.push(RadarBeamSnapshot {
+        ship: emitter.handle.into(),
+        team: emitter.team,
+        position: emitter.center,
+        start_bearing: emitter.start_bearing,
+        end_bearing: emitter.end_bearing,
+        min_distance: emitter.min_distance,
+        reliable_distance,
+    });
 }
 
 fn draw_contact(sim: &mut Simulation, emitter_handle: ShipHandle, contact: &ScanResult) {
@@ -763,10 +1224,238 @@ mod test {
         assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
     }
 
+    #[test]
+    fn test_max_range() {
+        let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let _ship1 = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::target(1),
+        );
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+
+        sim.ship_mut(ship0).radar_mut().unwrap().max_range = 950.0;
+        sim.ship_mut(ship0)
+            .radar_mut()
+            .unwrap()
+            .set_max_distance(1e6);
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
+    }
+
+    #[test]
+    fn test_closing_speed_filter() {
+        let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let ship1 = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![-100.0, 0.0],
+            0.0,
+            ship::target(1),
+        );
+        sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+
+        // Approaching at 100 m/s.
+        sim.ship_mut(ship0)
+            .radar_mut()
+            .unwrap()
+            .set_min_closing_speed(50.0);
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+
+        sim.ship_mut(ship0)
+            .radar_mut()
+            .unwrap()
+            .set_max_closing_speed(80.0);
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
+
+        // Receding at 100 m/s.
+        sim.ship_mut(ship1)
+            .body()
+            .set_linvel(vector![100.0, 0.0], true);
+        sim.ship_mut(ship0)
+            .radar_mut()
+            .unwrap()
+            .set_max_closing_speed(f64::INFINITY);
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
+
+        sim.ship_mut(ship0)
+            .radar_mut()
+            .unwrap()
+            .set_min_closing_speed(-150.0);
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+    }
+
+    #[test]
+    fn test_transponder() {
+        let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let ship1 = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::target(0),
+        );
+        sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+
+        // Friendly ship with transponder enabled.
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
+
+        // Friendly ship with transponder disabled.
+        sim.ship_mut(ship1).data_mut().transponder.enabled = false;
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+
+        // Enemy ship spoofing our IFF code.
+        sim.ship_mut(ship1).data_mut().team = 1;
+        sim.ship_mut(ship1).data_mut().transponder = ship::Transponder {
+            enabled: true,
+            code: Some(0),
+        };
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), false);
+
+        // Enemy ship broadcasting its own IFF code.
+        sim.ship_mut(ship1).data_mut().transponder.code = None;
+        sim.step();
+        assert_eq!(sim.ship(ship0).radar().unwrap().result.is_some(), true);
+    }
+
+    #[test]
+    fn test_asteroid_occlusion() {
+        let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let _ship1 = ship::create(
+            &mut sim,
+            vector![2000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(1),
+        );
+        sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+        sim.step();
+        assert_eq!(
+            sim.ship(ship0).radar().unwrap().result.map(|x| x.class),
+            Some(ShipClass::Fighter)
+        );
+
+        // Asteroid between the radar and the target.
+        let asteroid = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::asteroid(0),
+        );
+        sim.step();
+        assert_eq!(
+            sim.ship(ship0).radar().unwrap().result.map(|x| x.class),
+            Some(ShipClass::Asteroid { variant: 0 })
+        );
+
+        // Distant asteroid behind the target.
+        sim.ship_mut(asteroid)
+            .body()
+            .set_translation(vector![5000.0, 0.0], true);
+        sim.step();
+        assert_eq!(
+            sim.ship(ship0).radar().unwrap().result.map(|x| x.class),
+            Some(ShipClass::Fighter)
+        );
+    }
+
+    #[test]
+    fn test_asteroid_clutter() {
+        let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let ship1 = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(1),
+        );
+        for y in [-100.0, 100.0] {
+            ship::create(
+                &mut sim,
+                vector![1700.0, y],
+                vector![0.0, 0.0],
+                0.0,
+                ship::asteroid(0),
+            );
+        }
+        sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+
+        // Each asteroid is weaker than the target but together they hide it.
+        sim.step();
+        assert_eq!(
+            sim.ship(ship0).radar().unwrap().result.map(|x| x.class),
+            Some(ShipClass::Asteroid { variant: 0 })
+        );
+
+        // A closer target stands out from the clutter.
+        sim.ship_mut(ship1)
+            .body()
+            .set_translation(vector![500.0, 0.0], true);
+        sim.step();
+        assert_eq!(
+            sim.ship(ship0).radar().unwrap().result.map(|x| x.class),
+            Some(ShipClass::Fighter)
+        );
+    }
+
     #[test]
     fn test_detection_range() {
         let class_to_ship_data = |class, team| match class {
             ShipClass::Fighter => ship::fighter(team),
+            ShipClass::Corvette => ship::corvette(team),
             ShipClass::Frigate => ship::frigate(team),
             ShipClass::Cruiser => ship::cruiser(team),
             ShipClass::Missile => ship::missile(team),
@@ -854,20 +1543,97 @@ mod test {
         assert!(!check_detection(70e3));
     }
 
+    #[test]
+    fn test_sidelobes() {
+        let check_detection = |range: f64, bearing: f64| {
+            let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+            let ship0 = ship::create(
+                &mut sim,
+                vector![0.0, 0.0],
+                vector![0.0, 0.0],
+                0.0,
+                ship::fighter(0),
+            );
+            ship::create(
+                &mut sim,
+                vector![range * bearing.cos(), range * bearing.sin()],
+                vector![0.0, 0.0],
+                0.0,
+                ship::target(1),
+            );
+            sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+            sim.ship_mut(ship0).radar_mut().unwrap().width = TAU / 16.0;
+            sim.step();
+            sim.ship(ship0).radar().unwrap().result.is_some()
+        };
+
+        // In the beam.
+        assert!(check_detection(1e3, 0.0));
+
+        // Just past the edge of the beam, in the rolloff of the mainlobe.
+        assert!(check_detection(2e3, 0.3));
+
+        // Only close targets are strong enough to detect in the sidelobes.
+        assert!(check_detection(300.0, PI / 2.0));
+        assert!(check_detection(300.0, PI));
+        assert!(!check_detection(10e3, PI / 2.0));
+    }
+
+    #[test]
+    fn test_doppler_notch() {
+        let check_detection = |notch: bool, missile_velocity, target_velocity| {
+            let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+            sim.set_seeker_doppler_notch(notch);
+            let ship0 = ship::create(
+                &mut sim,
+                vector![0.0, 0.0],
+                missile_velocity,
+                0.0,
+                ship::missile(0),
+            );
+            ship::create(
+                &mut sim,
+                vector![5e3, 0.0],
+                target_velocity,
+                0.0,
+                ship::target(1),
+            );
+            sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+            sim.step();
+            sim.ship(ship0).radar().unwrap().result.is_some()
+        };
+
+        // Keeping pace with the missile.
+        let fast = vector![500.0, 0.0];
+        assert!(check_detection(false, fast, vector![500.0, 0.0]));
+        assert!(!check_detection(true, fast, vector![500.0, 0.0]));
+        assert!(!check_detection(true, fast, vector![510.0, 300.0]));
+
+        // Flying towards, away from, or perpendicular to a fast missile.
+        assert!(check_detection(true, fast, vector![-300.0, 0.0]));
+        assert!(check_detection(true, fast, vector![300.0, 0.0]));
+        assert!(check_detection(true, fast, vector![0.0, 300.0]));
+
+        // Flying perpendicular to a missile that has stopped closing.
+        assert!(!check_detection(
+            true,
+            vector![0.0, 0.0],
+            vector![0.0, 300.0]
+        ));
+    }
+
     #[test]
     fn test_random() {
         let mut rng = crate::rng::new_rng(1);
         for _ in 0..1000 {
             let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
-            let mut rand_vector =
-                || vector![rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)];
-            let p0 = rand_vector();
-            let p1 = rand_vector();
-            if (p1 - p0).magnitude() < 20.0 {
-                continue;
-            }
+            // Far enough that the sidelobes can't detect the target.
+            let p0 = vector![rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)];
+            let p1 = p0
+                + UnitComplex::new(rng.gen_range(0.0..TAU))
+                    .transform_vector(&vector![rng.gen_range(6e3..10e3), 0.0]);
             let h = rng.gen_range(0.0..TAU);
-            let w = rng.gen_range(0.0..(TAU / 4.0));
+            let w = rng.gen_range((TAU / 3600.0)..(TAU / 4.0));
 
             let ship0 = ship::create(&mut sim, p0, vector![0.0, 0.0], 0.0, ship::fighter(0));
             let _ship1 = ship::create(&mut sim, p1, vector![0.0, 0.0], 0.0, ship::target(1));
@@ -877,7 +1643,12 @@ mod test {
 
             let dp = p1 - p0;
             let center_vec = UnitComplex::new(h).transform_vector(&vector![1.0, 0.0]);
-            let expected = dp.angle(&center_vec).abs() < w * 0.5;
+            let angle = dp.angle(&center_vec).abs();
+            if angle > w * 0.5 && angle < super::mainlobe_width(w) * 0.5 {
+                // In the rolloff of the mainlobe, which depends on range.
+                continue;
+            }
+            let expected = angle < w * 0.5;
             let got = sim.ship(ship0).radar().unwrap().result.is_some();
             assert_eq!(
                 got, expected,
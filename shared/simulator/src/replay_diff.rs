@@ -0,0 +1,316 @@
+//! Compares two replays of the same scenario and seed, typically exported by
+//! different simulator versions, and reports where they diverge.
+//!
+//! Ships are matched by ID within each tick. Once a battle diverges nearly
+//! every later tick will differ too, so the first divergent tick is usually
+//! the interesting one.
+
+use crate::replay_export::{Record, Ship};
+use std::collections::BTreeMap;
+use std::f64::consts::{PI, TAU};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    // The ship exists in only one of the replays.
+    MissingShip {
+        tick: u32,
+        id: u64,
+        missing_from: Side,
+    },
+    ShipClass {
+        tick: u32,
+        id: u64,
+        a: String,
+        b: String,
+    },
+    ShipTeam {
+        tick: u32,
+        id: u64,
+        a: i32,
+        b: i32,
+    },
+    ShipState {
+        tick: u32,
+        id: u64,
+        field: &'static str,
+        a: f64,
+        b: f64,
+    },
+    BulletCount {
+        tick: u32,
+        a: usize,
+        b: usize,
+    },
+    // One replay ended before the other.
+    Length {
+        a: u32,
+        b: u32,
+    },
+    Outcome {
+        a: String,
+        b: String,
+    },
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Divergence::MissingShip {
+                tick,
+                id,
+                missing_from,
+            } => write!(f, "tick {tick}: ship {id} missing from {missing_from:?}"),
+            Divergence::ShipClass { tick, id, a, b } => {
+                write!(f, "tick {tick}: ship {id} class differs: a={a} b={b}")
+            }
+            Divergence::ShipTeam { tick, id, a, b } => {
+                write!(f, "tick {tick}: ship {id} team differs: a={a} b={b}")
+            }
+            Divergence::ShipState {
+                tick,
+                id,
+                field,
+                a,
+                b,
+            } => write!(
+                f,
+                "tick {tick}: ship {id} {field} differs by {}: a={a} b={b}",
+                (a - b).abs()
+            ),
+            Divergence::BulletCount { tick, a, b } => {
+                write!(f, "tick {tick}: bullet count differs: a={a} b={b}")
+            }
+            Divergence::Length { a, b } => write!(f, "tick count differs: a={a} b={b}"),
+            Divergence::Outcome { a, b } => write!(f, "outcome differs: a={a} b={b}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    // The replays are not of the same scenario and seed.
+    Incompatible(String),
+    Format(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Incompatible(msg) => write!(f, "Replays are not comparable: {msg}"),
+            Error::Format(msg) => write!(f, "Invalid replay: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub ticks_compared: u32,
+    pub divergent_ticks: u32,
+    pub first_divergent_tick: Option<u32>,
+    // At most `limit` divergences, in tick order.
+    pub divergences: Vec<Divergence>,
+    pub truncated: bool,
+}
+
+impl Report {
+    pub fn is_identical(&self) -> bool {
+        self.divergences.is_empty() && !self.truncated
+    }
+
+    fn push(&mut self, divergence: Divergence, limit: usize) {
+        if self.divergences.len() < limit {
+            self.divergences.push(divergence);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Compares replays `a` and `b` as returned by `replay_export::read`.
+///
+/// Floating point fields that differ by no more than `tolerance` are treated
+/// as equal. At most `limit` divergences are kept in the report.
+pub fn diff(a: &[Record], b: &[Record], tolerance: f64, limit: usize) -> Result<Report, Error> {
+    check_headers(a, b)?;
+
+    let mut report = Report::default();
+    let ticks_a = ticks(a);
+    let ticks_b = ticks(b);
+    for (&(tick, ships_a, bullets_a), &(_, ships_b, bullets_b)) in
+        ticks_a.iter().zip(ticks_b.iter())
+    {
+        report.ticks_compared += 1;
+        let mut divergences = vec![];
+
+        let ships_a: BTreeMap<u64, &Ship> = ships_a.iter().map(|ship| (ship.id, ship)).collect();
+        let ships_b: BTreeMap<u64, &Ship> = ships_b.iter().map(|ship| (ship.id, ship)).collect();
+        for (&id, &ship_a) in ships_a.iter() {
+            match ships_b.get(&id) {
+                Some(&ship_b) => divergences.extend(diff_ship(tick, ship_a, ship_b, tolerance)),
+                None => divergences.push(Divergence::MissingShip {
+                    tick,
+                    id,
+                    missing_from: Side::B,
+                }),
+            }
+        }
+        for &id in ships_b.keys() {
+            if !ships_a.contains_key(&id) {
+                divergences.push(Divergence::MissingShip {
+                    tick,
+                    id,
+                    missing_from: Side::A,
+                });
+            }
+        }
+        if bullets_a != bullets_b {
+            divergences.push(Divergence::BulletCount {
+                tick,
+                a: bullets_a,
+                b: bullets_b,
+            });
+        }
+
+        if !divergences.is_empty() {
+            report.divergent_ticks += 1;
+            report.first_divergent_tick.get_or_insert(tick);
+            for divergence in divergences {
+                report.push(divergence, limit);
+            }
+        }
+    }
+
+    if ticks_a.len() != ticks_b.len() {
+        report.push(
+            Divergence::Length {
+                a: ticks_a.len() as u32,
+                b: ticks_b.len() as u32,
+            },
+            limit,
+        );
+    }
+    let (outcome_a, outcome_b) = (outcome(a), outcome(b));
+    if outcome_a != outcome_b {
+        report.push(
+            Divergence::Outcome {
+                a: outcome_a,
+                b: outcome_b,
+            },
+            limit,
+        );
+    }
+
+    Ok(report)
+}
+
+fn check_headers(a: &[Record], b: &[Record]) -> Result<(), Error> {
+    let header = |records: &[Record]| match records.first() {
+        Some(Record::Header {
+            scenario,
+            seed,
+            tick_length,
+            ..
+        }) => Ok((scenario.clone(), *seed, *tick_length)),
+        _ => Err(Error::Format("missing header".to_string())),
+    };
+    let (scenario_a, seed_a, tick_length_a) = header(a)?;
+    let (scenario_b, seed_b, tick_length_b) = header(b)?;
+    if scenario_a != scenario_b {
+        return Err(Error::Incompatible(format!(
+            "scenario {scenario_a:?} vs {scenario_b:?}"
+        )));
+    }
+    if seed_a != seed_b {
+        return Err(Error::Incompatible(format!("seed {seed_a} vs {seed_b}")));
+    }
+    if tick_length_a != tick_length_b {
+        return Err(Error::Incompatible(format!(
+            "tick length {tick_length_a} vs {tick_length_b}"
+        )));
+    }
+    Ok(())
+}
+
+fn ticks(records: &[Record]) -> Vec<(u32, &[Ship], usize)> {
+    records
+        .iter()
+        .filter_map(|record| match record {
+            Record::Tick {
+                tick,
+                ships,
+                bullets,
+                ..
+            } => Some((*tick, ships.as_slice(), bullets.len())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn outcome(records: &[Record]) -> String {
+    match records.last() {
+        Some(Record::End {
+            status,
+            winner: Some(team),
+            ..
+        }) => format!("{status} (team {team})"),
+        Some(Record::End { status, .. }) => status.clone(),
+        _ => "incomplete".to_string(),
+    }
+}
+
+fn diff_ship(tick: u32, a: &Ship, b: &Ship, tolerance: f64) -> Vec<Divergence> {
+    let mut divergences = vec![];
+    if a.class != b.class {
+        divergences.push(Divergence::ShipClass {
+            tick,
+            id: a.id,
+            a: a.class.clone(),
+            b: b.class.clone(),
+        });
+    }
+    if a.team != b.team {
+        divergences.push(Divergence::ShipTeam {
+            tick,
+            id: a.id,
+            a: a.team,
+            b: b.team,
+        });
+    }
+    let fields = [
+        ("position.x", a.position[0], b.position[0]),
+        ("position.y", a.position[1], b.position[1]),
+        ("velocity.x", a.velocity[0], b.velocity[0]),
+        ("velocity.y", a.velocity[1], b.velocity[1]),
+        ("health", a.health, b.health),
+    ];
+    for (field, value_a, value_b) in fields {
+        if (value_a - value_b).abs() > tolerance {
+            divergences.push(Divergence::ShipState {
+                tick,
+                id: a.id,
+                field,
+                a: value_a,
+                b: value_b,
+            });
+        }
+    }
+    let heading_difference = (a.heading - b.heading + PI).rem_euclid(TAU) - PI;
+    if heading_difference.abs() > tolerance {
+        divergences.push(Divergence::ShipState {
+            tick,
+            id: a.id,
+            field: "heading",
+            a: a.heading,
+            b: b.heading,
+        });
+    }
+    divergences
+}
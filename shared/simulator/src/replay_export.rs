@@ -0,0 +1,233 @@
+//! Exports a battle as newline-delimited JSON for external tools.
+//!
+//! A replay is reproduced by re-running the scenario with the same seed and
+//! code. Each line of the output is a JSON object with a `type` field:
+//!
+//! - `header`: written once first. Contains `format` (always
+//!   `"oort-replay"`), `version`, `simulator_version`, `scenario`, `seed`,
+//!   `world_size` (meters) and `tick_length` (seconds).
+//! - `tick`: written after every simulation tick. Contains `tick`, `time`
+//!   (seconds), `ships`, `bullets`, `created` and `destroyed`.
+//!   - Each ship has `id`, `team`, `class`, `position` ([x, y] in meters),
+//!     `velocity` ([x, y] in m/s), `heading` (radians) and `health`.
+//!   - Each bullet has `team`, `position` and `velocity`.
+//!   - `created` and `destroyed` list the IDs of ships that appeared or
+//!     disappeared during the tick.
+//! - `end`: written once last. Contains the final `tick`, `status` (one of
+//!   `"victory"`, `"failed"`, `"draw"` or `"running"` if the tick limit was
+//!   hit) and `winner`, the winning team or null.
+//!
+//! Positions are in world coordinates, with the origin at the center of the
+//! world.
+
+use crate::bullet;
+use crate::result::SIMULATOR_VERSION;
+use crate::scenario::{self, Status};
+use crate::simulation::{Code, Simulation, PHYSICS_TICK_LENGTH};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::{BufRead, Write};
+
+pub const FORMAT: &str = "oort-replay";
+pub const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Record {
+    Header {
+        format: String,
+        version: u32,
+        simulator_version: String,
+        scenario: String,
+        seed: u32,
+        world_size: f64,
+        tick_length: f64,
+    },
+    Tick {
+        tick: u32,
+        time: f64,
+        ships: Vec<Ship>,
+        bullets: Vec<Bullet>,
+        created: Vec<u64>,
+        destroyed: Vec<u64>,
+    },
+    End {
+        tick: u32,
+        status: String,
+        winner: Option<i32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Ship {
+    pub id: u64,
+    pub team: i32,
+    pub class: String,
+    pub position: [f64; 2],
+    pub velocity: [f64; 2],
+    pub heading: f64,
+    pub health: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bullet {
+    pub team: i32,
+    pub position: [f64; 2],
+    pub velocity: [f64; 2],
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Format(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {e}"),
+            Error::Json(e) => write!(f, "JSON error: {e}"),
+            Error::Format(msg) => write!(f, "Invalid replay: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+/// Simulates the scenario to completion and writes every tick to `writer`.
+pub fn export(
+    scenario_name: &str,
+    seed: u32,
+    codes: &[Code],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let mut sim = Simulation::new(scenario_name, seed, codes);
+    write_record(
+        writer,
+        &Record::Header {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            simulator_version: SIMULATOR_VERSION.to_string(),
+            scenario: scenario_name.to_string(),
+            seed,
+            world_size: sim.world_size(),
+            tick_length: PHYSICS_TICK_LENGTH,
+        },
+    )?;
+
+    let mut previous_ids = ship_ids(&sim);
+    while sim.status() == Status::Running && sim.tick() < scenario::MAX_TICKS {
+        sim.step();
+        let ids = ship_ids(&sim);
+        write_record(writer, &tick_record(&sim, &previous_ids, &ids))?;
+        previous_ids = ids;
+    }
+
+    let (status, winner) = match sim.status() {
+        Status::Running => ("running", None),
+        Status::Victory { team } => ("victory", Some(team)),
+        Status::Failed => ("failed", None),
+        Status::Draw => ("draw", None),
+    };
+    write_record(
+        writer,
+        &Record::End {
+            tick: sim.tick(),
+            status: status.to_string(),
+            winner,
+        },
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a replay written by `export`.
+pub fn read(reader: impl BufRead) -> Result<Vec<Record>, Error> {
+    let mut records = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line)?);
+    }
+    match records.first() {
+        Some(Record::Header {
+            format, version, ..
+        }) if format == FORMAT && *version == VERSION => Ok(records),
+        Some(Record::Header {
+            format, version, ..
+        }) => Err(Error::Format(format!(
+            "unsupported format {format:?} version {version}"
+        ))),
+        _ => Err(Error::Format("missing header".to_string())),
+    }
+}
+
+fn write_record(writer: &mut impl Write, record: &Record) -> Result<(), Error> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+fn ship_ids(sim: &Simulation) -> BTreeSet<u64> {
+    sim.ships.iter().map(|&handle| handle.into()).collect()
+}
+
+fn tick_record(sim: &Simulation, previous_ids: &BTreeSet<u64>, ids: &BTreeSet<u64>) -> Record {
+    let origin = sim.origin();
+    let ships = sim
+        .ships
+        .iter()
+        .map(|&handle| {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            let position = ship.position().vector + origin;
+            let velocity = ship.velocity();
+            Ship {
+                id: handle.into(),
+                team: data.team,
+                class: data.class.name().to_string(),
+                position: [position.x, position.y],
+                velocity: [velocity.x, velocity.y],
+                heading: ship.heading(),
+                health: data.health,
+            }
+        })
+        .collect();
+    let bullets = sim
+        .bullets
+        .iter()
+        .map(|&handle| {
+            let body = bullet::body(sim, handle);
+            let position = body.translation() + origin;
+            let velocity = body.linvel();
+            Bullet {
+                team: bullet::data(sim, handle).team,
+                position: [position.x, position.y],
+                velocity: [velocity.x, velocity.y],
+            }
+        })
+        .collect();
+    Record::Tick {
+        tick: sim.tick(),
+        time: sim.time(),
+        ships,
+        bullets,
+        created: ids.difference(previous_ids).copied().collect(),
+        destroyed: previous_ids.difference(ids).copied().collect(),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::ship::{self, ShipClass, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::{vector, Vector2};
+
+#[derive(Clone, Debug)]
+pub struct SpawnPoint {
+    pub position: Vector2<f64>,
+    pub heading: f64,
+}
+
+// Per-team pool that scenarios credit and AIs spend on reinforcements.
+#[derive(Clone, Debug, Default)]
+pub struct TeamResources {
+    pub amount: f64,
+    pub spawn_points: Vec<SpawnPoint>,
+    next_spawn_point: usize,
+}
+
+pub fn reinforcement_cost(class: ShipClass) -> Option<f64> {
+    match class {
+        ShipClass::Fighter => Some(100.0),
+        ShipClass::Corvette => Some(250.0),
+        ShipClass::Frigate => Some(400.0),
+        ShipClass::Cruiser => Some(1000.0),
+        _ => None,
+    }
+}
+
+pub fn request_reinforcement(
+    sim: &mut Simulation,
+    team: i32,
+    class: ShipClass,
+) -> Option<ShipHandle> {
+    let cost = reinforcement_cost(class)?;
+    let origin = sim.origin();
+    let spawn_point = {
+        let resources = sim.team_resources.get_mut(&team)?;
+        if resources.spawn_points.is_empty() || resources.amount < cost {
+            return None;
+        }
+        resources.amount -= cost;
+        let index = resources.next_spawn_point % resources.spawn_points.len();
+        resources.next_spawn_point = index + 1;
+        resources.spawn_points[index].clone()
+    };
+    let mut data = match class {
+        ShipClass::Fighter => ship::fighter(team),
+        ShipClass::Corvette => ship::corvette(team),
+        ShipClass::Frigate => ship::frigate(team),
+        ShipClass::Cruiser => ship::cruiser(team),
+        _ => unreachable!(),
+    };
+    data.spawn_grace_ticks_remaining = sim.spawn_grace_ticks();
+    Some(ship::create(
+        sim,
+        spawn_point.position - origin,
+        vector![0.0, 0.0],
+        spawn_point.heading,
+        data,
+    ))
+}
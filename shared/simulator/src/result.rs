@@ -0,0 +1,178 @@
+use crate::scenario::{self, Difficulty, Status};
+use crate::simulation::{Code, Simulation};
+use oort_proto::ranked::{SeedGrant, SeedGrantError};
+use oort_proto::{code_hash, ScenarioResult, SCENARIO_RESULT_VERSION};
+
+pub const SIMULATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    UnsupportedVersion(u32),
+    SimulatorVersionMismatch(String),
+    CodeHashMismatch { expected: String, actual: String },
+    UnknownScenario(String),
+    UnsupportedDifficulty(Difficulty),
+    NotVictorious,
+    TicksMismatch { expected: u32, actual: u32 },
+    ReplayHashMismatch { expected: u64, actual: u64 },
+    InstructionsMismatch { expected: u64, actual: u64 },
+    StatsMismatch,
+    SeedGrant(SeedGrantError),
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported result version {version}")
+            }
+            ValidationError::SimulatorVersionMismatch(version) => write!(
+                f,
+                "Result from simulator version {version}, expected {SIMULATOR_VERSION}"
+            ),
+            ValidationError::CodeHashMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Expected code hash {expected}, source code hashes to {actual}"
+                )
+            }
+            ValidationError::UnknownScenario(name) => write!(f, "Unknown scenario {name:?}"),
+            ValidationError::UnsupportedDifficulty(difficulty) => {
+                write!(f, "Scenario has no {} difficulty", difficulty.name())
+            }
+            ValidationError::NotVictorious => write!(f, "Scenario did not end in victory"),
+            ValidationError::TicksMismatch { expected, actual } => {
+                write!(f, "Expected {expected} ticks, simulation took {actual}")
+            }
+            ValidationError::ReplayHashMismatch { expected, actual } => {
+                write!(f, "Expected replay hash {expected}, got {actual}")
+            }
+            ValidationError::InstructionsMismatch { expected, actual } => {
+                write!(f, "Expected {expected} instructions, AI used {actual}")
+            }
+            ValidationError::StatsMismatch => write!(f, "Battle stats don't match the simulation"),
+            ValidationError::SeedGrant(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+fn simulate(
+    scenario_name: &str,
+    seed: u32,
+    code: &Code,
+    difficulty: Difficulty,
+) -> Box<Simulation> {
+    let mut codes = scenario::load_with_difficulty(scenario_name, difficulty).initial_code();
+    codes[0] = code.clone();
+    let mut sim = Simulation::new_with_difficulty(scenario_name, seed, &codes, difficulty);
+    while sim.status() == Status::Running && sim.tick() < scenario::MAX_TICKS {
+        sim.step();
+    }
+    sim
+}
+
+// `code` must be compiled from `source_code`.
+pub fn generate(scenario_name: &str, seed: u32, source_code: &str, code: &Code) -> ScenarioResult {
+    generate_with_difficulty(scenario_name, seed, source_code, code, Difficulty::Normal)
+}
+
+pub fn generate_with_difficulty(
+    scenario_name: &str,
+    seed: u32,
+    source_code: &str,
+    code: &Code,
+    difficulty: Difficulty,
+) -> ScenarioResult {
+    let sim = simulate(scenario_name, seed, code, difficulty);
+    ScenarioResult {
+        version: SCENARIO_RESULT_VERSION,
+        scenario_name: scenario_name.to_string(),
+        seed,
+        code_hash: code_hash(source_code),
+        ticks: sim.tick(),
+        replay_hash: sim.hash(),
+        simulator_version: SIMULATOR_VERSION.to_string(),
+        instructions: sim.cpu_usage(0).total,
+        seed_grant: None,
+        difficulty,
+        stats: sim.battle_stats().clone(),
+    }
+}
+
+// Runs a ranked attempt on the seed issued by the backend.
+pub fn generate_ranked(grant: &SeedGrant, source_code: &str, code: &Code) -> ScenarioResult {
+    ScenarioResult {
+        seed_grant: Some(grant.clone()),
+        ..generate(&grant.scenario_name, grant.seed, source_code, code)
+    }
+}
+
+// Re-runs the scenario with the given code, which must be compiled from
+// `source_code`, and checks it reproduces the result. The seed grant's
+// signature is not checked here since that needs the backend's secret; only
+// that the run used the granted seed.
+pub fn validate(
+    result: &ScenarioResult,
+    source_code: &str,
+    code: &Code,
+) -> Result<(), ValidationError> {
+    if result.version != SCENARIO_RESULT_VERSION {
+        return Err(ValidationError::UnsupportedVersion(result.version));
+    }
+    if result.simulator_version != SIMULATOR_VERSION {
+        return Err(ValidationError::SimulatorVersionMismatch(
+            result.simulator_version.clone(),
+        ));
+    }
+    let actual_code_hash = code_hash(source_code);
+    if actual_code_hash != result.code_hash {
+        return Err(ValidationError::CodeHashMismatch {
+            expected: result.code_hash.clone(),
+            actual: actual_code_hash,
+        });
+    }
+    if scenario::load_safe(&result.scenario_name).is_none() {
+        return Err(ValidationError::UnknownScenario(
+            result.scenario_name.clone(),
+        ));
+    }
+
+    if scenario::load_safe_with_difficulty(&result.scenario_name, result.difficulty).is_none() {
+        return Err(ValidationError::UnsupportedDifficulty(result.difficulty));
+    }
+
+    if let Some(grant) = result.seed_grant.as_ref() {
+        grant
+            .check_run(&result.scenario_name, result.seed)
+            .map_err(ValidationError::SeedGrant)?;
+    }
+
+    let sim = simulate(&result.scenario_name, result.seed, code, result.difficulty);
+    if sim.status() != (Status::Victory { team: 0 }) {
+        return Err(ValidationError::NotVictorious);
+    }
+    if sim.tick() != result.ticks {
+        return Err(ValidationError::TicksMismatch {
+            expected: result.ticks,
+            actual: sim.tick(),
+        });
+    }
+    if sim.hash() != result.replay_hash {
+        return Err(ValidationError::ReplayHashMismatch {
+            expected: result.replay_hash,
+            actual: sim.hash(),
+        });
+    }
+    if sim.cpu_usage(0).total != result.instructions {
+        return Err(ValidationError::InstructionsMismatch {
+            expected: result.instructions,
+            actual: sim.cpu_usage(0).total,
+        });
+    }
+    if sim.battle_stats() != &result.stats {
+        return Err(ValidationError::StatsMismatch);
+    }
+    Ok(())
+}
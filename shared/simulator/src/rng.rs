@@ -3,3 +3,31 @@ pub use rand_chacha::ChaCha8Rng as SeededRng;
 pub fn new_rng(seed: u32) -> SeededRng {
     rand_seeder::Seeder::from(seed).make_rng()
 }
+
+// Sources of noise that affect a ship's sensors and weapons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Noise {
+    Gun(usize),
+    Radar,
+    Imu,
+}
+
+impl Noise {
+    fn key(self) -> u64 {
+        match self {
+            Noise::Gun(index) => 0x100 + index as u64,
+            Noise::Radar => 1,
+            Noise::Imu => 2,
+        }
+    }
+}
+
+// Returns the RNG for one source of noise on one ship for one tick.
+//
+// `noise_id` is the ship's index among the ships created for its team, so two
+// ships in mirrored positions on opposite teams get identical draws no matter
+// which team they are on or the order they were created in.
+pub fn noise_rng(seed: u32, tick: u32, noise_id: u64, noise: Noise) -> SeededRng {
+    // Hash fixed-width integers only so the stream is the same on wasm32.
+    rand_seeder::Seeder::from((seed, tick, noise_id, noise.key())).make_rng()
+}
@@ -1,5 +1,8 @@
 use super::prelude::*;
 
+const MIN_ORBIT_RADIUS: f64 = 2000.0;
+
+#[derive(Clone)]
 pub struct Belt {}
 
 impl Belt {
@@ -57,19 +60,26 @@ impl Scenario for Belt {
         }
 
         let bound = vector![(sim.world_size() / 2.0) * 0.9, (sim.world_size() / 4.0)];
-        for _ in 0..100 {
+        let mut num_asteroids = 0;
+        while num_asteroids < 100 {
+            let position = vector![
+                rng.gen_range(-bound.x..bound.x),
+                rng.gen_range(-bound.y..bound.y)
+            ];
+            // Keep asteroids away from the fast inner orbits and off the walls.
+            if position.magnitude() < MIN_ORBIT_RADIUS || position.magnitude() > bound.x {
+                continue;
+            }
             let mut data = asteroid(rng.gen_range(0..30));
             data.health = 10000.0;
             ship::create(
                 sim,
-                vector![
-                    rng.gen_range(-bound.x..bound.x),
-                    rng.gen_range(-bound.y..bound.y)
-                ],
-                vector![rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)],
+                position,
+                self.velocity_field().velocity(position),
                 rng.gen_range(0.0..(2.0 * std::f64::consts::PI)),
                 data,
             );
+            num_asteroids += 1;
         }
     }
 
@@ -77,6 +87,13 @@ impl Scenario for Belt {
         check_capital_ship_tournament_victory(sim)
     }
 
+    fn velocity_field(&self) -> VelocityField {
+        VelocityField::Keplerian {
+            center: vector![0.0, 0.0],
+            mu: 9e6,
+        }
+    }
+
     fn initial_code(&self) -> Vec<Code> {
         vec![empty_ai(), reference_ai()]
     }
@@ -0,0 +1,143 @@
+use super::prelude::*;
+use crate::ship::{ShipClass, ShipData};
+use crate::simulation::MAX_WORLD_SIZE;
+
+// Classes that can take part in a duel.
+const CLASSES: [ShipClass; 4] = [
+    ShipClass::Fighter,
+    ShipClass::Corvette,
+    ShipClass::Frigate,
+    ShipClass::Cruiser,
+];
+
+// Duels in worlds larger than this re-center on the ships as they drift, see
+// `Scenario::floating_origin`.
+const FLOATING_ORIGIN_WORLD_SIZE: f64 = 100e3;
+
+// A 1v1 between any two classes, generated rather than written by hand. The
+// ships start `separation` meters apart facing each other, mirrored about the
+// center of the world along an axis drawn from the duel's seed and the
+// simulation's seed.
+//
+// The name encodes every parameter, so `load_safe` can recreate the duel and
+// replays work like any other scenario.
+#[derive(Clone)]
+pub struct Duel {
+    classes: [ShipClass; 2],
+    separation: f64,
+    seed: u32,
+}
+
+impl Duel {
+    pub fn new(class_a: ShipClass, class_b: ShipClass, separation: f64, seed: u32) -> Self {
+        for class in [class_a, class_b] {
+            assert!(CLASSES.contains(&class), "Unsupported duel class");
+        }
+        assert!(
+            separation > 0.0 && separation <= MAX_WORLD_SIZE / 2.0,
+            "Invalid duel separation"
+        );
+        Self {
+            classes: [class_a, class_b],
+            separation,
+            seed,
+        }
+    }
+
+    // Parses the part of a name after "duel-".
+    pub fn parse(params: &str) -> Option<Self> {
+        let parse_class = |name: &str| CLASSES.iter().copied().find(|c| c.name() == name);
+        let mut parts = params.split('-');
+        let class_a = parse_class(parts.next()?)?;
+        let class_b = parse_class(parts.next()?)?;
+        let separation: f64 = parts.next()?.parse().ok()?;
+        let seed: u32 = parts.next()?.parse().ok()?;
+        if parts.next().is_some()
+            || !separation.is_finite()
+            || separation <= 0.0
+            || separation > MAX_WORLD_SIZE / 2.0
+        {
+            return None;
+        }
+        Some(Self::new(class_a, class_b, separation, seed))
+    }
+
+    fn create_ship(class: ShipClass, team: i32) -> ShipData {
+        match class {
+            ShipClass::Fighter => fighter(team),
+            ShipClass::Corvette => corvette(team),
+            ShipClass::Frigate => frigate(team),
+            ShipClass::Cruiser => cruiser(team),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Scenario for Duel {
+    fn name(&self) -> String {
+        format!(
+            "duel-{}-{}-{}-{}",
+            self.classes[0].name(),
+            self.classes[1].name(),
+            self.separation,
+            self.seed
+        )
+    }
+
+    fn human_name(&self) -> String {
+        let capitalize = |class: ShipClass| {
+            let name = class.name();
+            name[..1].to_uppercase() + &name[1..]
+        };
+        format!(
+            "{} vs {} Duel",
+            capitalize(self.classes[0]),
+            capitalize(self.classes[1])
+        )
+    }
+
+    fn init(&mut self, sim: &mut Simulation, seed: u32) {
+        let mut rng = new_rng(seed ^ self.seed);
+        let heading = rng.gen_range(0.0..TAU);
+        let offset = Rotation2::new(heading).transform_vector(&vector![self.separation / 2.0, 0.0]);
+
+        for (team, class) in self.classes.iter().copied().enumerate() {
+            let (position, heading) = if team == 0 {
+                (-offset, heading)
+            } else {
+                (offset, heading + PI)
+            };
+            ship::create(
+                sim,
+                position,
+                vector![0.0, 0.0],
+                heading,
+                Self::create_ship(class, team as i32),
+            );
+        }
+    }
+
+    fn status(&self, sim: &Simulation) -> Status {
+        check_tournament_victory(sim)
+    }
+
+    fn initial_code(&self) -> Vec<Code> {
+        vec![empty_ai(), reference_ai()]
+    }
+
+    fn solution(&self) -> Code {
+        reference_ai()
+    }
+
+    fn is_tournament(&self) -> bool {
+        true
+    }
+
+    fn world_size(&self) -> f64 {
+        (2.0 * self.separation).clamp(40000.0, MAX_WORLD_SIZE)
+    }
+
+    fn floating_origin(&self) -> bool {
+        self.world_size() > FLOATING_ORIGIN_WORLD_SIZE
+    }
+}
@@ -1,7 +1,6 @@
-use nalgebra::UnitComplex;
-
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct Fleet {}
 
 impl Fleet {
@@ -49,19 +48,13 @@ impl Scenario for Fleet {
             }
 
             // Fighters
+            let formation = Formation::new(FormationShape::Wedge, 100.0);
             for s in [-1.0, 1.0] {
-                let n = 5;
-                for i in 0..n {
-                    ship::create(
-                        sim,
-                        position
-                            + vector![signum * 1000.0, s * frigate_separation]
-                            + wedge(i, heading) * 100.0,
-                        vector![0.0, 0.0],
-                        heading,
-                        fighter(team as i32),
-                    );
-                }
+                let leader = Placement {
+                    position: position + vector![signum * 1000.0, s * frigate_separation],
+                    heading,
+                };
+                formation.create(sim, &leader, vec![fighter(team as i32); 5]);
             }
         }
     }
@@ -85,14 +78,12 @@ impl Scenario for Fleet {
     fn world_size(&self) -> f64 {
         100e3
     }
-}
 
-fn wedge(i: usize, heading: f64) -> Vector2<f64> {
-    if i == 0 {
-        return vector![0.0, 0.0];
+    fn physics_profile(&self) -> PhysicsProfile {
+        PhysicsProfile::fleet_battle()
     }
 
-    let s = [-1.0, 1.0][i % 2];
-    let j = ((i + 1) / 2) as f64;
-    UnitComplex::new(heading).transform_vector(&vector![-j, s * j])
+    fn leave_hulks(&self) -> bool {
+        true
+    }
 }
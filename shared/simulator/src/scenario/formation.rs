@@ -0,0 +1,103 @@
+use super::Placement;
+use crate::ship::{self, ShipData, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::{vector, UnitComplex, Vector2};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+
+// Arrangements of a group of ships around a point, so scenarios don't need to
+// list every ship's coordinates. Formations are plain data and can be
+// serialized as part of a scenario description.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FormationShape {
+    // Side by side, centered on the formation's position.
+    LineAbreast,
+    // A V with the first ship at the point and the rest alternating between
+    // the two arms, each rank `spacing` further back and out.
+    Wedge,
+    // Evenly spaced around a circle centered on the formation's position.
+    Ring,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Formation {
+    pub shape: FormationShape,
+    // Distance between neighbouring ships (in meters).
+    pub spacing: f64,
+    // Heading of each ship relative to the formation's heading.
+    #[serde(default)]
+    pub facing: f64,
+}
+
+impl Formation {
+    pub fn new(shape: FormationShape, spacing: f64) -> Self {
+        Self {
+            shape,
+            spacing,
+            facing: 0.0,
+        }
+    }
+
+    pub fn with_facing(self, facing: f64) -> Self {
+        Self { facing, ..self }
+    }
+
+    // Offsets of `n` ships from the formation's position, for a formation
+    // heading along the x axis.
+    pub fn offsets(&self, n: usize) -> Vec<Vector2<f64>> {
+        let spacing = self.spacing;
+        match self.shape {
+            FormationShape::LineAbreast => (0..n)
+                .map(|i| vector![0.0, (i as f64 - (n as f64 - 1.0) / 2.0) * spacing])
+                .collect(),
+            FormationShape::Wedge => (0..n)
+                .map(|i| {
+                    let rank = ((i + 1) / 2) as f64;
+                    let side = if i % 2 == 1 { 1.0 } else { -1.0 };
+                    vector![-rank, side * rank] * spacing
+                })
+                .collect(),
+            FormationShape::Ring => {
+                if n < 2 {
+                    return vec![vector![0.0, 0.0]; n];
+                }
+                // Radius at which neighbouring ships are `spacing` apart.
+                let radius = spacing / (2.0 * (TAU / n as f64 / 2.0).sin());
+                (0..n)
+                    .map(|i| {
+                        UnitComplex::new(TAU * i as f64 / n as f64)
+                            .transform_vector(&vector![radius, 0.0])
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    // Where each of `n` ships goes for a formation at `placement`.
+    pub fn place(&self, placement: &Placement, n: usize) -> Vec<Placement> {
+        let rotation = UnitComplex::new(placement.heading);
+        self.offsets(n)
+            .iter()
+            .map(|offset| Placement {
+                position: placement.position + rotation.transform_vector(offset),
+                heading: placement.heading + self.facing,
+            })
+            .collect()
+    }
+
+    // Creates `ships` in formation at `placement`, at rest.
+    pub fn create(
+        &self,
+        sim: &mut Simulation,
+        placement: &Placement,
+        ships: Vec<ShipData>,
+    ) -> Vec<ShipHandle> {
+        self.place(placement, ships.len())
+            .into_iter()
+            .zip(ships)
+            .map(|(Placement { position, heading }, data)| {
+                ship::create(sim, position, vector![0.0, 0.0], heading, data)
+            })
+            .collect()
+    }
+}
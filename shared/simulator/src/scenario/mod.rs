@@ -1,8 +1,11 @@
 mod asteroid_duel;
 mod belt;
+mod corvette_duel;
 mod cruiser_duel;
+mod duel;
 mod fighter_duel;
 mod fleet;
+mod formation;
 mod frigate_duel;
 mod gunnery;
 mod mini_fleet;
@@ -11,6 +14,7 @@ mod planetary_defense;
 mod primitive_duel;
 mod radar_duel;
 mod squadrons;
+mod station_defense;
 mod stress;
 mod test;
 mod tutorial_acceleration;
@@ -28,28 +32,44 @@ mod tutorial_search;
 mod tutorial_squadron;
 mod welcome;
 
+use crate::ammo::Resupply;
+use crate::collision::AsteroidTerrain;
+use crate::grader::Hint;
 use crate::ship::{asteroid, fighter, ShipAccessor, ShipClass, ShipData};
-use crate::simulation::{Code, Line, Simulation};
+use crate::simulation::{Code, Line, PhysicsProfile, Simulation};
+use crate::velocity_field::VelocityField;
+use crate::vm::CodeLimits;
 use nalgebra::{vector, Vector2};
 use rand::{seq::SliceRandom, Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use formation::{Formation, FormationShape};
+pub use oort_proto::Difficulty;
+
 pub mod prelude {
     pub use super::Scenario;
     pub use super::Status;
-    pub use super::{builtin, empty_ai, reference_ai};
+    pub use super::{builtin, empty_ai, opponent_ai, reference_ai, OpponentTier};
     pub use super::{
         check_capital_ship_tournament_victory, check_tournament_victory, check_tutorial_victory,
     };
     pub use super::{fighter_without_missiles, fighter_without_missiles_or_radar, target_asteroid};
-    pub use super::{place_teams, Placement};
+    pub use super::{place_teams, Formation, FormationShape, Placement, Tiebreak};
+    pub use super::{Difficulty, DifficultySettings};
     pub use super::{DEFAULT_TUTORIAL_MAX_TICKS, TOURNAMENT_MAX_TICKS};
+    pub use crate::ammo::Resupply;
+    pub use crate::collision::AsteroidTerrain;
+    pub use crate::grader::Hint;
+    pub use crate::hardpoint::Weapon;
     pub use crate::rng::{new_rng, SeededRng};
     pub use crate::ship::{
-        self, asteroid, cruiser, fighter, frigate, missile, target, torpedo, ShipHandle,
+        self, asteroid, corvette, cruiser, fighter, frigate, missile, station, target, torpedo,
+        turret, ShipHandle,
     };
-    pub use crate::simulation::{Code, Line, Simulation};
+    pub use crate::simulation::{Code, Line, PhysicsProfile, Simulation};
+    pub use crate::velocity_field::VelocityField;
+    pub use crate::vm::CodeLimits;
     pub use nalgebra::{point, vector, Point2, Rotation2, Vector2};
     pub use rand::Rng;
     pub use std::f64::consts::{PI, TAU};
@@ -58,16 +78,87 @@ pub mod prelude {
 pub const DEFAULT_TUTORIAL_MAX_TICKS: u32 = 30 * 60;
 pub const TOURNAMENT_MAX_TICKS: u32 = 10000;
 pub const MAX_TICKS: u32 = 10000;
+pub const DEFAULT_SPAWN_GRACE_TICKS: u32 = 60;
 
-#[derive(PartialEq, Eq, Hash, Debug, Serialize, Deserialize, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Serialize, Deserialize, Copy, Clone, Default)]
 pub enum Status {
+    #[default]
     Running,
-    Victory { team: i32 },
+    Victory {
+        team: i32,
+    },
     Failed,
     Draw,
 }
 
-pub trait Scenario {
+// How a match that reaches its time limit with several teams alive is decided.
+#[derive(PartialEq, Eq, Hash, Debug, Serialize, Deserialize, Copy, Clone, Default)]
+pub enum Tiebreak {
+    #[default]
+    Draw,
+    // The team with the most health left on its counted ships wins.
+    SurvivingHealth,
+    // The team that dealt the most damage wins.
+    DamageDealt,
+}
+
+// Knobs a scenario scales with the chosen difficulty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DifficultySettings {
+    // Multiplies the number of enemy ships.
+    pub enemy_count_factor: f64,
+    // AI flying the enemy ships.
+    pub enemy_ai: Code,
+    // Multiplies the scenario's time limit.
+    pub time_limit_factor: f64,
+}
+
+impl DifficultySettings {
+    pub fn new(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => DifficultySettings {
+                enemy_count_factor: 0.5,
+                enemy_ai: opponent_ai(OpponentTier::Gunner),
+                time_limit_factor: 1.5,
+            },
+            Difficulty::Normal => DifficultySettings {
+                enemy_count_factor: 1.0,
+                enemy_ai: reference_ai(),
+                time_limit_factor: 1.0,
+            },
+            Difficulty::Hard => DifficultySettings {
+                enemy_count_factor: 1.5,
+                enemy_ai: reference_ai(),
+                time_limit_factor: 0.75,
+            },
+        }
+    }
+
+    // Scales a count of enemy ships, keeping at least one if there were any.
+    pub fn enemy_count(&self, count: usize) -> usize {
+        if count == 0 {
+            return 0;
+        }
+        ((count as f64 * self.enemy_count_factor).round() as usize).max(1)
+    }
+
+    pub fn time_limit(&self, ticks: u32) -> u32 {
+        (ticks as f64 * self.time_limit_factor).round() as u32
+    }
+}
+
+// Lets simulation checkpoints copy a scenario along with its state.
+pub trait ScenarioClone {
+    fn clone_box(&self) -> Box<dyn Scenario>;
+}
+
+impl<T: 'static + Scenario + Clone> ScenarioClone for T {
+    fn clone_box(&self) -> Box<dyn Scenario> {
+        Box::new(self.clone())
+    }
+}
+
+pub trait Scenario: ScenarioClone {
     fn name(&self) -> String;
 
     fn human_name(&self) -> String {
@@ -120,6 +211,92 @@ pub trait Scenario {
     fn world_size(&self) -> f64 {
         40000.0
     }
+
+    // Periodically re-center the world around the fleet centroid.
+    fn floating_origin(&self) -> bool {
+        false
+    }
+
+    fn physics_profile(&self) -> PhysicsProfile {
+        PhysicsProfile::default()
+    }
+
+    // Leave drifting wrecks behind when ships are destroyed.
+    fn leave_hulks(&self) -> bool {
+        false
+    }
+
+    // Slow the reloads of guns and missile launchers as ships lose health.
+    fn damage_slows_reload(&self) -> bool {
+        false
+    }
+
+    // Give missile and torpedo seekers a Doppler notch that hides targets
+    // with little closing speed. See `Radar::doppler_notch`.
+    fn seeker_doppler_notch(&self) -> bool {
+        false
+    }
+
+    // Limits on the size and memory usage of each team's code.
+    fn code_limits(&self) -> CodeLimits {
+        CodeLimits::default()
+    }
+
+    fn velocity_field(&self) -> VelocityField {
+        VelocityField::None
+    }
+
+    // Linear drag in 1/s applied to all moving bodies. Zero is empty space;
+    // a positive value makes a nebula where ships coast to a stop.
+    fn drag(&self) -> f64 {
+        0.0
+    }
+
+    // Ticks before a tournament match is decided by `tiebreak`.
+    fn time_limit(&self) -> u32 {
+        TOURNAMENT_MAX_TICKS
+    }
+
+    fn tiebreak(&self) -> Tiebreak {
+        Tiebreak::Draw
+    }
+
+    // How ships with limited ammunition regain it, if at all.
+    fn resupply(&self) -> Option<Resupply> {
+        None
+    }
+
+    // Ticks after a reinforcement spawns before it can collide or fire, so
+    // ships spawned in close formation don't immediately crash.
+    fn spawn_grace_ticks(&self) -> u32 {
+        DEFAULT_SPAWN_GRACE_TICKS
+    }
+
+    // Whether bullets damage asteroids, bounce off harmlessly or pass through.
+    fn asteroid_terrain(&self) -> AsteroidTerrain {
+        AsteroidTerrain::Destructible
+    }
+
+    // Hints to give the player if they fail.
+    fn hints(&self) -> Vec<Hint> {
+        vec![]
+    }
+
+    // Difficulties this scenario can be played at. Most only have one.
+    fn difficulties(&self) -> Vec<Difficulty> {
+        vec![Difficulty::Normal]
+    }
+
+    // Called by `load_with_difficulty` before `init` and `initial_code`.
+    fn set_difficulty(&mut self, _difficulty: Difficulty) {}
+
+    fn difficulty(&self) -> Difficulty {
+        Difficulty::Normal
+    }
+
+    fn difficulty_settings(&self) -> DifficultySettings {
+        DifficultySettings::new(self.difficulty())
+    }
 }
 
 pub fn load_safe(name: &str) -> Option<Box<dyn Scenario>> {
@@ -146,6 +323,7 @@ pub fn load_safe(name: &str) -> Option<Box<dyn Scenario>> {
         "primitive_duel" => Some(Box::new(primitive_duel::PrimitiveDuel::new())),
         "radar_duel" => Some(Box::new(radar_duel::RadarDuel::new())),
         "fighter_duel" => Some(Box::new(fighter_duel::FighterDuel::new())),
+        "corvette_duel" => Some(Box::new(corvette_duel::CorvetteDuel::new())),
         "frigate_duel" => Some(Box::new(frigate_duel::FrigateDuel::new())),
         "cruiser_duel" => Some(Box::new(cruiser_duel::CruiserDuel::new())),
         "asteroid_duel" => Some(Box::new(asteroid_duel::AsteroidDuel::new())),
@@ -157,6 +335,7 @@ pub fn load_safe(name: &str) -> Option<Box<dyn Scenario>> {
         // Challenge
         "gunnery" => Some(Box::new(gunnery::GunneryScenario {})),
         "planetary_defense" => Some(Box::new(planetary_defense::PlanetaryDefense::new())),
+        "station_defense" => Some(Box::new(station_defense::StationDefense::new())),
         // Testing
         "test" => Some(Box::new(test::TestScenario {})),
         "basic" => Some(Box::new(test::BasicScenario {})),
@@ -165,6 +344,14 @@ pub fn load_safe(name: &str) -> Option<Box<dyn Scenario>> {
         "cruiser_vs_frigate" => Some(Box::new(test::CruiserVsFrigate::new())),
         "frigate_point_defense" => Some(Box::new(test::FrigatePointDefense {})),
         "radar_test" => Some(Box::new(test::RadarTest {})),
+        "floating_origin_test" => Some(Box::new(test::FloatingOriginTest {})),
+        "hulk_test" => Some(Box::new(test::HulkTest {})),
+        "damaged_reload_test" => Some(Box::new(test::DamagedReloadTest {})),
+        "velocity_field_test" => Some(Box::new(test::VelocityFieldTest {})),
+        "reinforcement_test" => Some(Box::new(test::ReinforcementTest {})),
+        "collision_assist_test" => Some(Box::new(test::CollisionAssistTest {})),
+        "code_limits_test" => Some(Box::new(test::CodeLimitsTest {})),
+        "cpu_test" => Some(Box::new(test::CpuTest {})),
         // Stress
         "stress" => Some(Box::new(stress::StressScenario {})),
         "asteroid-stress" => Some(Box::new(stress::AsteroidStressScenario {})),
@@ -172,7 +359,12 @@ pub fn load_safe(name: &str) -> Option<Box<dyn Scenario>> {
         "missile-stress" => Some(Box::new(stress::MissileStressScenario {})),
         // Miscellaneous
         "welcome" => Some(Box::new(welcome::Welcome::new())),
-        _ => None,
+        // Generated
+        _ => name
+            .strip_prefix("duel-")
+            .and_then(duel::Duel::parse)
+            .filter(|duel| duel.name() == name)
+            .map(|duel| Box::new(duel) as Box<dyn Scenario>),
     };
     if let Some(scenario) = scenario.as_ref() {
         assert_eq!(scenario.name(), name);
@@ -187,6 +379,37 @@ pub fn load(name: &str) -> Box<dyn Scenario> {
     }
 }
 
+// Returns None if the scenario doesn't exist or doesn't support the
+// difficulty.
+pub fn load_safe_with_difficulty(name: &str, difficulty: Difficulty) -> Option<Box<dyn Scenario>> {
+    let mut scenario = load_safe(name)?;
+    if !scenario.difficulties().contains(&difficulty) {
+        return None;
+    }
+    scenario.set_difficulty(difficulty);
+    Some(scenario)
+}
+
+pub fn load_with_difficulty(name: &str, difficulty: Difficulty) -> Box<dyn Scenario> {
+    match load_safe_with_difficulty(name, difficulty) {
+        Some(scenario) => scenario,
+        None => panic!("Unknown scenario or unsupported difficulty"),
+    }
+}
+
+// Builds a symmetric 1v1 between any two of the fighter, corvette, frigate
+// and cruiser classes, starting `separation` meters apart. `seed` varies the
+// layout independently of the simulation's seed. Panics for other classes or
+// a separation that doesn't fit in the world.
+pub fn duel(
+    class_a: ShipClass,
+    class_b: ShipClass,
+    separation: f64,
+    seed: u32,
+) -> Box<dyn Scenario> {
+    Box::new(duel::Duel::new(class_a, class_b, separation, seed))
+}
+
 pub fn list() -> Vec<(String, Vec<String>)> {
     vec![
         ("Introduction", vec!["welcome"]),
@@ -208,11 +431,15 @@ pub fn list() -> Vec<(String, Vec<String>)> {
                 "tutorial_cruiser",
             ],
         ),
-        ("Challenge", vec!["gunnery", "planetary_defense"]),
+        (
+            "Challenge",
+            vec!["gunnery", "planetary_defense", "station_defense"],
+        ),
         ("Tournament", vec!["fighter_duel", "mini_fleet"]),
         (
             "Future Tournaments",
             vec![
+                "corvette_duel",
                 "frigate_duel",
                 "cruiser_duel",
                 "asteroid_duel",
@@ -245,15 +472,56 @@ pub fn empty_ai() -> Code {
     builtin("empty")
 }
 
+// Built-in opponents of increasing skill, for scenarios that want
+// consistent opposition without depending on user code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpponentTier {
+    // Wanders around and never fires.
+    Drone,
+    // Fires straight at its target without leading it.
+    Gunner,
+    // Leads its shots, dodges and uses missiles.
+    Duelist,
+}
+
+impl OpponentTier {
+    pub const ALL: [OpponentTier; 3] = [
+        OpponentTier::Drone,
+        OpponentTier::Gunner,
+        OpponentTier::Duelist,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OpponentTier::Drone => "drone",
+            OpponentTier::Gunner => "gunner",
+            OpponentTier::Duelist => "duelist",
+        }
+    }
+}
+
+pub fn opponent_ai(tier: OpponentTier) -> Code {
+    builtin(&format!("opponent/{}", tier.name()))
+}
+
 pub fn check_victory_with_filter(
     sim: &Simulation,
     max_ticks: u32,
     ship_filter: fn(&ShipAccessor) -> bool,
+) -> Status {
+    check_victory_with_tiebreak(sim, max_ticks, Tiebreak::Draw, ship_filter)
+}
+
+pub fn check_victory_with_tiebreak(
+    sim: &Simulation,
+    max_ticks: u32,
+    tiebreak: Tiebreak,
+    ship_filter: fn(&ShipAccessor) -> bool,
 ) -> Status {
     let mut team_health: HashMap<i32, u32> = HashMap::new();
     for &handle in sim.ships.iter() {
         let ship = sim.ship(handle);
-        if ship_filter(&ship) {
+        if !ship.data().hulk && ship_filter(&ship) {
             *team_health.entry(ship.data().team).or_insert(0) += ship.data().health as u32;
         }
     }
@@ -264,12 +532,33 @@ pub fn check_victory_with_filter(
             team: *team_health.iter().next().unwrap().0,
         }
     } else if sim.tick() >= max_ticks - 1 {
-        Status::Draw
+        sim.record_tiebreak(tiebreak);
+        apply_tiebreak(sim, tiebreak, &team_health)
     } else {
         Status::Running
     }
 }
 
+fn apply_tiebreak(sim: &Simulation, tiebreak: Tiebreak, team_health: &HashMap<i32, u32>) -> Status {
+    let mut scores: Vec<(i32, f64)> = match tiebreak {
+        Tiebreak::Draw => return Status::Draw,
+        Tiebreak::SurvivingHealth => team_health
+            .iter()
+            .map(|(&team, &health)| (team, health as f64))
+            .collect(),
+        Tiebreak::DamageDealt => team_health
+            .keys()
+            .map(|&team| (team, sim.damage_dealt(team)))
+            .collect(),
+    };
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    if scores[0].1 > scores[1].1 {
+        Status::Victory { team: scores[0].0 }
+    } else {
+        Status::Draw
+    }
+}
+
 pub fn check_tutorial_victory(sim: &Simulation, max_ticks: u32) -> Status {
     match check_victory_with_filter(sim, max_ticks, |ship| {
         ![ShipClass::Missile, ShipClass::Torpedo].contains(&ship.data().class)
@@ -281,14 +570,20 @@ pub fn check_tutorial_victory(sim: &Simulation, max_ticks: u32) -> Status {
 }
 
 pub fn check_tournament_victory(sim: &Simulation) -> Status {
-    check_victory_with_filter(sim, TOURNAMENT_MAX_TICKS, |ship| {
-        [ShipClass::Fighter, ShipClass::Frigate, ShipClass::Cruiser].contains(&ship.data().class)
+    check_victory_with_tiebreak(sim, sim.time_limit(), sim.tiebreak(), |ship| {
+        [
+            ShipClass::Fighter,
+            ShipClass::Corvette,
+            ShipClass::Frigate,
+            ShipClass::Cruiser,
+        ]
+        .contains(&ship.data().class)
             && ship.data().team < 2
     })
 }
 
 pub fn check_capital_ship_tournament_victory(sim: &Simulation) -> Status {
-    check_victory_with_filter(sim, TOURNAMENT_MAX_TICKS, |ship| {
+    check_victory_with_tiebreak(sim, sim.time_limit(), sim.tiebreak(), |ship| {
         [ShipClass::Frigate, ShipClass::Cruiser].contains(&ship.data().class)
             && ship.data().team < 2
     })
@@ -313,6 +608,7 @@ pub fn target_asteroid(variant: i32) -> ShipData {
     asteroid
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
 pub struct Placement {
     pub position: Vector2<f64>,
     pub heading: f64,
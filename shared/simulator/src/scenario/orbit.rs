@@ -7,6 +7,7 @@ use crate::simulation::PHYSICS_TICK_LENGTH;
 const PLANET_MASS: f64 = 1.5e19;
 const G: f64 = 6.674e-11;
 
+#[derive(Clone)]
 pub struct Orbit {}
 
 impl Orbit {
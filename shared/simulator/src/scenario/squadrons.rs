@@ -1,6 +1,6 @@
 use super::prelude::*;
-use nalgebra::UnitComplex;
 
+#[derive(Clone)]
 pub struct Squadrons {}
 
 impl Squadrons {
@@ -21,23 +21,10 @@ impl Scenario for Squadrons {
     fn init(&mut self, sim: &mut Simulation, seed: u32) {
         let mut rng = new_rng(seed);
         let placements = place_teams(&mut rng, self.world_size());
-        let offsets = [
-            vector![0.0, 0.0],
-            vector![-100.0, 100.0],
-            vector![-100.0, -100.0],
-        ];
+        let formation = Formation::new(FormationShape::Wedge, 100.0);
 
         for (team, placement) in placements.into_iter().enumerate() {
-            let Placement { position, heading } = placement;
-            for offset in &offsets {
-                ship::create(
-                    sim,
-                    position + UnitComplex::new(heading).transform_vector(offset),
-                    vector![0.0, 0.0],
-                    heading,
-                    fighter(team as i32),
-                );
-            }
+            formation.create(sim, &placement, vec![fighter(team as i32); 3]);
         }
     }
 
@@ -0,0 +1,140 @@
+use super::prelude::*;
+use crate::ship::ShipClass;
+
+#[derive(Clone)]
+pub struct StationDefense {
+    rng: SeededRng,
+    next_wave: usize,
+    difficulty: Difficulty,
+}
+
+impl StationDefense {
+    // Spawn time in seconds, number of fighters, and number of frigates.
+    const WAVES: &'static [(f64, usize, usize)] = &[(0.0, 4, 0), (30.0, 6, 0), (60.0, 4, 2)];
+
+    pub fn new() -> Self {
+        Self {
+            rng: new_rng(0),
+            next_wave: 0,
+            difficulty: Difficulty::Normal,
+        }
+    }
+
+    fn spawn_wave(&mut self, sim: &mut Simulation, fighters: usize, frigates: usize) {
+        let center = vector![15000.0, self.rng.gen_range(-5000.0..5000.0)];
+        let heading = PI;
+        for i in 0..fighters {
+            ship::create(
+                sim,
+                center + vector![0.0, (i as f64 - fighters as f64 / 2.0) * 200.0],
+                vector![0.0, 0.0],
+                heading,
+                fighter(1),
+            );
+        }
+        for i in 0..frigates {
+            ship::create(
+                sim,
+                center + vector![1000.0, (i as f64 - frigates as f64 / 2.0) * 500.0],
+                vector![0.0, 0.0],
+                heading,
+                frigate(1),
+            );
+        }
+    }
+}
+
+impl Scenario for StationDefense {
+    fn name(&self) -> String {
+        "station_defense".into()
+    }
+
+    fn human_name(&self) -> String {
+        "Station Defense".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, seed: u32) {
+        self.rng = new_rng(seed);
+        self.next_wave = 0;
+
+        let team = 0;
+        ship::create(
+            sim,
+            vector![0.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            station(team),
+        );
+        for (x, y) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+            ship::create(
+                sim,
+                vector![x * 400.0, y * 400.0],
+                vector![0.0, 0.0],
+                0.0,
+                turret(team),
+            );
+        }
+        for i in 0..4 {
+            ship::create(
+                sim,
+                vector![-1000.0, (i as f64 - 1.5) * 200.0],
+                vector![0.0, 0.0],
+                0.0,
+                fighter(team),
+            );
+        }
+    }
+
+    fn tick(&mut self, sim: &mut Simulation) {
+        while let Some(&(time, fighters, frigates)) = Self::WAVES.get(self.next_wave) {
+            if sim.time() < time {
+                break;
+            }
+            let settings = self.difficulty_settings();
+            self.spawn_wave(
+                sim,
+                settings.enemy_count(fighters),
+                settings.enemy_count(frigates),
+            );
+            self.next_wave += 1;
+        }
+    }
+
+    fn status(&self, sim: &Simulation) -> Status {
+        let station_alive = sim
+            .ships
+            .iter()
+            .any(|&handle| sim.ship(handle).data().class == ShipClass::Station);
+        let enemy_alive = sim
+            .ships
+            .iter()
+            .any(|&handle| sim.ship(handle).data().team == 1);
+        if !station_alive {
+            Status::Victory { team: 1 }
+        } else if self.next_wave >= Self::WAVES.len() && !enemy_alive {
+            Status::Victory { team: 0 }
+        } else {
+            Status::Running
+        }
+    }
+
+    fn initial_code(&self) -> Vec<Code> {
+        vec![empty_ai(), self.difficulty_settings().enemy_ai]
+    }
+
+    fn solution(&self) -> Code {
+        reference_ai()
+    }
+
+    fn difficulties(&self) -> Vec<Difficulty> {
+        vec![Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+    }
+
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+}
@@ -1,6 +1,7 @@
 use super::prelude::*;
 use crate::{bullet, simulation};
 
+#[derive(Clone)]
 pub struct TestScenario {}
 
 impl Scenario for TestScenario {
@@ -15,6 +16,7 @@ impl Scenario for TestScenario {
     }
 }
 
+#[derive(Clone)]
 pub struct BasicScenario {}
 
 impl Scenario for BasicScenario {
@@ -44,6 +46,7 @@ impl Scenario for BasicScenario {
     }
 }
 
+#[derive(Clone)]
 pub struct MissileTest {
     target: Option<ShipHandle>,
     rng: SeededRng,
@@ -145,6 +148,7 @@ impl Scenario for MissileTest {
     }
 }
 
+#[derive(Clone)]
 pub struct FrigateVsCruiser {}
 
 impl FrigateVsCruiser {
@@ -188,6 +192,7 @@ impl Scenario for FrigateVsCruiser {
     }
 }
 
+#[derive(Clone)]
 pub struct CruiserVsFrigate {}
 
 impl CruiserVsFrigate {
@@ -231,6 +236,7 @@ impl Scenario for CruiserVsFrigate {
     }
 }
 
+#[derive(Clone)]
 pub struct FrigatePointDefense {}
 
 impl Scenario for FrigatePointDefense {
@@ -265,6 +271,7 @@ impl Scenario for FrigatePointDefense {
     }
 }
 
+#[derive(Clone)]
 pub struct RadarTest {}
 
 impl Scenario for RadarTest {
@@ -314,3 +321,190 @@ impl Scenario for RadarTest {
         }
     }
 }
+
+#[derive(Clone)]
+pub struct FloatingOriginTest {}
+
+impl Scenario for FloatingOriginTest {
+    fn name(&self) -> String {
+        "floating_origin_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        let mut data = fighter(0);
+        data.fuel = None;
+        ship::create(sim, vector![-50e3, 0.0], vector![1000.0, 0.0], 0.0, data);
+    }
+
+    fn world_size(&self) -> f64 {
+        simulation::MAX_WORLD_SIZE
+    }
+
+    fn floating_origin(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct HulkTest {}
+
+impl Scenario for HulkTest {
+    fn name(&self) -> String {
+        "hulk_test".into()
+    }
+
+    fn init(&mut self, _sim: &mut Simulation, _seed: u32) {}
+
+    fn leave_hulks(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct DamagedReloadTest {}
+
+impl Scenario for DamagedReloadTest {
+    fn name(&self) -> String {
+        "damaged_reload_test".into()
+    }
+
+    fn init(&mut self, _sim: &mut Simulation, _seed: u32) {}
+
+    fn damage_slows_reload(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct VelocityFieldTest {}
+
+impl Scenario for VelocityFieldTest {
+    fn name(&self) -> String {
+        "velocity_field_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        let position = vector![1000.0, 0.0];
+        ship::create(
+            sim,
+            position,
+            self.velocity_field().velocity(position),
+            0.0,
+            asteroid(0),
+        );
+        ship::create(
+            sim,
+            vector![-1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            fighter(0),
+        );
+    }
+
+    fn velocity_field(&self) -> VelocityField {
+        VelocityField::Keplerian {
+            center: vector![0.0, 0.0],
+            mu: 1e6,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReinforcementTest {}
+
+impl Scenario for ReinforcementTest {
+    fn name(&self) -> String {
+        "reinforcement_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        ship::create(sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, fighter(0));
+        sim.add_spawn_point(0, vector![-1000.0, 0.0], 0.0);
+        sim.add_spawn_point(0, vector![1000.0, 0.0], std::f64::consts::PI);
+    }
+
+    fn tick(&mut self, sim: &mut Simulation) {
+        sim.credit_team(0, 1.0);
+    }
+}
+
+#[derive(Clone)]
+pub struct CollisionAssistTest {}
+
+impl Scenario for CollisionAssistTest {
+    fn name(&self) -> String {
+        "collision_assist_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        ship::create(
+            sim,
+            vector![-500.0, 0.0],
+            vector![100.0, 0.0],
+            0.0,
+            fighter(0),
+        );
+        ship::create(
+            sim,
+            vector![500.0, 0.0],
+            vector![-100.0, 0.0],
+            std::f64::consts::PI,
+            fighter(0),
+        );
+        ship::create(
+            sim,
+            vector![0.0, 2000.0],
+            vector![0.0, 0.0],
+            0.0,
+            asteroid(0),
+        );
+        ship::create(
+            sim,
+            vector![0.0, 1000.0],
+            vector![0.0, 100.0],
+            std::f64::consts::FRAC_PI_2,
+            fighter(0),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct CodeLimitsTest {}
+
+impl Scenario for CodeLimitsTest {
+    fn name(&self) -> String {
+        "code_limits_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        ship::create(sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, fighter(0));
+    }
+
+    fn code_limits(&self) -> CodeLimits {
+        CodeLimits {
+            max_code_size: 32 << 10,
+            max_memory: 32 << 10,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CpuTest {}
+
+impl Scenario for CpuTest {
+    fn name(&self) -> String {
+        "cpu_test".into()
+    }
+
+    fn init(&mut self, sim: &mut Simulation, _seed: u32) {
+        ship::create(sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, fighter(0));
+    }
+
+    fn code_limits(&self) -> CodeLimits {
+        CodeLimits {
+            soft_instructions_per_tick: Some(1),
+            ..Default::default()
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialAcceleration {
     hit_target: bool,
 }
@@ -80,6 +81,10 @@ impl Scenario for TutorialAcceleration {
         builtin("tutorial/tutorial_acceleration_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![Hint::NeverAccelerated]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_acceleration2".to_string())
     }
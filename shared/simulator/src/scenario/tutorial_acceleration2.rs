@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialAcceleration2 {
     hit_target: bool,
     target: Option<Point2<f64>>,
@@ -88,6 +89,10 @@ impl Scenario for TutorialAcceleration2 {
         builtin("tutorial/tutorial_acceleration2_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![Hint::NeverAccelerated]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_rotation".to_string())
     }
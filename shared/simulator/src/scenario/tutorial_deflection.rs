@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialDeflection {
     ship_handle: Option<ShipHandle>,
     target_handle: Option<ShipHandle>,
@@ -79,6 +80,10 @@ impl Scenario for TutorialDeflection {
         builtin("tutorial/tutorial_deflection_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![Hint::NeverRotated, Hint::NeverFired]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_radar".to_string())
     }
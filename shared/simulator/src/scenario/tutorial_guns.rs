@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialGuns {}
 
 impl Scenario for TutorialGuns {
@@ -40,6 +41,10 @@ impl Scenario for TutorialGuns {
         builtin("tutorial/tutorial_guns_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![Hint::NeverFired, Hint::FiredBeforeAiming]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_acceleration".to_string())
     }
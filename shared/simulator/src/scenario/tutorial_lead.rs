@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialLead {
     ship_handle: Option<ShipHandle>,
     target_handle: Option<ShipHandle>,
@@ -80,6 +81,10 @@ impl Scenario for TutorialLead {
         builtin("tutorial/tutorial_lead_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![Hint::NeverRotated, Hint::NeverFired]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_deflection".to_string())
     }
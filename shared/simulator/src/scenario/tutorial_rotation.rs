@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+#[derive(Clone)]
 pub struct TutorialRotation {}
 
 impl TutorialRotation {
@@ -51,6 +52,14 @@ impl Scenario for TutorialRotation {
         builtin("tutorial/tutorial_rotation_solution")
     }
 
+    fn hints(&self) -> Vec<Hint> {
+        vec![
+            Hint::NeverRotated,
+            Hint::NeverFired,
+            Hint::FiredBeforeAiming,
+        ]
+    }
+
     fn next_scenario(&self) -> Option<String> {
         Some("tutorial_lead".to_string())
     }
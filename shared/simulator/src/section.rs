@@ -0,0 +1,142 @@
+//! Sections of compound ships.
+//!
+//! A capital ship can be built from several rigid bodies: its hull plus
+//! sections welded on with fixed joints. Each section is a ship of its own,
+//! with its own collider and health, so hits that land on it wear it down
+//! rather than the hull. Sections have no AI and follow the parent wherever
+//! its thrusters push the assembly, which makes the whole ship heavier and
+//! slower to maneuver.
+//!
+//! When a section's health is depleted its joint breaks and it's destroyed,
+//! taking the parent's missile launchers it carried with it. The parent
+//! fights on without them, and is a little lighter for it. Sections are
+//! destroyed along with their parent.
+
+use crate::index_set::HasIndex;
+use crate::ship::{self, ShipData, ShipHandle};
+use crate::simulation::Simulation;
+use nalgebra::{vector, Vector2};
+use rapier2d_f64::prelude::*;
+
+// Offset of each missile battery from the center of a cruiser, and the
+// launcher it carries.
+const CRUISER_LAUNCHER_PODS: &[(Vector2<f64>, usize)] =
+    &[(vector![0.0, 42.0], 0), (vector![0.0, -42.0], 1)];
+
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub parent: ShipHandle,
+    pub joint: ImpulseJointHandle,
+    // Indices of the parent's missile launchers lost with this section.
+    pub missile_launchers: Vec<usize>,
+}
+
+// Creates a section at `offset` from the parent's center, in the parent's
+// frame, and welds it on.
+pub fn attach(
+    sim: &mut Simulation,
+    parent: ShipHandle,
+    offset: Vector2<f64>,
+    mut data: ShipData,
+    missile_launchers: Vec<usize>,
+) -> ShipHandle {
+    let (position, velocity, heading) = {
+        let body = sim.ship(parent).body();
+        let position = body.position() * Point::from(offset);
+        (
+            position.coords,
+            body.velocity_at_point(&position),
+            body.rotation().angle(),
+        )
+    };
+    data.team = sim.ship(parent).data().team;
+    let handle = ship::create(sim, position, velocity, heading, data);
+    let joint = FixedJointBuilder::new()
+        .local_anchor1(Point::from(offset))
+        .local_anchor2(point![0.0, 0.0])
+        .contacts_enabled(false);
+    let joint = sim.impulse_joints.insert(
+        RigidBodyHandle(parent.index()),
+        RigidBodyHandle(handle.index()),
+        joint,
+        true,
+    );
+    sim.ship_mut(handle).data_mut().section = Some(Section {
+        parent,
+        joint,
+        missile_launchers,
+    });
+    handle
+}
+
+// Moves a cruiser's side missile launchers into pods that can be shot off.
+pub fn attach_launcher_pods(sim: &mut Simulation, cruiser: ShipHandle) -> Vec<ShipHandle> {
+    let team = sim.ship(cruiser).data().team;
+    CRUISER_LAUNCHER_PODS
+        .iter()
+        .map(|&(offset, launcher)| {
+            attach(
+                sim,
+                cruiser,
+                offset,
+                ship::launcher_pod(team),
+                vec![launcher],
+            )
+        })
+        .collect()
+}
+
+// Returns the sections attached to `parent`.
+pub fn sections(sim: &Simulation, parent: ShipHandle) -> Vec<ShipHandle> {
+    sim.ships
+        .iter()
+        .filter(|&&handle| {
+            sim.ship(handle)
+                .data()
+                .section
+                .as_ref()
+                .map(|section| section.parent == parent)
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+// Runs before ships tick, so sections destroyed this tick are removed along
+// with everything else.
+pub fn tick(sim: &mut Simulation) {
+    let sections: Vec<(ShipHandle, Section)> = sim
+        .ships
+        .iter()
+        .filter_map(|&handle| {
+            sim.ship(handle)
+                .data()
+                .section
+                .clone()
+                .map(|section| (handle, section))
+        })
+        .collect();
+    for (handle, section) in sections {
+        let parent_alive =
+            sim.ships.contains(section.parent) && !sim.ship(section.parent).data().destroyed;
+        if !parent_alive {
+            sim.ship_mut(handle).data_mut().destroyed = true;
+        }
+        if sim.ship(handle).data().destroyed {
+            break_off(sim, handle, &section);
+        }
+    }
+}
+
+fn break_off(sim: &mut Simulation, handle: ShipHandle, section: &Section) {
+    sim.impulse_joints.remove(section.joint, true);
+    if sim.ships.contains(section.parent) {
+        let mut parent = sim.ship_mut(section.parent);
+        for &index in section.missile_launchers.iter() {
+            if let Some(launcher) = parent.data_mut().missile_launchers.get_mut(index) {
+                launcher.lost = true;
+            }
+        }
+    }
+    sim.ship_mut(handle).data_mut().section = None;
+}
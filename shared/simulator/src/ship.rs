@@ -1,21 +1,39 @@
 use super::index_set::{HasIndex, Index};
 use super::rng::new_rng;
+use crate::api_log::ApiCallLog;
 use crate::color;
+use crate::imu::Imu;
 use crate::model;
+use crate::orbit::Orbit;
 use crate::radar::Radar;
 use crate::radio::Radio;
 use crate::rng;
+use crate::section::Section;
 use crate::simulation::{self, PHYSICS_TICK_LENGTH};
 use crate::simulation::{Particle, Simulation};
+use crate::snapshot::Effect;
+use crate::stats::{RecordStats, ShotSource};
+use crate::waypoint::WaypointPilot;
 use crate::{bullet, collision};
-use bullet::BulletData;
+use bullet::{BulletData, DamageFalloff};
 use nalgebra::{vector, Rotation2, UnitComplex, Vector2};
+use oort_api::constants;
 use oort_api::Ability;
 use rand::Rng;
 use rapier2d_f64::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::TAU;
 
+// Instructions a ship's AI may execute each tick. Missiles and torpedoes get
+// much less than ships, so heavy computation can't be spread across a cloud
+// of cheap missiles.
+pub const DEFAULT_INSTRUCTIONS_PER_TICK: u32 = 1_000_000;
+pub const MISSILE_INSTRUCTIONS_PER_TICK: u32 = 50_000;
+pub const TORPEDO_INSTRUCTIONS_PER_TICK: u32 = 100_000;
+
+// Team of asteroids, hulks and anything else that doesn't belong to a player.
+pub const NEUTRAL_TEAM: i32 = 9;
+
 #[derive(Hash, PartialEq, Eq, Copy, Clone, Debug, Ord, PartialOrd)]
 pub struct ShipHandle(pub Index);
 
@@ -42,6 +60,10 @@ pub enum ShipClass {
     Missile,
     Torpedo,
     Planet,
+    Station,
+    Turret,
+    Corvette,
+    Pod,
 }
 
 impl ShipClass {
@@ -55,8 +77,59 @@ impl ShipClass {
             ShipClass::Missile => "missile",
             ShipClass::Torpedo => "torpedo",
             ShipClass::Planet => "planet",
+            ShipClass::Station => "station",
+            ShipClass::Turret => "turret",
+            ShipClass::Corvette => "corvette",
+            ShipClass::Pod => "pod",
         }
     }
+
+    // Structures are attackable like ships but cannot move.
+    pub fn is_structure(&self) -> bool {
+        matches!(self, ShipClass::Station | ShipClass::Turret)
+    }
+
+    // Mass per unit of collider area, in kg/m². Tuned so that the current
+    // models keep roughly the masses they had before mass was derived from
+    // area.
+    pub fn density(&self) -> f64 {
+        match self {
+            ShipClass::Fighter => 125.0,
+            ShipClass::Corvette => 235.0,
+            ShipClass::Frigate => 920.0,
+            ShipClass::Cruiser => 455.0,
+            ShipClass::Asteroid { .. } => 8800.0,
+            ShipClass::Target => 0.03,
+            ShipClass::Missile => 14.0,
+            ShipClass::Torpedo => 11.0,
+            ShipClass::Planet => 0.064,
+            ShipClass::Station => 785.0,
+            ShipClass::Turret => 625.0,
+            ShipClass::Pod => 455.0,
+        }
+    }
+}
+
+// Mass of a ship of `class`, from the area of its model and the class density.
+pub fn hull_mass(class: ShipClass) -> f64 {
+    model::area(class) * class.density()
+}
+
+// What kind of round a gun fires, so the renderer and audio can tell them
+// apart without guessing from colors or masses.
+#[derive(Serialize, Deserialize, Default, Hash, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum WeaponClass {
+    // Light rapid-fire guns like the fighter's vulcan.
+    #[default]
+    Autocannon,
+    // Turreted rapid-fire guns defending larger ships.
+    PointDefense,
+    // Slow-firing, high velocity main guns.
+    HeavySlug,
+    // Short-lived bursts fired in flak mode.
+    Flak,
+    // Released by an exploding warhead.
+    Fragment,
 }
 
 #[derive(Debug, Clone)]
@@ -70,12 +143,57 @@ pub struct Gun {
     pub speed_error: f64,
     pub offset: Vector2<f64>,
     pub heading: f64,
+    // Firing arc relative to the ship's heading, counter-clockwise from
+    // `min_angle` to `max_angle`. Equal for fixed guns. `min_angle` may be
+    // negative for arcs that cross the bow.
     pub min_angle: f64,
     pub max_angle: f64,
     pub inaccuracy: f64,
     pub burst_size: i32,
     pub ttl: f32,
     pub bullet_mass: f64,
+    // Heat ranges from 0 to 1. The gun is locked out once it reaches 1.
+    pub heat: f64,
+    pub heat_per_shot: f64,
+    pub cooling_per_tick: f64,
+    pub overheated: bool,
+    // Can be aimed and fired automatically in flak mode.
+    pub flak: bool,
+    pub damage_falloff: Option<DamageFalloff>,
+    // Rounds the gun can carry, or None for unlimited ammunition.
+    pub ammo_capacity: Option<u32>,
+    // Rounds left. Only meaningful with an `ammo_capacity`.
+    pub ammo: u32,
+    pub weapon_class: WeaponClass,
+}
+
+impl Gun {
+    // Rounds left, or None if ammunition is unlimited.
+    pub fn ammo_remaining(&self) -> Option<u32> {
+        self.ammo_capacity.map(|_| self.ammo)
+    }
+
+    pub fn has_ammo(&self) -> bool {
+        self.ammo_remaining() != Some(0)
+    }
+
+    // Returns true if the gun can fire along `relative_heading`.
+    pub fn in_arc(&self, relative_heading: f64) -> bool {
+        self.min_angle + (relative_heading - self.min_angle).rem_euclid(TAU) <= self.max_angle
+    }
+
+    // Moves `relative_heading` into the firing arc, stopping at whichever
+    // edge is closer if it's outside.
+    pub fn clamp_to_arc(&self, relative_heading: f64) -> f64 {
+        let angle = self.min_angle + (relative_heading - self.min_angle).rem_euclid(TAU);
+        if angle <= self.max_angle {
+            angle
+        } else if angle - self.max_angle <= self.min_angle + TAU - angle {
+            self.max_angle
+        } else {
+            self.min_angle
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +204,54 @@ pub struct MissileLauncher {
     pub initial_speed: f64,
     pub offset: Vector2<f64>,
     pub angle: f64,
+    // Ticks after launch before the missile's motor ignites and it responds
+    // to commands.
+    pub ignition_ticks: u32,
+    // Thrust profile given to launched missiles, or None for a motor that
+    // burns at full thrust until out of fuel.
+    pub burn_profile: Option<BurnProfile>,
+    // Set when the section carrying the launcher breaks off. A lost launcher
+    // can't fire again.
+    pub lost: bool,
+}
+
+// Forward thrust limits of a rocket motor over time since ignition: a short
+// high-thrust boost, a lower sustain and then a coast with no forward thrust.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurnProfile {
+    pub boost_ticks: u32,
+    pub boost_acceleration: f64,
+    pub sustain_ticks: u32,
+    pub sustain_acceleration: f64,
+}
+
+impl BurnProfile {
+    // Maximum forward acceleration after the motor has burned for `ticks`.
+    pub fn max_forward_acceleration(&self, ticks: u32) -> f64 {
+        if ticks < self.boost_ticks {
+            self.boost_acceleration
+        } else if ticks < self.boost_ticks + self.sustain_ticks {
+            self.sustain_acceleration
+        } else {
+            0.0
+        }
+    }
+
+    // Ticks of burning before the motor starts coasting.
+    pub fn burnout_ticks(&self) -> u32 {
+        self.boost_ticks + self.sustain_ticks
+    }
+}
+
+// Boosts at the missile's full acceleration for a second, then sustains at a
+// third of it for four seconds.
+pub fn missile_burn_profile() -> BurnProfile {
+    BurnProfile {
+        boost_ticks: 60,
+        boost_acceleration: 300.0,
+        sustain_ticks: 240,
+        sustain_acceleration: 100.0,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -104,6 +270,50 @@ pub struct Warhead {
     pub width: f64,
     pub speed: f64,
     pub ttl: f32,
+    // Fraction of `count` released as a secondary blast when the ship is
+    // destroyed by another explosion's fragments.
+    pub chain_fraction: f64,
+}
+
+impl Warhead {
+    // Reach of the fastest fragments.
+    pub fn radius(&self) -> f64 {
+        self.speed * 2.0 * self.ttl as f64
+    }
+
+    pub fn chain_count(&self) -> i32 {
+        (self.count as f64 * self.chain_fraction).round() as i32
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Armor {
+    // Damage absorbed from each hit.
+    pub flat: f64,
+    // Fraction of the remaining damage absorbed.
+    pub fraction: f64,
+}
+
+impl Armor {
+    pub fn apply(&self, damage: f64) -> f64 {
+        ((damage - self.flat) * (1.0 - self.fraction)).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Transponder {
+    pub enabled: bool,
+    // IFF code to broadcast instead of the ship's team.
+    pub code: Option<i32>,
+}
+
+impl Default for Transponder {
+    fn default() -> Transponder {
+        Transponder {
+            enabled: true,
+            code: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -111,6 +321,9 @@ pub struct ShipData {
     pub class: ShipClass,
     pub team: i32,
     pub health: f64,
+    // Health when the ship was created.
+    pub max_health: f64,
+    pub armor: Armor,
     pub mass: f64,
     pub acceleration: Vector2<f64>,
     pub last_acceleration: Vector2<f64>,
@@ -120,17 +333,69 @@ pub struct ShipData {
     pub max_lateral_acceleration: f64,
     pub max_angular_acceleration: f64,
     pub destroyed: bool,
+    pub hulk: bool,
     pub crash_message: Option<String>,
     pub ttl: Option<u64>,
+    // Lifetime in ticks given to missiles and torpedoes this ship launches,
+    // or None for the class default.
+    pub launch_ttl: Option<u64>,
     pub fuel: Option<f64>,
     pub guns: Vec<Gun>,
     pub missile_launchers: Vec<MissileLauncher>,
     pub radar: Option<Radar>,
     pub radar_cross_section: f64,
+    pub imu: Imu,
+    pub transponder: Transponder,
+    pub collision_assist: bool,
+    // Cancel any spin on ticks the AI doesn't command a torque.
+    pub rotation_stabilizer: bool,
+    pub orbit: Option<Orbit>,
+    pub waypoint_pilot: Option<WaypointPilot>,
+    pub flak_mode: bool,
+    // Steer towards a jammer that blinds the radar. Needs a radar with
+    // `home_on_jam`.
+    pub home_on_jam: bool,
+    // Ticks until a newly launched missile's motor ignites.
+    pub ignition_ticks_remaining: u32,
+    // Limits `max_forward_acceleration` over time, and ticks the motor has
+    // burned so far.
+    pub burn_profile: Option<BurnProfile>,
+    pub burn_ticks: u32,
+    // Ticks until a newly spawned ship can collide and fire.
+    pub spawn_grace_ticks_remaining: u32,
+    // Destroyed by an explosion's fragments, so its own warhead cooks off.
+    pub chain_reaction: bool,
+    // Events since the controller last ran.
+    pub damage_taken: f64,
+    pub launched_weapons: u64,
+    pub reloaded_weapons: u64,
+    // Recent actions requested by the ship's AI.
+    pub api_calls: ApiCallLog,
+    // Index of this ship among the ships created for its team, used to key
+    // sensor and weapon noise.
+    pub noise_id: u64,
     pub radios: Vec<Radio>,
     pub abilities: Vec<ShipAbility>,
     pub target: Option<Box<Target>>,
     pub warhead: Warhead,
+    pub instructions_per_tick: u32,
+    // Set while the AI's commands are applied if it designated a target for
+    // weapons launched this tick.
+    pub launch_designation: Option<Designation>,
+    // Target designated by the launching ship, reported as the first radar
+    // contact of a new missile or torpedo.
+    pub designated_contact: Option<Designation>,
+    // Set on sections of compound ships. See the `section` module.
+    pub section: Option<Section>,
+}
+
+// Target designated by a ship for the missiles it launches, in simulation
+// coordinates.
+#[derive(Debug, Clone)]
+pub struct Designation {
+    pub class: oort_api::Class,
+    pub position: Vector2<f64>,
+    pub velocity: Vector2<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -145,6 +410,8 @@ impl Default for ShipData {
             class: ShipClass::Fighter,
             team: 0,
             health: 100.0,
+            max_health: 0.0,
+            armor: Default::default(),
             mass: 1000.0,
             acceleration: vector![0.0, 0.0],
             last_acceleration: vector![0.0, 0.0],
@@ -154,17 +421,41 @@ impl Default for ShipData {
             max_lateral_acceleration: 0.0,
             max_angular_acceleration: 0.0,
             destroyed: false,
+            hulk: false,
             crash_message: None,
             ttl: None,
+            launch_ttl: None,
             fuel: None,
             guns: vec![],
             missile_launchers: vec![],
             radar: None,
             radar_cross_section: 10.0,
+            imu: Default::default(),
+            transponder: Default::default(),
+            collision_assist: false,
+            rotation_stabilizer: false,
+            orbit: None,
+            waypoint_pilot: None,
+            flak_mode: false,
+            home_on_jam: false,
+            ignition_ticks_remaining: 0,
+            burn_profile: None,
+            burn_ticks: 0,
+            spawn_grace_ticks_remaining: 0,
+            chain_reaction: false,
+            damage_taken: 0.0,
+            launched_weapons: 0,
+            reloaded_weapons: 0,
+            api_calls: Default::default(),
+            noise_id: 0,
             radios: vec![],
             abilities: vec![],
             target: None,
             warhead: Default::default(),
+            instructions_per_tick: DEFAULT_INSTRUCTIONS_PER_TICK,
+            launch_designation: None,
+            designated_contact: None,
+            section: None,
         }
     }
 }
@@ -187,6 +478,15 @@ impl Default for Gun {
             burst_size: 1,
             ttl: 10.0,
             bullet_mass: 1.0,
+            heat: 0.0,
+            heat_per_shot: 0.0,
+            cooling_per_tick: 0.0,
+            overheated: false,
+            flak: false,
+            damage_falloff: None,
+            ammo_capacity: None,
+            ammo: 0,
+            weapon_class: WeaponClass::Autocannon,
         }
     }
 }
@@ -211,6 +511,7 @@ impl Default for Warhead {
             width: TAU,
             speed: 1e3,
             ttl: (PHYSICS_TICK_LENGTH * 5.0) as f32,
+            chain_fraction: 0.25,
         }
     }
 }
@@ -218,9 +519,9 @@ impl Default for Warhead {
 fn radio() -> Radio {
     // TODO tune this
     Radio {
-        power: 20e3,
-        rx_cross_section: 5.0,
-        min_rssi: 1e-5,
+        power: constants::RADIO_POWER,
+        rx_cross_section: constants::RADIO_RX_CROSS_SECTION,
+        min_rssi: constants::RADIO_MIN_RSSI,
         channel: 0,
         sent: None,
         received: None,
@@ -230,11 +531,14 @@ fn radio() -> Radio {
 pub fn vulcan_gun() -> Gun {
     Gun {
         magazine_size: 30,
-        reload_ticks: 4,
+        reload_ticks: constants::VULCAN.reload_ticks,
         magazine_reload_ticks: 60,
-        speed: 1000.0,
+        speed: constants::VULCAN.speed,
+        ttl: constants::VULCAN.bullet_ttl,
         inaccuracy: 0.0025,
         bullet_mass: 0.1,
+        heat_per_shot: 0.025,
+        cooling_per_tick: 0.2 / 60.0,
         ..Default::default()
     }
 }
@@ -243,124 +547,206 @@ pub fn fighter(team: i32) -> ShipData {
     ShipData {
         class: ShipClass::Fighter,
         team,
-        health: 100.0,
-        mass: 15000.0,
-        max_forward_acceleration: 60.0,
-        max_backward_acceleration: 30.0,
-        max_lateral_acceleration: 30.0,
-        max_angular_acceleration: TAU,
+        health: constants::FIGHTER.health,
+        mass: hull_mass(ShipClass::Fighter),
+        max_forward_acceleration: constants::FIGHTER.max_forward_acceleration,
+        max_backward_acceleration: constants::FIGHTER.max_backward_acceleration,
+        max_lateral_acceleration: constants::FIGHTER.max_lateral_acceleration,
+        max_angular_acceleration: constants::FIGHTER.max_angular_acceleration,
         guns: vec![Gun {
             offset: vector![20.0, 0.0],
             ..vulcan_gun()
         }],
         missile_launchers: vec![MissileLauncher {
             class: ShipClass::Missile,
-            reload_ticks: 5 * 60,
+            reload_ticks: constants::FIGHTER_MISSILE_RELOAD_TICKS,
             reload_ticks_remaining: 0,
-            initial_speed: 100.0,
+            initial_speed: constants::LAUNCH_SPEED,
             offset: vector![20.0, 0.0],
             angle: 0.0,
+            ignition_ticks: 0,
+            burn_profile: None,
+            lost: false,
         }],
         radar: Some(Radar {
-            power: 20e3,
-            rx_cross_section: 5.0,
+            power: constants::FIGHTER.radar_power,
+            max_range: constants::FIGHTER.radar_max_range,
+            rx_cross_section: constants::FIGHTER.rx_cross_section,
             ..Default::default()
         }),
-        radar_cross_section: 10.0,
+        radar_cross_section: constants::FIGHTER.radar_cross_section,
         radios: vec![radio(), radio()],
         abilities: vec![ShipAbility {
             ability: Ability::Boost,
-            active_time: 2.0,
-            reload_time: 10.0,
+            active_time: constants::BOOST.active_time,
+            reload_time: constants::BOOST.reload_time,
             ..Default::default()
         }],
         ..Default::default()
     }
 }
 
+pub fn corvette(team: i32) -> ShipData {
+    let missile_launcher = MissileLauncher {
+        class: ShipClass::Missile,
+        reload_ticks: constants::CORVETTE_MISSILE_RELOAD_TICKS,
+        reload_ticks_remaining: 0,
+        initial_speed: constants::LAUNCH_SPEED,
+        offset: vector![0.0, 0.0],
+        angle: 0.0,
+        ignition_ticks: 0,
+        burn_profile: None,
+        lost: false,
+    };
+    ShipData {
+        class: ShipClass::Corvette,
+        team,
+        health: constants::CORVETTE.health,
+        mass: hull_mass(ShipClass::Corvette),
+        max_forward_acceleration: constants::CORVETTE.max_forward_acceleration,
+        max_backward_acceleration: constants::CORVETTE.max_backward_acceleration,
+        max_lateral_acceleration: constants::CORVETTE.max_lateral_acceleration,
+        max_angular_acceleration: constants::CORVETTE.max_angular_acceleration,
+        guns: vec![Gun {
+            offset: vector![35.0, 0.0],
+            ..vulcan_gun()
+        }],
+        missile_launchers: vec![
+            MissileLauncher {
+                offset: vector![0.0, 15.0],
+                angle: TAU / 8.0,
+                ..missile_launcher
+            },
+            MissileLauncher {
+                offset: vector![0.0, -15.0],
+                angle: -TAU / 8.0,
+                ..missile_launcher
+            },
+        ],
+        radar: Some(Radar {
+            power: constants::CORVETTE.radar_power,
+            max_range: constants::CORVETTE.radar_max_range,
+            rx_cross_section: constants::CORVETTE.rx_cross_section,
+            ..Default::default()
+        }),
+        radar_cross_section: constants::CORVETTE.radar_cross_section,
+        radios: vec![radio(), radio()],
+        ..Default::default()
+    }
+}
+
 pub fn frigate(team: i32) -> ShipData {
     ShipData {
         class: ShipClass::Frigate,
         team,
-        health: 10000.0,
-        mass: 4e6,
-        max_forward_acceleration: 10.0,
-        max_backward_acceleration: 5.0,
-        max_lateral_acceleration: 5.0,
-        max_angular_acceleration: TAU / 8.0,
+        health: constants::FRIGATE.health,
+        armor: Armor {
+            flat: constants::FRIGATE.armor,
+            fraction: 0.0,
+        },
+        mass: hull_mass(ShipClass::Frigate),
+        max_forward_acceleration: constants::FRIGATE.max_forward_acceleration,
+        max_backward_acceleration: constants::FRIGATE.max_backward_acceleration,
+        max_lateral_acceleration: constants::FRIGATE.max_lateral_acceleration,
+        max_angular_acceleration: constants::FRIGATE.max_angular_acceleration,
         guns: vec![
             Gun {
                 magazine_size: 1,
                 magazine_reload_ticks: 0,
-                reload_ticks: 2 * 60,
-                speed: 4000.0,
+                reload_ticks: constants::FRIGATE_MAIN_GUN.reload_ticks,
+                speed: constants::FRIGATE_MAIN_GUN.speed,
                 offset: vector![40.0, 0.0],
                 bullet_mass: 1.0,
-                ttl: 60.0,
+                ttl: constants::FRIGATE_MAIN_GUN.bullet_ttl,
+                ammo_capacity: Some(FRIGATE_MAIN_GUN_AMMO),
+                weapon_class: WeaponClass::HeavySlug,
                 ..Default::default()
             },
             Gun {
                 offset: vector![0.0, 15.0],
                 max_angle: TAU,
+                weapon_class: WeaponClass::PointDefense,
                 ..vulcan_gun()
             },
             Gun {
                 offset: vector![0.0, -15.0],
                 max_angle: TAU,
+                weapon_class: WeaponClass::PointDefense,
                 ..vulcan_gun()
             },
         ],
         missile_launchers: vec![MissileLauncher {
             class: ShipClass::Missile,
-            reload_ticks: 2 * 60,
+            reload_ticks: constants::FRIGATE_MISSILE_RELOAD_TICKS,
             reload_ticks_remaining: 0,
-            initial_speed: 100.0,
+            initial_speed: constants::LAUNCH_SPEED,
             offset: vector![60.0, 0.0],
             angle: 0.0,
+            ignition_ticks: 0,
+            burn_profile: None,
+            lost: false,
         }],
         radar: Some(Radar {
-            power: 100e3,
-            rx_cross_section: 10.0,
+            power: constants::FRIGATE.radar_power,
+            max_range: constants::FRIGATE.radar_max_range,
+            rx_cross_section: constants::FRIGATE.rx_cross_section,
             ..Default::default()
         }),
-        radar_cross_section: 30.0,
+        radar_cross_section: constants::FRIGATE.radar_cross_section,
         radios: vec![radio(), radio(), radio(), radio()],
         ..Default::default()
     }
 }
 
-pub const CRUISER_RADAR_CROSS_SECTION: f64 = 40.0;
+// Rounds carried by the frigate's main gun.
+pub const FRIGATE_MAIN_GUN_AMMO: u32 = 40;
+// Inaccuracy is multiplied by up to 1 + this factor as the gun heats up.
+pub const HEAT_INACCURACY_FACTOR: f64 = 4.0;
+// An overheated gun can fire again once it cools down to this heat.
+pub const OVERHEAT_RECOVERY_HEAT: f64 = 0.25;
+// A launch is blocked if the point this far along the launch direction from
+// the launcher is inside the launching ship's hull.
+pub const LAUNCH_CLEARANCE: f64 = 10.0;
 
 pub fn cruiser(team: i32) -> ShipData {
     let missile_launcher = MissileLauncher {
         class: ShipClass::Missile,
-        reload_ticks: 72,
+        reload_ticks: constants::CRUISER_MISSILE_RELOAD_TICKS,
         reload_ticks_remaining: 0,
-        initial_speed: 100.0,
+        initial_speed: constants::LAUNCH_SPEED,
         offset: vector![0.0, 0.0],
         angle: 0.0,
+        ignition_ticks: 0,
+        burn_profile: None,
+        lost: false,
     };
     ShipData {
         class: ShipClass::Cruiser,
         team,
-        health: 20000.0,
-        mass: 9e6,
-        max_forward_acceleration: 5.0,
-        max_backward_acceleration: 2.5,
-        max_lateral_acceleration: 2.5,
-        max_angular_acceleration: TAU / 16.0,
+        health: constants::CRUISER.health,
+        armor: Armor {
+            flat: constants::CRUISER.armor,
+            fraction: 0.0,
+        },
+        mass: hull_mass(ShipClass::Cruiser),
+        max_forward_acceleration: constants::CRUISER.max_forward_acceleration,
+        max_backward_acceleration: constants::CRUISER.max_backward_acceleration,
+        max_lateral_acceleration: constants::CRUISER.max_lateral_acceleration,
+        max_angular_acceleration: constants::CRUISER.max_angular_acceleration,
         guns: vec![Gun {
             magazine_size: 30,
             magazine_reload_ticks: 60,
-            reload_ticks: 24,
-            speed: 1000.0,
+            reload_ticks: constants::CRUISER_FLAK.reload_ticks,
+            speed: constants::CRUISER_FLAK.speed,
             speed_error: 50.0,
             offset: vector![0.0, 0.0],
             max_angle: TAU,
             inaccuracy: 0.02,
-            burst_size: 6,
-            ttl: 1.0,
+            burst_size: constants::CRUISER_FLAK.burst_size,
+            ttl: constants::CRUISER_FLAK.bullet_ttl,
             bullet_mass: 0.1,
+            flak: true,
+            weapon_class: WeaponClass::Flak,
             ..Default::default()
         }],
         missile_launchers: vec![
@@ -376,19 +762,23 @@ pub fn cruiser(team: i32) -> ShipData {
             },
             MissileLauncher {
                 class: ShipClass::Torpedo,
-                reload_ticks: 180,
+                reload_ticks: constants::CRUISER_TORPEDO_RELOAD_TICKS,
                 reload_ticks_remaining: 0,
-                initial_speed: 100.0,
+                initial_speed: constants::LAUNCH_SPEED,
                 offset: vector![140.0, 0.0],
                 angle: 0.0,
+                ignition_ticks: 0,
+                burn_profile: None,
+                lost: false,
             },
         ],
         radar: Some(Radar {
-            power: 200e3,
-            rx_cross_section: 20.0,
+            power: constants::CRUISER.radar_power,
+            max_range: constants::CRUISER.radar_max_range,
+            rx_cross_section: constants::CRUISER.rx_cross_section,
             ..Default::default()
         }),
-        radar_cross_section: CRUISER_RADAR_CROSS_SECTION,
+        radar_cross_section: constants::CRUISER.radar_cross_section,
         radios: vec![
             radio(),
             radio(),
@@ -401,21 +791,24 @@ pub fn cruiser(team: i32) -> ShipData {
         ],
         abilities: vec![ShipAbility {
             ability: Ability::Shield,
-            active_time: 1.0,
-            reload_time: 5.0,
+            active_time: constants::SHIELD.active_time,
+            reload_time: constants::SHIELD.reload_time,
             ..Default::default()
         }],
         ..Default::default()
     }
 }
 
+// Smaller tiers have less health and radar cross section, see
+// `asteroid::scale`.
 pub fn asteroid(variant: i32) -> ShipData {
+    let area_scale = crate::asteroid::scale(variant).powi(2);
     ShipData {
         class: ShipClass::Asteroid { variant },
-        team: 9,
-        health: 200.0,
-        mass: 20e6,
-        radar_cross_section: 50.0,
+        team: NEUTRAL_TEAM,
+        health: 200.0 * area_scale,
+        mass: hull_mass(ShipClass::Asteroid { variant }),
+        radar_cross_section: 50.0 * area_scale,
         ..Default::default()
     }
 }
@@ -425,7 +818,7 @@ pub fn target(team: i32) -> ShipData {
         class: ShipClass::Target,
         team,
         health: 1.0,
-        mass: 10.0,
+        mass: hull_mass(ShipClass::Target),
         ..Default::default()
     }
 }
@@ -434,25 +827,27 @@ pub fn missile(team: i32) -> ShipData {
     ShipData {
         class: ShipClass::Missile,
         team,
-        health: 20.0,
-        mass: 150.0,
-        max_forward_acceleration: 300.0,
-        max_backward_acceleration: 0.0,
-        max_lateral_acceleration: 100.0,
-        max_angular_acceleration: 4.0 * TAU,
+        health: constants::MISSILE.health,
+        mass: hull_mass(ShipClass::Missile),
+        max_forward_acceleration: constants::MISSILE.max_forward_acceleration,
+        max_backward_acceleration: constants::MISSILE.max_backward_acceleration,
+        max_lateral_acceleration: constants::MISSILE.max_lateral_acceleration,
+        max_angular_acceleration: constants::MISSILE.max_angular_acceleration,
         radar: Some(Radar {
-            power: 1e3,
-            rx_cross_section: 3.0,
+            power: constants::MISSILE.radar_power,
+            max_range: constants::MISSILE.radar_max_range,
+            rx_cross_section: constants::MISSILE.rx_cross_section,
+            doppler_notch: constants::MISSILE.doppler_notch,
             ..Default::default()
         }),
-        radar_cross_section: 0.1,
+        radar_cross_section: constants::MISSILE.radar_cross_section,
         radios: vec![radio()],
-        ttl: Some(60 * 60),
-        fuel: Some(2000.0),
+        ttl: constants::MISSILE.ttl.map(u64::from),
+        fuel: constants::MISSILE.fuel,
         abilities: vec![ShipAbility {
             ability: Ability::Boost,
-            active_time: 2.0,
-            reload_time: 10.0,
+            active_time: constants::BOOST.active_time,
+            reload_time: constants::BOOST.reload_time,
             ..Default::default()
         }],
         warhead: Warhead {
@@ -460,8 +855,10 @@ pub fn missile(team: i32) -> ShipData {
             mass: 0.05,
             width: 0.4,
             speed: 1e3,
-            ttl: 0.2,
+            ttl: constants::WARHEAD_FRAGMENT_TTL,
+            chain_fraction: 0.5,
         },
+        instructions_per_tick: MISSILE_INSTRUCTIONS_PER_TICK,
         ..Default::default()
     }
 }
@@ -470,25 +867,28 @@ pub fn torpedo(team: i32) -> ShipData {
     ShipData {
         class: ShipClass::Torpedo,
         team,
-        health: 100.0,
-        mass: 500.0,
-        max_forward_acceleration: 70.0,
-        max_backward_acceleration: 0.0,
-        max_lateral_acceleration: 20.0,
-        max_angular_acceleration: 2.0 * TAU,
+        health: constants::TORPEDO.health,
+        mass: hull_mass(ShipClass::Torpedo),
+        max_forward_acceleration: constants::TORPEDO.max_forward_acceleration,
+        max_backward_acceleration: constants::TORPEDO.max_backward_acceleration,
+        max_lateral_acceleration: constants::TORPEDO.max_lateral_acceleration,
+        max_angular_acceleration: constants::TORPEDO.max_angular_acceleration,
         radar: Some(Radar {
-            power: 10e3,
-            rx_cross_section: 3.0,
+            power: constants::TORPEDO.radar_power,
+            max_range: constants::TORPEDO.radar_max_range,
+            rx_cross_section: constants::TORPEDO.rx_cross_section,
+            home_on_jam: true,
+            doppler_notch: constants::TORPEDO.doppler_notch,
             ..Default::default()
         }),
-        radar_cross_section: 0.3,
+        radar_cross_section: constants::TORPEDO.radar_cross_section,
         radios: vec![radio()],
-        ttl: Some(60 * 60),
-        fuel: Some(3000.0),
+        ttl: constants::TORPEDO.ttl.map(u64::from),
+        fuel: constants::TORPEDO.fuel,
         abilities: vec![ShipAbility {
             ability: Ability::Decoy,
-            active_time: 0.5,
-            reload_time: 10.0,
+            active_time: constants::DECOY.active_time,
+            reload_time: constants::DECOY.reload_time,
             ..Default::default()
         }],
         warhead: Warhead {
@@ -496,12 +896,125 @@ pub fn torpedo(team: i32) -> ShipData {
             mass: 0.25,
             width: 0.5,
             speed: 1e3,
-            ttl: 0.2,
+            ttl: constants::WARHEAD_FRAGMENT_TTL,
+            chain_fraction: 0.5,
+        },
+        instructions_per_tick: TORPEDO_INSTRUCTIONS_PER_TICK,
+        ..Default::default()
+    }
+}
+
+pub fn station(team: i32) -> ShipData {
+    ShipData {
+        class: ShipClass::Station,
+        team,
+        health: constants::STATION.health,
+        armor: Armor {
+            flat: constants::STATION.armor,
+            fraction: 0.0,
+        },
+        mass: hull_mass(ShipClass::Station),
+        radar: Some(Radar {
+            power: constants::STATION.radar_power,
+            max_range: constants::STATION.radar_max_range,
+            rx_cross_section: constants::STATION.rx_cross_section,
+            ..Default::default()
+        }),
+        radar_cross_section: constants::STATION.radar_cross_section,
+        radios: vec![
+            radio(),
+            radio(),
+            radio(),
+            radio(),
+            radio(),
+            radio(),
+            radio(),
+            radio(),
+        ],
+        ..Default::default()
+    }
+}
+
+pub fn turret(team: i32) -> ShipData {
+    ShipData {
+        class: ShipClass::Turret,
+        team,
+        health: constants::TURRET.health,
+        armor: Armor {
+            flat: constants::TURRET.armor,
+            fraction: 0.0,
+        },
+        mass: hull_mass(ShipClass::Turret),
+        guns: vec![Gun {
+            magazine_size: 1,
+            magazine_reload_ticks: 0,
+            reload_ticks: constants::TURRET_GUN.reload_ticks,
+            speed: constants::TURRET_GUN.speed,
+            max_angle: TAU,
+            bullet_mass: 0.5,
+            ttl: constants::TURRET_GUN.bullet_ttl,
+            weapon_class: WeaponClass::HeavySlug,
+            ..Default::default()
+        }],
+        radar: Some(Radar {
+            power: constants::TURRET.radar_power,
+            max_range: constants::TURRET.radar_max_range,
+            rx_cross_section: constants::TURRET.rx_cross_section,
+            ..Default::default()
+        }),
+        radar_cross_section: constants::TURRET.radar_cross_section,
+        radios: vec![radio(), radio()],
+        ..Default::default()
+    }
+}
+
+// A cruiser's missile battery built as a separate section. See
+// `section::attach_launcher_pods`.
+pub fn launcher_pod(team: i32) -> ShipData {
+    ShipData {
+        class: ShipClass::Pod,
+        team,
+        health: 4000.0,
+        armor: Armor {
+            flat: 5.0,
+            fraction: 0.0,
+        },
+        mass: hull_mass(ShipClass::Pod),
+        radar_cross_section: 10.0,
+        ..Default::default()
+    }
+}
+
+pub const HULK_TTL: u64 = 30 * 60;
+
+pub fn hulk(data: &ShipData) -> ShipData {
+    ShipData {
+        class: data.class,
+        team: NEUTRAL_TEAM,
+        health: 200.0,
+        armor: data.armor.clone(),
+        mass: data.mass,
+        hulk: true,
+        ttl: Some(HULK_TTL),
+        radar_cross_section: data.radar_cross_section * 0.5,
+        warhead: Warhead {
+            count: 0,
+            ..Default::default()
         },
         ..Default::default()
     }
 }
 
+fn interaction_groups(sim: &Simulation, data: &ShipData) -> InteractionGroups {
+    match data.class {
+        ShipClass::Planet => collision::planet_interaction_groups(),
+        ShipClass::Asteroid { .. } => {
+            collision::asteroid_interaction_groups(data.team, sim.asteroid_terrain())
+        }
+        _ => collision::ship_interaction_groups(data.team),
+    }
+}
+
 pub fn create(
     sim: &mut Simulation,
     position: Vector2<f64>,
@@ -509,6 +1022,7 @@ pub fn create(
     heading: f64,
     mut data: ShipData,
 ) -> ShipHandle {
+    data.max_health = data.health;
     let mut builder = RigidBodyBuilder::dynamic()
         .translation(position)
         .linvel(velocity)
@@ -518,27 +1032,32 @@ pub fn create(
     if data.class == ShipClass::Planet {
         builder = builder.lock_translations()
     }
+    if data.class.is_structure() {
+        builder = builder.lock_translations().lock_rotations();
+    }
     let rigid_body = builder.build();
     let body_handle = sim.bodies.insert(rigid_body);
     let handle = ShipHandle(body_handle.0);
     let team = data.team;
-    let model = model::load(data.class);
+    let model = model::get(data.class);
     let restitution = match data.class {
         ShipClass::Missile => 0.0,
         _ => 0.1,
     };
     let vertices = model
+        .hull
         .iter()
         .map(|&v| point![v.x as f64, v.y as f64])
         .collect::<Vec<_>>();
+    // A degenerate hull (e.g. all points on a line) has no convex hull.
     let collider = ColliderBuilder::convex_hull(&vertices)
-        .unwrap()
+        .unwrap_or_else(|| ColliderBuilder::ball(model.radius as f64))
         .mass(data.mass)
         .restitution(restitution)
-        .collision_groups(if data.class == ShipClass::Planet {
-            collision::planet_interaction_groups()
+        .collision_groups(if data.spawn_grace_ticks_remaining > 0 {
+            collision::spawning_interaction_groups()
         } else {
-            collision::ship_interaction_groups(team)
+            interaction_groups(sim, &data)
         })
         .active_events(ActiveEvents::COLLISION_EVENTS)
         .build();
@@ -547,8 +1066,13 @@ pub fn create(
 
     for gun in data.guns.iter_mut() {
         gun.magazine_remaining = gun.magazine_size;
+        gun.ammo = gun.ammo_capacity.unwrap_or(0);
     }
 
+    let noise_id = sim.noise_ids.entry(team).or_default();
+    data.noise_id = *noise_id;
+    *noise_id += 1;
+
     sim.ships.insert(handle);
     sim.new_ships.push((data.team, handle));
     sim.ship_data.insert(handle.index(), data);
@@ -589,6 +1113,17 @@ impl<'a> ShipAccessor<'a> {
         self.body().angvel()
     }
 
+    // Moment of inertia about the center of mass, from the collider.
+    pub fn moment_of_inertia(&self) -> Real {
+        let inertia_sqrt = 1.0
+            / self
+                .body()
+                .mass_properties()
+                .local_mprops
+                .inv_principal_inertia_sqrt;
+        inertia_sqrt * inertia_sqrt
+    }
+
     pub fn data(&self) -> &ShipData {
         self.simulation.ship_data.get(self.handle.index()).unwrap()
     }
@@ -619,6 +1154,73 @@ impl<'a> ShipAccessor<'a> {
             .collect()
     }
 
+    // Whether a point in simulation coordinates is inside the ship's hull.
+    pub fn contains_point(&self, point: Vector2<f64>) -> bool {
+        self.body().colliders().iter().any(|&handle| {
+            let collider = self.simulation.colliders.get(handle).unwrap();
+            collider
+                .shape()
+                .contains_point(collider.position(), &point.into())
+        })
+    }
+
+    // Multiplier on reload times, growing as the ship loses health in
+    // scenarios where damage slows reloads.
+    pub fn reload_factor(&self) -> f64 {
+        let data = self.data();
+        if !self.simulation.damage_slows_reload() || data.max_health <= 0.0 {
+            return 1.0;
+        }
+        let fraction = (data.health / data.max_health).clamp(0.0, 1.0);
+        1.0 / (constants::DAMAGED_RELOAD_SPEED + (1.0 - constants::DAMAGED_RELOAD_SPEED) * fraction)
+    }
+
+    pub fn get_gun_heat(&self, idx: usize) -> f64 {
+        self.data().guns.get(idx).map(|gun| gun.heat).unwrap_or(0.0)
+    }
+
+    // Firing arc of a gun relative to the ship's heading, or None if there's
+    // no gun at `idx`.
+    pub fn get_gun_arc(&self, idx: usize) -> Option<(f64, f64)> {
+        self.data()
+            .guns
+            .get(idx)
+            .map(|gun| (gun.min_angle, gun.max_angle))
+    }
+
+    // Bitmask of weapon indices ready to fire.
+    pub fn ready_weapons(&self) -> u64 {
+        let data = self.data();
+        let guns = data
+            .guns
+            .iter()
+            .map(|gun| gun.reload_ticks_remaining == 0 && !gun.overheated && gun.has_ammo());
+        let launchers = data
+            .missile_launchers
+            .iter()
+            .map(|launcher| launcher.reload_ticks_remaining == 0 && !launcher.lost);
+        guns.chain(launchers)
+            .take(64)
+            .enumerate()
+            .filter(|(_, ready)| *ready)
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    // Rounds left for weapon `idx`. Missile launchers and most guns have
+    // unlimited ammunition.
+    pub fn get_ammo(&self, idx: usize) -> f64 {
+        let data = self.data();
+        if let Some(gun) = data.guns.get(idx) {
+            gun.ammo_remaining()
+                .map(|ammo| ammo as f64)
+                .unwrap_or(f64::INFINITY)
+        } else if idx < data.guns.len() + data.missile_launchers.len() {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
     pub fn get_reload_ticks(&self, idx: usize) -> u32 {
         if let Some(gun) = self.data().guns.get(idx) {
             gun.reload_ticks_remaining
@@ -673,18 +1275,20 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
         self.data_mut().radios.get_mut(idx)
     }
 
-    pub fn accelerate(&mut self, acceleration: Vector2<f64>) {
+    // Scales down the commanded acceleration until it is within the class's
+    // forward, backward, and lateral limits, preserving its direction.
+    pub fn accelerate(&mut self, mut acceleration: Vector2<f64>) {
         let data = self.data();
-        let clamped_acceleration = acceleration
-            .inf(&vector![
-                data.max_forward_acceleration,
-                data.max_lateral_acceleration
-            ])
-            .sup(&vector![
-                -data.max_backward_acceleration,
-                -data.max_lateral_acceleration
-            ]);
-        self.data_mut().acceleration = clamped_acceleration;
+        if acceleration.x > data.max_forward_acceleration {
+            acceleration *= data.max_forward_acceleration / acceleration.x;
+        }
+        if acceleration.x < -data.max_backward_acceleration {
+            acceleration *= data.max_backward_acceleration / -acceleration.x;
+        }
+        if acceleration.y.abs() > data.max_lateral_acceleration {
+            acceleration *= data.max_lateral_acceleration / acceleration.y.abs();
+        }
+        self.data_mut().acceleration = acceleration;
     }
 
     pub fn torque(&mut self, angular_acceleration: f64) {
@@ -695,6 +1299,9 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
     }
 
     pub fn fire(&mut self, index: i64) {
+        if self.data().spawn_grace_ticks_remaining > 0 {
+            return;
+        }
         let num_guns = self.data().guns.len() as i64;
         if index >= num_guns {
             self.launch_missile(index - num_guns);
@@ -704,39 +1311,95 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
     }
 
     pub fn fire_gun(&mut self, index: i64) {
+        self.fire_gun_spread(index, &[]);
+    }
+
+    // Fires a burst with one shot along each of `headings`, or as `fire` does
+    // for weapons that aren't burst guns.
+    pub fn fire_spread(&mut self, index: i64, headings: &[f64]) {
+        if self.data().spawn_grace_ticks_remaining > 0 {
+            return;
+        }
+        if index >= self.data().guns.len() as i64 {
+            self.fire(index);
+        } else {
+            self.fire_gun_spread(index, headings);
+        }
+    }
+
+    fn fire_gun_spread(&mut self, index: i64, headings: &[f64]) {
+        let reload_factor = self.readonly().reload_factor();
+        let ship_id = self.handle.into();
         let ship_data = self.data_mut();
         if index as usize >= ship_data.guns.len() {
             return;
         }
         let team = ship_data.team;
+        let source = ShotSource {
+            ship: ship_id,
+            team,
+            class: ship_data.class,
+            gun: Some(index as usize),
+        };
         let gun = {
             let gun = &mut ship_data.guns[index as usize];
-            if gun.reload_ticks_remaining > 0 {
+            if gun.reload_ticks_remaining > 0 || gun.overheated || !gun.has_ammo() {
                 return;
             }
+            if gun.ammo_capacity.is_some() {
+                gun.ammo = gun.ammo.saturating_sub(gun.burst_size.max(1) as u32);
+            }
             gun.reload_ticks_remaining = gun.reload_ticks;
             gun.magazine_remaining -= gun.burst_size;
             if gun.magazine_remaining <= 0 {
                 gun.magazine_remaining = gun.magazine_size;
                 gun.reload_ticks_remaining += gun.magazine_reload_ticks;
             }
-            gun.clone()
+            gun.reload_ticks_remaining =
+                (gun.reload_ticks_remaining as f64 * reload_factor).round() as u32;
+            let gun_before_shot = gun.clone();
+            gun.heat += gun.heat_per_shot;
+            if gun.heat >= 1.0 {
+                gun.heat = 1.0;
+                gun.overheated = true;
+            }
+            gun_before_shot
         };
+        self.simulation
+            .events
+            .gun_shots
+            .push((self.handle, index as usize));
+        self.simulation.events.effects.push(Effect::MuzzleFlash {
+            ship: self.handle.into(),
+            gun: index as usize,
+            weapon_class: gun.weapon_class,
+        });
+
+        self.simulation
+            .stats
+            .record_shots(source, gun.burst_size as u64);
 
-        let mut rng =
-            rng::new_rng(self.simulation.tick() ^ u64::from(self.handle) as u32 ^ index as u32);
+        let mut rng = self
+            .simulation
+            .noise_rng(self.handle, rng::Noise::Gun(index as usize));
         let alpha = (gun.bullet_mass as f32).clamp(0.7, 1.0);
         let color = color::to_u32(vector![1.0, 1.0, 1.0, alpha]);
         let mut t = 0.0;
         let dt = simulation::PHYSICS_TICK_LENGTH / gun.burst_size as f64;
 
-        let relative_heading = (gun.heading - self.readonly().heading())
-            .rem_euclid(TAU)
-            .clamp(gun.min_angle, gun.max_angle);
+        let ship_heading = self.readonly().heading();
+        let to_relative = |heading: f64| gun.clamp_to_arc(heading - ship_heading);
+        let aimed_heading = to_relative(gun.heading);
+        let headings = if gun.burst_size > 1 { headings } else { &[] };
 
-        for _ in 0..gun.burst_size {
-            let relative_heading = if gun.inaccuracy > 0.0 {
-                relative_heading + rng.gen_range(-gun.inaccuracy..gun.inaccuracy)
+        let inaccuracy = gun.inaccuracy * (1.0 + HEAT_INACCURACY_FACTOR * gun.heat);
+        for shot in 0..gun.burst_size as usize {
+            let relative_heading = match headings.get(shot) {
+                Some(&heading) => to_relative(heading),
+                None => aimed_heading,
+            };
+            let relative_heading = if inaccuracy > 0.0 {
+                relative_heading + rng.gen_range(-inaccuracy..inaccuracy)
             } else {
                 relative_heading
             };
@@ -747,10 +1410,12 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
             };
             let body = self.body();
             let rot = body.position().rotation * UnitComplex::new(relative_heading);
-            let v = body.linvel() + rot.transform_vector(&vector![speed, 0.0]);
-            let p = body.position().translation.vector
-                + body.position().rotation.transform_vector(&gun.offset)
-                + v * t;
+            let offset = body.position().rotation.transform_vector(&gun.offset);
+            // Velocity of the muzzle due to the ship's rotation.
+            let tangential_velocity = vector![-offset.y, offset.x] * body.angvel();
+            let v =
+                body.linvel() + tangential_velocity + rot.transform_vector(&vector![speed, 0.0]);
+            let p = body.position().translation.vector + offset + v * t;
             bullet::create(
                 self.simulation,
                 p,
@@ -760,26 +1425,52 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
                     team,
                     color,
                     ttl: gun.ttl + t as f32,
+                    damage_falloff: gun.damage_falloff,
+                    source: Some(source),
+                    weapon_class: gun.weapon_class,
+                    ..Default::default()
                 },
             );
             t += dt;
         }
     }
 
+    // Fires so the bullet passes through `point`. The heading accounts for the
+    // muzzle's position and for the velocity the bullet inherits from it.
+    pub fn fire_at(&mut self, index: i64, point: Vector2<f64>) {
+        let gun = self
+            .data()
+            .guns
+            .get(index as usize)
+            .map(|gun| (gun.offset, gun.speed));
+        if let Some((offset, speed)) = gun {
+            let body = self.body();
+            let offset = body.position().rotation.transform_vector(&offset);
+            let muzzle_position = body.position().translation.vector + offset;
+            let muzzle_velocity = body.linvel() + vector![-offset.y, offset.x] * body.angvel();
+
+            let dp = point - muzzle_position;
+            if dp.norm() > 1e-9 {
+                // Cancel the muzzle velocity across the line of fire and put
+                // the rest of the bullet's speed along it.
+                let u = dp.normalize();
+                let cross = muzzle_velocity - u * muzzle_velocity.dot(&u);
+                let along = (speed * speed - cross.norm_squared()).max(0.0).sqrt();
+                let direction = u * along - cross;
+                self.aim(index, direction.y.atan2(direction.x));
+            }
+        }
+        self.fire(index);
+    }
+
     pub fn launch_missile(&mut self, index: i64) {
-        let missile_launcher = {
-            let ship_data = self.data_mut();
-            if let Some(missile_launcher) =
-                ship_data.missile_launchers.get_mut(index as usize).as_mut()
+        let missile_launcher = match self.data().missile_launchers.get(index as usize) {
+            Some(missile_launcher)
+                if missile_launcher.reload_ticks_remaining == 0 && !missile_launcher.lost =>
             {
-                if missile_launcher.reload_ticks_remaining > 0 {
-                    return;
-                }
-                missile_launcher.reload_ticks_remaining = missile_launcher.reload_ticks;
                 missile_launcher.clone()
-            } else {
-                return;
             }
+            _ => return,
         };
 
         let speed = missile_launcher.initial_speed;
@@ -789,18 +1480,48 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
         let p = body.position().translation.vector + rot.transform_vector(&offset);
         let rot2 = rot * UnitComplex::new(missile_launcher.angle);
         let v = body.linvel() + rot2.transform_vector(&vector![speed, 0.0]);
+
+        let exit = p + rot2.transform_vector(&vector![LAUNCH_CLEARANCE, 0.0]);
+        if self.readonly().contains_point(exit) {
+            self.simulation
+                .events
+                .blocked_launches
+                .push((self.handle, index as usize));
+            return;
+        }
+
+        let reload_factor = self.readonly().reload_factor();
+        self.data_mut().missile_launchers[index as usize].reload_ticks_remaining =
+            (missile_launcher.reload_ticks as f64 * reload_factor).round() as u32;
+        let weapon_index = self.data().guns.len() as i64 + index;
+        if weapon_index < 64 {
+            self.data_mut().launched_weapons |= 1 << weapon_index;
+        }
+
         let team = self.data().team;
-        create(
-            self.simulation,
-            p,
-            v,
-            rot2.angle(),
-            match missile_launcher.class {
-                ShipClass::Missile => missile(team),
-                ShipClass::Torpedo => torpedo(team),
-                _ => unimplemented!(),
-            },
-        );
+        self.simulation.stats.record_launch(team);
+        let mut data = match missile_launcher.class {
+            ShipClass::Missile => missile(team),
+            ShipClass::Torpedo => torpedo(team),
+            _ => unimplemented!(),
+        };
+        data.ignition_ticks_remaining = missile_launcher.ignition_ticks;
+        if let Some(profile) = missile_launcher.burn_profile {
+            data.burn_profile = Some(profile);
+            data.max_forward_acceleration = profile.max_forward_acceleration(0);
+        }
+        if let (Some(launch_ttl), Some(max_ttl)) = (self.data().launch_ttl, data.ttl) {
+            data.ttl =
+                Some(launch_ttl.clamp((constants::MIN_LAUNCH_TTL as u64).min(max_ttl), max_ttl));
+        }
+        if let Some(designation) = self.data().launch_designation.clone() {
+            let dp = designation.position - p;
+            if let Some(radar) = data.radar.as_mut() {
+                radar.set_heading(dp.y.atan2(dp.x));
+            }
+            data.designated_contact = Some(designation);
+        }
+        create(self.simulation, p, v, rot2.angle(), data);
     }
 
     pub fn aim(&mut self, index: i64, heading: f64) {
@@ -812,18 +1533,52 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
         gun.heading = heading;
     }
 
+    // Aims each turreted gun from its mount point at `point`, so the shots
+    // converge there. Fixed guns are left alone.
+    pub fn aim_all_at(&mut self, point: Vector2<f64>) {
+        let position = *self.body().translation();
+        let rotation = *self.body().rotation();
+        for gun in self.data_mut().guns.iter_mut() {
+            if gun.min_angle == gun.max_angle {
+                continue;
+            }
+            let dp = point - (position + rotation.transform_vector(&gun.offset));
+            gun.heading = dp.y.atan2(dp.x);
+        }
+    }
+
+    // Makes the warhead safe. The ship can still be destroyed, but won't
+    // release any fragments.
+    pub fn disarm(&mut self) {
+        self.data_mut().warhead.count = 0;
+    }
+
     pub fn explode(&mut self) {
         if self.data().destroyed {
             return;
         }
         self.data_mut().destroyed = true;
+        let count = self.data().warhead.count;
+        self.release_fragments(count);
+    }
 
+    // Scatters `count` fragments using this ship's warhead.
+    fn release_fragments(&mut self, count: i32) {
         let warhead = self.data().warhead.clone();
         let team = self.data().team;
+        let source = ShotSource {
+            ship: self.handle.into(),
+            team,
+            class: self.data().class,
+            gun: None,
+        };
+        if count > 0 {
+            self.simulation.stats.record_shots(source, count as u64);
+        }
         let p =
             self.body().position().translation.vector - self.body().linvel() * PHYSICS_TICK_LENGTH;
         let mut rng = new_rng(0);
-        for _ in 0..warhead.count {
+        for _ in 0..count {
             let color = vector![rng.gen_range(0.7..1.0), 0.5, 0.5, rng.gen_range(0.5..1.0)];
             let rot = self.body().rotation()
                 * Rotation2::new(rng.gen_range((-warhead.width / 2.0)..(warhead.width / 2.0)));
@@ -839,6 +1594,10 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
                     team,
                     color: color::to_u32(color),
                     ttl: warhead.ttl,
+                    fragment: true,
+                    source: Some(source),
+                    weapon_class: WeaponClass::Fragment,
+                    ..Default::default()
                 },
             );
             self.simulation.events.particles.push(Particle {
@@ -878,6 +1637,7 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
 
     pub fn tick(&mut self) {
         // Weapons.
+        let mut reloaded = vec![];
         {
             let ship_data = self
                 .simulation
@@ -888,20 +1648,74 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
                 if gun.reload_ticks_remaining > 0 {
                     gun.reload_ticks_remaining -= 1;
                 }
+                gun.heat = (gun.heat - gun.cooling_per_tick).max(0.0);
+                if gun.overheated && gun.heat <= OVERHEAT_RECOVERY_HEAT {
+                    gun.overheated = false;
+                }
             }
 
-            for missile_launcher in ship_data.missile_launchers.iter_mut() {
+            let num_guns = ship_data.guns.len();
+            for (i, missile_launcher) in ship_data.missile_launchers.iter_mut().enumerate() {
                 if missile_launcher.reload_ticks_remaining > 0 {
                     missile_launcher.reload_ticks_remaining -= 1;
+                    if missile_launcher.reload_ticks_remaining == 0 {
+                        reloaded.push(i);
+                        let weapon_index = num_guns + i;
+                        if weapon_index < 64 {
+                            ship_data.reloaded_weapons |= 1 << weapon_index;
+                        }
+                    }
                 }
             }
         }
+        for launcher in reloaded {
+            self.simulation
+                .events
+                .effects
+                .push(Effect::LauncherReloaded {
+                    ship: self.handle.into(),
+                    launcher,
+                });
+        }
+
+        // Once ignited, the motor steps through its burn profile. The limit
+        // for the next tick is set here so AIs can read it beforehand.
+        if let Some(profile) = self.data().burn_profile {
+            let data = self.data_mut();
+            if data.ignition_ticks_remaining == 0 {
+                data.burn_ticks += 1;
+            }
+            data.max_forward_acceleration = profile.max_forward_acceleration(data.burn_ticks);
+        }
+
+        // Unpowered and unresponsive until the motor ignites.
+        let ignited = self.data().ignition_ticks_remaining == 0;
+        if !ignited {
+            let data = self.data_mut();
+            data.ignition_ticks_remaining -= 1;
+            data.acceleration = vector![0.0, 0.0];
+            data.angular_acceleration = 0.0;
+        }
+
+        // Intangible and unable to fire until the spawn grace period ends.
+        if self.data().spawn_grace_ticks_remaining > 0 {
+            self.data_mut().spawn_grace_ticks_remaining -= 1;
+            if self.data().spawn_grace_ticks_remaining == 0 {
+                let groups = interaction_groups(self.simulation, self.data());
+                let collider_handle = self.body().colliders()[0];
+                self.simulation
+                    .colliders
+                    .get_mut(collider_handle)
+                    .unwrap()
+                    .set_collision_groups(groups);
+            }
+        }
 
         // Acceleration.
         {
             let mut acceleration = self.data().acceleration;
             if self.readonly().is_ability_active(Ability::Boost) {
-                acceleration += vector![100.0, 0.0];
+                acceleration += vector![constants::BOOST_ACCELERATION, 0.0];
             }
             let fuel_consumption = (acceleration * PHYSICS_TICK_LENGTH).norm();
             if let Some(fuel) = self.data_mut().fuel {
@@ -918,6 +1732,13 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
             self.body().reset_forces(false);
             self.body().add_force(inertial_acceleration * mass, true);
             self.data_mut().last_acceleration = inertial_acceleration;
+            if inertial_acceleration != vector![0.0, 0.0] {
+                self.simulation.events.effects.push(Effect::Thruster {
+                    ship: self.handle.into(),
+                    direction: inertial_acceleration.y.atan2(inertial_acceleration.x),
+                    magnitude: inertial_acceleration.magnitude(),
+                });
+            }
             self.data_mut().acceleration = vector![0.0, 0.0];
         }
 
@@ -929,7 +1750,13 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
                     .mass_properties()
                     .local_mprops
                     .inv_principal_inertia_sqrt;
-            let torque = self.data().angular_acceleration * inertia_sqrt * inertia_sqrt;
+            let mut angular_acceleration = self.data().angular_acceleration;
+            if angular_acceleration == 0.0 && ignited && self.data().rotation_stabilizer {
+                let max_angular_acceleration = self.data().max_angular_acceleration;
+                angular_acceleration = (-self.body().angvel() / PHYSICS_TICK_LENGTH)
+                    .clamp(-max_angular_acceleration, max_angular_acceleration);
+            }
+            let torque = angular_acceleration * inertia_sqrt * inertia_sqrt;
             self.body().reset_torques(false);
             self.body().add_torque(torque, true);
             self.data_mut().angular_acceleration = 0.0;
@@ -957,9 +1784,68 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
 
         // Destruction.
         if self.data().destroyed {
+            if self.data().chain_reaction {
+                let count = self.data().warhead.chain_count();
+                self.release_fragments(count);
+            }
+            let radius = {
+                let data = self.data();
+                if data.warhead.count > 0 {
+                    data.warhead.radius()
+                } else {
+                    model::radius(data.class) as f64
+                }
+            };
+            let position = self.readonly().position().vector.into();
+            self.simulation
+                .events
+                .effects
+                .push(Effect::Explosion { position, radius });
             if let Some(team_ctrl) = self.simulation.get_team_controller(self.data().team) {
                 team_ctrl.borrow_mut().remove_ship(self.handle);
             }
+            let hulk = if self.simulation.leave_hulks()
+                && !self.data().hulk
+                && [
+                    ShipClass::Fighter,
+                    ShipClass::Corvette,
+                    ShipClass::Frigate,
+                    ShipClass::Cruiser,
+                ]
+                .contains(&self.data().class)
+            {
+                let ship = self.readonly();
+                Some((
+                    ship.position().vector,
+                    ship.velocity(),
+                    ship.heading(),
+                    ship.angular_velocity(),
+                    hulk(ship.data()),
+                ))
+            } else {
+                None
+            };
+            let fragments = if self.simulation.asteroid_terrain()
+                == collision::AsteroidTerrain::Splitting
+                && self.data().health <= 0.0
+            {
+                let ship = self.readonly();
+                let (position, velocity, angular_velocity) = (
+                    ship.position().vector,
+                    ship.velocity(),
+                    ship.angular_velocity(),
+                );
+                let data = ship.data().clone();
+                crate::asteroid::split(
+                    &mut self.simulation.rng,
+                    &data,
+                    position,
+                    velocity,
+                    angular_velocity,
+                )
+            } else {
+                vec![]
+            };
             self.simulation.ships.remove(self.handle);
             self.simulation.bodies.remove(
                 RigidBodyHandle(self.handle.index()),
@@ -972,6 +1858,26 @@ impl<'a: 'b, 'b> ShipAccessorMut<'a> {
             self.simulation
                 .ship_data
                 .remove(self.handle.index(), ShipData::default());
+            if let Some((position, velocity, heading, angular_velocity, data)) = hulk {
+                let handle = create(self.simulation, position, velocity, heading, data);
+                self.simulation
+                    .ship_mut(handle)
+                    .body()
+                    .set_angvel(angular_velocity, false);
+            }
+            for fragment in fragments {
+                let handle = create(
+                    self.simulation,
+                    fragment.position,
+                    fragment.velocity,
+                    fragment.heading,
+                    fragment.data,
+                );
+                self.simulation
+                    .ship_mut(handle)
+                    .body()
+                    .set_angvel(fragment.angular_velocity, false);
+            }
         }
     }
 
@@ -1046,4 +1952,42 @@ mod test {
         sim.ship_mut(ship0).fire(1);
         assert_eq!(sim.ships.len(), 3);
     }
+
+    #[test]
+    fn test_accelerate_limits() {
+        let mut sim = Simulation::new("test", 0, &[Code::None]);
+        let check = |sim: &mut Simulation, data: ship::ShipData, input, expected| {
+            let handle = ship::create(sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+            sim.ship_mut(handle).accelerate(input);
+            assert_eq!(sim.ship(handle).data().acceleration, expected);
+        };
+
+        // Fighter: 60 forward, 30 backward, 30 lateral.
+        check(
+            &mut sim,
+            ship::fighter(0),
+            vector![120.0, 30.0],
+            vector![60.0, 15.0],
+        );
+        check(
+            &mut sim,
+            ship::fighter(0),
+            vector![-60.0, 0.0],
+            vector![-30.0, 0.0],
+        );
+        check(
+            &mut sim,
+            ship::fighter(0),
+            vector![0.0, -90.0],
+            vector![0.0, -30.0],
+        );
+
+        // Missiles can't thrust backward.
+        check(
+            &mut sim,
+            ship::missile(0),
+            vector![-10.0, 10.0],
+            vector![0.0, 0.0],
+        );
+    }
 }
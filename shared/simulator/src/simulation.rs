@@ -1,16 +1,39 @@
+#[cfg(all(feature = "sys", not(target_arch = "wasm32")))]
+pub mod batch;
+pub mod checkpoint;
+pub mod lockstep;
+pub mod memory;
+pub mod pool;
+
+use crate::ammo::{self, Resupply};
 use crate::bullet::{self, BulletData, BulletHandle};
-use crate::collision;
+use crate::collision::{self, AsteroidTerrain};
+use crate::collision_assist;
 use crate::debug;
 pub use crate::debug::Line;
+use crate::drag;
+use crate::flak;
+use crate::grader::{Grader, HintEvent};
+use crate::hardpoint::{self, RefitError, Weapon};
+use crate::imu;
 use crate::index_set::{HasIndex, IndexSet};
+use crate::observer::{self, Observer, ObserverPicture};
+use crate::orbit;
 use crate::radar;
 use crate::radio;
+use crate::resources::{self, SpawnPoint, TeamResources};
+use crate::rng;
 use crate::scenario;
-use crate::scenario::Scenario;
-use crate::ship::{ShipAccessor, ShipAccessorMut, ShipData, ShipHandle, Target};
+use crate::scenario::{Scenario, Tiebreak};
+use crate::section;
+use crate::ship::{ShipAccessor, ShipAccessorMut, ShipClass, ShipData, ShipHandle, Target};
 use crate::snapshot::*;
+use crate::stats::BattleStats;
+use crate::team_log::TeamLog;
+use crate::velocity_field::{self, VelocityField};
 use crate::vm;
-use crate::vm::TeamController;
+use crate::vm::{CodeLimits, TeamController};
+use crate::waypoint;
 use crossbeam::channel::Sender;
 use instant::Instant;
 use nalgebra::{Vector2, Vector4};
@@ -19,12 +42,57 @@ use rand_chacha::ChaCha8Rng;
 use rapier2d_f64::data::Coarena;
 use rapier2d_f64::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
+
+pub use oort_api::constants::MAX_WORLD_SIZE;
+pub const PHYSICS_TICK_LENGTH: f64 = oort_api::constants::TICK_LENGTH;
+pub const RECENTER_INTERVAL: u32 = 60;
+pub const RECENTER_THRESHOLD: f64 = 1000.0;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct PhysicsProfile {
+    pub max_ccd_substeps: usize,
+    pub max_velocity_iterations: usize,
+    pub max_stabilization_iterations: usize,
+    // Ticks between query pipeline updates, or zero to disable it.
+    pub query_pipeline_interval: u32,
+}
+
+impl PhysicsProfile {
+    // Accurate settings for small scenarios.
+    pub fn tutorial() -> Self {
+        Self {
+            max_ccd_substeps: 4,
+            max_velocity_iterations: 8,
+            max_stabilization_iterations: 2,
+            query_pipeline_interval: 1,
+        }
+    }
 
-pub const MAX_WORLD_SIZE: f64 = 200000.0;
-pub const PHYSICS_TICK_LENGTH: f64 = 1.0 / 60.0;
+    // Fast settings for scenarios with many ships and bullets.
+    pub fn fleet_battle() -> Self {
+        Self {
+            max_ccd_substeps: 1,
+            max_velocity_iterations: 2,
+            max_stabilization_iterations: 1,
+            query_pipeline_interval: 0,
+        }
+    }
+}
+
+impl Default for PhysicsProfile {
+    fn default() -> Self {
+        Self {
+            max_ccd_substeps: 2,
+            max_velocity_iterations: 4,
+            max_stabilization_iterations: 1,
+            query_pipeline_interval: 0,
+        }
+    }
+}
 
 #[derive(Clone, Serialize, Deserialize, Debug, Eq, Hash, PartialEq)]
 pub enum Code {
@@ -49,7 +117,9 @@ pub struct Simulation {
     pub(crate) multibody_joints: MultibodyJointSet,
     pub(crate) colliders: ColliderSet,
     integration_parameters: IntegrationParameters,
+    physics_profile: PhysicsProfile,
     physics_pipeline: PhysicsPipeline,
+    query_pipeline: QueryPipeline,
     pub(crate) island_manager: IslandManager,
     broad_phase: BroadPhase,
     narrow_phase: NarrowPhase,
@@ -63,12 +133,91 @@ pub struct Simulation {
     timing: Timing,
     pub(crate) rng: ChaCha8Rng,
     world_size: f64,
+    floating_origin: bool,
+    origin: Vector2<f64>,
+    leave_hulks: bool,
+    damage_slows_reload: bool,
+    seeker_doppler_notch: bool,
+    velocity_field: VelocityField,
+    drag: f64,
+    grader: Option<Grader>,
+    pub(crate) team_resources: BTreeMap<i32, TeamResources>,
+    code_limits: CodeLimits,
+    pub(crate) observer: Option<Observer>,
+    time_limit: u32,
+    tiebreak: Tiebreak,
+    // Set while evaluating the status if the match was decided by a tiebreak
+    // rule. See `tiebreak_applied`.
+    decided_by_tiebreak: Cell<Option<Tiebreak>>,
+    resupply: Option<Resupply>,
+    asteroid_terrain: AsteroidTerrain,
+    spawn_grace_ticks: u32,
+    pub(crate) damage_dealt: BTreeMap<i32, f64>,
+    pub(crate) stats: BattleStats,
+    cpu: BTreeMap<i32, CpuUsage>,
+    team_log: TeamLog,
+    // Ships created so far for each team.
+    pub(crate) noise_ids: BTreeMap<i32, u64>,
+    // World state published at the end of the last tick, and the previously
+    // published buffer to refill once readers have dropped it.
+    publishing: bool,
+    published: Option<Arc<Snapshot>>,
+    spare: Option<Arc<Snapshot>>,
+    // Indexed by SubscriptionId, None once unsubscribed.
+    subscriptions: Vec<Option<Subscription>>,
+    checkpoints: Option<checkpoint::Checkpoints>,
+    memory_accounting: bool,
+    snapshot_mode: SnapshotMode,
 }
 
 impl Simulation {
     pub fn new(scenario_name: &str, seed: u32, codes: &[Code]) -> Box<Simulation> {
-        let mut scenario = scenario::load(scenario_name);
+        let scenario = scenario::load(scenario_name);
+        let physics_profile = scenario.physics_profile();
+        Simulation::new_with_scenario(scenario, seed, codes, physics_profile)
+    }
 
+    // Like `new`, but snapshots only show what `snapshot_mode` allows. The
+    // mode can't be changed afterwards.
+    pub fn new_with_snapshot_mode(
+        scenario_name: &str,
+        seed: u32,
+        codes: &[Code],
+        snapshot_mode: SnapshotMode,
+    ) -> Box<Simulation> {
+        let mut sim = Simulation::new(scenario_name, seed, codes);
+        sim.snapshot_mode = snapshot_mode;
+        sim
+    }
+
+    // Like `new`, with the scenario set up for `difficulty`. Panics if the
+    // scenario doesn't support it.
+    pub fn new_with_difficulty(
+        scenario_name: &str,
+        seed: u32,
+        codes: &[Code],
+        difficulty: scenario::Difficulty,
+    ) -> Box<Simulation> {
+        let scenario = scenario::load_with_difficulty(scenario_name, difficulty);
+        let physics_profile = scenario.physics_profile();
+        Simulation::new_with_scenario(scenario, seed, codes, physics_profile)
+    }
+
+    pub fn new_with_physics_profile(
+        scenario_name: &str,
+        seed: u32,
+        codes: &[Code],
+        physics_profile: PhysicsProfile,
+    ) -> Box<Simulation> {
+        Simulation::new_with_scenario(scenario::load(scenario_name), seed, codes, physics_profile)
+    }
+
+    fn new_with_scenario(
+        mut scenario: Box<dyn Scenario>,
+        seed: u32,
+        codes: &[Code],
+        physics_profile: PhysicsProfile,
+    ) -> Box<Simulation> {
         log::debug!("seed {seed}");
         let (contact_send, contact_recv) = crossbeam::channel::unbounded();
         let mut sim = Box::new(Simulation {
@@ -85,10 +234,14 @@ impl Simulation {
             colliders: ColliderSet::new(),
             integration_parameters: IntegrationParameters {
                 dt: PHYSICS_TICK_LENGTH,
-                max_ccd_substeps: 2,
+                max_ccd_substeps: physics_profile.max_ccd_substeps,
+                max_velocity_iterations: physics_profile.max_velocity_iterations,
+                max_stabilization_iterations: physics_profile.max_stabilization_iterations,
                 ..Default::default()
             },
+            physics_profile,
             physics_pipeline: PhysicsPipeline::new(),
+            query_pipeline: QueryPipeline::new(),
             island_manager: IslandManager::new(),
             broad_phase: BroadPhase::new(),
             narrow_phase: NarrowPhase::new(),
@@ -102,6 +255,39 @@ impl Simulation {
             timing: Default::default(),
             rng: crate::rng::new_rng(seed),
             world_size: scenario.world_size(),
+            floating_origin: scenario.floating_origin(),
+            origin: vector![0.0, 0.0],
+            leave_hulks: scenario.leave_hulks(),
+            damage_slows_reload: scenario.damage_slows_reload(),
+            seeker_doppler_notch: scenario.seeker_doppler_notch(),
+            velocity_field: scenario.velocity_field(),
+            drag: scenario.drag(),
+            grader: Some(scenario.hints())
+                .filter(|hints| !hints.is_empty())
+                .map(Grader::new),
+            team_resources: BTreeMap::new(),
+            code_limits: scenario.code_limits(),
+            observer: None,
+            time_limit: scenario
+                .difficulty_settings()
+                .time_limit(scenario.time_limit()),
+            tiebreak: scenario.tiebreak(),
+            decided_by_tiebreak: Cell::new(None),
+            resupply: scenario.resupply(),
+            asteroid_terrain: scenario.asteroid_terrain(),
+            spawn_grace_ticks: scenario.spawn_grace_ticks(),
+            damage_dealt: BTreeMap::new(),
+            stats: Default::default(),
+            cpu: BTreeMap::new(),
+            team_log: TeamLog::new(),
+            noise_ids: BTreeMap::new(),
+            publishing: false,
+            published: None,
+            spare: None,
+            subscriptions: vec![],
+            checkpoints: None,
+            memory_accounting: false,
+            snapshot_mode: SnapshotMode::GroundTruth,
         });
 
         for (team, code) in codes.iter().enumerate() {
@@ -138,10 +324,195 @@ impl Simulation {
         self.seed
     }
 
+    // RNG for noise in a ship's sensors and weapons this tick. Derived from
+    // the match seed and independent of team, so both sides of a symmetric
+    // scenario see the same noise.
+    pub(crate) fn noise_rng(&self, handle: ShipHandle, noise: rng::Noise) -> ChaCha8Rng {
+        let noise_id = self.ship(handle).data().noise_id;
+        rng::noise_rng(self.seed, self.tick, noise_id, noise)
+    }
+
     pub fn world_size(&self) -> f64 {
         self.world_size
     }
 
+    // Offset from local simulation coordinates to world coordinates.
+    pub fn origin(&self) -> Vector2<f64> {
+        self.origin
+    }
+
+    pub fn team_resources(&self, team: i32) -> f64 {
+        self.team_resources
+            .get(&team)
+            .map(|resources| resources.amount)
+            .unwrap_or(0.0)
+    }
+
+    pub fn credit_team(&mut self, team: i32, amount: f64) {
+        self.team_resources.entry(team).or_default().amount += amount;
+    }
+
+    pub fn time_limit(&self) -> u32 {
+        self.time_limit
+    }
+
+    pub fn tiebreak(&self) -> Tiebreak {
+        self.tiebreak
+    }
+
+    // Overrides the scenario's time limit, e.g. for a tournament with its own
+    // rules. Must be called before the match ends.
+    pub fn set_time_limit(&mut self, time_limit: u32, tiebreak: Tiebreak) {
+        self.time_limit = time_limit;
+        self.tiebreak = tiebreak;
+    }
+
+    pub fn resupply(&self) -> Option<&Resupply> {
+        self.resupply.as_ref()
+    }
+
+    // Overrides the scenario's resupply rules.
+    pub fn set_resupply(&mut self, resupply: Option<Resupply>) {
+        self.resupply = resupply;
+    }
+
+    pub fn spawn_grace_ticks(&self) -> u32 {
+        self.spawn_grace_ticks
+    }
+
+    // Overrides the scenario's spawn grace period for ships spawned later.
+    pub fn set_spawn_grace_ticks(&mut self, ticks: u32) {
+        self.spawn_grace_ticks = ticks;
+    }
+
+    pub fn asteroid_terrain(&self) -> AsteroidTerrain {
+        self.asteroid_terrain
+    }
+
+    // Overrides the scenario's asteroid terrain, including for asteroids that
+    // already exist.
+    pub fn set_asteroid_terrain(&mut self, terrain: AsteroidTerrain) {
+        self.asteroid_terrain = terrain;
+        let handles: Vec<ShipHandle> = self.ships.iter().cloned().collect();
+        for handle in handles {
+            let (class, team) = {
+                let data = self.ship(handle).data();
+                (data.class, data.team)
+            };
+            if !matches!(class, ShipClass::Asteroid { .. }) {
+                continue;
+            }
+            let groups = collision::asteroid_interaction_groups(team, terrain);
+            let collider_handle = self.ship(handle).body().colliders()[0];
+            self.colliders
+                .get_mut(collider_handle)
+                .unwrap()
+                .set_collision_groups(groups);
+        }
+    }
+
+    // The tiebreak rule that decided the match, if it ran out of time.
+    pub fn tiebreak_applied(&self) -> Option<Tiebreak> {
+        self.decided_by_tiebreak.set(None);
+        if self.status() == scenario::Status::Running {
+            return None;
+        }
+        self.decided_by_tiebreak.get()
+    }
+
+    // Called by the victory check when the time limit forces a decision.
+    pub(crate) fn record_tiebreak(&self, tiebreak: Tiebreak) {
+        self.decided_by_tiebreak.set(Some(tiebreak));
+    }
+
+    // Total damage done to other teams' ships by this team's bullets.
+    pub fn damage_dealt(&self, team: i32) -> f64 {
+        self.damage_dealt.get(&team).copied().unwrap_or(0.0)
+    }
+
+    // Position is in world coordinates.
+    pub fn add_spawn_point(&mut self, team: i32, position: Vector2<f64>, heading: f64) {
+        self.team_resources
+            .entry(team)
+            .or_default()
+            .spawn_points
+            .push(SpawnPoint { position, heading });
+    }
+
+    pub fn request_reinforcement(&mut self, team: i32, class: ShipClass) -> Option<ShipHandle> {
+        resources::request_reinforcement(self, team, class)
+    }
+
+    // Replaces the ship's weapons, e.g. between waves of a scenario.
+    pub fn refit_ship(
+        &mut self,
+        handle: ShipHandle,
+        loadout: &[Option<Weapon>],
+    ) -> Result<(), RefitError> {
+        hardpoint::refit(self.ship_mut(handle).data_mut(), loadout)
+    }
+
+    // Starts recording a fused picture of all teams for a spectator, available
+    // `delay_ticks` after it was taken.
+    pub fn add_observer(&mut self, delay_ticks: u32) {
+        self.observer = Some(Observer::new(delay_ticks));
+    }
+
+    pub fn observer_picture(&self) -> Option<&ObserverPicture> {
+        self.observer
+            .as_ref()
+            .and_then(|observer| observer.picture())
+    }
+
+    pub fn hints(&self) -> &[HintEvent] {
+        self.grader
+            .as_ref()
+            .map(|grader| grader.events())
+            .unwrap_or_default()
+    }
+
+    pub fn velocity_field(&self) -> &VelocityField {
+        &self.velocity_field
+    }
+
+    pub fn drag(&self) -> f64 {
+        self.drag
+    }
+
+    // Overrides the scenario's drag.
+    pub fn set_drag(&mut self, drag: f64) {
+        self.drag = drag;
+    }
+
+    pub fn leave_hulks(&self) -> bool {
+        self.leave_hulks
+    }
+
+    pub fn damage_slows_reload(&self) -> bool {
+        self.damage_slows_reload
+    }
+
+    pub fn seeker_doppler_notch(&self) -> bool {
+        self.seeker_doppler_notch
+    }
+
+    // Overrides whether the scenario's seekers have Doppler notches.
+    pub fn set_seeker_doppler_notch(&mut self, enabled: bool) {
+        self.seeker_doppler_notch = enabled;
+    }
+
+    pub fn physics_profile(&self) -> PhysicsProfile {
+        self.physics_profile
+    }
+
+    pub fn query_pipeline(&self) -> &QueryPipeline {
+        &self.query_pipeline
+    }
+
+    pub fn difficulty(&self) -> scenario::Difficulty {
+        self.scenario.as_ref().unwrap().difficulty()
+    }
+
     pub fn status(&self) -> scenario::Status {
         self.scenario.as_ref().unwrap().status(self)
     }
@@ -167,17 +538,36 @@ impl Simulation {
 
         let new_ships = std::mem::take(&mut self.new_ships);
         for (team, handle) in new_ships.iter() {
+            // Sections are driven by their parent ship.
+            let is_section = self
+                .ship_data
+                .get(handle.index())
+                .map(|data| data.section.is_some())
+                .unwrap_or(false);
+            if is_section {
+                continue;
+            }
             if let Some(team_ctrl) = self.get_team_controller(*team) {
                 if let Err(e) = team_ctrl.borrow_mut().add_ship(*handle, self) {
                     log::warn!("Ship creation error: {:?}", e);
-                    self.events.errors.push(e);
+                    self.events.errors.push(e.with_ship(*handle, self.tick()));
                 }
             }
         }
 
+        velocity_field::tick(self);
+        drag::tick(self);
+
         let physics_timer = Timer::new();
         let gravity = vector![0.0, 0.0];
         let physics_hooks = ();
+        let query_pipeline_interval = self.physics_profile.query_pipeline_interval;
+        let query_pipeline =
+            if query_pipeline_interval > 0 && self.tick % query_pipeline_interval == 0 {
+                Some(&mut self.query_pipeline)
+            } else {
+                None
+            };
         self.physics_pipeline.step(
             &gravity,
             &self.integration_parameters,
@@ -189,7 +579,7 @@ impl Simulation {
             &mut self.impulse_joints,
             &mut self.multibody_joints,
             &mut self.ccd_solver,
-            None,
+            query_pipeline,
             &physics_hooks,
             &self.event_collector,
         );
@@ -200,10 +590,14 @@ impl Simulation {
         collision::handle_collisions(self, &collision_events);
         self.timing.collision += collision_timer.elapsed();
 
+        imu::tick(self);
+
         let radar_timer = Timer::new();
         radar::tick(self);
         self.timing.radar += radar_timer.elapsed();
 
+        observer::tick(self);
+
         let radio_timer = Timer::new();
         radio::tick(self);
         self.timing.radio += radio_timer.elapsed();
@@ -216,11 +610,44 @@ impl Simulation {
             .collect();
         teams.sort_by_key(|(k, _)| *k);
 
-        for (_, team_controller) in teams.iter() {
-            team_controller.borrow_mut().tick(self);
+        {
+            let mut team_controllers: Vec<_> = teams.iter().map(|(_, v)| v.borrow_mut()).collect();
+            let mut team_controllers: Vec<&mut TeamController> =
+                team_controllers.iter_mut().map(|v| v.as_mut()).collect();
+            vm::tick_team_controllers(self, &mut team_controllers);
         }
         self.timing.vm += vm_timer.elapsed();
 
+        for (team, team_ctrl) in teams.iter() {
+            let instructions = team_ctrl.borrow().instructions();
+            let usage = self.cpu.entry(*team).or_default();
+            usage.last_tick = instructions;
+            usage.max_tick = usage.max_tick.max(instructions);
+            usage.total += instructions;
+            if let Some(cap) = self.code_limits.soft_instructions_per_tick {
+                if instructions > cap {
+                    if usage.ticks_over_cap == 0 {
+                        log::warn!("Team {team} used {instructions} instructions, over the soft cap of {cap}");
+                    }
+                    usage.ticks_over_cap += 1;
+                }
+            }
+        }
+
+        orbit::tick(self);
+        waypoint::tick(self);
+        home_on_jam::tick(self);
+        collision_assist::tick(self);
+        flak::tick(self);
+        ammo::tick(self);
+        section::tick(self);
+
+        let mut grader = self.grader.take();
+        if let Some(grader) = grader.as_mut() {
+            grader.observe(self);
+        }
+        self.grader = grader;
+
         let ship_timer = Timer::new();
         let handle_snapshot: Vec<ShipHandle> = self.ships.iter().cloned().collect();
         for handle in handle_snapshot {
@@ -239,22 +666,187 @@ impl Simulation {
         self.scenario = scenario;
         self.timing.scenario += scenario_timer.elapsed();
 
+        if self.floating_origin && self.tick % RECENTER_INTERVAL == 0 {
+            self.recenter();
+        }
+
         self.tick += 1;
+
+        let mut grader = self.grader.take();
+        if let Some(grader) = grader.as_mut() {
+            grader.finish(self);
+        }
+        self.grader = grader;
+
+        self.take_checkpoint();
+
+        if self.publishing {
+            self.publish();
+        }
+        self.deliver_snapshots();
+    }
+
+    // Enables publishing an immutable copy of the world state after each
+    // tick. Off by default so that batch runs don't pay for the copy.
+    pub fn set_publishing(&mut self, publishing: bool) {
+        self.publishing = publishing;
+        if publishing {
+            self.publish();
+        } else {
+            self.published = None;
+            self.spare = None;
+        }
+    }
+
+    // The world state as of the end of the last tick. Readers can hold on to
+    // it while the simulation keeps stepping.
+    pub fn published(&self) -> Option<Arc<Snapshot>> {
+        self.published.clone()
+    }
+
+    // Swaps the front and back buffers. The back buffer is refilled in place
+    // unless a reader still holds it.
+    fn publish(&mut self) {
+        let back = match self.spare.take() {
+            Some(mut back) => match Arc::get_mut(&mut back) {
+                Some(snapshot) => {
+                    self.fill_snapshot(snapshot);
+                    back
+                }
+                None => Arc::new(self.snapshot(0)),
+            },
+            None => Arc::new(self.snapshot(0)),
+        };
+        self.spare = self.published.replace(back);
+    }
+
+    // Registers a consumer of snapshots. It's given one straight away, and
+    // then a new one on each tick its filter is due.
+    pub fn subscribe(&mut self, filter: SnapshotFilter) -> SubscriptionId {
+        let latest = Some(Arc::new(self.filtered_snapshot(&filter)));
+        self.subscriptions
+            .push(Some(Subscription { filter, latest }));
+        SubscriptionId(self.subscriptions.len() - 1)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        if let Some(subscription) = self.subscriptions.get_mut(id.0) {
+            *subscription = None;
+        }
+    }
+
+    // The last snapshot delivered to a subscriber.
+    pub fn subscription(&self, id: SubscriptionId) -> Option<Arc<Snapshot>> {
+        self.subscriptions
+            .get(id.0)
+            .and_then(|subscription| subscription.as_ref())
+            .and_then(|subscription| subscription.latest.clone())
+    }
+
+    // Generates one snapshot with everything wanted by the subscribers due
+    // this tick. Subscribers wanting less get a stripped copy, shared between
+    // those with the same filter.
+    fn deliver_snapshots(&mut self) {
+        let tick = self.tick;
+        let Some(contents) = self
+            .subscriptions
+            .iter()
+            .flatten()
+            .filter(|subscription| subscription.filter.is_due(tick))
+            .map(|subscription| subscription.filter.contents())
+            .reduce(|a, b| a.union(&b))
+        else {
+            return;
+        };
+        let full = Arc::new(self.filtered_snapshot(&contents));
+        let mut copies: Vec<(SnapshotFilter, Arc<Snapshot>)> = vec![(contents, full.clone())];
+        for subscription in self.subscriptions.iter_mut().flatten() {
+            if !subscription.filter.is_due(tick) {
+                continue;
+            }
+            let wanted = subscription.filter.contents();
+            let snapshot = match copies.iter().find(|(filter, _)| *filter == wanted) {
+                Some((_, snapshot)) => snapshot.clone(),
+                None => {
+                    let mut snapshot = (*full).clone();
+                    wanted.strip(&mut snapshot);
+                    let snapshot = Arc::new(snapshot);
+                    copies.push((wanted, snapshot.clone()));
+                    snapshot
+                }
+            };
+            subscription.latest = Some(snapshot);
+        }
+    }
+
+    fn recenter(&mut self) {
+        let positions: Vec<Vector2<f64>> = self
+            .ships
+            .iter()
+            .filter(|&&handle| self.ship(handle).data().class != ShipClass::Planet)
+            .map(|&handle| self.ship(handle).position().vector)
+            .collect();
+        if positions.is_empty() {
+            return;
+        }
+        let centroid = positions.iter().sum::<Vector2<f64>>() / positions.len() as f64;
+        if centroid.magnitude() < RECENTER_THRESHOLD {
+            return;
+        }
+
+        for (_, body) in self.bodies.iter_mut() {
+            let translation = body.translation() - centroid;
+            body.set_translation(translation, false);
+        }
+        for &handle in self.ships.iter() {
+            if let Some(radar) = self
+                .ship_data
+                .get_mut(handle.index())
+                .and_then(|data| data.radar.as_mut())
+            {
+                radar.recenter(centroid);
+            }
+        }
+        self.events.translate(-centroid);
+        self.origin += centroid;
     }
 
     pub fn upload_code(&mut self, team: i32, code: &Code) {
-        match vm::new_team_controller(code) {
+        match vm::new_team_controller(code, &self.code_limits) {
+            Ok(team_ctrl) => {
+                self.team_controllers
+                    .insert(team, Rc::new(RefCell::new(team_ctrl)));
+            }
+            Err(mut e) => {
+                log::warn!("Creating team controller failed: {:?}", e);
+                e.tick = Some(self.tick());
+                self.events.errors.push(e);
+            }
+        }
+    }
+
+    // Like `upload_code` but reuses an already compiled module.
+    #[cfg(feature = "sys")]
+    pub fn upload_compiled_module(&mut self, team: i32, compiled: &vm::CompiledModule) {
+        match TeamController::create_from_module(compiled, &self.code_limits) {
             Ok(team_ctrl) => {
                 self.team_controllers
                     .insert(team, Rc::new(RefCell::new(team_ctrl)));
+                self.update_environment(team, BTreeMap::new());
             }
-            Err(e) => {
+            Err(mut e) => {
                 log::warn!("Creating team controller failed: {:?}", e);
+                e.tick = Some(self.tick());
                 self.events.errors.push(e);
             }
         }
     }
 
+    // Instructions executed by the team's AI so far.
+    pub fn cpu_usage(&self, team: i32) -> CpuUsage {
+        self.cpu.get(&team).cloned().unwrap_or_default()
+    }
+
     pub fn events(&self) -> &SimEvents {
         &self.events
     }
@@ -279,6 +871,22 @@ impl Simulation {
         }
     }
 
+    pub fn emit_team_log(&mut self, ship: ShipHandle, s: &str) {
+        let team = self.ship(ship).data().team;
+        let entries = self.team_log.push(self.tick, team, ship.into(), s);
+        self.events.team_log.extend_from_slice(entries);
+    }
+
+    // Damage, accuracy and missile statistics for the match so far.
+    pub fn battle_stats(&self) -> &BattleStats {
+        &self.stats
+    }
+
+    // Lines logged by team AIs over the whole match.
+    pub fn team_log(&self) -> &TeamLog {
+        &self.team_log
+    }
+
     pub fn emit_drawn_text(&mut self, ship: Option<ShipHandle>, texts: &[Text]) {
         self.events
             .drawn_text
@@ -319,27 +927,190 @@ impl Simulation {
         s.finish()
     }
 
+    // Hashes the full simulation state: tick, RNG, ship kinematics, health,
+    // weapons and radars, bullets, and the teams' AIs. Unlike `hash` the
+    // result doesn't depend on the Rust version, so it can be compared across
+    // builds.
+    pub fn state_hash(&self) -> u64 {
+        use std::hash::Hasher;
+        let mut s = StateHasher::default();
+        s.write_u32(self.tick);
+        s.write_u32(self.seed);
+        s.write(&self.rng.get_seed());
+        s.write_u64(self.rng.get_stream());
+        s.write(&self.rng.get_word_pos().to_le_bytes());
+        for &handle in self.ships.iter() {
+            let ship = self.ship(handle);
+            let data = ship.data();
+            s.write_u64(handle.into());
+            s.write_u32(data.team as u32);
+            s.write(data.class.name().as_bytes());
+            for v in [
+                ship.position().x,
+                ship.position().y,
+                ship.heading(),
+                ship.velocity().x,
+                ship.velocity().y,
+                ship.angular_velocity(),
+                data.last_acceleration.x,
+                data.last_acceleration.y,
+                data.health,
+                data.fuel.unwrap_or(f64::INFINITY),
+            ] {
+                s.write_u64(v.to_bits());
+            }
+            s.write(&[data.destroyed as u8]);
+            s.write_u32(data.ignition_ticks_remaining);
+            if data.burn_profile.is_some() {
+                s.write_u32(data.burn_ticks);
+            }
+            for gun in data.guns.iter() {
+                s.write_u32(gun.magazine_remaining as u32);
+                s.write_u32(gun.reload_ticks_remaining);
+                s.write_u64(gun.heading.to_bits());
+                s.write_u64(gun.heat.to_bits());
+            }
+            for missile_launcher in data.missile_launchers.iter() {
+                s.write_u32(missile_launcher.reload_ticks_remaining);
+            }
+            if let Some(radar) = data.radar.as_ref() {
+                for v in [
+                    radar.heading,
+                    radar.width,
+                    radar.min_distance,
+                    radar.max_distance,
+                    radar.power_fraction,
+                    radar.jam_bearing.unwrap_or(f64::NAN),
+                ] {
+                    s.write_u64(v.to_bits());
+                }
+                s.write(&[radar.ecm_mode as u8, radar.passive as u8]);
+                let last_contact = radar.last_contact.as_ref();
+                for result in radar.result.iter().chain(last_contact.map(|(r, _)| r)) {
+                    for v in [
+                        result.position.x,
+                        result.position.y,
+                        result.velocity.x,
+                        result.velocity.y,
+                        result.rssi,
+                        result.snr,
+                    ] {
+                        s.write_u64(v.to_bits());
+                    }
+                }
+                if let Some((_, ticks)) = last_contact {
+                    s.write_u32(*ticks);
+                }
+            }
+        }
+        for &handle in self.bullets.iter() {
+            let body = bullet::body(self, handle);
+            let data = bullet::data(self, handle);
+            let (gen, idx) = handle.index().into_raw_parts();
+            s.write_u32(gen);
+            s.write_u32(idx);
+            s.write_u32(data.team as u32);
+            for v in [
+                body.translation().x,
+                body.translation().y,
+                body.linvel().x,
+                body.linvel().y,
+            ] {
+                s.write_u64(v.to_bits());
+            }
+            s.write_u32(data.mass.to_bits());
+            s.write_u32(data.ttl.to_bits());
+        }
+        let mut teams: Vec<i32> = self.team_controllers.keys().copied().collect();
+        teams.sort();
+        for team in teams {
+            let ships: Vec<ShipHandle> = self
+                .ships
+                .iter()
+                .copied()
+                .filter(|&handle| self.ship(handle).data().team == team)
+                .collect();
+            s.write_u32(team as u32);
+            self.team_controllers[&team]
+                .borrow()
+                .hash_state(&ships, &mut s);
+        }
+        s.finish()
+    }
+
+    pub fn snapshot_mode(&self) -> SnapshotMode {
+        self.snapshot_mode
+    }
+
     pub fn snapshot(&self, nonce: u32) -> Snapshot {
-        let mut snapshot = Snapshot {
-            nonce,
-            time: self.time(),
-            score_time: self.score_time(),
-            status: self.status(),
-            ships: vec![],
-            bullets: vec![],
-            scenario_lines: self.scenario.as_ref().unwrap().lines(),
-            debug_lines: self.events.debug_lines.clone(),
-            debug_text: self.events.debug_text.clone(),
-            drawn_text: self.events.drawn_text.clone(),
-            particles: self.events.particles.clone(),
-            errors: self.events.errors.clone(),
-            cheats: self.cheats,
-            timing: self.timing.clone(),
-            world_size: self.world_size,
+        let mut snapshot = self.filtered_snapshot(&SnapshotFilter::default());
+        snapshot.nonce = nonce;
+        snapshot
+    }
+
+    // Like `snapshot`, skipping whatever `filter` leaves out.
+    pub fn filtered_snapshot(&self, filter: &SnapshotFilter) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        self.fill_filtered_snapshot(&mut snapshot, filter);
+        snapshot
+    }
+
+    // Overwrites every field of `snapshot` except the nonce, reusing its
+    // allocations.
+    fn fill_snapshot(&self, snapshot: &mut Snapshot) {
+        self.fill_filtered_snapshot(snapshot, &SnapshotFilter::default());
+    }
+
+    fn fill_filtered_snapshot(&self, snapshot: &mut Snapshot, filter: &SnapshotFilter) {
+        snapshot.time = self.time();
+        snapshot.score_time = self.score_time();
+        snapshot.status = self.status();
+        snapshot.scenario_lines = self.scenario.as_ref().unwrap().lines();
+        if filter.debug {
+            snapshot.debug_lines.clone_from(&self.events.debug_lines);
+            snapshot.debug_text.clone_from(&self.events.debug_text);
+            snapshot.drawn_text.clone_from(&self.events.drawn_text);
+        } else {
+            snapshot.debug_lines.clear();
+            snapshot.debug_text.clear();
+            snapshot.drawn_text.clear();
+        }
+        snapshot.particles.clone_from(&self.events.particles);
+        snapshot.errors.clone_from(&self.events.errors);
+        snapshot.cheats = self.cheats;
+        snapshot.timing.clone_from(&self.timing);
+        snapshot.world_size = self.world_size;
+        snapshot.origin = self.origin;
+        snapshot.hints.clear();
+        snapshot.hints.extend_from_slice(self.hints());
+        snapshot.effects.clone_from(&self.events.effects);
+        snapshot.cpu.clone_from(&self.cpu);
+        snapshot.team_log.clone_from(&self.events.team_log);
+        snapshot.stats = if self.status() == scenario::Status::Running {
+            None
+        } else {
+            Some(self.stats.clone())
         };
+        snapshot.mode = self.snapshot_mode;
+        snapshot.radar_beams.clear();
+        snapshot.radar_beams.extend(
+            self.events
+                .radar_beams
+                .iter()
+                .filter(|beam| self.snapshot_mode.shows_team(beam.team))
+                .cloned(),
+        );
+        snapshot.ships.clear();
+        snapshot.bullets.clear();
+        snapshot.launchers.clear();
+        snapshot.sections.clear();
+        snapshot.api_calls.clear();
 
         for &handle in self.ships.iter() {
             let ship = self.ship(handle);
+            if !self.snapshot_mode.shows_team(ship.data().team) {
+                continue;
+            }
             let id = handle.into();
             let position = ship.position().vector.into();
             let acceleration = ship.data().last_acceleration;
@@ -359,21 +1130,73 @@ impl Simulation {
                 health,
                 fuel,
                 active_abilities: ship.active_abilities(),
+                hulk: ship.data().hulk,
             });
+            if let Some(section) = ship.data().section.as_ref() {
+                snapshot.sections.push(SectionSnapshot {
+                    ship: id,
+                    parent: section.parent.into(),
+                });
+            }
+            for (index, launcher) in ship.data().missile_launchers.iter().enumerate() {
+                snapshot.launchers.push(LauncherSnapshot {
+                    ship: id,
+                    index,
+                    reload_ticks: launcher.reload_ticks,
+                    reload_ticks_remaining: launcher.reload_ticks_remaining,
+                });
+            }
+            if filter.debug && !ship.data().api_calls.is_empty() {
+                snapshot
+                    .api_calls
+                    .insert(id, ship.data().api_calls.records().cloned().collect());
+            }
         }
 
-        for &handle in self.bullets.iter() {
-            let body = self.bodies.get(handle.into()).unwrap();
-            let data = self.bullet_data.get(handle.index()).unwrap();
-            snapshot.bullets.push(BulletSnapshot {
-                position: body.position().translation.vector.into(),
-                velocity: *body.linvel(),
-                color: data.color,
-                ttl: data.ttl,
+        if filter.bullets {
+            for &handle in self.bullets.iter() {
+                let body = self.bodies.get(handle.into()).unwrap();
+                let data = self.bullet_data.get(handle.index()).unwrap();
+                if !self.snapshot_mode.shows_team(data.team) {
+                    continue;
+                }
+                snapshot.bullets.push(BulletSnapshot {
+                    position: body.position().translation.vector.into(),
+                    velocity: *body.linvel(),
+                    color: data.color,
+                    ttl: data.ttl,
+                    weapon_class: data.weapon_class,
+                });
+            }
+        }
+
+        if let SnapshotMode::Team(team) = self.snapshot_mode {
+            // Debug output and effects are keyed by ship.
+            let shown: HashSet<u64> = snapshot.ships.iter().map(|ship| ship.id).collect();
+            snapshot.debug_lines.retain(|(id, _)| shown.contains(id));
+            snapshot.debug_text.retain(|id, _| shown.contains(id));
+            snapshot.drawn_text.retain(|id, _| match id {
+                Some(id) => shown.contains(id),
+                None => true,
             });
+            snapshot.effects.retain(|effect| match effect {
+                Effect::Thruster { ship, .. }
+                | Effect::MuzzleFlash { ship, .. }
+                | Effect::LauncherReloaded { ship, .. }
+                | Effect::Hit { ship, .. } => shown.contains(ship),
+                Effect::Explosion { .. } => true,
+            });
+            snapshot.cpu.retain(|t, _| *t == team);
+            snapshot.team_log.retain(|entry| entry.team == team);
         }
 
-        snapshot
+        snapshot.memory = if self.memory_accounting {
+            let mut usage = self.memory_usage();
+            usage.snapshot_bytes = memory::snapshot_bytes(snapshot);
+            Some(usage)
+        } else {
+            None
+        };
     }
 
     pub fn get_team_controller(&mut self, team: i32) -> Option<Rc<RefCell<Box<TeamController>>>> {
@@ -386,6 +1209,15 @@ impl Simulation {
             self.scenario.as_ref().unwrap().name(),
         );
         environment.insert("WORLD_SIZE".to_string(), format!("{}", self.world_size));
+        environment.insert("DRAG".to_string(), format!("{}", self.drag));
+        environment.insert(
+            "ASTEROID_TERRAIN".to_string(),
+            self.asteroid_terrain.name().to_string(),
+        );
+        environment.insert(
+            "SEEKER_DOPPLER_NOTCH".to_string(),
+            format!("{}", self.seeker_doppler_notch),
+        );
         if let Some(team_ctrl) = self.get_team_controller(team) {
             team_ctrl
                 .borrow_mut()
@@ -444,6 +1276,12 @@ pub struct SimEvents {
     pub debug_lines: Vec<(u64, Vec<Line>)>,
     pub debug_text: BTreeMap<u64, String>,
     pub drawn_text: BTreeMap<Option<u64>, Vec<Text>>,
+    pub gun_shots: Vec<(ShipHandle, usize)>,
+    // Missile launches rejected because the ship's own hull was in the way.
+    pub blocked_launches: Vec<(ShipHandle, usize)>,
+    pub effects: Vec<Effect>,
+    pub team_log: Vec<TeamLogEntry>,
+    pub radar_beams: Vec<RadarBeamSnapshot>,
 }
 
 impl SimEvents {
@@ -454,6 +1292,11 @@ impl SimEvents {
             debug_lines: Vec::new(),
             debug_text: BTreeMap::new(),
             drawn_text: BTreeMap::new(),
+            gun_shots: vec![],
+            blocked_launches: vec![],
+            effects: vec![],
+            team_log: vec![],
+            radar_beams: vec![],
         }
     }
 
@@ -463,6 +1306,39 @@ impl SimEvents {
         self.debug_lines.clear();
         self.debug_text.clear();
         self.drawn_text.clear();
+        self.gun_shots.clear();
+        self.blocked_launches.clear();
+        self.effects.clear();
+        self.team_log.clear();
+        self.radar_beams.clear();
+    }
+
+    // Moves this tick's events along with the world when the origin is
+    // recentered, so they line up with the ships in the snapshot.
+    fn translate(&mut self, offset: Vector2<f64>) {
+        for particle in self.particles.iter_mut() {
+            particle.position += offset;
+        }
+        for (_, lines) in self.debug_lines.iter_mut() {
+            for line in lines.iter_mut() {
+                line.a += offset;
+                line.b += offset;
+            }
+        }
+        for texts in self.drawn_text.values_mut() {
+            for text in texts.iter_mut() {
+                text.x += offset.x;
+                text.y += offset.y;
+            }
+        }
+        for effect in self.effects.iter_mut() {
+            if let Effect::Explosion { position, .. } | Effect::Hit { position, .. } = effect {
+                *position += offset;
+            }
+        }
+        for beam in self.radar_beams.iter_mut() {
+            beam.position += offset;
+        }
     }
 }
 
@@ -493,3 +1369,43 @@ impl Default for Timer {
         Self::new()
     }
 }
+
+// Handle returned by Simulation::subscribe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+struct Subscription {
+    filter: SnapshotFilter,
+    latest: Option<Arc<Snapshot>>,
+}
+
+// 64-bit FNV-1a over little-endian integers, which unlike DefaultHasher is
+// the same on every platform and Rust version.
+struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        StateHasher(0xcbf29ce484222325)
+    }
+}
+
+impl std::hash::Hasher for StateHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_u32(&mut self, v: u32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, v: u64) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,178 @@
+//! Runs many simulations concurrently in a single process.
+//!
+//! Each distinct piece of code is compiled once and shared by every
+//! simulation that uses it.
+//!
+//! To pit two AIs against each other from your own tools, for example when
+//! tuning parameters, use [`run_match`]:
+//!
+//! ```no_run
+//! use oort_simulator::scenario::Status;
+//! use oort_simulator::simulation::{batch, Code};
+//!
+//! let a = Code::Wasm(std::fs::read("a.wasm").unwrap());
+//! let b = Code::Wasm(std::fs::read("b.wasm").unwrap());
+//! let wins = (0..10)
+//!     .filter(|&seed| {
+//!         let outcome = batch::run_match(&a, &b, "fighter_duel", seed).unwrap();
+//!         outcome.status == Status::Victory { team: 0 }
+//!     })
+//!     .count();
+//! println!("a won {wins} of 10");
+//! ```
+
+use crate::scenario::{self, Status, Tiebreak};
+use crate::simulation::{Code, Simulation};
+use crate::snapshot::TeamLogEntry;
+use crate::stats::BattleStats;
+use crate::vm::{self, CompiledModule, Error, ErrorKind};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub scenario_name: String,
+    pub seed: u32,
+    // Indexed by team ID.
+    pub codes: Vec<Code>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub scenario_name: String,
+    pub seed: u32,
+    pub status: Status,
+    pub ticks: u32,
+    pub time: f64,
+    pub score_time: f64,
+    // Hash of the final simulation state, see `Simulation::hash`.
+    pub hash: u64,
+    // Set if the match hit its time limit and was decided by this rule.
+    pub tiebreak: Option<Tiebreak>,
+    // Total instructions executed by each team's AI.
+    pub instructions: Vec<u64>,
+    // Transcript of `team_log!` lines from all teams.
+    pub team_log: Vec<TeamLogEntry>,
+    pub stats: BattleStats,
+}
+
+/// Runs `jobs` on up to `num_threads` threads, or one per CPU if zero.
+///
+/// Outcomes are returned in the same order as the jobs. Returns an error
+/// before running anything if a job's scenario doesn't exist or any AI fails
+/// to compile.
+pub fn run(jobs: &[Job], num_threads: usize) -> Result<Vec<Outcome>, Error> {
+    for job in jobs {
+        check_scenario(&job.scenario_name)?;
+    }
+    let modules = compile_modules(jobs.iter().flat_map(|job| job.codes.iter()))?;
+
+    let num_threads = if num_threads == 0 {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        num_threads
+    }
+    .min(jobs.len());
+
+    let next_job = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Outcome)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = vec![];
+                    loop {
+                        let index = next_job.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(index) else {
+                            break;
+                        };
+                        outcomes.push((index, run_job(job, &modules)));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    outcomes.sort_by_key(|(index, _)| *index);
+    Ok(outcomes.into_iter().map(|(_, outcome)| outcome).collect())
+}
+
+/// Plays `ai_a` as team 0 against `ai_b` as team 1 in `scenario_name` and
+/// returns the outcome.
+///
+/// The match runs on the calling thread until the scenario finishes or hits
+/// [`MAX_TICKS`](scenario::MAX_TICKS). The same AIs, scenario and seed always
+/// give the same outcome. Returns an error if the scenario doesn't exist or
+/// either AI fails to compile.
+pub fn run_match(
+    ai_a: &Code,
+    ai_b: &Code,
+    scenario_name: &str,
+    seed: u32,
+) -> Result<Outcome, Error> {
+    check_scenario(scenario_name)?;
+    let job = Job {
+        scenario_name: scenario_name.to_string(),
+        seed,
+        codes: vec![ai_a.clone(), ai_b.clone()],
+    };
+    let modules = compile_modules(job.codes.iter())?;
+    Ok(run_job(&job, &modules))
+}
+
+fn check_scenario(scenario_name: &str) -> Result<(), Error> {
+    if scenario::load_safe(scenario_name).is_none() {
+        return Err(Error {
+            kind: ErrorKind::ApiMisuse,
+            msg: format!("Unknown scenario {scenario_name:?}"),
+            ..Default::default()
+        });
+    }
+    Ok(())
+}
+
+// Compiles each distinct piece of code once.
+fn compile_modules<'a>(
+    codes: impl Iterator<Item = &'a Code>,
+) -> Result<HashMap<&'a Code, CompiledModule>, Error> {
+    let mut modules = HashMap::new();
+    for code in codes {
+        if !matches!(code, Code::None) && !modules.contains_key(code) {
+            modules.insert(code, vm::compile_module(code)?);
+        }
+    }
+    Ok(modules)
+}
+
+fn run_job(job: &Job, modules: &HashMap<&Code, CompiledModule>) -> Outcome {
+    let codes = vec![Code::None; job.codes.len()];
+    let mut sim = Simulation::new(&job.scenario_name, job.seed, &codes);
+    for (team, code) in job.codes.iter().enumerate() {
+        if let Some(module) = modules.get(code) {
+            sim.upload_compiled_module(team as i32, module);
+        }
+    }
+    while sim.status() == Status::Running && sim.tick() < scenario::MAX_TICKS {
+        sim.step();
+    }
+    Outcome {
+        scenario_name: job.scenario_name.clone(),
+        seed: job.seed,
+        status: sim.status(),
+        ticks: sim.tick(),
+        time: sim.time(),
+        score_time: sim.score_time(),
+        hash: sim.hash(),
+        tiebreak: sim.tiebreak_applied(),
+        instructions: (0..job.codes.len())
+            .map(|team| sim.cpu_usage(team as i32).total)
+            .collect(),
+        team_log: sim.team_log().entries().to_vec(),
+        stats: sim.battle_stats().clone(),
+    }
+}
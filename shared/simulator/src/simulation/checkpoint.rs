@@ -0,0 +1,233 @@
+//! Rewinds a simulation for step-back debugging.
+//!
+//! A [`Checkpoint`] copies everything that changes as the simulation runs:
+//! physics, ships and bullets, the scenario, the simulation's RNG and the
+//! memory of each team's VM. When enabled with
+//! [`Simulation::set_checkpoints`], a ring buffer of checkpoints is taken at a
+//! fixed interval, so [`Simulation::step_back`] only re-runs the ticks since
+//! the nearest one instead of the whole match.
+
+use super::Simulation;
+use crate::bullet::{BulletData, BulletHandle};
+use crate::grader::Grader;
+use crate::index_set::IndexSet;
+use crate::observer::Observer;
+use crate::resources::TeamResources;
+use crate::scenario::{Scenario, ScenarioClone};
+use crate::ship::{ShipData, ShipHandle};
+use crate::snapshot::CpuUsage;
+use crate::stats::BattleStats;
+use crate::team_log::TeamLog;
+use crate::vm::{ControllerCheckpoint, Error};
+use nalgebra::Vector2;
+use rand_chacha::ChaCha8Rng;
+use rapier2d_f64::data::Coarena;
+use rapier2d_f64::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    // Ticks between checkpoints.
+    pub interval: u32,
+    // Number of checkpoints kept. Older ones are dropped.
+    pub capacity: usize,
+}
+
+impl Default for CheckpointConfig {
+    // Keeps the last 30 seconds, one checkpoint per second.
+    fn default() -> Self {
+        CheckpointConfig {
+            interval: 60,
+            capacity: 30,
+        }
+    }
+}
+
+pub struct Checkpoint {
+    tick: u32,
+    scenario: Box<dyn Scenario>,
+    ships: IndexSet<ShipHandle>,
+    ship_data: Coarena<ShipData>,
+    new_ships: Vec<(i32, ShipHandle)>,
+    bullets: IndexSet<BulletHandle>,
+    bullet_data: Coarena<BulletData>,
+    bodies: RigidBodySet,
+    impulse_joints: ImpulseJointSet,
+    multibody_joints: MultibodyJointSet,
+    colliders: ColliderSet,
+    query_pipeline: QueryPipeline,
+    island_manager: IslandManager,
+    broad_phase: BroadPhase,
+    narrow_phase: NarrowPhase,
+    rng: ChaCha8Rng,
+    origin: Vector2<f64>,
+    grader: Option<Grader>,
+    team_resources: BTreeMap<i32, TeamResources>,
+    observer: Option<Observer>,
+    damage_dealt: BTreeMap<i32, f64>,
+    stats: BattleStats,
+    cpu: BTreeMap<i32, CpuUsage>,
+    team_log: TeamLog,
+    noise_ids: BTreeMap<i32, u64>,
+    controllers: BTreeMap<i32, ControllerCheckpoint>,
+}
+
+impl Checkpoint {
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+}
+
+pub(super) struct Checkpoints {
+    config: CheckpointConfig,
+    ring: VecDeque<Checkpoint>,
+}
+
+impl Simulation {
+    // Copies the current state of the simulation.
+    pub fn checkpoint(&self) -> Result<Checkpoint, Error> {
+        let mut controllers = BTreeMap::new();
+        for (team, controller) in self.team_controllers.iter() {
+            controllers.insert(*team, controller.borrow().checkpoint()?);
+        }
+        Ok(Checkpoint {
+            tick: self.tick,
+            scenario: self.scenario.as_ref().unwrap().clone_box(),
+            ships: self.ships.clone(),
+            ship_data: self.ship_data.clone(),
+            new_ships: self.new_ships.clone(),
+            bullets: self.bullets.clone(),
+            bullet_data: self.bullet_data.clone(),
+            bodies: self.bodies.clone(),
+            impulse_joints: self.impulse_joints.clone(),
+            multibody_joints: self.multibody_joints.clone(),
+            colliders: self.colliders.clone(),
+            query_pipeline: self.query_pipeline.clone(),
+            island_manager: self.island_manager.clone(),
+            broad_phase: self.broad_phase.clone(),
+            narrow_phase: self.narrow_phase.clone(),
+            rng: self.rng.clone(),
+            origin: self.origin,
+            grader: self.grader.clone(),
+            team_resources: self.team_resources.clone(),
+            observer: self.observer.clone(),
+            damage_dealt: self.damage_dealt.clone(),
+            stats: self.stats.clone(),
+            cpu: self.cpu.clone(),
+            team_log: self.team_log.clone(),
+            noise_ids: self.noise_ids.clone(),
+            controllers,
+        })
+    }
+
+    // Returns the simulation to the state saved in `checkpoint`, which must
+    // have been taken from this simulation. Scenario options changed with
+    // setters since then are kept.
+    pub fn restore(&mut self, checkpoint: &Checkpoint) -> Result<(), Error> {
+        // Restore the VMs first so that a failure leaves the rest untouched.
+        for (team, controller) in checkpoint.controllers.iter() {
+            if let Some(team_ctrl) = self.team_controllers.get(team) {
+                team_ctrl.borrow_mut().restore(controller)?;
+            }
+        }
+
+        self.tick = checkpoint.tick;
+        self.scenario = Some(checkpoint.scenario.clone_box());
+        self.ships = checkpoint.ships.clone();
+        self.ship_data = checkpoint.ship_data.clone();
+        self.new_ships = checkpoint.new_ships.clone();
+        self.bullets = checkpoint.bullets.clone();
+        self.bullet_data = checkpoint.bullet_data.clone();
+        self.bodies = checkpoint.bodies.clone();
+        self.impulse_joints = checkpoint.impulse_joints.clone();
+        self.multibody_joints = checkpoint.multibody_joints.clone();
+        self.colliders = checkpoint.colliders.clone();
+        self.query_pipeline = checkpoint.query_pipeline.clone();
+        self.island_manager = checkpoint.island_manager.clone();
+        self.broad_phase = checkpoint.broad_phase.clone();
+        self.narrow_phase = checkpoint.narrow_phase.clone();
+        self.rng = checkpoint.rng.clone();
+        self.origin = checkpoint.origin;
+        self.grader = checkpoint.grader.clone();
+        self.team_resources = checkpoint.team_resources.clone();
+        self.observer = checkpoint.observer.clone();
+        self.damage_dealt = checkpoint.damage_dealt.clone();
+        self.stats = checkpoint.stats.clone();
+        self.cpu = checkpoint.cpu.clone();
+        self.team_log = checkpoint.team_log.clone();
+        self.noise_ids = checkpoint.noise_ids.clone();
+        self.events.clear();
+        if self.publishing {
+            self.publish();
+        }
+        Ok(())
+    }
+
+    // Enables taking checkpoints automatically, starting with the current
+    // tick, or disables it and drops the checkpoints taken so far.
+    pub fn set_checkpoints(&mut self, config: Option<CheckpointConfig>) {
+        self.checkpoints = config.map(|config| Checkpoints {
+            config,
+            ring: VecDeque::new(),
+        });
+        self.take_checkpoint();
+    }
+
+    // Ticks of the checkpoints currently held, oldest first.
+    pub fn checkpoint_ticks(&self) -> Vec<u32> {
+        self.checkpoints
+            .as_ref()
+            .map(|checkpoints| checkpoints.ring.iter().map(|c| c.tick).collect())
+            .unwrap_or_default()
+    }
+
+    // Rewinds `ticks` ticks by restoring the nearest earlier checkpoint and
+    // stepping forward from it. Returns false, leaving the simulation
+    // unchanged, if no checkpoint is old enough.
+    pub fn step_back(&mut self, ticks: u32) -> bool {
+        let target = self.tick.saturating_sub(ticks);
+        let Some(mut checkpoints) = self.checkpoints.take() else {
+            return false;
+        };
+        let Some(index) = checkpoints.ring.iter().rposition(|c| c.tick <= target) else {
+            self.checkpoints = Some(checkpoints);
+            return false;
+        };
+        checkpoints.ring.truncate(index + 1);
+        let result = self.restore(&checkpoints.ring[index]);
+        self.checkpoints = Some(checkpoints);
+        if let Err(e) = result {
+            log::warn!("Failed to restore checkpoint: {:?}", e);
+            return false;
+        }
+        while self.tick < target {
+            self.step();
+        }
+        true
+    }
+
+    // Called after each tick.
+    pub(super) fn take_checkpoint(&mut self) {
+        let Some(mut checkpoints) = self.checkpoints.take() else {
+            return;
+        };
+        let interval = checkpoints.config.interval;
+        let due = interval > 0
+            && match checkpoints.ring.back() {
+                Some(last) => self.tick >= last.tick + interval,
+                None => true,
+            };
+        if due {
+            match self.checkpoint() {
+                Ok(checkpoint) => {
+                    checkpoints.ring.push_back(checkpoint);
+                    while checkpoints.ring.len() > checkpoints.config.capacity {
+                        checkpoints.ring.pop_front();
+                    }
+                }
+                Err(e) => log::warn!("Failed to take checkpoint: {:?}", e),
+            }
+        }
+        self.checkpoints = Some(checkpoints);
+    }
+}
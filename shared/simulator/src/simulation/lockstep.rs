@@ -0,0 +1,249 @@
+//! Lockstep synchronization for live matches between two clients.
+//!
+//! Each client runs its own copy of the deterministic simulator with both
+//! teams' code. The only things that differ between the clients are the
+//! commands their players issue during the match, so each tick a client
+//! sends the other a [`TickMessage`] with its player's commands. A tick is
+//! only simulated once both clients' commands for it have arrived, so both
+//! apply the same commands on the same tick.
+//!
+//! Every `desync_check_interval` ticks the message also carries the
+//! [`Simulation::state_hash`] of the client's world, and comparing hashes
+//! catches a desync soon after the two simulations diverge. Hashing covers
+//! each AI's whole memory, so it's too slow to do every tick.
+//!
+//! Commands are scheduled `input_delay` ticks after the tick they were sent
+//! on. That many messages can be in flight without either client waiting, so
+//! a delay longer than the round trip time hides the network latency.
+
+use super::Simulation;
+use crate::ship::ShipClass;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Something a player does during a live match.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Command {
+    RequestReinforcement(ShipClass),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TickMessage {
+    pub team: i32,
+    // The tick the commands apply on.
+    pub tick: u32,
+    pub commands: Vec<Command>,
+    // State hash when the message was sent, `input_delay` ticks before `tick`.
+    // Only sent on desync check ticks.
+    pub state_hash: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LockstepError {
+    Desync { tick: u32, local: u64, remote: u64 },
+    UnexpectedTeam { team: i32 },
+    DuplicateMessage { tick: u32 },
+    StaleMessage { tick: u32, current: u32 },
+}
+
+impl std::fmt::Display for LockstepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockstepError::Desync {
+                tick,
+                local,
+                remote,
+            } => write!(
+                f,
+                "desync at tick {}: local state hash {:016x}, remote {:016x}",
+                tick, local, remote
+            ),
+            LockstepError::UnexpectedTeam { team } => {
+                write!(f, "message from unexpected team {}", team)
+            }
+            LockstepError::DuplicateMessage { tick } => {
+                write!(f, "second message for tick {}", tick)
+            }
+            LockstepError::StaleMessage { tick, current } => {
+                write!(
+                    f,
+                    "message for tick {} arrived after simulating it (now at tick {})",
+                    tick, current
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LockstepError {}
+
+pub struct Lockstep {
+    sim: Box<Simulation>,
+    team: i32,
+    remote_team: i32,
+    input_delay: u32,
+    // Ticks between state hashes.
+    desync_check_interval: u32,
+    // Commands waiting for the next message.
+    queued: Vec<Command>,
+    // Commands received from each team, by the tick they apply on.
+    commands: BTreeMap<u32, BTreeMap<i32, Vec<Command>>>,
+    // State hashes from each side that haven't been compared yet, by tick.
+    local_hashes: BTreeMap<u32, u64>,
+    remote_hashes: BTreeMap<u32, u64>,
+    // Tick of the last message sent.
+    last_sent: Option<u32>,
+}
+
+// Once a second.
+const DEFAULT_DESYNC_CHECK_INTERVAL: u32 = 60;
+
+impl Lockstep {
+    // Wraps a simulation that has just been created, in which this client
+    // controls `team` and the other client controls `remote_team`.
+    pub fn new(sim: Box<Simulation>, team: i32, remote_team: i32, input_delay: u32) -> Self {
+        Self {
+            sim,
+            team,
+            remote_team,
+            input_delay,
+            desync_check_interval: DEFAULT_DESYNC_CHECK_INTERVAL,
+            queued: vec![],
+            commands: BTreeMap::new(),
+            local_hashes: BTreeMap::new(),
+            remote_hashes: BTreeMap::new(),
+            last_sent: None,
+        }
+    }
+
+    // Sets how many ticks apart state hashes are compared. Both clients must
+    // use the same interval.
+    pub fn set_desync_check_interval(&mut self, interval: u32) {
+        self.desync_check_interval = interval.max(1);
+    }
+
+    pub fn sim(&self) -> &Simulation {
+        &self.sim
+    }
+
+    // Issues a command for the local team, sent with the next message.
+    pub fn queue(&mut self, command: Command) {
+        self.queued.push(command);
+    }
+
+    // Returns the message to send to the other client for the current tick,
+    // or None if it's already been sent.
+    pub fn send(&mut self) -> Result<Option<TickMessage>, LockstepError> {
+        let tick = self.sim.tick();
+        if self.last_sent == Some(tick) {
+            return Ok(None);
+        }
+        self.last_sent = Some(tick);
+        let state_hash = if tick % self.desync_check_interval == 0 {
+            Some(self.sim.state_hash())
+        } else {
+            None
+        };
+        let message = TickMessage {
+            team: self.team,
+            tick: tick + self.input_delay,
+            commands: std::mem::take(&mut self.queued),
+            state_hash,
+        };
+        self.commands
+            .entry(message.tick)
+            .or_default()
+            .insert(self.team, message.commands.clone());
+        if let Some(state_hash) = state_hash {
+            self.local_hashes.insert(tick, state_hash);
+            self.compare_hashes()?;
+        }
+        Ok(Some(message))
+    }
+
+    pub fn receive(&mut self, message: TickMessage) -> Result<(), LockstepError> {
+        if message.team != self.remote_team {
+            return Err(LockstepError::UnexpectedTeam { team: message.team });
+        }
+        // Commands for a tick that's already been simulated can't be applied
+        // any more.
+        if message.tick < self.sim.tick() {
+            return Err(LockstepError::StaleMessage {
+                tick: message.tick,
+                current: self.sim.tick(),
+            });
+        }
+        let commands = self.commands.entry(message.tick).or_default();
+        if commands.contains_key(&message.team) {
+            return Err(LockstepError::DuplicateMessage { tick: message.tick });
+        }
+        commands.insert(message.team, message.commands);
+        match message.state_hash {
+            Some(state_hash) => {
+                self.remote_hashes
+                    .insert(message.tick.saturating_sub(self.input_delay), state_hash);
+                self.compare_hashes()
+            }
+            None => Ok(()),
+        }
+    }
+
+    // Whether both clients' commands for the current tick are in. The first
+    // `input_delay` ticks have no commands, and only need this client's
+    // message to have been sent.
+    pub fn ready(&self) -> bool {
+        let tick = self.sim.tick();
+        if self.last_sent != Some(tick) {
+            return false;
+        }
+        tick < self.input_delay
+            || self
+                .commands
+                .get(&tick)
+                .map(|commands| {
+                    commands.contains_key(&self.team) && commands.contains_key(&self.remote_team)
+                })
+                .unwrap_or(false)
+    }
+
+    // Applies this tick's commands, in team order, and steps the simulation.
+    // Returns false without stepping if the other client hasn't caught up.
+    pub fn advance(&mut self) -> bool {
+        if !self.ready() {
+            return false;
+        }
+        let tick = self.sim.tick();
+        for (team, commands) in self.commands.remove(&tick).unwrap_or_default() {
+            for command in commands {
+                match command {
+                    Command::RequestReinforcement(class) => {
+                        self.sim.request_reinforcement(team, class);
+                    }
+                }
+            }
+        }
+        self.sim.step();
+        true
+    }
+
+    fn compare_hashes(&mut self) -> Result<(), LockstepError> {
+        let ticks: Vec<u32> = self
+            .local_hashes
+            .keys()
+            .filter(|tick| self.remote_hashes.contains_key(tick))
+            .cloned()
+            .collect();
+        for tick in ticks {
+            let local = self.local_hashes.remove(&tick).unwrap();
+            let remote = self.remote_hashes.remove(&tick).unwrap();
+            if local != remote {
+                return Err(LockstepError::Desync {
+                    tick,
+                    local,
+                    remote,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+//! Approximate memory accounting for diagnosing leaks in long runs.
+//!
+//! When enabled with [`Simulation::set_memory_accounting`], each snapshot
+//! carries a [`MemoryUsage`] summary. Byte counts are estimates from the size
+//! of each element and the capacity of each buffer, which is enough to see
+//! whether something keeps growing over a tournament match.
+
+use super::Simulation;
+use crate::bullet::BulletData;
+use crate::ship::ShipData;
+use crate::simulation::{Line, Particle};
+use crate::snapshot::{
+    BulletSnapshot, Effect, LauncherSnapshot, MemoryUsage, RadarBeamSnapshot, ShipSnapshot,
+    Snapshot, TeamLogEntry,
+};
+use rapier2d_f64::prelude::*;
+use std::mem::size_of;
+
+impl Simulation {
+    // Enables attaching a memory summary to each snapshot. Off by default
+    // since measuring walks every controller and snapshot buffer.
+    pub fn set_memory_accounting(&mut self, enabled: bool) {
+        self.memory_accounting = enabled;
+    }
+
+    pub fn memory_accounting(&self) -> bool {
+        self.memory_accounting
+    }
+
+    // Measures the current state. The snapshot size is that of the last
+    // published snapshot, if any.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut teams: Vec<_> = self.team_controllers.iter().collect();
+        teams.sort_by_key(|(team, _)| **team);
+        MemoryUsage {
+            bodies: self.bodies.len(),
+            colliders: self.colliders.len(),
+            ships: self.ships.len(),
+            bullets: self.bullets.len(),
+            physics_bytes: self.bodies.len() * size_of::<RigidBody>()
+                + self.colliders.len() * size_of::<Collider>(),
+            ship_bytes: self.ships.len() * size_of::<ShipData>(),
+            bullet_bytes: self.bullets.len() * size_of::<BulletData>(),
+            snapshot_bytes: self
+                .published
+                .as_ref()
+                .map(|snapshot| snapshot_bytes(snapshot))
+                .unwrap_or_default(),
+            controller_bytes: teams
+                .into_iter()
+                .map(|(team, controller)| (*team, controller.borrow().memory_size()))
+                .collect(),
+        }
+    }
+}
+
+// Estimated size of `snapshot` including the buffers it owns. Uses capacity
+// rather than length since `fill_snapshot` reuses allocations.
+pub fn snapshot_bytes(snapshot: &Snapshot) -> usize {
+    size_of::<Snapshot>()
+        + snapshot.ships.capacity() * size_of::<ShipSnapshot>()
+        + snapshot.bullets.capacity() * size_of::<BulletSnapshot>()
+        + snapshot.scenario_lines.capacity() * size_of::<Line>()
+        + snapshot.particles.capacity() * size_of::<Particle>()
+        + snapshot
+            .debug_lines
+            .iter()
+            .map(|(_, lines)| size_of::<(u64, Vec<Line>)>() + lines.capacity() * size_of::<Line>())
+            .sum::<usize>()
+        + snapshot
+            .debug_text
+            .values()
+            .map(|text| size_of::<(u64, String)>() + text.capacity())
+            .sum::<usize>()
+        + snapshot.effects.capacity() * size_of::<Effect>()
+        + snapshot.launchers.capacity() * size_of::<LauncherSnapshot>()
+        + snapshot.radar_beams.capacity() * size_of::<RadarBeamSnapshot>()
+        + snapshot
+            .team_log
+            .iter()
+            .map(|entry| size_of::<TeamLogEntry>() + entry.text.capacity())
+            .sum::<usize>()
+}
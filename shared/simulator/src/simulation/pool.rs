@@ -0,0 +1,110 @@
+//! Hosts several independent simulations in one process.
+//!
+//! Each workspace owns its simulation, so RNG state and team controllers are
+//! never shared. This lets the editor keep the current mission paused while a
+//! quick test against another AI runs alongside it.
+
+use crate::scenario::{self, Status};
+use crate::simulation::{Code, Simulation};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WorkspaceId(pub u32);
+
+struct Workspace {
+    sim: Box<Simulation>,
+    // Set once the simulation reports an error, after which it isn't stepped.
+    errored: bool,
+}
+
+#[derive(Default)]
+pub struct SimulationPool {
+    workspaces: BTreeMap<WorkspaceId, Workspace>,
+    next_id: u32,
+}
+
+impl SimulationPool {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn create(&mut self, scenario_name: &str, seed: u32, codes: &[Code]) -> WorkspaceId {
+        self.insert(Simulation::new(scenario_name, seed, codes))
+    }
+
+    pub fn insert(&mut self, sim: Box<Simulation>) -> WorkspaceId {
+        let id = WorkspaceId(self.next_id);
+        self.next_id += 1;
+        let errored = !sim.events().errors.is_empty();
+        self.workspaces.insert(id, Workspace { sim, errored });
+        id
+    }
+
+    pub fn remove(&mut self, id: WorkspaceId) -> Option<Box<Simulation>> {
+        self.workspaces.remove(&id).map(|workspace| workspace.sim)
+    }
+
+    pub fn get(&self, id: WorkspaceId) -> Option<&Simulation> {
+        self.workspaces
+            .get(&id)
+            .map(|workspace| workspace.sim.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: WorkspaceId) -> Option<&mut Simulation> {
+        self.workspaces
+            .get_mut(&id)
+            .map(|workspace| workspace.sim.as_mut())
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = WorkspaceId> + '_ {
+        self.workspaces.keys().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.workspaces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workspaces.is_empty()
+    }
+
+    pub fn errored(&self, id: WorkspaceId) -> bool {
+        self.workspaces
+            .get(&id)
+            .map(|workspace| workspace.errored)
+            .unwrap_or(false)
+    }
+
+    /// Steps the workspace's simulation up to `ticks` times, stopping early if
+    /// it finishes or reports an error. A publishing simulation only publishes
+    /// the state after the last tick, since nothing can read the ones between.
+    ///
+    /// Returns the number of ticks stepped, or None if there is no such
+    /// workspace.
+    pub fn step(&mut self, id: WorkspaceId, ticks: u32) -> Option<u32> {
+        let workspace = self.workspaces.get_mut(&id)?;
+        let sim = &mut workspace.sim;
+        let publishing = std::mem::replace(&mut sim.publishing, false);
+        let mut stepped = 0;
+        while stepped < ticks && !workspace.errored && is_running(sim) {
+            sim.step();
+            workspace.errored = !sim.events().errors.is_empty();
+            stepped += 1;
+        }
+        sim.publishing = publishing;
+        if publishing && stepped > 0 {
+            sim.publish();
+        }
+        Some(stepped)
+    }
+
+    /// Runs the workspace's simulation until it finishes or reports an error.
+    pub fn run(&mut self, id: WorkspaceId) -> Option<Status> {
+        self.step(id, scenario::MAX_TICKS)?;
+        self.get(id).map(|sim| sim.status())
+    }
+}
+
+fn is_running(sim: &Simulation) -> bool {
+    sim.status() == Status::Running && sim.tick() < scenario::MAX_TICKS
+}
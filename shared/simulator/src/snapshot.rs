@@ -1,13 +1,21 @@
+use crate::api_log::ApiCallRecord;
+use crate::grader::HintEvent;
 use crate::scenario::Status;
-use crate::ship::ShipClass;
+use crate::ship::{ShipClass, WeaponClass};
 use crate::simulation::{Line, Particle};
+use crate::stats::BattleStats;
 use crate::vm;
 use nalgebra::{Point2, Vector2};
+use oort_api::prelude::angle_diff;
 use oort_api::{Ability, Text};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+pub mod v1;
+
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Snapshot {
     pub nonce: u32,
     pub time: f64,
@@ -24,6 +32,183 @@ pub struct Snapshot {
     pub drawn_text: BTreeMap<Option<u64>, Vec<Text>>,
     pub timing: Timing,
     pub world_size: f64,
+    #[serde(default)]
+    pub origin: Vector2<f64>,
+    #[serde(default)]
+    pub hints: Vec<HintEvent>,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    #[serde(default)]
+    pub cpu: BTreeMap<i32, CpuUsage>,
+    // Lines logged by team AIs this tick.
+    #[serde(default)]
+    pub team_log: Vec<TeamLogEntry>,
+    #[serde(default)]
+    pub launchers: Vec<LauncherSnapshot>,
+    // Recent API calls made by each ship's AI.
+    #[serde(default)]
+    pub api_calls: BTreeMap<u64, Vec<ApiCallRecord>>,
+    // Set once the match has finished.
+    #[serde(default)]
+    pub stats: Option<BattleStats>,
+    // Only set when memory accounting is enabled.
+    #[serde(default)]
+    pub memory: Option<MemoryUsage>,
+    #[serde(default)]
+    pub mode: SnapshotMode,
+    #[serde(default)]
+    pub radar_beams: Vec<RadarBeamSnapshot>,
+    #[serde(default)]
+    pub sections: Vec<SectionSnapshot>,
+}
+
+// Stored and transferred snapshots are wrapped in this enum so that the
+// variant acts as a version header. Bump `SNAPSHOT_VERSION` and add a
+// variant when a change to `Snapshot` breaks decoding of the previous layout.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum VersionedSnapshot {
+    V1(v1::Snapshot),
+    V2(Snapshot),
+}
+
+impl VersionedSnapshot {
+    pub fn version(&self) -> u32 {
+        match self {
+            VersionedSnapshot::V1(_) => 1,
+            VersionedSnapshot::V2(_) => 2,
+        }
+    }
+
+    pub fn into_latest(self) -> Snapshot {
+        match self {
+            VersionedSnapshot::V1(snapshot) => snapshot.into(),
+            VersionedSnapshot::V2(snapshot) => snapshot,
+        }
+    }
+}
+
+impl From<Snapshot> for VersionedSnapshot {
+    fn from(snapshot: Snapshot) -> Self {
+        VersionedSnapshot::V2(snapshot)
+    }
+}
+
+// Decodes a bincode-serialized `VersionedSnapshot`. Snapshots saved before
+// versioning have no header and are decoded with the frozen V1 layout.
+pub fn decode(bytes: &[u8]) -> Result<Snapshot, bincode::Error> {
+    use bincode::Options;
+    // Same encoding as `bincode::serialize`. Rejecting trailing bytes keeps
+    // an unversioned snapshot from being misread as a versioned one.
+    let options = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes();
+    match options.deserialize::<VersionedSnapshot>(bytes) {
+        Ok(versioned) => Ok(versioned.into_latest()),
+        Err(e) => options
+            .deserialize::<v1::Snapshot>(bytes)
+            .map(Snapshot::from)
+            .map_err(|_| e),
+    }
+}
+
+// What snapshots show, chosen when the simulation is created.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SnapshotMode {
+    // Everything in the world, including every radar beam. For local viewing
+    // and replays.
+    #[default]
+    GroundTruth,
+    // Only what belongs to the given team: its ships, bullets, radar beams,
+    // debug output and logs.
+    Team(i32),
+}
+
+impl SnapshotMode {
+    pub fn shows_team(&self, team: i32) -> bool {
+        match self {
+            SnapshotMode::GroundTruth => true,
+            SnapshotMode::Team(t) => *t == team,
+        }
+    }
+}
+
+// What a snapshot subscriber wants to receive, and how often. Subscribers due
+// on the same tick share snapshots generated in a single pass over the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotFilter {
+    // Deliver a snapshot every this many ticks.
+    pub interval: u32,
+    pub bullets: bool,
+    // Debug lines and text, drawn text and API call records.
+    pub debug: bool,
+}
+
+impl Default for SnapshotFilter {
+    fn default() -> Self {
+        Self {
+            interval: 1,
+            bullets: true,
+            debug: true,
+        }
+    }
+}
+
+impl SnapshotFilter {
+    // Everything, every `interval` ticks.
+    pub fn every(interval: u32) -> Self {
+        Self {
+            interval,
+            ..Default::default()
+        }
+    }
+
+    pub fn without_bullets(self) -> Self {
+        Self {
+            bullets: false,
+            ..self
+        }
+    }
+
+    pub fn without_debug(self) -> Self {
+        Self {
+            debug: false,
+            ..self
+        }
+    }
+
+    pub fn is_due(&self, tick: u32) -> bool {
+        tick % self.interval.max(1) == 0
+    }
+
+    // The same contents, ignoring cadence.
+    pub fn contents(&self) -> Self {
+        Self {
+            interval: 1,
+            ..*self
+        }
+    }
+
+    // Contents wanted by either filter.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            interval: 1,
+            bullets: self.bullets || other.bullets,
+            debug: self.debug || other.debug,
+        }
+    }
+
+    // Clears the parts of `snapshot` this filter leaves out.
+    pub fn strip(&self, snapshot: &mut Snapshot) {
+        if !self.bullets {
+            snapshot.bullets.clear();
+        }
+        if !self.debug {
+            snapshot.debug_lines.clear();
+            snapshot.debug_text.clear();
+            snapshot.drawn_text.clear();
+            snapshot.api_calls.clear();
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -39,6 +224,9 @@ pub struct ShipSnapshot {
     pub health: f64,
     pub fuel: Option<f64>,
     pub active_abilities: Vec<Ability>,
+    // The wreck of a destroyed ship, see `Scenario::leave_hulks`.
+    #[serde(default)]
+    pub hulk: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -47,6 +235,141 @@ pub struct BulletSnapshot {
     pub velocity: Vector2<f64>,
     pub color: u32,
     pub ttl: f32,
+    #[serde(default)]
+    pub weapon_class: WeaponClass,
+}
+
+// Emitted by the simulator on the tick the effect happens, so the renderer
+// doesn't have to infer them from state changes. Positions are in local
+// simulation coordinates like the rest of the snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Effect {
+    Thruster {
+        ship: u64,
+        // Direction of the thrust in world frame, in radians.
+        direction: f64,
+        // Acceleration in m/s².
+        magnitude: f64,
+    },
+    MuzzleFlash {
+        ship: u64,
+        gun: usize,
+        #[serde(default)]
+        weapon_class: WeaponClass,
+    },
+    Explosion {
+        position: Point2<f64>,
+        radius: f64,
+    },
+    // A missile launcher finished reloading.
+    LauncherReloaded {
+        ship: u64,
+        launcher: usize,
+    },
+    // A bullet hit a ship.
+    Hit {
+        ship: u64,
+        position: Point2<f64>,
+        // Outward normal of the ship's surface at the impact point.
+        normal: Vector2<f64>,
+        damage: f64,
+        // Damage beyond the ship's remaining health.
+        overkill: f64,
+    },
+}
+
+// Instructions executed by a team's AI, which unlike wall time is the same on
+// every machine.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpuUsage {
+    pub last_tick: u64,
+    pub max_tick: u64,
+    pub total: u64,
+    // Ticks where the team went over the scenario's soft cap.
+    pub ticks_over_cap: u32,
+}
+
+// The area covered by a ship's radar this tick. Positions are in local
+// simulation coordinates like the rest of the snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RadarBeamSnapshot {
+    pub ship: u64,
+    pub team: i32,
+    pub position: Point2<f64>,
+    pub start_bearing: f64,
+    pub end_bearing: f64,
+    pub min_distance: f64,
+    // Distance at which a fighter would be reliably detected.
+    pub reliable_distance: f64,
+}
+
+// Approximate memory held by the simulation, to spot leaks in long runs.
+// Counts are reported alongside byte estimates so that, for example,
+// colliders outliving the bullets they belonged to stand out.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub bodies: usize,
+    pub colliders: usize,
+    pub ships: usize,
+    pub bullets: usize,
+    // Estimated from the size of each element, not counting heap data they
+    // point to such as collider shapes.
+    pub physics_bytes: usize,
+    pub ship_bytes: usize,
+    pub bullet_bytes: usize,
+    pub snapshot_bytes: usize,
+    // Size of each team's VM linear memory.
+    pub controller_bytes: BTreeMap<i32, usize>,
+}
+
+impl MemoryUsage {
+    pub fn total_bytes(&self) -> usize {
+        self.physics_bytes
+            + self.ship_bytes
+            + self.bullet_bytes
+            + self.snapshot_bytes
+            + self.controller_bytes.values().sum::<usize>()
+    }
+}
+
+// A section welded to a compound ship. See the `section` module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SectionSnapshot {
+    pub ship: u64,
+    pub parent: u64,
+}
+
+// Reload state of a ship's missile launcher.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LauncherSnapshot {
+    pub ship: u64,
+    pub index: usize,
+    pub reload_ticks: u32,
+    pub reload_ticks_remaining: u32,
+}
+
+impl LauncherSnapshot {
+    pub fn ready(&self) -> bool {
+        self.reload_ticks_remaining == 0
+    }
+
+    // Fraction of the reload completed, from 0 to 1.
+    pub fn reload_fraction(&self) -> f64 {
+        if self.reload_ticks == 0 {
+            1.0
+        } else {
+            1.0 - self.reload_ticks_remaining as f64 / self.reload_ticks as f64
+        }
+    }
+}
+
+// A line logged with `team_log!`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TeamLogEntry {
+    pub tick: u32,
+    pub team: i32,
+    pub ship: u64,
+    pub text: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -126,3 +449,65 @@ pub fn interpolate(snapshot: &mut Snapshot, dt: f64) {
         bullet.position += bullet.velocity * dt;
     }
 }
+
+// Like `interpolate`, but ships also follow their last acceleration. Suited to
+// remote viewers that receive snapshots much less often than they render.
+pub fn dead_reckon(snapshot: &mut Snapshot, dt: f64) {
+    snapshot.time += dt;
+
+    for ship in snapshot.ships.iter_mut() {
+        ship.position += ship.velocity * dt + ship.acceleration * (0.5 * dt * dt);
+        ship.velocity += ship.acceleration * dt;
+        ship.heading += ship.angular_velocity * dt;
+    }
+
+    for bullet in snapshot.bullets.iter_mut() {
+        bullet.position += bullet.velocity * dt;
+    }
+}
+
+// The world a fraction `t` of the way from `from` to `to`, for a viewer that
+// renders one snapshot behind what it has received.
+//
+// Ships present in both follow a cubic Hermite curve through their positions
+// and velocities, so they move smoothly across snapshots. Everything else is
+// dead reckoned from `from`.
+pub fn blend(from: &Snapshot, to: &Snapshot, t: f64) -> Snapshot {
+    let span = to.time - from.time;
+    if span <= 0.0 {
+        return to.clone();
+    }
+    let t = t.clamp(0.0, 1.0);
+    let mut snapshot = from.clone();
+    dead_reckon(&mut snapshot, t * span);
+
+    let targets: BTreeMap<u64, &ShipSnapshot> =
+        to.ships.iter().map(|ship| (ship.id, ship)).collect();
+    for (ship, start) in snapshot.ships.iter_mut().zip(from.ships.iter()) {
+        let Some(end) = targets.get(&ship.id) else {
+            continue;
+        };
+        let (t2, t3) = (t * t, t * t * t);
+        let p0 = start.position.coords;
+        let p1 = end.position.coords;
+        let m0 = start.velocity * span;
+        let m1 = end.velocity * span;
+        ship.position = (p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+            + m0 * (t3 - 2.0 * t2 + t)
+            + p1 * (-2.0 * t3 + 3.0 * t2)
+            + m1 * (t3 - t2))
+            .into();
+        ship.velocity = (p0 * (6.0 * t2 - 6.0 * t)
+            + m0 * (3.0 * t2 - 4.0 * t + 1.0)
+            + p1 * (-6.0 * t2 + 6.0 * t)
+            + m1 * (3.0 * t2 - 2.0 * t))
+            / span;
+        ship.acceleration = start.acceleration + (end.acceleration - start.acceleration) * t;
+        ship.heading = start.heading + angle_diff(start.heading, end.heading) * t;
+        ship.angular_velocity =
+            start.angular_velocity + (end.angular_velocity - start.angular_velocity) * t;
+        ship.health = start.health + (end.health - start.health) * t;
+    }
+
+    snapshot
+}
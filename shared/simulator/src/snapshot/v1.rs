@@ -0,0 +1,120 @@
+//! Layout of snapshots before they were versioned.
+//!
+//! These types are frozen: changing them breaks decoding of old snapshots.
+//! Nested types that haven't changed since, like `Timing`, are shared with
+//! the latest layout and may only grow in ways bincode can still decode, such
+//! as new enum variants at the end.
+
+use super::Timing;
+use crate::scenario::Status;
+use crate::ship::{ShipClass, WeaponClass};
+use crate::simulation::{Line, Particle};
+use crate::vm;
+use nalgebra::{Point2, Vector2};
+use oort_api::{Ability, Text};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Snapshot {
+    pub nonce: u32,
+    pub time: f64,
+    pub score_time: f64,
+    pub status: Status,
+    pub ships: Vec<ShipSnapshot>,
+    pub bullets: Vec<BulletSnapshot>,
+    pub scenario_lines: Vec<Line>,
+    pub particles: Vec<Particle>,
+    pub errors: Vec<Error>,
+    pub cheats: bool,
+    pub debug_lines: Vec<(u64, Vec<Line>)>,
+    pub debug_text: BTreeMap<u64, String>,
+    pub drawn_text: BTreeMap<Option<u64>, Vec<Text>>,
+    pub timing: Timing,
+    pub world_size: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShipSnapshot {
+    pub id: u64,
+    pub position: Point2<f64>,
+    pub velocity: Vector2<f64>,
+    pub acceleration: Vector2<f64>,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub team: i32,
+    pub class: ShipClass,
+    pub health: f64,
+    pub fuel: Option<f64>,
+    pub active_abilities: Vec<Ability>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BulletSnapshot {
+    pub position: Point2<f64>,
+    pub velocity: Vector2<f64>,
+    pub color: u32,
+    pub ttl: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Error {
+    pub msg: String,
+}
+
+impl From<Snapshot> for super::Snapshot {
+    fn from(snapshot: Snapshot) -> Self {
+        super::Snapshot {
+            nonce: snapshot.nonce,
+            time: snapshot.time,
+            score_time: snapshot.score_time,
+            status: snapshot.status,
+            ships: snapshot
+                .ships
+                .into_iter()
+                .map(|ship| super::ShipSnapshot {
+                    id: ship.id,
+                    position: ship.position,
+                    velocity: ship.velocity,
+                    acceleration: ship.acceleration,
+                    heading: ship.heading,
+                    angular_velocity: ship.angular_velocity,
+                    team: ship.team,
+                    class: ship.class,
+                    health: ship.health,
+                    fuel: ship.fuel,
+                    active_abilities: ship.active_abilities,
+                    hulk: false,
+                })
+                .collect(),
+            bullets: snapshot
+                .bullets
+                .into_iter()
+                .map(|bullet| super::BulletSnapshot {
+                    position: bullet.position,
+                    velocity: bullet.velocity,
+                    color: bullet.color,
+                    ttl: bullet.ttl,
+                    weapon_class: WeaponClass::default(),
+                })
+                .collect(),
+            scenario_lines: snapshot.scenario_lines,
+            particles: snapshot.particles,
+            errors: snapshot
+                .errors
+                .into_iter()
+                .map(|error| vm::Error {
+                    msg: error.msg,
+                    ..Default::default()
+                })
+                .collect(),
+            cheats: snapshot.cheats,
+            debug_lines: snapshot.debug_lines,
+            debug_text: snapshot.debug_text,
+            drawn_text: snapshot.drawn_text,
+            timing: snapshot.timing,
+            world_size: snapshot.world_size,
+            ..Default::default()
+        }
+    }
+}
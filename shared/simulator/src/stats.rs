@@ -0,0 +1,108 @@
+use crate::ship::ShipClass;
+pub use oort_proto::stats::{BattleStats, MissileStats, ShipGunStats, WeaponStats};
+use std::collections::BTreeMap;
+
+// What fired a bullet, used to attribute its hits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShotSource {
+    // ID of the ship that fired.
+    pub ship: u64,
+    pub team: i32,
+    pub class: ShipClass,
+    // Gun index, or None for warhead fragments.
+    pub gun: Option<usize>,
+}
+
+impl ShotSource {
+    // Name of the weapon type, e.g. "frigate gun 0" or "missile warhead".
+    pub fn weapon_name(&self) -> String {
+        match self.gun {
+            Some(index) => format!("{} gun {}", self.class.name(), index),
+            None => format!("{} warhead", self.class.name()),
+        }
+    }
+}
+
+// Recording of battle statistics as the simulation runs.
+pub trait RecordStats {
+    fn record_shots(&mut self, source: ShotSource, count: u64);
+    fn record_hit(
+        &mut self,
+        attacker_team: i32,
+        source: Option<ShotSource>,
+        target_team: i32,
+        target_class: ShipClass,
+        damage: f64,
+        overkill: f64,
+    );
+    fn record_launch(&mut self, team: i32);
+    fn record_interception(&mut self, team: i32);
+}
+
+impl RecordStats for BattleStats {
+    fn record_shots(&mut self, source: ShotSource, count: u64) {
+        self.weapons.entry(source.weapon_name()).or_default().shots += count;
+        if let Some(gun) = ship_gun_mut(self, source) {
+            gun.shots += count;
+        }
+    }
+
+    fn record_hit(
+        &mut self,
+        attacker_team: i32,
+        source: Option<ShotSource>,
+        target_team: i32,
+        target_class: ShipClass,
+        damage: f64,
+        overkill: f64,
+    ) {
+        *self
+            .team_damage
+            .entry(attacker_team)
+            .or_default()
+            .entry(target_team)
+            .or_default() += damage;
+        if let Some(source) = source {
+            *self
+                .class_damage
+                .entry(source.class.name().to_string())
+                .or_default()
+                .entry(target_class.name().to_string())
+                .or_default() += damage;
+            let weapon = self.weapons.entry(source.weapon_name()).or_default();
+            weapon.hits += 1;
+            weapon.damage += damage;
+            weapon.overkill += overkill;
+            if let Some(gun) = ship_gun_mut(self, source) {
+                gun.hits += 1;
+                gun.damage += damage;
+                gun.overkill += overkill;
+            }
+        }
+    }
+
+    fn record_launch(&mut self, team: i32) {
+        self.missiles.entry(team).or_default().launched += 1;
+    }
+
+    fn record_interception(&mut self, team: i32) {
+        self.missiles.entry(team).or_default().intercepted += 1;
+    }
+}
+
+fn ship_gun_mut(stats: &mut BattleStats, source: ShotSource) -> Option<&mut WeaponStats> {
+    let gun = source.gun?;
+    Some(
+        stats
+            .ship_guns
+            .entry(source.ship)
+            .or_insert_with(|| ShipGunStats {
+                team: source.team,
+                class: source.class.name().to_string(),
+                guns: BTreeMap::new(),
+            })
+            .guns
+            .entry(gun)
+            .or_default(),
+    )
+}
@@ -0,0 +1,109 @@
+use crate::simulation::PHYSICS_TICK_LENGTH;
+use crate::snapshot::TeamLogEntry;
+use std::collections::BTreeMap;
+
+// Each team can log this many lines per second on average, with bursts of up
+// to TEAM_LOG_BURST lines. Lines over the limit are dropped.
+pub const TEAM_LOG_LINES_PER_SECOND: f64 = 5.0;
+pub const TEAM_LOG_BURST: f64 = 20.0;
+// Longer lines are truncated.
+pub const MAX_TEAM_LOG_LINE_LENGTH: usize = 200;
+
+#[derive(Clone, Debug)]
+struct Budget {
+    lines: f64,
+    tick: u32,
+}
+
+// Transcript of the lines logged by every team over a match.
+#[derive(Clone, Debug, Default)]
+pub struct TeamLog {
+    entries: Vec<TeamLogEntry>,
+    budgets: BTreeMap<i32, Budget>,
+    dropped: BTreeMap<i32, u64>,
+}
+
+impl TeamLog {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Appends each line of `text`, returning the entries that fit within the
+    // team's rate limit.
+    pub fn push(&mut self, tick: u32, team: i32, ship: u64, text: &str) -> &[TeamLogEntry] {
+        let start = self.entries.len();
+        let budget = self.budgets.entry(team).or_insert(Budget {
+            lines: TEAM_LOG_BURST,
+            tick,
+        });
+        budget.lines = (budget.lines
+            + (tick - budget.tick) as f64 * PHYSICS_TICK_LENGTH * TEAM_LOG_LINES_PER_SECOND)
+            .min(TEAM_LOG_BURST);
+        budget.tick = tick;
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if budget.lines < 1.0 {
+                *self.dropped.entry(team).or_default() += 1;
+                continue;
+            }
+            budget.lines -= 1.0;
+            self.entries.push(TeamLogEntry {
+                tick,
+                team,
+                ship,
+                text: truncate(line, MAX_TEAM_LOG_LINE_LENGTH).to_string(),
+            });
+        }
+        &self.entries[start..]
+    }
+
+    pub fn entries(&self) -> &[TeamLogEntry] {
+        &self.entries
+    }
+
+    // Lines from `team` dropped by the rate limit.
+    pub fn dropped(&self, team: i32) -> u64 {
+        self.dropped.get(&team).copied().unwrap_or(0)
+    }
+}
+
+fn truncate(s: &str, max_len: usize) -> &str {
+    if s.len() <= max_len {
+        return s;
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let mut log = TeamLog::new();
+        let text = "line\n".repeat(TEAM_LOG_BURST as usize + 5);
+        assert_eq!(log.push(0, 0, 1, &text).len(), TEAM_LOG_BURST as usize);
+        assert_eq!(log.dropped(0), 5);
+
+        // Other teams have their own budget.
+        assert_eq!(log.push(0, 1, 2, "hello").len(), 1);
+
+        // One line is earned back every 1/TEAM_LOG_LINES_PER_SECOND seconds.
+        let ticks = (1.0 / (TEAM_LOG_LINES_PER_SECOND * PHYSICS_TICK_LENGTH)) as u32 + 1;
+        assert_eq!(log.push(ticks, 0, 1, "a\nb").len(), 1);
+        assert_eq!(log.dropped(0), 6);
+        assert_eq!(log.entries().len(), TEAM_LOG_BURST as usize + 2);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut log = TeamLog::new();
+        let text = "é".repeat(MAX_TEAM_LOG_LINE_LENGTH);
+        let entries = log.push(0, 0, 1, &text);
+        assert_eq!(entries[0].text.len(), MAX_TEAM_LOG_LINE_LENGTH);
+    }
+}
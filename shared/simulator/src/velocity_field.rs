@@ -0,0 +1,69 @@
+use crate::ship::{ShipClass, ShipHandle};
+use crate::simulation::{Simulation, PHYSICS_TICK_LENGTH};
+use nalgebra::{vector, Vector2};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum VelocityField {
+    #[default]
+    None,
+    Uniform(Vector2<f64>),
+    // Circular orbits around `center` with gravitational parameter `mu`.
+    Keplerian {
+        center: Vector2<f64>,
+        mu: f64,
+    },
+}
+
+impl VelocityField {
+    pub fn velocity(&self, position: Vector2<f64>) -> Vector2<f64> {
+        match *self {
+            VelocityField::None => vector![0.0, 0.0],
+            VelocityField::Uniform(v) => v,
+            VelocityField::Keplerian { center, mu } => {
+                let dp = position - center;
+                let r = dp.magnitude();
+                if r < 1.0 {
+                    return vector![0.0, 0.0];
+                }
+                let speed = (mu / r).sqrt();
+                vector![-dp.y, dp.x] / r * speed
+            }
+        }
+    }
+
+    // Acceleration that keeps a body moving with the field once it's moving
+    // at the field's velocity. For circular orbits this is gravity.
+    pub fn acceleration(&self, position: Vector2<f64>) -> Vector2<f64> {
+        match *self {
+            VelocityField::None | VelocityField::Uniform(_) => vector![0.0, 0.0],
+            VelocityField::Keplerian { center, mu } => {
+                let dp = position - center;
+                let r = dp.magnitude();
+                if r < 1.0 {
+                    return vector![0.0, 0.0];
+                }
+                -dp * mu / (r * r * r)
+            }
+        }
+    }
+}
+
+// Accelerates asteroids so those created at the field's velocity keep
+// following it. Collisions and explosions still knock them off course.
+pub fn tick(sim: &mut Simulation) {
+    if !matches!(sim.velocity_field(), VelocityField::Keplerian { .. }) {
+        return;
+    }
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for handle in handle_snapshot {
+        if !matches!(sim.ship(handle).data().class, ShipClass::Asteroid { .. }) {
+            continue;
+        }
+        let position = sim.ship(handle).position().vector + sim.origin();
+        let acceleration = sim.velocity_field().acceleration(position);
+        let mut ship = sim.ship_mut(handle);
+        let body = ship.body();
+        let impulse = acceleration * body.mass() * PHYSICS_TICK_LENGTH;
+        body.apply_impulse(impulse, true);
+    }
+}
@@ -6,7 +6,9 @@ pub fn rewrite(wasm: &[u8]) -> Result<Vec<u8>, super::Error> {
         Ok(m) => m,
         Err(e) => {
             return Err(super::Error {
+                kind: super::ErrorKind::Compile,
                 msg: format!("{e:?}"),
+                ..Default::default()
             })
         }
     };
@@ -1,21 +1,23 @@
 pub mod builtin;
 mod limiter;
 
+use crate::api_log::{self, ApiCall, ApiCallOutcome};
 use crate::color;
 use crate::debug;
+use crate::orbit::{Orbit, OrbitDirection};
 use crate::rng::new_rng;
-use crate::ship::{ShipClass, ShipHandle};
+use crate::ship::{Designation, ShipAccessorMut, ShipClass, ShipHandle};
 use crate::simulation::{Code, Particle, Simulation, PHYSICS_TICK_LENGTH};
 use nalgebra::{point, vector, Rotation2};
+use oort_api::sys::{pack_f32_pair, unpack_f32_pair};
 use oort_api::{ActiveAbilities, Class, EcmMode, Line, SystemState, Text};
 use rand::Rng;
+use rapier2d_f64::data::Coarena;
 use serde::{Deserialize, Serialize};
 use std::cell::{Ref, RefCell, RefMut};
 use std::collections::BTreeMap;
-use std::collections::HashMap;
 use std::f64::consts::TAU;
 use std::ops::{Deref, DerefMut};
-use std::rc::Rc;
 use wasmer::{imports, Instance, MemoryView, Module, Store, WasmPtr};
 
 pub type Vec2 = nalgebra::Vector2<f64>;
@@ -24,36 +26,175 @@ pub type Environment = BTreeMap<String, String>;
 const GAS_PER_TICK: i32 = 1_000_000;
 const MAX_DEBUG_LINES: u32 = 1024;
 const MAX_DRAWN_TEXT: u32 = 128;
+// Size of SYSTEM_STATE in modules that predate the SYSTEM_STATE_SIZE export.
+const LEGACY_SYSTEM_STATE_SIZE: u32 = 128;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Error {
+    #[serde(default)]
+    pub kind: ErrorKind,
     pub msg: String,
+    // Set when the code was rejected for exceeding one of its `CodeLimits`.
+    #[serde(default)]
+    pub limit_exceeded: Option<LimitExceeded>,
+    // The ship and tick, for errors raised while running a ship's code.
+    #[serde(default)]
+    pub ship: Option<u64>,
+    #[serde(default)]
+    pub tick: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorKind {
+    // The code couldn't be loaded: invalid WASM, missing exports, etc.
+    #[default]
+    Compile,
+    // The code called `panic!`.
+    Panic,
+    // The code passed the simulator something it can't use.
+    ApiMisuse,
+    // The code exceeded a limit on instructions, code size or memory.
+    BudgetExceeded,
+    // The code trapped, e.g. on an out-of-bounds memory access.
+    SandboxViolation,
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ErrorKind::Compile => "Compile error",
+            ErrorKind::Panic => "Panic",
+            ErrorKind::ApiMisuse => "API misuse",
+            ErrorKind::BudgetExceeded => "Budget exceeded",
+            ErrorKind::SandboxViolation => "Sandbox violation",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Error {
+    fn new(kind: ErrorKind, msg: impl Into<String>) -> Self {
+        Self {
+            kind,
+            msg: msg.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_ship(mut self, ship: ShipHandle, tick: u32) -> Self {
+        self.ship = Some(ship.into());
+        self.tick = Some(tick);
+        self
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind, self.msg)?;
+        match (self.ship, self.tick) {
+            (Some(ship), Some(tick)) => write!(f, " (ship {ship}, tick {tick})"),
+            (None, Some(tick)) => write!(f, " (tick {tick})"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    // Size of the WASM module in bytes.
+    CodeSize { size: usize, limit: usize },
+    // Size of linear memory in bytes after initialization.
+    Memory { size: u64, limit: u64 },
+}
+
+impl LimitExceeded {
+    fn into_error(self) -> Error {
+        let msg = match self {
+            LimitExceeded::CodeSize { size, limit } => {
+                format!("Code size of {size} bytes exceeds the limit of {limit} bytes")
+            }
+            LimitExceeded::Memory { size, limit } => {
+                format!("Memory usage of {size} bytes exceeds the limit of {limit} bytes")
+            }
+        };
+        Error {
+            kind: ErrorKind::BudgetExceeded,
+            msg,
+            limit_exceeded: Some(self),
+            ..Default::default()
+        }
+    }
+}
+
+// Checked when a team controller is created.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeLimits {
+    pub max_code_size: usize,
+    pub max_memory: u64,
+    // Instructions per tick a team may use before it's flagged as over budget.
+    // Unlike the hard per-ship limit this doesn't stop the AI.
+    pub soft_instructions_per_tick: Option<u64>,
+}
+
+impl Default for CodeLimits {
+    fn default() -> Self {
+        CodeLimits {
+            max_code_size: 16 << 20,
+            max_memory: 64 << 20,
+            soft_instructions_per_tick: None,
+        }
+    }
+}
+
+impl CodeLimits {
+    fn check_code_size(&self, size: usize) -> Result<(), Error> {
+        if size > self.max_code_size {
+            return Err(LimitExceeded::CodeSize {
+                size,
+                limit: self.max_code_size,
+            }
+            .into_error());
+        }
+        Ok(())
+    }
+
+    fn check_memory(&self, size: u64) -> Result<(), Error> {
+        if size > self.max_memory {
+            return Err(LimitExceeded::Memory {
+                size,
+                limit: self.max_memory,
+            }
+            .into_error());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 impl From<wasm_bindgen::JsValue> for Error {
     fn from(err: wasm_bindgen::JsValue) -> Self {
-        Self {
-            msg: format!("JS error: {:?}", err),
-        }
+        Self::new(ErrorKind::Compile, format!("JS error: {:?}", err))
     }
 }
 
 impl From<wasmer::InstantiationError> for Error {
     fn from(err: wasmer::InstantiationError) -> Self {
-        Self {
-            msg: format!("Wasmer instantiation error: {err:?}"),
-        }
+        Self::new(
+            ErrorKind::Compile,
+            format!("Wasmer instantiation error: {err:?}"),
+        )
     }
 }
-pub fn new_team_controller(code: &Code) -> Result<Box<TeamController>, Error> {
+pub fn new_team_controller(code: &Code, limits: &CodeLimits) -> Result<Box<TeamController>, Error> {
     match code {
-        Code::Wasm(_) => TeamController::create(code),
+        Code::Wasm(_) => TeamController::create(code, limits),
         #[cfg(feature = "precompile")]
-        Code::Precompiled(_) => TeamController::create(code),
+        Code::Precompiled(_) => TeamController::create(code, limits),
         Code::Builtin(name) => match builtin::load_compiled(name) {
-            Ok(code) => new_team_controller(&code),
-            Err(e) => Err(Error { msg: e }),
+            Ok(code) => new_team_controller(&code, limits),
+            Err(e) => Err(Error::new(ErrorKind::Compile, e)),
         },
         _ => unreachable!(),
     }
@@ -61,17 +202,114 @@ pub fn new_team_controller(code: &Code) -> Result<Box<TeamController>, Error> {
 
 pub struct TeamController {
     vm: WasmVm,
-    states: HashMap<ShipHandle, LocalSystemState>,
+    states: Coarena<LocalSystemState>,
+    next_id: u32,
+    pending: Vec<ShipHandle>,
+    commands: Vec<ShipCommands>,
+    instructions: u64,
+    // Set once the team's initial ships have been passed to the
+    // `on_scenario_start` hook.
+    started: bool,
+    // Flattened `ROSTER` entries waiting for the next `run`.
+    roster: Option<Vec<f64>>,
+}
+
+// Saved state of a team's controller, used to rewind the simulation.
+#[derive(Clone)]
+pub struct ControllerCheckpoint {
+    // Contents of the module's linear memory, which includes the AI's own
+    // state and its random number generator. Globals aren't saved: the stack
+    // pointer is back at its initial value between calls and gas is reset
+    // before each one.
+    memory: Vec<u8>,
+    states: Coarena<LocalSystemState>,
     next_id: u32,
+    pending: Vec<ShipHandle>,
+    instructions: u64,
+    started: bool,
+    roster: Option<Vec<f64>>,
+}
+
+// Output of a single ship's VM tick, buffered until it can be applied to the
+// simulation in team order.
+struct ShipCommands {
+    handle: ShipHandle,
+    result: Result<(), Error>,
+    debug_text: Option<String>,
+    team_log: Option<String>,
+    debug_lines: Option<Vec<Line>>,
+    drawn_text: Option<Vec<Text>>,
+}
+
+// Runs each team's controller in three phases. Only the middle phase, which
+// executes the VMs, runs in parallel; state generation and command application
+// happen in team order so results are deterministic.
+pub fn tick_team_controllers(sim: &mut Simulation, controllers: &mut [&mut TeamController]) {
+    for controller in controllers.iter_mut() {
+        controller.prepare(sim);
+    }
+
+    run_team_controllers(controllers);
+
+    for controller in controllers.iter_mut() {
+        controller.apply(sim);
+    }
+}
+
+#[cfg(all(feature = "sys", not(target_arch = "wasm32")))]
+fn run_team_controllers(controllers: &mut [&mut TeamController]) {
+    use rayon::prelude::*;
+
+    let busy = controllers
+        .iter()
+        .filter(|controller| !controller.pending.is_empty())
+        .count();
+    if busy < 2 {
+        for controller in controllers.iter_mut() {
+            controller.run();
+        }
+        return;
+    }
+
+    // Rayon's global pool keeps its threads alive between ticks.
+    controllers
+        .par_iter_mut()
+        .for_each(|controller| controller.run());
+}
+
+// Teams run one after another in the browser. Fanning them out there would
+// mean a web worker per team with its own copy of the VM, which isn't done.
+#[cfg(not(all(feature = "sys", not(target_arch = "wasm32"))))]
+fn run_team_controllers(controllers: &mut [&mut TeamController]) {
+    for controller in controllers.iter_mut() {
+        controller.run();
+    }
 }
 
 impl TeamController {
-    pub fn create(code: &Code) -> Result<Box<TeamController>, Error> {
-        Ok(Box::new(TeamController {
-            vm: WasmVm::create(code)?,
-            states: HashMap::new(),
+    pub fn create(code: &Code, limits: &CodeLimits) -> Result<Box<TeamController>, Error> {
+        Ok(Self::new(WasmVm::create(code, limits)?))
+    }
+
+    #[cfg(feature = "sys")]
+    pub fn create_from_module(
+        compiled: &CompiledModule,
+        limits: &CodeLimits,
+    ) -> Result<Box<TeamController>, Error> {
+        Ok(Self::new(WasmVm::create_from_module(compiled, limits)?))
+    }
+
+    fn new(vm: WasmVm) -> Box<TeamController> {
+        Box::new(TeamController {
+            vm,
+            states: Coarena::new(),
             next_id: 1,
-        }))
+            pending: Vec::new(),
+            commands: Vec::new(),
+            instructions: 0,
+            started: false,
+            roster: None,
+        })
     }
 
     pub fn add_ship(&mut self, handle: ShipHandle, sim: &Simulation) -> Result<(), Error> {
@@ -88,15 +326,17 @@ impl TeamController {
             state.set(SystemState::RadarWidth, radar.width);
             state.set(SystemState::RadarMinDistance, radar.min_distance);
             state.set(SystemState::RadarMaxDistance, radar.max_distance);
+            state.set(SystemState::RadarPower, radar.power_fraction);
+            state.set(SystemState::RadarMemory, radar.get_memory());
         }
 
-        self.states.insert(handle, state);
+        self.states.insert(handle.0, state);
 
         Ok(())
     }
 
     pub fn remove_ship(&mut self, handle: ShipHandle) {
-        self.states.remove(&handle);
+        self.states.remove(handle.0, LocalSystemState::new());
         let (index, _) = handle.0.into_raw_parts();
         let index = index as i32;
         self.vm
@@ -112,59 +352,195 @@ impl TeamController {
         }
     }
 
-    pub fn tick(&mut self, sim: &mut Simulation) {
-        let mut handles: Vec<_> = self.states.keys().cloned().collect();
-        handles.sort_by_key(|x| x.0);
+    // Generates system state for each ship. Must be followed by `run` and
+    // `apply`.
+    pub fn prepare(&mut self, sim: &mut Simulation) {
+        self.pending.clear();
+        // Iterates in index order.
+        let handles: Vec<_> = self
+            .states
+            .iter()
+            .map(|(index, _)| ShipHandle(index))
+            .collect();
 
         for handle in handles {
-            if let Err(e) = self.tick_ship(sim, handle) {
+            if let Some(msg) = sim.ship(handle).data().crash_message.as_ref() {
+                sim.emit_debug_text(handle, format!("Crashed: {}", msg.clone()));
+                let mut rng = new_rng(sim.tick());
+                if rng.gen_range(0.0..1.0) < 0.2 {
+                    let color = vector![0.5, 0.5, 0.9, rng.gen_range(0.5..1.0)];
+                    let rot = Rotation2::new(rng.gen_range(0.0..TAU));
+                    let speed = 300.0 * rng.gen_range(0.0..1.0);
+                    let p = sim.ship(handle).position().vector;
+                    let v = sim.ship(handle).body().linvel()
+                        + rot.transform_vector(&vector![speed, 0.0]);
+                    let offset = v * rng.gen_range(0.0..PHYSICS_TICK_LENGTH);
+                    sim.events.particles.push(Particle {
+                        position: p + offset,
+                        velocity: v,
+                        color,
+                        lifetime: 1.0,
+                    });
+                }
+                continue;
+            }
+
+            generate_system_state(sim, handle, self.states.get_mut(handle.0).unwrap());
+            self.pending.push(handle);
+        }
+
+        if !self.started && !self.pending.is_empty() {
+            self.started = true;
+            self.roster = Some(self.build_roster(sim));
+        }
+    }
+
+    // Describes the pending ships for the `on_scenario_start` hook, in the
+    // layout of `oort_api::team::ROSTER`.
+    fn build_roster(&self, sim: &Simulation) -> Vec<f64> {
+        let origin = sim.origin();
+        let mut roster = Vec::new();
+        for &handle in self.pending.iter().take(oort_api::team::MAX_ROSTER_SIZE) {
+            let state = self.states.get(handle.0).unwrap();
+            let ship = sim.ship(handle);
+            let position = ship.position().vector + origin;
+            roster.extend_from_slice(&[
+                state.get(SystemState::Id),
+                translate_class(ship.data().class) as u32 as f64,
+                position.x,
+                position.y,
+                ship.heading(),
+            ]);
+        }
+        roster
+    }
+
+    // Runs the VM for each prepared ship. Does not touch the simulation, so
+    // different teams can run concurrently.
+    pub fn run(&mut self) {
+        self.commands.clear();
+        self.instructions = 0;
+        // A failing hook crashes every ship it was meant to set up.
+        let start_result = match self.roster.take() {
+            Some(roster) if self.vm.scenario_start.is_some() => {
+                let result = self.vm.scenario_start(&roster);
+                self.instructions += self.gas_used(GAS_PER_TICK);
+                result
+            }
+            _ => Ok(()),
+        };
+        for handle in std::mem::take(&mut self.pending) {
+            let mut commands = ShipCommands {
+                handle,
+                result: Ok(()),
+                debug_text: None,
+                team_log: None,
+                debug_lines: None,
+                drawn_text: None,
+            };
+            if let Err(e) = start_result.as_ref() {
+                commands.result = Err(e.clone());
+                self.commands.push(commands);
+                continue;
+            }
+            let budget = self.instruction_budget(handle);
+            commands.result = self.run_ship(&mut commands, budget);
+            self.instructions += self.gas_used(budget);
+            self.commands.push(commands);
+        }
+    }
+
+    // Instructions executed by all of the team's ships in the last `run`.
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    // Instructions the ship may execute this tick, set by `prepare` from its
+    // class.
+    fn instruction_budget(&self, handle: ShipHandle) -> i32 {
+        let state = self.states.get(handle.0).unwrap();
+        state.get_u64(SystemState::InstructionBudget) as i32
+    }
+
+    fn gas_used(&mut self, budget: i32) -> u64 {
+        let vm = &mut self.vm;
+        match vm.get_gas.call(vm.store_mut().deref_mut(), &[]) {
+            Ok(ret) if !ret.is_empty() => (budget - ret[0].i32().unwrap().max(0)) as u64,
+            _ => 0,
+        }
+    }
+
+    // Applies the commands produced by `run` to the simulation.
+    pub fn apply(&mut self, sim: &mut Simulation) {
+        let origin = sim.origin();
+        for commands in std::mem::take(&mut self.commands) {
+            let handle = commands.handle;
+            if let Err(e) = commands.result {
                 log::warn!("{}", e.msg);
                 sim.emit_debug_text(handle, format!("Crashed: {}", e.msg.clone()));
                 sim.ship_mut(handle).data_mut().crash_message = Some(e.msg);
+                continue;
             }
-        }
-    }
 
-    fn tick_ship(&mut self, sim: &mut Simulation, handle: ShipHandle) -> Result<(), Error> {
-        if let Some(msg) = sim.ship(handle).data().crash_message.as_ref() {
-            sim.emit_debug_text(handle, format!("Crashed: {}", msg.clone()));
-            let mut rng = new_rng(sim.tick());
-            if rng.gen_range(0.0..1.0) < 0.2 {
-                let color = vector![0.5, 0.5, 0.9, rng.gen_range(0.5..1.0)];
-                let rot = Rotation2::new(rng.gen_range(0.0..TAU));
-                let speed = 300.0 * rng.gen_range(0.0..1.0);
-                let p = sim.ship(handle).position().vector;
-                let v =
-                    sim.ship(handle).body().linvel() + rot.transform_vector(&vector![speed, 0.0]);
-                let offset = v * rng.gen_range(0.0..PHYSICS_TICK_LENGTH);
-                sim.events.particles.push(Particle {
-                    position: p + offset,
-                    velocity: v,
-                    color,
-                    lifetime: 1.0,
-                });
+            apply_system_state(sim, handle, self.states.get_mut(handle.0).unwrap());
+
+            if let Some(s) = commands.debug_text {
+                sim.emit_debug_text(handle, s);
+            }
+
+            if let Some(s) = commands.team_log {
+                sim.emit_team_log(handle, &s);
+            }
+
+            if let Some(lines) = commands.debug_lines {
+                sim.emit_debug_lines(
+                    handle,
+                    lines
+                        .iter()
+                        .map(|v| crate::debug::Line {
+                            a: point![v.x0, v.y0] - origin,
+                            b: point![v.x1, v.y1] - origin,
+                            color: color::from_u24(v.color),
+                        })
+                        .collect::<Vec<debug::Line>>(),
+                );
+            }
+
+            if let Some(texts) = commands.drawn_text {
+                let texts: Vec<Text> = texts
+                    .iter()
+                    .map(|text| Text {
+                        x: text.x - origin.x,
+                        y: text.y - origin.y,
+                        ..*text
+                    })
+                    .collect();
+                sim.emit_drawn_text(Some(handle), &texts);
             }
-            return Ok(());
         }
+    }
 
+    fn run_ship(&mut self, commands: &mut ShipCommands, budget: i32) -> Result<(), Error> {
+        let handle = commands.handle;
         let vm = &mut self.vm;
-        let state = self.states.get_mut(&handle).unwrap();
+        let state = self.states.get_mut(handle.0).unwrap();
 
         {
             translate_runtime_error(
                 vm.reset_gas
-                    .call(vm.store_mut().deref_mut(), &[GAS_PER_TICK.into()]),
+                    .call(vm.store_mut().deref_mut(), &[budget.into()]),
             )?;
 
-            generate_system_state(sim, handle, state);
-
             let store = vm.store();
             let memory_view = vm.memory.view(store.deref());
+            let size = vm.system_state_size;
             let slice = vm
                 .system_state_ptr
-                .slice(&memory_view, SystemState::Size as u32)
+                .slice(&memory_view, size)
+                .expect("system state write");
+            slice
+                .write_slice(&state.state[..size as usize])
                 .expect("system state write");
-            slice.write_slice(&state.state).expect("system state write");
         }
 
         let (index, _) = handle.0.into_raw_parts();
@@ -177,9 +553,10 @@ impl TeamController {
                 if !ret.is_empty() {
                     let gas: i32 = ret[0].i32().unwrap();
                     if gas <= 0 {
-                        return Err(Error {
-                            msg: "Ship exceeded maximum number of instructions".to_string(),
-                        });
+                        return Err(Error::new(
+                            ErrorKind::BudgetExceeded,
+                            "Ship exceeded maximum number of instructions",
+                        ));
                     }
                 }
             }
@@ -195,11 +572,12 @@ impl TeamController {
                     let null_pos = vec.iter().position(|&x| x == 0).unwrap_or(vec.len());
                     let msg = String::from_utf8_lossy(&vec[0..null_pos]).to_string();
                     if msg.is_empty() {
-                        return Err(Error {
-                            msg: "Ship exceeded maximum number of instructions".to_string(),
-                        });
+                        return Err(Error::new(
+                            ErrorKind::BudgetExceeded,
+                            "Ship exceeded maximum number of instructions",
+                        ));
                     } else {
-                        return Err(Error { msg });
+                        return Err(Error::new(ErrorKind::Panic, msg));
                     }
                 }
             }
@@ -210,21 +588,27 @@ impl TeamController {
         {
             let store = vm.store();
             let memory_view = vm.memory.view(store.deref());
+            let size = vm.system_state_size;
             let slice = vm
                 .system_state_ptr
-                .slice(&memory_view, SystemState::Size as u32)
+                .slice(&memory_view, size)
                 .expect("system state read");
             slice
-                .read_slice(&mut state.state)
+                .read_slice(&mut state.state[..size as usize])
                 .expect("system state read");
-            apply_system_state(sim, handle, state);
 
+            // The VM's memory is reused by the next ship, so copy out anything
+            // it points to.
             if state.get(SystemState::DebugTextLength) > 0.0 {
                 let offset = state.get(SystemState::DebugTextPointer) as u32;
                 let length = state.get(SystemState::DebugTextLength) as u32;
-                if let Some(s) = WasmVm::read_string(&memory_view, offset, length) {
-                    sim.emit_debug_text(handle, s);
-                }
+                commands.debug_text = WasmVm::read_string(&memory_view, offset, length);
+            }
+
+            if state.get(SystemState::TeamLogLength) > 0.0 {
+                let offset = state.get(SystemState::TeamLogPointer) as u32;
+                let length = state.get(SystemState::TeamLogLength) as u32;
+                commands.team_log = WasmVm::read_string(&memory_view, offset, length);
             }
 
             if state.get(SystemState::DebugLinesLength) > 0.0 {
@@ -233,17 +617,7 @@ impl TeamController {
                 if length <= MAX_DEBUG_LINES {
                     if let Some(lines) = WasmVm::read_vec::<Line>(&memory_view, offset, length) {
                         if validate_lines(&lines) {
-                            sim.emit_debug_lines(
-                                handle,
-                                lines
-                                    .iter()
-                                    .map(|v| crate::debug::Line {
-                                        a: point![v.x0, v.y0],
-                                        b: point![v.x1, v.y1],
-                                        color: color::from_u24(v.color),
-                                    })
-                                    .collect::<Vec<debug::Line>>(),
-                            );
+                            commands.debug_lines = Some(lines);
                         }
                     }
                 }
@@ -255,7 +629,7 @@ impl TeamController {
                 if length <= MAX_DRAWN_TEXT {
                     if let Some(texts) = WasmVm::read_vec::<Text>(&memory_view, offset, length) {
                         if validate_texts(&texts) {
-                            sim.emit_drawn_text(Some(handle), &texts);
+                            commands.drawn_text = Some(texts);
                         }
                     }
                 }
@@ -268,40 +642,104 @@ impl TeamController {
     pub fn update_environment(&mut self, environment: &Environment) -> Result<(), Error> {
         self.vm.update_environment(environment)
     }
+
+    // Size of the VM's linear memory in bytes, which only ever grows.
+    pub fn memory_size(&self) -> usize {
+        self.vm.memory_size()
+    }
+
+    // Feeds everything the AI's future behavior depends on to `hasher`: its
+    // memory, bookkeeping, and the system state of `ships`.
+    pub fn hash_state(&self, ships: &[ShipHandle], hasher: &mut impl std::hash::Hasher) {
+        if let Ok(memory) = self.vm.read_memory() {
+            hasher.write(&memory);
+        }
+        hasher.write_u32(self.next_id);
+        hasher.write_u64(self.instructions);
+        hasher.write_u8(self.started as u8);
+        for handle in ships {
+            if let Some(state) = self.states.get(handle.0) {
+                for &v in state.state.iter() {
+                    hasher.write_u64(v);
+                }
+            }
+        }
+    }
+
+    pub fn checkpoint(&self) -> Result<ControllerCheckpoint, Error> {
+        Ok(ControllerCheckpoint {
+            memory: self.vm.read_memory()?,
+            states: self.states.clone(),
+            next_id: self.next_id,
+            pending: self.pending.clone(),
+            instructions: self.instructions,
+            started: self.started,
+            roster: self.roster.clone(),
+        })
+    }
+
+    pub fn restore(&mut self, checkpoint: &ControllerCheckpoint) -> Result<(), Error> {
+        self.vm.write_memory(&checkpoint.memory)?;
+        self.states = checkpoint.states.clone();
+        self.next_id = checkpoint.next_id;
+        self.pending = checkpoint.pending.clone();
+        self.commands.clear();
+        self.instructions = checkpoint.instructions;
+        self.started = checkpoint.started;
+        self.roster = checkpoint.roster.clone();
+        Ok(())
+    }
 }
 
-#[derive(Clone)]
 pub struct WasmVm {
-    store: Rc<RefCell<wasmer::Store>>,
+    store: RefCell<wasmer::Store>,
     memory: wasmer::Memory,
     system_state_ptr: WasmPtr<u64>,
+    // Number of system state slots shared with the module.
+    system_state_size: u32,
     environment_ptr: WasmPtr<u8>,
     panic_buffer_ptr: WasmPtr<u8>,
     tick_ship: wasmer::Function,
     delete_ship: wasmer::Function,
+    // Missing from modules built against an older API.
+    scenario_start: Option<(wasmer::Function, WasmPtr<f64>)>,
     reset_gas: wasmer::Function,
     get_gas: wasmer::Function,
 }
 
 impl WasmVm {
-    pub fn create(code: &Code) -> Result<WasmVm, Error> {
+    pub fn create(code: &Code, limits: &CodeLimits) -> Result<WasmVm, Error> {
+        if let Code::Wasm(wasm) = code {
+            limits.check_code_size(wasm.len())?;
+        }
         #[cfg(feature = "js")]
-        let mut store = Store::default();
+        let store = Store::default();
         #[cfg(feature = "sys")]
-        let mut store = Store::new(wasmer_compiler_singlepass::Singlepass::new());
-        let module = match code {
-            Code::Wasm(wasm) => {
-                let wasm = limiter::rewrite(wasm)?;
-                translate_error(Module::new(&store, wasm))?
-            }
-            #[cfg(feature = "precompile")]
-            Code::Precompiled(bytes) => {
-                translate_error(unsafe { Module::deserialize(&store, bytes.clone()) })?
-            }
-            _ => unreachable!(),
-        };
+        let store = Store::new(wasmer_compiler_singlepass::Singlepass::new());
+        let module = compile(&store, code)?;
+        Self::instantiate(store, &module, limits)
+    }
+
+    #[cfg(feature = "sys")]
+    pub fn create_from_module(
+        compiled: &CompiledModule,
+        limits: &CodeLimits,
+    ) -> Result<WasmVm, Error> {
+        limits.check_code_size(compiled.code_size)?;
+        Self::instantiate(
+            Store::new(compiled.engine.clone()),
+            &compiled.module,
+            limits,
+        )
+    }
+
+    fn instantiate(
+        mut store: Store,
+        module: &Module,
+        limits: &CodeLimits,
+    ) -> Result<WasmVm, Error> {
         let import_object = imports! {};
-        let instance = Instance::new(&mut store, &module, &import_object)?;
+        let instance = Instance::new(&mut store, module, &import_object)?;
 
         let memory = translate_error(instance.exports.get_memory("memory"))?.clone();
         let system_state_offset: i32 =
@@ -310,6 +748,15 @@ impl WasmVm {
                 .i32()
                 .unwrap();
         let system_state_ptr: WasmPtr<u64> = WasmPtr::new(system_state_offset as u32);
+        let system_state_size = match instance.exports.get_global("SYSTEM_STATE_SIZE") {
+            Ok(global) => {
+                let offset = global.get(&mut store).i32().unwrap();
+                let ptr: WasmPtr<u32> = WasmPtr::new(offset as u32);
+                translate_error(ptr.read(&memory.view(&store)))?
+            }
+            Err(_) => LEGACY_SYSTEM_STATE_SIZE,
+        }
+        .min(SystemState::Size as u32);
         let environment_offset: i32 = translate_error(instance.exports.get_global("ENVIRONMENT"))?
             .get(&mut store)
             .i32()
@@ -327,20 +774,33 @@ impl WasmVm {
         let tick_ship = translate_error(instance.exports.get_function("export_tick_ship"))?.clone();
         let delete_ship =
             translate_error(instance.exports.get_function("export_delete_ship"))?.clone();
+        let scenario_start = match (
+            instance.exports.get_function("export_scenario_start"),
+            instance.exports.get_global("ROSTER"),
+        ) {
+            (Ok(function), Ok(global)) => {
+                let offset = global.get(&mut store).i32().unwrap();
+                Some((function.clone(), WasmPtr::new(offset as u32)))
+            }
+            _ => None,
+        };
         let reset_gas = translate_error(instance.exports.get_function("reset_gas"))?.clone();
         let get_gas = translate_error(instance.exports.get_function("get_gas"))?.clone();
 
         translate_runtime_error(reset_gas.call(&mut store, &[GAS_PER_TICK.into()]))?;
         translate_runtime_error(initialize.call(&mut store, &[]))?;
+        limits.check_memory(memory.view(&store).data_size())?;
 
         Ok(WasmVm {
-            store: Rc::new(RefCell::new(store)),
+            store: RefCell::new(store),
             memory,
             system_state_ptr,
+            system_state_size,
             environment_ptr,
             panic_buffer_ptr,
             tick_ship,
             delete_ship,
+            scenario_start,
             reset_gas,
             get_gas,
         })
@@ -377,6 +837,53 @@ impl WasmVm {
         Some(src_slice.to_vec())
     }
 
+    // Writes `roster` to the module's `ROSTER` and calls its
+    // `on_scenario_start` hook.
+    fn scenario_start(&self, roster: &[f64]) -> Result<(), Error> {
+        let Some((function, roster_ptr)) = self.scenario_start.as_ref() else {
+            return Ok(());
+        };
+        let count = roster.len() / oort_api::team::ROSTER_ENTRY_SIZE;
+        {
+            let store = self.store();
+            let view = self.memory.view(store.deref());
+            translate_error(
+                roster_ptr
+                    .slice(&view, roster.len() as u32)
+                    .and_then(|slice| slice.write_slice(roster)),
+            )?;
+        }
+        translate_runtime_error(
+            self.reset_gas
+                .call(self.store_mut().deref_mut(), &[GAS_PER_TICK.into()]),
+        )?;
+        translate_runtime_error(
+            function.call(self.store_mut().deref_mut(), &[(count as i32).into()]),
+        )?;
+        Ok(())
+    }
+
+    fn memory_size(&self) -> usize {
+        let store = self.store();
+        self.memory.view(store.deref()).data_size() as usize
+    }
+
+    fn read_memory(&self) -> Result<Vec<u8>, Error> {
+        let store = self.store();
+        translate_error(self.memory.view(store.deref()).copy_to_vec())
+    }
+
+    // Memory can't shrink, so anything that has grown since `data` was read
+    // is zeroed instead.
+    fn write_memory(&self, data: &[u8]) -> Result<(), Error> {
+        let store = self.store();
+        let view = self.memory.view(store.deref());
+        let grown = (view.data_size() as usize).saturating_sub(data.len());
+        translate_error(view.write(0, data))?;
+        translate_error(view.write(data.len() as u64, &vec![0; grown]))?;
+        Ok(())
+    }
+
     fn update_environment(&self, environment: &Environment) -> Result<(), Error> {
         let environment_string = environment
             .iter()
@@ -384,9 +891,7 @@ impl WasmVm {
             .collect::<Vec<String>>()
             .join("\n");
         if environment_string.len() > oort_api::MAX_ENVIRONMENT_SIZE {
-            return Err(Error {
-                msg: "environment too large".to_string(),
-            });
+            return Err(Error::new(ErrorKind::ApiMisuse, "environment too large"));
         }
         let store = self.store_mut();
         let view = self.memory.view(&store);
@@ -403,10 +908,17 @@ impl WasmVm {
     }
 }
 
+#[derive(Clone)]
 struct LocalSystemState {
     pub state: [u64; SystemState::Size as usize],
 }
 
+impl Default for LocalSystemState {
+    fn default() -> Self {
+        LocalSystemState::new()
+    }
+}
+
 impl LocalSystemState {
     fn new() -> Self {
         Self {
@@ -442,9 +954,13 @@ fn generate_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut L
         translate_class(sim.ship(handle).data().class) as u32 as f64,
     );
 
-    let position = sim.ship(handle).position();
+    let origin = sim.origin();
+    let position = sim.ship(handle).position().vector + origin;
     state.set(SystemState::PositionX, position.x);
     state.set(SystemState::PositionY, position.y);
+    let velocity_field = sim.velocity_field().velocity(position);
+    state.set(SystemState::VelocityFieldX, velocity_field.x);
+    state.set(SystemState::VelocityFieldY, velocity_field.y);
 
     let velocity = sim.ship(handle).velocity();
     state.set(SystemState::VelocityX, velocity.x);
@@ -456,16 +972,24 @@ fn generate_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut L
         sim.ship(handle).angular_velocity(),
     );
 
+    let designated_contact = sim.ship_mut(handle).data_mut().designated_contact.take();
     if let Some(radar) = sim.ship_mut(handle).data_mut().radar.as_mut() {
         state.set(SystemState::RadarHeading, radar.get_heading());
         state.set(SystemState::RadarWidth, radar.get_width());
         state.set(SystemState::RadarMinDistance, radar.get_min_distance());
         state.set(SystemState::RadarMaxDistance, radar.get_max_distance());
+        state.set(SystemState::RadarMaxRange, radar.get_max_range());
 
-        if let Some(contact) = radar.scan() {
+        if let Some((contact, age)) = radar.contact() {
             state.set(SystemState::RadarContactFound, 1.0);
-            state.set(SystemState::RadarContactPositionX, contact.position.x);
-            state.set(SystemState::RadarContactPositionY, contact.position.y);
+            state.set(
+                SystemState::RadarContactPositionX,
+                contact.position.x + origin.x,
+            );
+            state.set(
+                SystemState::RadarContactPositionY,
+                contact.position.y + origin.y,
+            );
             state.set(SystemState::RadarContactVelocityX, contact.velocity.x);
             state.set(SystemState::RadarContactVelocityY, contact.velocity.y);
             state.set(
@@ -474,13 +998,60 @@ fn generate_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut L
             );
             state.set(SystemState::RadarContactRssi, contact.rssi);
             state.set(SystemState::RadarContactSnr, contact.snr);
+            state.set(
+                SystemState::RadarContactAge,
+                age as f64 * PHYSICS_TICK_LENGTH,
+            );
+        } else if let Some(designation) = designated_contact {
+            // The designation was made on the tick before launch.
+            let position =
+                designation.position + designation.velocity * PHYSICS_TICK_LENGTH + origin;
+            state.set(SystemState::RadarContactFound, 1.0);
+            state.set(SystemState::RadarContactPositionX, position.x);
+            state.set(SystemState::RadarContactPositionY, position.y);
+            state.set(SystemState::RadarContactVelocityX, designation.velocity.x);
+            state.set(SystemState::RadarContactVelocityY, designation.velocity.y);
+            state.set(
+                SystemState::RadarContactClass,
+                designation.class as u32 as f64,
+            );
+            state.set(SystemState::RadarContactAge, 0.0);
         } else {
             state.set(SystemState::RadarContactFound, 0.0);
         }
+
+        state.set(SystemState::RadarPower, radar.get_power_fraction());
+        state.set(SystemState::RadarMemory, radar.get_memory());
+        if let Some(warning) = radar.warning {
+            state.set(SystemState::RadarWarningFound, 1.0);
+            state.set(SystemState::RadarWarningBearing, warning.bearing);
+            state.set(SystemState::RadarWarningRssi, warning.rssi);
+        } else {
+            state.set(SystemState::RadarWarningFound, 0.0);
+        }
+        if let Some(bearing) = radar.jam_bearing {
+            state.set(SystemState::JamFound, 1.0);
+            state.set(SystemState::JamBearing, bearing);
+        } else {
+            state.set(SystemState::JamFound, 0.0);
+        }
+        if let Some(contact) = radar.passive_contact {
+            state.set(SystemState::PassiveContactFound, 1.0);
+            state.set(SystemState::PassiveContactBearing, contact.bearing);
+            state.set(SystemState::PassiveContactRssi, contact.rssi);
+        } else {
+            state.set(SystemState::PassiveContactFound, 0.0);
+        }
     } else if let Some(target) = sim.ship(handle).data().target.as_ref() {
         state.set(SystemState::RadarContactFound, 1.0);
-        state.set(SystemState::RadarContactPositionX, target.position.x);
-        state.set(SystemState::RadarContactPositionY, target.position.y);
+        state.set(
+            SystemState::RadarContactPositionX,
+            target.position.x + origin.x,
+        );
+        state.set(
+            SystemState::RadarContactPositionY,
+            target.position.y + origin.y,
+        );
         state.set(SystemState::RadarContactVelocityX, target.velocity.x);
         state.set(SystemState::RadarContactVelocityY, target.velocity.y);
         state.set(
@@ -509,7 +1080,28 @@ fn generate_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut L
             data.max_angular_acceleration,
         );
         state.set(SystemState::Health, data.health);
+        state.set(SystemState::DamageTaken, data.damage_taken);
+        state.set_u64(SystemState::LaunchedWeapons, data.launched_weapons);
+        state.set_u64(SystemState::ReloadedWeapons, data.reloaded_weapons);
         state.set(SystemState::Fuel, data.fuel.unwrap_or(f64::INFINITY));
+        state.set(
+            SystemState::BurnTimeRemaining,
+            match data.burn_profile {
+                Some(profile) => {
+                    profile.burnout_ticks().saturating_sub(data.burn_ticks) as f64
+                        * PHYSICS_TICK_LENGTH
+                }
+                None => f64::INFINITY,
+            },
+        );
+    }
+
+    {
+        let mut ship = sim.ship_mut(handle);
+        let data = ship.data_mut();
+        data.damage_taken = 0.0;
+        data.launched_weapons = 0;
+        data.reloaded_weapons = 0;
     }
 
     for (i, radio) in sim.ship(handle).data().radios.iter().enumerate() {
@@ -540,9 +1132,63 @@ fn generate_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut L
     {
         state.set(*idx, sim.ship(handle).get_reload_ticks(i) as f64)
     }
+    for (i, idx) in [
+        SystemState::Ammo0,
+        SystemState::Ammo1,
+        SystemState::Ammo2,
+        SystemState::Ammo3,
+    ]
+    .iter()
+    .enumerate()
+    {
+        state.set(*idx, sim.ship(handle).get_ammo(i))
+    }
+    state.set_u64(SystemState::ReadyWeapons, sim.ship(handle).ready_weapons());
+    state.set_u64(
+        SystemState::InstructionBudget,
+        sim.ship(handle).data().instructions_per_tick as u64,
+    );
+    state.set(SystemState::Mass, sim.ship(handle).body().mass());
+    state.set(
+        SystemState::MomentOfInertia,
+        sim.ship(handle).moment_of_inertia(),
+    );
+
+    let mut gun_heat = 0;
+    for i in 0..4 {
+        let heat = sim.ship(handle).get_gun_heat(i).clamp(0.0, 1.0);
+        gun_heat |= ((heat * u16::MAX as f64).round() as u64) << (16 * i);
+    }
+    state.set_u64(SystemState::GunHeat, gun_heat);
+    for (i, idx) in [
+        SystemState::GunArc0,
+        SystemState::GunArc1,
+        SystemState::GunArc2,
+        SystemState::GunArc3,
+    ]
+    .iter()
+    .enumerate()
+    {
+        let (min, max) = sim.ship(handle).get_gun_arc(i).unwrap_or((0.0, 0.0));
+        state.set_u64(*idx, pack_f32_pair(min, max));
+    }
+
+    let team = sim.ship(handle).data().team;
+    state.set(SystemState::TeamResources, sim.team_resources(team));
+
+    let ship = sim.ship(handle);
+    let imu = &ship.data().imu;
+    state.set(SystemState::ImuAccelerationX, imu.acceleration.x);
+    state.set(SystemState::ImuAccelerationY, imu.acceleration.y);
+    state.set(
+        SystemState::ImuAngularAcceleration,
+        imu.angular_acceleration,
+    );
 }
 
 fn apply_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut LocalSystemState) {
+    let mut calls = vec![];
+
     sim.ship_mut(handle).accelerate(Vec2::new(
         state.get(SystemState::AccelerateX),
         state.get(SystemState::AccelerateY),
@@ -553,28 +1199,162 @@ fn apply_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut Loca
     sim.ship_mut(handle).torque(state.get(SystemState::Torque));
     state.set(SystemState::Torque, 0.0);
 
-    for (i, (aim, fire)) in [
-        (SystemState::Aim0, SystemState::Fire0),
-        (SystemState::Aim1, SystemState::Fire1),
-        (SystemState::Aim2, SystemState::Fire2),
-        (SystemState::Aim3, SystemState::Fire3),
+    let aim_all_target = state.get_u64(SystemState::AimAllTarget);
+    if aim_all_target != 0 {
+        let (x, y) = unpack_f32_pair(aim_all_target);
+        let point = vector![x, y] - sim.origin();
+        sim.ship_mut(handle).aim_all_at(point);
+        calls.push((ApiCall::AimAllAt, ApiCallOutcome::Ok));
+        // Firing re-aims from the Aim states, so copy the headings back.
+        for (i, aim) in [
+            SystemState::Aim0,
+            SystemState::Aim1,
+            SystemState::Aim2,
+            SystemState::Aim3,
+        ]
+        .iter()
+        .enumerate()
+        {
+            if let Some(gun) = sim.ship(handle).data().guns.get(i) {
+                if gun.min_angle != gun.max_angle {
+                    state.set(*aim, gun.heading);
+                }
+            }
+        }
+        state.set_u64(SystemState::AimAllTarget, 0);
+    }
+
+    let designation = state.get_u64(SystemState::DesignationPosition);
+    if designation != 0 {
+        let (x, y) = unpack_f32_pair(designation);
+        let (vx, vy) = unpack_f32_pair(state.get_u64(SystemState::DesignationVelocity));
+        let position = vector![x, y] - sim.origin();
+        sim.ship_mut(handle).data_mut().launch_designation = Some(Designation {
+            class: Class::from_f64(state.get(SystemState::DesignationClass)),
+            position,
+            velocity: vector![vx, vy],
+        });
+        state.set_u64(SystemState::DesignationPosition, 0);
+    }
+
+    let spread = if state.get(SystemState::FireSpreadCount) > 0.0 {
+        let headings: Vec<f64> = oort_api::FIRE_SPREAD_SLOTS
+            .iter()
+            .take(state.get(SystemState::FireSpreadCount) as usize)
+            .map(|slot| state.get(*slot))
+            .collect();
+        state.set(SystemState::FireSpreadCount, 0.0);
+        Some((state.get(SystemState::FireSpreadWeapon) as usize, headings))
+    } else {
+        None
+    };
+
+    for (i, (aim, fire, fire_at)) in [
+        (SystemState::Aim0, SystemState::Fire0, SystemState::FireAt0),
+        (SystemState::Aim1, SystemState::Fire1, SystemState::FireAt1),
+        (SystemState::Aim2, SystemState::Fire2, SystemState::FireAt2),
+        (SystemState::Aim3, SystemState::Fire3, SystemState::FireAt3),
     ]
     .iter()
     .enumerate()
     {
-        if state.get(*fire) > 0.0 {
+        let fire_at_target = state.get_u64(*fire_at);
+        if fire_at_target != 0 {
+            let (x, y) = unpack_f32_pair(fire_at_target);
+            let point = vector![x, y] - sim.origin();
+            let outcome = fire_outcome(sim, handle, i, |ship| ship.fire_at(i as i64, point));
+            calls.push((ApiCall::FireAt { index: i }, outcome));
+            state.set_u64(*fire_at, 0);
+            state.set(*fire, 0.0);
+        } else if let Some((_, headings)) = spread.as_ref().filter(|(index, _)| *index == i) {
             sim.ship_mut(handle).aim(i as i64, state.get(*aim));
-            sim.ship_mut(handle).fire(i as i64);
+            let outcome = fire_outcome(sim, handle, i, |ship| ship.fire_spread(i as i64, headings));
+            calls.push((ApiCall::FireSpread { index: i }, outcome));
+            state.set(*fire, 0.0);
+        } else if state.get(*fire) > 0.0 {
+            sim.ship_mut(handle).aim(i as i64, state.get(*aim));
+            let outcome = fire_outcome(sim, handle, i, |ship| ship.fire(i as i64));
+            calls.push((ApiCall::Fire { index: i }, outcome));
             state.set(*fire, 0.0);
         }
     }
+    sim.ship_mut(handle).data_mut().launch_designation = None;
 
     if let Some(radar) = sim.ship_mut(handle).data_mut().radar.as_mut() {
         radar.set_heading(state.get(SystemState::RadarHeading));
         radar.set_width(state.get(SystemState::RadarWidth));
         radar.set_min_distance(state.get(SystemState::RadarMinDistance));
         radar.set_max_distance(state.get(SystemState::RadarMaxDistance));
+        if state.get(SystemState::RadarClosingSpeedFilter) != 0.0 {
+            radar.set_min_closing_speed(state.get(SystemState::RadarMinClosingSpeed));
+            radar.set_max_closing_speed(state.get(SystemState::RadarMaxClosingSpeed));
+        } else {
+            radar.set_min_closing_speed(f64::NEG_INFINITY);
+            radar.set_max_closing_speed(f64::INFINITY);
+        }
         radar.set_ecm_mode(translate_ecm_mode(state.get(SystemState::RadarEcmMode)));
+        radar.set_power_fraction(state.get(SystemState::RadarPower));
+        radar.set_memory(state.get(SystemState::RadarMemory));
+        radar.passive = state.get(SystemState::RadarPassive) != 0.0;
+    }
+
+    sim.ship_mut(handle).data_mut().transponder.enabled =
+        state.get(SystemState::TransponderDisabled) == 0.0;
+    sim.ship_mut(handle).data_mut().collision_assist =
+        state.get(SystemState::CollisionAssist) != 0.0;
+    sim.ship_mut(handle).data_mut().rotation_stabilizer =
+        state.get(SystemState::RotationStabilizer) != 0.0;
+    sim.ship_mut(handle).data_mut().flak_mode = state.get(SystemState::FlakMode) != 0.0;
+    sim.ship_mut(handle).data_mut().home_on_jam = state.get(SystemState::HomeOnJam) != 0.0;
+
+    let orbit_radius = state.get(SystemState::OrbitRadius);
+    if orbit_radius == 0.0 {
+        sim.ship_mut(handle).data_mut().orbit = None;
+    } else {
+        let origin = sim.origin();
+        let direction = if orbit_radius > 0.0 {
+            OrbitDirection::Counterclockwise
+        } else {
+            OrbitDirection::Clockwise
+        };
+        let (x, y) = unpack_f32_pair(state.get_u64(SystemState::OrbitContactPosition));
+        let contact_position = vector![x, y] - origin;
+        let (vx, vy) = unpack_f32_pair(state.get_u64(SystemState::OrbitContactVelocity));
+        let contact_velocity = vector![vx, vy];
+        // Keep extrapolating the contact until the AI gives us a new one.
+        let unchanged = sim
+            .ship(handle)
+            .data()
+            .orbit
+            .as_ref()
+            .map_or(false, |orbit| {
+                orbit.radius == orbit_radius.abs()
+                    && orbit.direction == direction
+                    && orbit.contact_position == contact_position
+                    && orbit.contact_velocity == contact_velocity
+            });
+        if !unchanged {
+            sim.ship_mut(handle).data_mut().orbit = Some(Orbit::new(
+                contact_position,
+                contact_velocity,
+                orbit_radius.abs(),
+                direction,
+            ));
+        }
+    }
+
+    let reinforcement_request = state.get(SystemState::ReinforcementRequest);
+    if reinforcement_request > 0.0 {
+        let class = Class::from_f64(reinforcement_request - 1.0);
+        if let Some(class) = translate_api_class(class) {
+            let team = sim.ship(handle).data().team;
+            let outcome = match sim.request_reinforcement(team, class) {
+                Some(_) => ApiCallOutcome::Ok,
+                None => ApiCallOutcome::Denied,
+            };
+            calls.push((ApiCall::RequestReinforcement(class), outcome));
+        }
+        state.set(SystemState::ReinforcementRequest, 0.0);
     }
 
     let active_abilities = ActiveAbilities(state.get_u64(SystemState::ActivateAbility));
@@ -583,15 +1363,32 @@ fn apply_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut Loca
         let requested = active_abilities.get_ability(ability);
         if requested != current {
             if requested {
+                let outcome = api_log::ability_outcome(&sim.ship(handle), ability);
                 sim.ship_mut(handle).activate_ability(ability);
+                calls.push((ApiCall::ActivateAbility(ability), outcome));
             } else {
                 sim.ship_mut(handle).deactivate_ability(ability);
             }
         }
     }
 
+    let launch_ttl = state.get(SystemState::LaunchTtl);
+    sim.ship_mut(handle).data_mut().launch_ttl = if launch_ttl > 0.0 {
+        Some((launch_ttl / PHYSICS_TICK_LENGTH).round() as u64)
+    } else {
+        None
+    };
+
+    if state.get(SystemState::Disarm) > 0.0 {
+        if sim.ship(handle).data().warhead.count > 0 {
+            calls.push((ApiCall::Disarm, ApiCallOutcome::Ok));
+        }
+        sim.ship_mut(handle).disarm();
+    }
+
     if state.get(SystemState::Explode) > 0.0 {
         sim.ship_mut(handle).explode();
+        calls.push((ApiCall::Explode, ApiCallOutcome::Ok));
         state.set(SystemState::Explode, 0.0);
     }
 
@@ -612,8 +1409,32 @@ fn apply_system_state(sim: &mut Simulation, handle: ShipHandle, state: &mut Loca
                 state.get(idxs.data[3]),
             ];
             radio.set_sent(Some(msg));
+            calls.push((ApiCall::SendMessage { radio: i }, ApiCallOutcome::Ok));
         }
     }
+
+    let tick = sim.tick();
+    let api_calls = &mut sim.ship_mut(handle).data_mut().api_calls;
+    for (call, outcome) in calls {
+        api_calls.push(tick, call, outcome);
+    }
+}
+
+// Fires weapon `index` with `f` and returns how it went.
+fn fire_outcome(
+    sim: &mut Simulation,
+    handle: ShipHandle,
+    index: usize,
+    f: impl FnOnce(&mut ShipAccessorMut),
+) -> ApiCallOutcome {
+    let outcome = api_log::fire_outcome(&sim.ship(handle), index);
+    let blocked = sim.events().blocked_launches.len();
+    f(&mut sim.ship_mut(handle));
+    if sim.events().blocked_launches.len() > blocked {
+        ApiCallOutcome::Blocked
+    } else {
+        outcome
+    }
 }
 
 fn translate_class(class: ShipClass) -> Class {
@@ -625,10 +1446,23 @@ fn translate_class(class: ShipClass) -> Class {
         ShipClass::Target => Class::Target,
         ShipClass::Missile => Class::Missile,
         ShipClass::Torpedo => Class::Torpedo,
+        ShipClass::Station => Class::Station,
+        ShipClass::Turret => Class::Turret,
+        ShipClass::Corvette => Class::Corvette,
         _ => Class::Unknown,
     }
 }
 
+fn translate_api_class(class: Class) -> Option<ShipClass> {
+    match class {
+        Class::Fighter => Some(ShipClass::Fighter),
+        Class::Frigate => Some(ShipClass::Frigate),
+        Class::Corvette => Some(ShipClass::Corvette),
+        Class::Cruiser => Some(ShipClass::Cruiser),
+        _ => None,
+    }
+}
+
 fn translate_ecm_mode(v: f64) -> EcmMode {
     let v = v as u32;
     if v == EcmMode::None as u32 {
@@ -646,18 +1480,20 @@ where
 {
     match err {
         Ok(val) => Ok(val),
-        Err(err) => Err(Error {
-            msg: format!("Wasmer error: {err:?}"),
-        }),
+        Err(err) => Err(Error::new(
+            ErrorKind::Compile,
+            format!("Wasmer error: {err:?}"),
+        )),
     }
 }
 
 fn translate_runtime_error<T>(err: Result<T, wasmer::RuntimeError>) -> Result<T, Error> {
     match err {
         Ok(val) => Ok(val),
-        Err(err) => Err(Error {
-            msg: format!("Ship runtime error: {err:?}"),
-        }),
+        Err(err) => Err(Error::new(
+            ErrorKind::SandboxViolation,
+            format!("Ship runtime error: {err:?}"),
+        )),
     }
 }
 
@@ -677,6 +1513,56 @@ fn validate_texts(texts: &[Text]) -> bool {
         .all(|t| validate_floats(&[t.x, t.y]) && t.length as usize <= t.text.len())
 }
 
+fn compile(store: &Store, code: &Code) -> Result<Module, Error> {
+    match code {
+        Code::Wasm(wasm) => {
+            let wasm = limiter::rewrite(wasm)?;
+            translate_error(Module::new(store, wasm))
+        }
+        #[cfg(feature = "precompile")]
+        Code::Precompiled(bytes) => {
+            translate_error(unsafe { Module::deserialize(store, bytes.clone()) })
+        }
+        _ => unreachable!(),
+    }
+}
+
+// A module compiled once and instantiated by any number of team controllers,
+// possibly on different threads.
+#[cfg(feature = "sys")]
+#[derive(Clone)]
+pub struct CompiledModule {
+    engine: wasmer::Engine,
+    module: Module,
+    code_size: usize,
+}
+
+#[cfg(feature = "sys")]
+pub fn compile_module(code: &Code) -> Result<CompiledModule, Error> {
+    match code {
+        Code::Builtin(name) => match builtin::load_compiled(name) {
+            Ok(code) => compile_module(&code),
+            Err(e) => Err(Error::new(ErrorKind::Compile, e)),
+        },
+        Code::None | Code::Rust(_) => Err(Error::new(
+            ErrorKind::Compile,
+            "Code must be compiled to WASM first",
+        )),
+        _ => {
+            let store = Store::new(wasmer_compiler_singlepass::Singlepass::new());
+            let module = compile(&store, code)?;
+            Ok(CompiledModule {
+                engine: store.engine().clone(),
+                module,
+                code_size: match code {
+                    Code::Wasm(wasm) => wasm.len(),
+                    _ => 0,
+                },
+            })
+        }
+    }
+}
+
 #[cfg(feature = "precompile")]
 pub fn precompile(code: &[u8]) -> Result<Code, Error> {
     let code = limiter::rewrite(code)?;
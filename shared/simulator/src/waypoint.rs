@@ -0,0 +1,129 @@
+use crate::ship::ShipHandle;
+use crate::simulation::Simulation;
+use nalgebra::{Rotation2, Vector2};
+use oort_api::prelude::angle_diff;
+
+// Distance in meters at which a waypoint counts as reached.
+pub const DEFAULT_ARRIVAL_RADIUS: f64 = 100.0;
+// Acceleration commanded per m/s of velocity error, in 1/s.
+pub const VELOCITY_GAIN: f64 = 2.0;
+// Angular acceleration commanded per radian of heading error and per rad/s of
+// angular velocity. Critically damped.
+pub const HEADING_GAIN: f64 = 4.0;
+pub const ANGULAR_VELOCITY_GAIN: f64 = 4.0;
+// Fraction of the ship's acceleration assumed available when slowing for the
+// final waypoint.
+pub const BRAKING_FRACTION: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    // World coordinates.
+    pub position: Vector2<f64>,
+    // Speed in m/s to fly towards this waypoint.
+    pub speed: f64,
+}
+
+// Built-in controller that flies a ship through a list of waypoints, for
+// scripted ships like convoys and patrol targets that don't need their own AI.
+// The ship turns to face its direction of travel.
+#[derive(Debug, Clone)]
+pub struct WaypointPilot {
+    pub waypoints: Vec<Waypoint>,
+    // Start again from the first waypoint after reaching the last one.
+    // Otherwise the ship stops at the last waypoint.
+    pub looping: bool,
+    pub arrival_radius: f64,
+    // Index of the waypoint being flown to.
+    pub next: usize,
+    pub finished: bool,
+}
+
+impl WaypointPilot {
+    pub fn new(waypoints: Vec<Waypoint>, looping: bool) -> Self {
+        Self {
+            finished: waypoints.is_empty(),
+            waypoints,
+            looping,
+            arrival_radius: DEFAULT_ARRIVAL_RADIUS,
+            next: 0,
+        }
+    }
+
+    // World-frame velocity to fly at from `position`, advancing to the next
+    // waypoint if the current one has been reached.
+    pub fn target_velocity(
+        &mut self,
+        position: Vector2<f64>,
+        stop_acceleration: f64,
+    ) -> Vector2<f64> {
+        if self.waypoints.is_empty() {
+            return Vector2::zeros();
+        }
+
+        if !self.finished
+            && (self.waypoints[self.next].position - position).norm() < self.arrival_radius
+        {
+            if self.next + 1 < self.waypoints.len() {
+                self.next += 1;
+            } else if self.looping {
+                self.next = 0;
+            } else {
+                self.finished = true;
+            }
+        }
+
+        let waypoint = &self.waypoints[self.next];
+        let dp = waypoint.position - position;
+        let distance = dp.norm();
+        if distance < 1e-6 {
+            return Vector2::zeros();
+        }
+        let mut speed = waypoint.speed;
+        if self.finished || (!self.looping && self.next + 1 == self.waypoints.len()) {
+            speed = speed.min((2.0 * stop_acceleration * distance).sqrt());
+        }
+        dp / distance * speed
+    }
+}
+
+// Sets the acceleration and torque of ships with a waypoint pilot. Runs after
+// the ships' AIs, overriding them.
+pub fn tick(sim: &mut Simulation) {
+    let handle_snapshot: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for handle in handle_snapshot {
+        let (position, velocity, heading, angular_velocity, stop_acceleration) = {
+            let ship = sim.ship(handle);
+            let data = ship.data();
+            if data.waypoint_pilot.is_none() {
+                continue;
+            }
+            (
+                ship.position().vector + sim.origin(),
+                ship.velocity(),
+                ship.heading(),
+                ship.angular_velocity(),
+                BRAKING_FRACTION
+                    * data
+                        .max_backward_acceleration
+                        .max(data.max_lateral_acceleration),
+            )
+        };
+
+        let mut ship = sim.ship_mut(handle);
+        let pilot = ship.data_mut().waypoint_pilot.as_mut().unwrap();
+        let target_velocity = pilot.target_velocity(position, stop_acceleration);
+
+        let acceleration = (target_velocity - velocity) * VELOCITY_GAIN;
+        ship.accelerate(Rotation2::new(-heading) * acceleration);
+
+        let target_heading = if target_velocity.norm() > 1.0 {
+            target_velocity.y.atan2(target_velocity.x)
+        } else {
+            heading
+        };
+        ship.torque(
+            HEADING_GAIN * angle_diff(heading, target_heading)
+                - ANGULAR_VELOCITY_GAIN * angular_velocity,
+        );
+    }
+}
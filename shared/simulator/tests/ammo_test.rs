@@ -0,0 +1,94 @@
+use nalgebra::vector;
+use oort_simulator::ammo::Resupply;
+use oort_simulator::api_log::{self, ApiCallOutcome};
+use oort_simulator::ship::{self, FRIGATE_MAIN_GUN_AMMO};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn empty_main_gun(sim: &mut simulation::Simulation, frigate: ship::ShipHandle) {
+    for _ in 0..FRIGATE_MAIN_GUN_AMMO {
+        sim.ship_mut(frigate).data_mut().guns[0].reload_ticks_remaining = 0;
+        sim.ship_mut(frigate).fire_gun(0);
+    }
+    sim.ship_mut(frigate).data_mut().guns[0].reload_ticks_remaining = 0;
+}
+
+#[test]
+fn test_ammo() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let frigate = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+    assert_eq!(sim.ship(frigate).get_ammo(0), FRIGATE_MAIN_GUN_AMMO as f64);
+    assert_eq!(sim.ship(frigate).get_ammo(1), f64::INFINITY);
+    assert_eq!(sim.ship(frigate).get_ammo(3), f64::INFINITY);
+    assert_eq!(sim.ship(frigate).get_ammo(4), 0.0);
+
+    empty_main_gun(&mut sim, frigate);
+    assert_eq!(sim.ship(frigate).get_ammo(0), 0.0);
+    assert_eq!(sim.ship(frigate).ready_weapons() & 1, 0);
+    assert_eq!(
+        api_log::fire_outcome(&sim.ship(frigate), 0),
+        ApiCallOutcome::OutOfAmmo
+    );
+
+    let bullets = sim.bullets.len();
+    sim.ship_mut(frigate).fire_gun(0);
+    assert_eq!(sim.bullets.len(), bullets);
+}
+
+#[test]
+fn test_resupply() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    sim.set_resupply(Some(Resupply {
+        interval: 1,
+        ..Default::default()
+    }));
+    ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::station(0),
+    );
+    let near = ship::create(
+        &mut sim,
+        vector![500.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+    let far = ship::create(
+        &mut sim,
+        vector![5000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+    let enemy = ship::create(
+        &mut sim,
+        vector![-500.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(1),
+    );
+    for &handle in &[near, far, enemy] {
+        sim.ship_mut(handle).data_mut().guns[0].ammo = 0;
+    }
+
+    for _ in 0..10 {
+        sim.step();
+    }
+    assert_eq!(sim.ship(near).get_ammo(0), 10.0);
+    assert_eq!(sim.ship(far).get_ammo(0), 0.0);
+    assert_eq!(sim.ship(enemy).get_ammo(0), 0.0);
+
+    for _ in 0..100 {
+        sim.step();
+    }
+    assert_eq!(sim.ship(near).get_ammo(0), FRIGATE_MAIN_GUN_AMMO as f64);
+}
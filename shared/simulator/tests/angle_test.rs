@@ -0,0 +1,95 @@
+use oort_api::prelude::*;
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use test_log::test;
+
+const EPSILON: f64 = 1e-9;
+
+// Random angles, including large multiples of 2π and the boundaries.
+fn angles() -> Vec<f64> {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut angles: Vec<f64> = (0..1000).map(|_| rng.gen_range(-100.0..100.0)).collect();
+    angles.extend([0.0, PI, -PI, TAU, -TAU, 1e-18, -1e-18, 3.0 * PI, -3.0 * PI]);
+    angles
+}
+
+fn same_direction(a: f64, b: f64) -> bool {
+    angle_diff(a, b).abs() < EPSILON
+}
+
+#[test]
+fn test_wrap_angle() {
+    for a in angles() {
+        let w = wrap_angle(a);
+        assert!(w > -PI && w <= PI, "wrap_angle({a}) = {w}");
+        assert!(same_direction(w, a));
+    }
+    assert_eq!(wrap_angle(PI), PI);
+    assert_eq!(wrap_angle(-PI), PI);
+}
+
+#[test]
+fn test_normalize_angle() {
+    for a in angles() {
+        let n = normalize_angle(a);
+        assert!((0.0..TAU).contains(&n), "normalize_angle({a}) = {n}");
+        assert!(same_direction(n, a));
+        assert_eq!(normalize_angle(n), n);
+    }
+    assert_eq!(normalize_angle(-1e-18), 0.0);
+}
+
+#[test]
+fn test_lerp_angle() {
+    let angles = angles();
+    for (&a, &b) in angles.iter().zip(angles.iter().rev()) {
+        assert!(same_direction(lerp_angle(a, b, 0.0), a));
+        assert!(same_direction(lerp_angle(a, b, 1.0), b));
+        let mid = lerp_angle(a, b, 0.5);
+        assert!((0.0..TAU).contains(&mid));
+        // The midpoint is halfway along the shorter way around.
+        assert!((angle_diff(a, mid).abs() - angle_diff(a, b).abs() / 2.0).abs() < EPSILON);
+    }
+    // Wraps through zero rather than going the long way.
+    approx::assert_abs_diff_eq!(lerp_angle(0.1, TAU - 0.1, 0.5), 0.0, epsilon = EPSILON);
+}
+
+#[test]
+fn test_polar() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for a in angles() {
+        let radius = rng.gen_range(0.1..1e5);
+        let v = from_polar(radius, a);
+        approx::assert_relative_eq!(v.length(), radius, max_relative = EPSILON);
+        let (r, angle) = to_polar(v);
+        approx::assert_relative_eq!(r, radius, max_relative = EPSILON);
+        assert!((0.0..TAU).contains(&angle));
+        assert!(same_direction(angle, a));
+        let w = from_polar(r, angle);
+        assert!(v.distance(w) < radius * EPSILON);
+    }
+}
+
+#[test]
+fn test_bearing() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    for a in angles() {
+        let from = vec2(rng.gen_range(-1e4..1e4), rng.gen_range(-1e4..1e4));
+        let distance = rng.gen_range(1.0..1e4);
+        let to = from + from_polar(distance, a);
+        let b = bearing(from, to);
+        assert!((0.0..TAU).contains(&b));
+        assert!((angle_diff(b, a)).abs() < 1e-6);
+
+        let heading = rng.gen_range(0.0..TAU);
+        let turn = relative_bearing(from, heading, to);
+        assert!(turn > -PI && turn <= PI);
+        assert!(same_direction(heading + turn, b));
+    }
+    approx::assert_abs_diff_eq!(
+        relative_bearing(vec2(0.0, 0.0), 0.0, vec2(0.0, -10.0)),
+        -PI / 2.0,
+        epsilon = EPSILON
+    );
+}
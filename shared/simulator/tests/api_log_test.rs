@@ -0,0 +1,75 @@
+use nalgebra::vector;
+use oort_api::Ability;
+use oort_simulator::api_log::{self, ApiCall, ApiCallLog, ApiCallOutcome, API_CALL_LOG_LENGTH};
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_log_length() {
+    let mut log = ApiCallLog::default();
+    for tick in 0..(API_CALL_LOG_LENGTH as u32 + 5) {
+        log.push(tick, ApiCall::Fire { index: 0 }, ApiCallOutcome::Ok);
+    }
+    assert_eq!(log.records().count(), API_CALL_LOG_LENGTH);
+    assert_eq!(log.records().next().unwrap().tick, 5);
+}
+
+#[test]
+fn test_fire_outcome() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    assert_eq!(
+        api_log::fire_outcome(&sim.ship(fighter), 0),
+        ApiCallOutcome::Ok
+    );
+    sim.ship_mut(fighter).fire(0);
+    assert!(matches!(
+        api_log::fire_outcome(&sim.ship(fighter), 0),
+        ApiCallOutcome::Reloading { ticks } if ticks > 0
+    ));
+
+    let launcher = sim.ship(fighter).data().guns.len();
+    sim.ship_mut(fighter).fire(launcher as i64);
+    assert!(matches!(
+        api_log::fire_outcome(&sim.ship(fighter), launcher),
+        ApiCallOutcome::Reloading { .. }
+    ));
+    assert_eq!(
+        api_log::fire_outcome(&sim.ship(fighter), 99),
+        ApiCallOutcome::Missing
+    );
+    assert_eq!(
+        api_log::ability_outcome(&sim.ship(fighter), Ability::Shield),
+        ApiCallOutcome::Missing
+    );
+}
+
+#[test]
+fn test_records_in_snapshot() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    assert!(sim.snapshot(0).api_calls.is_empty());
+
+    sim.ship_mut(fighter).data_mut().api_calls.push(
+        3,
+        ApiCall::Fire { index: 0 },
+        ApiCallOutcome::Overheated,
+    );
+    let api_calls = sim.snapshot(0).api_calls;
+    let records = &api_calls[&u64::from(fighter)];
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].to_string(), "3: fire(0): overheated");
+}
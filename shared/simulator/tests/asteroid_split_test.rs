@@ -0,0 +1,127 @@
+use approx::assert_abs_diff_eq;
+use nalgebra::{vector, Vector2};
+use oort_simulator::asteroid::{self, TIER_STRIDE};
+use oort_simulator::collision::AsteroidTerrain;
+use oort_simulator::rng::new_rng;
+use oort_simulator::ship::{self, asteroid, fighter, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::{bullet, model};
+use test_log::test;
+
+fn variant(class: ShipClass) -> i32 {
+    match class {
+        ShipClass::Asteroid { variant } => variant,
+        _ => panic!("Not an asteroid: {:?}", class),
+    }
+}
+
+#[test]
+fn test_momentum_conserved() {
+    for seed in 0..100 {
+        let mut rng = new_rng(seed);
+        let parent = asteroid(seed as i32 % asteroid::SHAPES);
+        let position = vector![1000.0, -500.0];
+        let velocity = vector![30.0, 45.0];
+        let fragments = asteroid::split(&mut rng, &parent, position, velocity, 0.5);
+        assert!((2..=3).contains(&fragments.len()));
+
+        let mass: f64 = fragments.iter().map(|f| f.data.mass).sum();
+        assert_abs_diff_eq!(mass / parent.mass, 1.0, epsilon = 1e-9);
+
+        let momentum: Vector2<f64> = fragments.iter().map(|f| f.velocity * f.data.mass).sum();
+        assert_abs_diff_eq!(
+            (momentum / parent.mass - velocity).magnitude(),
+            0.0,
+            epsilon = 1e-6
+        );
+
+        let center: Vector2<f64> = fragments
+            .iter()
+            .map(|f| f.position * f.data.mass)
+            .sum::<Vector2<f64>>()
+            / mass;
+        assert_abs_diff_eq!((center - position).magnitude(), 0.0, epsilon = 1e-6);
+
+        for fragment in fragments.iter() {
+            assert_eq!(asteroid::tier(variant(fragment.data.class)), 1);
+            assert!((fragment.velocity - velocity).magnitude() > 0.0);
+        }
+    }
+}
+
+#[test]
+fn test_fragments_smaller() {
+    let mut rng = new_rng(0);
+    let parent = asteroid(3);
+    let fragments = asteroid::split(&mut rng, &parent, vector![0.0, 0.0], vector![0.0, 0.0], 0.0);
+    for fragment in fragments.iter() {
+        assert!(model::radius(fragment.data.class) < model::radius(parent.class));
+        assert!(fragment.data.health < parent.health);
+    }
+}
+
+#[test]
+fn test_smallest_vaporize() {
+    let mut rng = new_rng(0);
+    let smallest = asteroid(asteroid::MAX_TIER * TIER_STRIDE + 3);
+    assert!(asteroid::split(
+        &mut rng,
+        &smallest,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0
+    )
+    .is_empty());
+    assert!(asteroid::split(
+        &mut rng,
+        &fighter(0),
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0
+    )
+    .is_empty());
+}
+
+fn destroy_asteroid(terrain: AsteroidTerrain) -> Vec<ShipClass> {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    sim.set_asteroid_terrain(terrain);
+    ship::create(
+        &mut sim,
+        vector![100.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        asteroid(0),
+    );
+    bullet::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![1000.0, 0.0],
+        bullet::BulletData {
+            mass: 10.0,
+            team: 0,
+            color: 0xffffffff,
+            ttl: 5.0,
+            ..Default::default()
+        },
+    );
+
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    sim.ships
+        .iter()
+        .map(|&handle| sim.ship(handle).data().class)
+        .collect()
+}
+
+#[test]
+fn test_destroyed_asteroid_splits() {
+    assert!(destroy_asteroid(AsteroidTerrain::Destructible).is_empty());
+
+    let fragments = destroy_asteroid(AsteroidTerrain::Splitting);
+    assert!((2..=3).contains(&fragments.len()));
+    for class in fragments {
+        assert_eq!(asteroid::tier(variant(class)), 1);
+    }
+}
@@ -0,0 +1,73 @@
+use oort_simulator::scenario::{self, Status};
+use oort_simulator::simulation::batch::{self, Job};
+use oort_simulator::simulation::{Code, Simulation};
+use test_log::test;
+
+#[test]
+fn test_batch_matches_sequential() {
+    let scenario_name = "tutorial_guns";
+    let codes = scenario::load(scenario_name).solution_codes();
+    let jobs: Vec<Job> = (0..4)
+        .map(|seed| Job {
+            scenario_name: scenario_name.to_string(),
+            seed,
+            codes: codes.clone(),
+        })
+        .collect();
+
+    let outcomes = batch::run(&jobs, 2).unwrap();
+    assert_eq!(outcomes.len(), jobs.len());
+
+    for (job, outcome) in jobs.iter().zip(outcomes.iter()) {
+        assert_eq!(outcome.seed, job.seed);
+        assert_eq!(outcome.status, Status::Victory { team: 0 });
+
+        let mut sim = Simulation::new(scenario_name, job.seed, &job.codes);
+        while sim.status() == Status::Running && sim.tick() < scenario::MAX_TICKS {
+            sim.step();
+        }
+        assert_eq!(outcome.ticks, sim.tick());
+        assert_eq!(outcome.hash, sim.hash());
+    }
+}
+
+#[test]
+fn test_batch_rejects_uncompiled_code() {
+    let jobs = vec![Job {
+        scenario_name: "tutorial_guns".to_string(),
+        seed: 0,
+        codes: vec![Code::Rust("fn main() {}".to_string())],
+    }];
+    assert!(batch::run(&jobs, 1).is_err());
+}
+
+#[test]
+fn test_batch_rejects_unknown_scenario() {
+    let codes = scenario::load("tutorial_guns").solution_codes();
+    let jobs: Vec<Job> = ["tutorial_guns", "no_such_scenario"]
+        .iter()
+        .map(|scenario_name| Job {
+            scenario_name: scenario_name.to_string(),
+            seed: 0,
+            codes: codes.clone(),
+        })
+        .collect();
+    assert!(batch::run(&jobs, 2).is_err());
+}
+
+#[test]
+fn test_run_match() {
+    let scenario_name = "tutorial_guns";
+    let solution = scenario::load(scenario_name).solution();
+    let job = Job {
+        scenario_name: scenario_name.to_string(),
+        seed: 3,
+        codes: vec![solution.clone(), Code::None],
+    };
+    let expected = batch::run(&[job], 1).unwrap().remove(0);
+    let outcome = batch::run_match(&solution, &Code::None, scenario_name, 3).unwrap();
+    assert_eq!(outcome, expected);
+    assert_eq!(outcome.status, Status::Victory { team: 0 });
+
+    assert!(batch::run_match(&solution, &Code::None, "no_such_scenario", 0).is_err());
+}
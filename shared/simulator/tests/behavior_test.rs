@@ -0,0 +1,81 @@
+use oort_api::behavior::*;
+use test_log::test;
+
+#[derive(Default)]
+struct Context {
+    log: Vec<&'static str>,
+    ready: bool,
+    work_remaining: u32,
+}
+
+fn step(name: &'static str, status: Status) -> BoxNode<Context> {
+    action(move |ctx: &mut Context| {
+        ctx.log.push(name);
+        status
+    })
+}
+
+#[test]
+fn test_sequence() {
+    let mut tree = sequence(vec![
+        step("a", Status::Success),
+        step("b", Status::Failure),
+        step("c", Status::Success),
+    ]);
+    let mut ctx = Context::default();
+    assert_eq!(tree.tick(&mut ctx), Status::Failure);
+    assert_eq!(ctx.log, vec!["a", "b"]);
+}
+
+#[test]
+fn test_fallback() {
+    let mut tree = fallback(vec![
+        step("a", Status::Failure),
+        step("b", Status::Success),
+        step("c", Status::Success),
+    ]);
+    let mut ctx = Context::default();
+    assert_eq!(tree.tick(&mut ctx), Status::Success);
+    assert_eq!(ctx.log, vec!["a", "b"]);
+
+    let mut tree = fallback(vec![step("a", Status::Failure)]);
+    assert_eq!(tree.tick(&mut ctx), Status::Failure);
+}
+
+#[test]
+fn test_running_child_resumes() {
+    let mut tree = sequence(vec![
+        step("check", Status::Success),
+        action(|ctx: &mut Context| {
+            ctx.log.push("work");
+            if ctx.work_remaining == 0 {
+                Status::Success
+            } else {
+                ctx.work_remaining -= 1;
+                Status::Running
+            }
+        }),
+    ]);
+    let mut ctx = Context {
+        work_remaining: 2,
+        ..Default::default()
+    };
+    assert_eq!(tree.tick(&mut ctx), Status::Running);
+    assert_eq!(tree.tick(&mut ctx), Status::Running);
+    assert_eq!(tree.tick(&mut ctx), Status::Success);
+    assert_eq!(ctx.log, vec!["check", "work", "work", "work"]);
+
+    // Starts over from the first child once finished.
+    ctx.log.clear();
+    assert_eq!(tree.tick(&mut ctx), Status::Success);
+    assert_eq!(ctx.log, vec!["check", "work"]);
+}
+
+#[test]
+fn test_condition_and_invert() {
+    let mut tree = invert(condition(|ctx: &mut Context| ctx.ready));
+    let mut ctx = Context::default();
+    assert_eq!(tree.tick(&mut ctx), Status::Success);
+    ctx.ready = true;
+    assert_eq!(tree.tick(&mut ctx), Status::Failure);
+}
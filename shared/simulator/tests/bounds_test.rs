@@ -0,0 +1,21 @@
+use oort_api::prelude::{distance_to_bounds, vec2};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use test_log::test;
+
+#[test]
+fn test_distance_to_bounds() {
+    let bounds = (vec2(-1000.0, -1000.0), vec2(1000.0, 1000.0));
+    let check = |x: f64, y: f64, heading: f64, expected: f64| {
+        let distance = distance_to_bounds(vec2(x, y), heading, bounds);
+        approx::assert_abs_diff_eq!(distance, expected, epsilon = 1e-6);
+    };
+    check(0.0, 0.0, 0.0, 1000.0);
+    check(0.0, 0.0, FRAC_PI_2, 1000.0);
+    check(0.0, 0.0, PI, 1000.0);
+    check(0.0, 0.0, -FRAC_PI_2, 1000.0);
+    check(500.0, 0.0, 0.0, 500.0);
+    check(500.0, 0.0, PI, 1500.0);
+    check(0.0, 0.0, FRAC_PI_4, 1000.0 * 2.0f64.sqrt());
+    check(900.0, 0.0, FRAC_PI_4, 100.0 * 2.0f64.sqrt());
+    check(2000.0, 0.0, 0.0, 0.0);
+}
@@ -1,5 +1,5 @@
 use nalgebra::vector;
-use oort_simulator::ship::{fighter, frigate, target};
+use oort_simulator::ship::{cruiser, fighter, frigate, target};
 use oort_simulator::simulation::{self, Code};
 use oort_simulator::{bullet, ship};
 use test_log::test;
@@ -113,3 +113,126 @@ fn test_penetration() {
     assert_ne!(bullet::data(&sim, bullet).mass, initial_bullet_mass);
     assert_ne!(*bullet::body(&sim, bullet).linvel(), initial_velocity);
 }
+
+#[test]
+fn test_tangential_velocity() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        fighter(0),
+    );
+    {
+        let mut ship = sim.ship_mut(ship0);
+        let gun = &mut ship.data_mut().guns[0];
+        gun.inaccuracy = 0.0;
+        gun.speed_error = 0.0;
+        ship.body().set_angvel(5.0, true);
+    }
+    let gun = sim.ship(ship0).data().guns[0].clone();
+
+    sim.ship_mut(ship0).fire_gun(0);
+    let bullet = *sim.bullets.iter().next().unwrap();
+    let v = bullet::body(&sim, bullet).linvel();
+    approx::assert_abs_diff_eq!(v.x, gun.speed, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(v.y, gun.offset.x * 5.0, epsilon = 1e-6);
+}
+
+#[test]
+fn test_aim_all_at() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        std::f64::consts::FRAC_PI_2,
+        frigate(0),
+    );
+
+    sim.ship_mut(ship0).aim_all_at(vector![0.0, 100.0]);
+    let guns = &sim.ship(ship0).data().guns;
+    // The main gun is fixed.
+    assert_eq!(guns[0].heading, 0.0);
+    // The turrets are mounted 15m either side of the centerline.
+    approx::assert_abs_diff_eq!(guns[1].heading, 100.0f64.atan2(15.0), epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(guns[2].heading, 100.0f64.atan2(-15.0), epsilon = 1e-9);
+}
+
+#[test]
+fn test_fire_at() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 300.0],
+        0.0,
+        frigate(0),
+    );
+    sim.ship_mut(ship0).data_mut().guns[1].inaccuracy = 0.0;
+
+    let point = vector![2000.0, 500.0];
+    sim.ship_mut(ship0).fire_at(1, point);
+    let handle = *sim.bullets.iter().next().unwrap();
+    let body = bullet::body(&sim, handle);
+    let dp = point - body.translation();
+    let v = *body.linvel();
+    approx::assert_abs_diff_eq!(dp.perp(&v) / (dp.norm() * v.norm()), 0.0, epsilon = 1e-9);
+    assert!(dp.dot(&v) > 0.0);
+}
+
+#[test]
+fn test_fire_spread() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        cruiser(0),
+    );
+    {
+        let gun = &mut sim.ship_mut(ship0).data_mut().guns[0];
+        gun.inaccuracy = 0.0;
+        gun.speed_error = 0.0;
+        assert_eq!(gun.burst_size, 6);
+    }
+
+    sim.ship_mut(ship0).fire_spread(0, &[0.1, 0.2, 0.3]);
+    let mut headings: Vec<f64> = sim
+        .bullets
+        .iter()
+        .map(|&handle| {
+            let v = bullet::body(&sim, handle).linvel();
+            v.y.atan2(v.x)
+        })
+        .collect();
+    headings.sort_by(f64::total_cmp);
+    let expected = [0.0, 0.0, 0.0, 0.1, 0.2, 0.3];
+    assert_eq!(headings.len(), expected.len());
+    for (heading, expected) in headings.iter().zip(expected.iter()) {
+        approx::assert_abs_diff_eq!(heading, expected, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_fire_spread_single_shot() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        fighter(0),
+    );
+    sim.ship_mut(ship0).data_mut().guns[0].inaccuracy = 0.0;
+
+    // Guns that fire one bullet at a time ignore the headings.
+    sim.ship_mut(ship0).fire_spread(0, &[1.0]);
+    assert_eq!(sim.bullets.len(), 1);
+    let handle = *sim.bullets.iter().next().unwrap();
+    let v = bullet::body(&sim, handle).linvel();
+    approx::assert_abs_diff_eq!(v.y.atan2(v.x), 0.0, epsilon = 1e-9);
+}
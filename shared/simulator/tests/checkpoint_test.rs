@@ -0,0 +1,53 @@
+use oort_simulator::scenario;
+use oort_simulator::simulation::checkpoint::CheckpointConfig;
+use oort_simulator::simulation::{Code, Simulation};
+use test_log::test;
+
+#[test]
+fn test_step_back() {
+    let scenario_name = "frigate_vs_cruiser";
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut reference = Simulation::new(scenario_name, 0, &codes);
+    let mut sim = Simulation::new(scenario_name, 0, &codes);
+    sim.set_checkpoints(Some(CheckpointConfig {
+        interval: 60,
+        capacity: 3,
+    }));
+
+    let mut hashes = vec![reference.hash()];
+    for _ in 0..300 {
+        reference.step();
+        hashes.push(reference.hash());
+    }
+    for _ in 0..300 {
+        sim.step();
+    }
+    assert_eq!(sim.checkpoint_ticks(), vec![180, 240, 300]);
+
+    assert!(sim.step_back(90));
+    assert_eq!(sim.tick(), 210);
+    assert_eq!(sim.hash(), hashes[210]);
+    assert_eq!(sim.checkpoint_ticks(), vec![180]);
+
+    // Stepping forward again follows the original run.
+    for _ in 0..90 {
+        sim.step();
+    }
+    assert_eq!(sim.hash(), hashes[300]);
+    assert_eq!(sim.checkpoint_ticks(), vec![180, 240, 300]);
+
+    // Further back than the oldest checkpoint.
+    assert!(!sim.step_back(200));
+    assert_eq!(sim.tick(), 300);
+}
+
+#[test]
+fn test_checkpoints_disabled() {
+    let mut sim = Simulation::new("test", 0, &[Code::None]);
+    for _ in 0..10 {
+        sim.step();
+    }
+    assert!(sim.checkpoint_ticks().is_empty());
+    assert!(!sim.step_back(5));
+    assert_eq!(sim.tick(), 10);
+}
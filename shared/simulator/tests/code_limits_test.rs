@@ -0,0 +1,40 @@
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::vm::LimitExceeded;
+use test_log::test;
+
+fn load_errors(scenario_name: &str, builtin: &str) -> Vec<Option<LimitExceeded>> {
+    let sim = simulation::Simulation::new(scenario_name, 0, &[Code::Builtin(builtin.into())]);
+    sim.events()
+        .errors
+        .iter()
+        .map(|e| e.limit_exceeded)
+        .collect()
+}
+
+#[test]
+fn test_default_limits() {
+    assert!(load_errors("test", "reference").is_empty());
+}
+
+#[test]
+fn test_code_size_limit() {
+    let errors = load_errors("code_limits_test", "reference");
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        Some(LimitExceeded::CodeSize { limit: 32768, .. })
+    ));
+}
+
+#[test]
+fn test_memory_limit() {
+    let errors = load_errors("code_limits_test", "empty");
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        Some(LimitExceeded::Memory {
+            size: 65536,
+            limit: 32768
+        })
+    ));
+}
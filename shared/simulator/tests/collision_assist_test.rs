@@ -0,0 +1,49 @@
+use oort_simulator::model;
+use oort_simulator::ship::{ShipClass, ShipHandle};
+use oort_simulator::simulation::{self, Code, Simulation};
+use test_log::test;
+
+fn min_distances(collision_assist: bool) -> (f64, f64) {
+    let mut sim = Simulation::new("collision_assist_test", 0, &[Code::None]);
+    let handles: Vec<ShipHandle> = sim.ships.iter().cloned().collect();
+    for &handle in handles.iter() {
+        sim.ship_mut(handle).data_mut().collision_assist = collision_assist;
+    }
+    let fighters: Vec<ShipHandle> = handles
+        .iter()
+        .cloned()
+        .filter(|&h| sim.ship(h).data().class == ShipClass::Fighter)
+        .collect();
+    let asteroid = *handles
+        .iter()
+        .find(|&&h| matches!(sim.ship(h).data().class, ShipClass::Asteroid { .. }))
+        .unwrap();
+
+    let distance = |sim: &Simulation, a: ShipHandle, b: ShipHandle| -> f64 {
+        (sim.ship(a).position().vector - sim.ship(b).position().vector).magnitude()
+    };
+    let mut min_fighter_distance = f64::INFINITY;
+    let mut min_asteroid_distance = f64::INFINITY;
+    for _ in 0..(simulation::PHYSICS_TICK_LENGTH.recip() as usize * 15) {
+        sim.step();
+        min_fighter_distance = min_fighter_distance.min(distance(&sim, fighters[0], fighters[1]));
+        min_asteroid_distance = min_asteroid_distance.min(distance(&sim, fighters[2], asteroid));
+    }
+    (min_fighter_distance, min_asteroid_distance)
+}
+
+#[test]
+fn test_collision_assist() {
+    let fighter_radius = model::radius(ShipClass::Fighter) as f64;
+    let asteroid_radius = model::radius(ShipClass::Asteroid { variant: 0 }) as f64;
+
+    let (fighter_distance, asteroid_distance) = min_distances(false);
+    log::info!("without assist: {fighter_distance} {asteroid_distance}");
+    assert!(fighter_distance < 2.0 * fighter_radius);
+    assert!(asteroid_distance < fighter_radius + asteroid_radius);
+
+    let (fighter_distance, asteroid_distance) = min_distances(true);
+    log::info!("with assist: {fighter_distance} {asteroid_distance}");
+    assert!(fighter_distance > 2.0 * fighter_radius);
+    assert!(asteroid_distance > fighter_radius + asteroid_radius);
+}
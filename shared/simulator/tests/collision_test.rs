@@ -1,6 +1,7 @@
 use nalgebra::vector;
-use oort_simulator::ship::{fighter, missile};
-use oort_simulator::simulation::{self, Code};
+use oort_simulator::collision::AsteroidTerrain;
+use oort_simulator::ship::{asteroid, fighter, missile};
+use oort_simulator::simulation::{self, Code, PhysicsProfile};
 use oort_simulator::{bullet, collision, ship};
 use rand::Rng;
 use test_log::test;
@@ -73,6 +74,41 @@ fn test_head_on_collision() {
     assert!(sim.ship(ship1).velocity().x > 0.0);
 }
 
+#[test]
+fn test_head_on_collision_physics_profiles() {
+    for profile in [
+        PhysicsProfile::default(),
+        PhysicsProfile::tutorial(),
+        PhysicsProfile::fleet_battle(),
+    ] {
+        let mut sim =
+            simulation::Simulation::new_with_physics_profile("test", 0, &[Code::None], profile);
+        assert_eq!(sim.physics_profile(), profile);
+
+        let ship0 = ship::create(
+            &mut sim,
+            vector![-100.0, 0.0],
+            vector![100.0, 0.0],
+            0.0,
+            fighter(0),
+        );
+        let ship1 = ship::create(
+            &mut sim,
+            vector![100.0, 0.0],
+            vector![-100.0, 0.0],
+            0.0,
+            fighter(0),
+        );
+
+        for _ in 0..1000 {
+            sim.step();
+        }
+
+        assert!(sim.ship(ship0).velocity().x < 0.0);
+        assert!(sim.ship(ship1).velocity().x > 0.0);
+    }
+}
+
 #[test]
 fn test_fighter_bullet_collision_same_team() {
     let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
@@ -93,6 +129,7 @@ fn test_fighter_bullet_collision_same_team() {
             team: 0,
             color: BULLET_COLOR,
             ttl: 5.0,
+            ..Default::default()
         },
     );
 
@@ -124,6 +161,7 @@ fn test_fighter_bullet_collision_different_team() {
             team: 1,
             color: BULLET_COLOR,
             ttl: 5.0,
+            ..Default::default()
         },
     );
 
@@ -155,6 +193,7 @@ fn test_missile_bullet_collision_same_team() {
             team: 0,
             color: BULLET_COLOR,
             ttl: 5.0,
+            ..Default::default()
         },
     );
 
@@ -186,6 +225,7 @@ fn test_missile_bullet_collision_different_team() {
             team: 1,
             color: BULLET_COLOR,
             ttl: 5.0,
+            ..Default::default()
         },
     );
 
@@ -273,6 +313,7 @@ fn test_bullet_continuous_collision_detection() {
                 team: 1,
                 color: BULLET_COLOR,
                 ttl: 1.5,
+                ..Default::default()
             },
         );
 
@@ -304,3 +345,52 @@ fn test_ship_wall_collision() {
 
     assert!(!sim.ship(ship0).exists());
 }
+
+fn shoot_asteroid(terrain: AsteroidTerrain) -> (f64, usize) {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+
+    let ship = ship::create(
+        &mut sim,
+        vector![100.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        asteroid(0),
+    );
+    sim.set_asteroid_terrain(terrain);
+
+    bullet::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![1000.0, 0.0],
+        bullet::BulletData {
+            mass: 0.1,
+            team: 0,
+            color: BULLET_COLOR,
+            ttl: 5.0,
+            ..Default::default()
+        },
+    );
+
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    (sim.ship(ship).data().health, sim.bullets.len())
+}
+
+#[test]
+fn test_asteroid_terrain() {
+    let initial_health = asteroid(0).health;
+
+    let (health, bullets) = shoot_asteroid(AsteroidTerrain::Destructible);
+    assert!(health < initial_health);
+    assert_eq!(bullets, 0);
+
+    let (health, bullets) = shoot_asteroid(AsteroidTerrain::Shielded);
+    assert_eq!(health, initial_health);
+    assert_eq!(bullets, 0);
+
+    let (health, bullets) = shoot_asteroid(AsteroidTerrain::Intangible);
+    assert_eq!(health, initial_health);
+    assert_eq!(bullets, 1);
+}
@@ -0,0 +1,233 @@
+use oort_api::constants::{self, ClassStats, GunStats};
+use oort_api::{Ability, Class};
+use oort_simulator::ship::{self, ShipClass, ShipData};
+use std::f64::consts::PI;
+use test_log::test;
+
+const README: &str = include_str!("../../api/README.md");
+
+fn ships() -> Vec<(&'static str, ShipData, ClassStats)> {
+    vec![
+        ("Fighter", ship::fighter(0), constants::FIGHTER),
+        ("Corvette", ship::corvette(0), constants::CORVETTE),
+        ("Frigate", ship::frigate(0), constants::FRIGATE),
+        ("Cruiser", ship::cruiser(0), constants::CRUISER),
+        ("Missile", ship::missile(0), constants::MISSILE),
+        ("Torpedo", ship::torpedo(0), constants::TORPEDO),
+        ("Station", ship::station(0), constants::STATION),
+        ("Turret", ship::turret(0), constants::TURRET),
+    ]
+}
+
+// Formats an angular acceleration the way the README does, as a multiple or
+// fraction of π.
+fn format_angular(v: f64) -> String {
+    let n = v / PI;
+    if n == 1.0 {
+        "π".to_string()
+    } else if n > 1.0 {
+        format!("{n}π")
+    } else {
+        format!("π/{}", 1.0 / n)
+    }
+}
+
+fn format_reload(ticks: u32) -> String {
+    let ticks_per_second = (1.0 / constants::TICK_LENGTH).round();
+    let seconds = ticks as f64 / ticks_per_second;
+    let ms = ticks as f64 * 1000.0 / ticks_per_second;
+    if (ms / 100.0).fract() == 0.0 {
+        format!("{seconds}s")
+    } else {
+        format!("{}ms", ms.floor())
+    }
+}
+
+fn format_gun(gun: &GunStats) -> String {
+    let mut s = format!("Gun, Speed: {} m/s", gun.speed);
+    if gun.burst_size > 1 {
+        s += &format!(", Burst size: {}", gun.burst_size);
+    }
+    s + &format!(", Reload: {}", format_reload(gun.reload_ticks))
+}
+
+// Returns the README's bullet list for `class`, with durations like
+// "2 seconds" shortened to "2s".
+fn readme_section(class: &str) -> String {
+    let start = README
+        .find(&format!("- [`{class}`](prelude::Class::{class})"))
+        .unwrap_or_else(|| panic!("{class} missing from README"));
+    let rest = &README[start + 1..];
+    let end = rest.find("\n- ").unwrap_or(rest.len());
+    README[start..start + 1 + end].replace(" seconds", "s")
+}
+
+#[test]
+fn test_ships_match_constants() {
+    for (name, data, stats) in ships() {
+        assert_eq!(data.health, stats.health, "{name}");
+        assert_eq!(data.armor.flat, stats.armor, "{name}");
+        assert_eq!(
+            data.max_forward_acceleration, stats.max_forward_acceleration,
+            "{name}"
+        );
+        assert_eq!(
+            data.max_backward_acceleration, stats.max_backward_acceleration,
+            "{name}"
+        );
+        assert_eq!(
+            data.max_lateral_acceleration, stats.max_lateral_acceleration,
+            "{name}"
+        );
+        assert_eq!(
+            data.max_angular_acceleration, stats.max_angular_acceleration,
+            "{name}"
+        );
+        assert_eq!(data.fuel, stats.fuel, "{name}");
+        let radar = data.radar.as_ref().unwrap();
+        assert_eq!(radar.power, stats.radar_power, "{name}");
+        assert_eq!(radar.rx_cross_section, stats.rx_cross_section, "{name}");
+        assert_eq!(radar.max_range, stats.radar_max_range, "{name}");
+        for launcher in data.missile_launchers.iter() {
+            assert_eq!(launcher.initial_speed, constants::LAUNCH_SPEED, "{name}");
+        }
+        for ability in data.abilities.iter() {
+            let expected = match ability.ability {
+                Ability::Boost => constants::BOOST,
+                Ability::Decoy => constants::DECOY,
+                Ability::Shield => constants::SHIELD,
+                other => panic!("{name}: unexpected ability {other:?}"),
+            };
+            assert_eq!(ability.active_time, expected.active_time, "{name}");
+            assert_eq!(ability.reload_time, expected.reload_time, "{name}");
+        }
+        assert_eq!(radar.doppler_notch, stats.doppler_notch, "{name}");
+        assert_eq!(
+            data.radar_cross_section, stats.radar_cross_section,
+            "{name}"
+        );
+        assert_eq!(data.ttl, stats.ttl.map(u64::from), "{name}");
+    }
+}
+
+#[test]
+fn test_readme_matches_constants() {
+    for (name, _, stats) in ships() {
+        let section = readme_section(name);
+        let mut expected = vec![format!("Health: {}", stats.health)];
+        if stats.armor > 0.0 {
+            expected.push(format!("Armor: {} damage absorbed per hit", stats.armor));
+        }
+        if let Some(fuel) = stats.fuel {
+            expected.push(format!("Fuel: {fuel} m/s"));
+        }
+        if stats.max_forward_acceleration > 0.0 {
+            for (label, value) in [
+                ("Forward", stats.max_forward_acceleration),
+                ("Lateral", stats.max_lateral_acceleration),
+                ("Reverse", stats.max_backward_acceleration),
+            ] {
+                expected.push(format!("{label}: {value} m/s²"));
+            }
+            expected.push(format!(
+                "Angular: {} rad/s²",
+                format_angular(stats.max_angular_acceleration)
+            ));
+        }
+        for line in expected {
+            assert!(section.contains(&line), "{name}: {line:?} not in README");
+        }
+    }
+
+    let weapons = [
+        ("Fighter", format_gun(&constants::VULCAN)),
+        (
+            "Fighter",
+            format!(
+                "Missile, Reload: {}",
+                format_reload(constants::FIGHTER_MISSILE_RELOAD_TICKS)
+            ),
+        ),
+        (
+            "Corvette",
+            format!(
+                "Missile, Reload: {}",
+                format_reload(constants::CORVETTE_MISSILE_RELOAD_TICKS)
+            ),
+        ),
+        ("Frigate", format_gun(&constants::FRIGATE_MAIN_GUN)),
+        ("Frigate", format_gun(&constants::VULCAN) + ", Turreted"),
+        (
+            "Frigate",
+            format!(
+                "Missile, Reload: {}",
+                format_reload(constants::FRIGATE_MISSILE_RELOAD_TICKS)
+            ),
+        ),
+        (
+            "Cruiser",
+            format_gun(&constants::CRUISER_FLAK) + ", Turreted",
+        ),
+        (
+            "Cruiser",
+            format!(
+                "Missile, Reload: {}",
+                format_reload(constants::CRUISER_MISSILE_RELOAD_TICKS)
+            ),
+        ),
+        (
+            "Cruiser",
+            format!(
+                "Torpedo, Reload: {}",
+                format_reload(constants::CRUISER_TORPEDO_RELOAD_TICKS)
+            ),
+        ),
+        ("Turret", format_gun(&constants::TURRET_GUN) + ", Turreted"),
+    ];
+    for (name, line) in weapons {
+        assert!(
+            readme_section(name).contains(&line),
+            "{name}: {line:?} not in README"
+        );
+    }
+}
+
+#[test]
+fn test_capabilities_match_ships() {
+    for (class, data) in [
+        (Class::Fighter, ship::fighter(0)),
+        (Class::Corvette, ship::corvette(0)),
+        (Class::Frigate, ship::frigate(0)),
+        (Class::Cruiser, ship::cruiser(0)),
+        (Class::Missile, ship::missile(0)),
+        (Class::Torpedo, ship::torpedo(0)),
+        (Class::Station, ship::station(0)),
+        (Class::Turret, ship::turret(0)),
+    ] {
+        let capabilities = constants::capabilities(class).unwrap();
+        assert_eq!(capabilities.stats.health, data.health, "{class:?}");
+        assert_eq!(capabilities.guns.len(), data.guns.len(), "{class:?}");
+        for (stats, gun) in capabilities.guns.iter().zip(data.guns.iter()) {
+            assert_eq!(stats.speed, gun.speed, "{class:?}");
+            assert_eq!(stats.bullet_ttl, gun.ttl, "{class:?}");
+            assert_eq!(stats.reload_ticks, gun.reload_ticks, "{class:?}");
+        }
+        let launchers = |launched: ShipClass| {
+            data.missile_launchers
+                .iter()
+                .filter(|launcher| launcher.class == launched)
+                .count() as u32
+        };
+        assert_eq!(
+            capabilities.missile_launchers,
+            launchers(ShipClass::Missile),
+            "{class:?}"
+        );
+        assert_eq!(
+            capabilities.torpedo_launchers,
+            launchers(ShipClass::Torpedo),
+            "{class:?}"
+        );
+    }
+    assert!(constants::capabilities(Class::Asteroid).is_none());
+}
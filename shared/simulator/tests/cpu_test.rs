@@ -0,0 +1,66 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use std::collections::BTreeMap;
+use test_log::test;
+
+#[test]
+fn test_cpu_usage() {
+    let mut sim =
+        simulation::Simulation::new("test", 0, &[Code::Builtin("reference".into()), Code::None]);
+    ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    for _ in 0..10 {
+        sim.step();
+    }
+
+    let usage = sim.cpu_usage(0);
+    assert!(usage.last_tick > 0);
+    assert!(usage.max_tick >= usage.last_tick);
+    assert!(usage.total >= usage.max_tick);
+    assert_eq!(usage.ticks_over_cap, 0);
+    assert_eq!(sim.cpu_usage(1), Default::default());
+    assert_eq!(sim.snapshot(0).cpu.get(&0), Some(&usage));
+}
+
+#[test]
+fn test_soft_cap() {
+    let mut sim = simulation::Simulation::new("cpu_test", 0, &[Code::Builtin("reference".into())]);
+    for _ in 0..10 {
+        sim.step();
+    }
+
+    // Over the cap but still running.
+    let usage = sim.cpu_usage(0);
+    assert_eq!(usage.ticks_over_cap, 10);
+    assert!(usage.last_tick > 0);
+}
+
+#[test]
+fn test_missile_budget() {
+    let mut sim =
+        simulation::Simulation::new("test", 0, &[Code::Builtin("test".to_string()), Code::None]);
+    let mut env = BTreeMap::new();
+    env.insert("TESTCASE".to_string(), "infinite_loop".to_string());
+    sim.update_environment(0, env);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::missile(0),
+    );
+    sim.step();
+
+    assert!(sim.ship(handle).data().crash_message.is_some());
+    let usage = sim.cpu_usage(0);
+    assert!(usage.last_tick > 0);
+    assert!(usage.last_tick <= ship::MISSILE_INSTRUCTIONS_PER_TICK as u64);
+    assert!(ship::MISSILE_INSTRUCTIONS_PER_TICK < ship::fighter(0).instructions_per_tick);
+    assert!(ship::TORPEDO_INSTRUCTIONS_PER_TICK < ship::fighter(0).instructions_per_tick);
+}
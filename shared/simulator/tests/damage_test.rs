@@ -1,4 +1,5 @@
 use nalgebra::vector;
+use oort_simulator::bullet::DamageFalloff;
 use oort_simulator::ship;
 use oort_simulator::ship::{cruiser, fighter, frigate, target};
 use oort_simulator::simulation::{self, Code};
@@ -10,7 +11,16 @@ fn high_health_target(team: i32) -> ship::ShipData {
     data
 }
 
-fn find_gun_dps(mut ship_data: ship::ShipData, gun: i64) -> f64 {
+fn find_gun_dps(ship_data: ship::ShipData, gun: i64) -> f64 {
+    find_gun_dps_against(ship_data, gun, high_health_target(1), 100.0)
+}
+
+fn find_gun_dps_against(
+    mut ship_data: ship::ShipData,
+    gun: i64,
+    target_data: ship::ShipData,
+    distance: f64,
+) -> f64 {
     let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
 
     let offset = ship_data.guns[gun as usize].offset;
@@ -25,10 +35,10 @@ fn find_gun_dps(mut ship_data: ship::ShipData, gun: i64) -> f64 {
     );
     let ship1 = ship::create(
         &mut sim,
-        vector![100.0, 0.0] + offset,
+        vector![distance, 0.0] + offset,
         vector![0.0, 0.0],
         0.0,
-        high_health_target(1),
+        target_data,
     );
 
     assert!(sim.ships.contains(ship0));
@@ -63,3 +73,29 @@ fn test_dps() {
     approx::assert_abs_diff_eq!(find_gun_dps(frigate(0), 2), 73.5, epsilon = 1.0);
     approx::assert_abs_diff_eq!(find_gun_dps(cruiser(0), 0), 75.7, epsilon = 1.0);
 }
+
+#[test]
+fn test_armor() {
+    let mut target = cruiser(1);
+    target.health = 1e6;
+    approx::assert_abs_diff_eq!(
+        find_gun_dps_against(fighter(0), 0, target, 500.0),
+        21.0,
+        epsilon = 1.0
+    );
+}
+
+#[test]
+fn test_damage_falloff() {
+    let mut ship_data = fighter(0);
+    ship_data.guns[0].damage_falloff = Some(DamageFalloff {
+        start: 0.5,
+        rate: 1.0,
+        min: 0.25,
+    });
+    let dps =
+        |distance| find_gun_dps_against(ship_data.clone(), 0, high_health_target(1), distance);
+    approx::assert_abs_diff_eq!(dps(100.0), 73.5, epsilon = 1.0);
+    approx::assert_abs_diff_eq!(dps(1000.0), 73.5 * 0.5, epsilon = 2.0);
+    approx::assert_abs_diff_eq!(dps(1600.0), 73.5 * 0.25, epsilon = 1.0);
+}
@@ -0,0 +1,51 @@
+use nalgebra::vector;
+use oort_api::constants;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+// Returns the reload times of a frigate's main gun and missile launcher
+// after firing each with the given fraction of its health left.
+fn reload_ticks(scenario: &str, health_fraction: f64) -> (u32, u32) {
+    let mut sim = simulation::Simulation::new(scenario, 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+    sim.ship_mut(handle).data_mut().health *= health_fraction;
+    sim.ship_mut(handle).fire_gun(0);
+    let launcher = sim.ship(handle).data().guns.len() as i64;
+    sim.ship_mut(handle).fire(launcher);
+    let data = sim.ship(handle).data();
+    (
+        data.guns[0].reload_ticks_remaining,
+        data.missile_launchers[0].reload_ticks_remaining,
+    )
+}
+
+#[test]
+fn test_damaged_reload() {
+    let full = (
+        constants::FRIGATE_MAIN_GUN.reload_ticks,
+        constants::FRIGATE_MISSILE_RELOAD_TICKS,
+    );
+
+    // Damage only matters in scenarios that opt in.
+    assert_eq!(reload_ticks("test", 0.1), full);
+    assert_eq!(reload_ticks("damaged_reload_test", 1.0), full);
+
+    // Half health gives 75% reload speed.
+    let (gun, launcher) = reload_ticks("damaged_reload_test", 0.5);
+    assert_eq!(gun, (full.0 as f64 / 0.75).round() as u32);
+    assert_eq!(launcher, (full.1 as f64 / 0.75).round() as u32);
+
+    // Reloads never get slower than DAMAGED_RELOAD_SPEED allows.
+    let (gun, _) = reload_ticks("damaged_reload_test", 0.0);
+    assert_eq!(
+        gun,
+        (full.0 as f64 / constants::DAMAGED_RELOAD_SPEED).round() as u32
+    );
+}
@@ -0,0 +1,63 @@
+use nalgebra::{vector, Point2};
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code, PHYSICS_TICK_LENGTH};
+use oort_simulator::snapshot::{self, Snapshot};
+use test_log::test;
+
+// Snapshots of a fighter accelerating steadily, one every 6 ticks (10 Hz).
+fn accelerating_fighter() -> Vec<Snapshot> {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let mut snapshots = vec![];
+    for tick in 0..=18 {
+        if tick > 0 && tick % 6 == 0 {
+            snapshots.push(sim.snapshot(0));
+        }
+        sim.ship_mut(handle).accelerate(vector![0.0, 30.0]);
+        sim.step();
+    }
+    snapshots
+}
+
+fn assert_near(a: Point2<f64>, b: Point2<f64>, epsilon: f64) {
+    assert!(nalgebra::distance(&a, &b) < epsilon, "{a} != {b}");
+}
+
+#[test]
+fn test_dead_reckon() {
+    let snapshots = accelerating_fighter();
+    let mut predicted = snapshots[0].clone();
+    for _ in 0..6 {
+        snapshot::dead_reckon(&mut predicted, PHYSICS_TICK_LENGTH);
+    }
+    let actual = &snapshots[1];
+    approx::assert_abs_diff_eq!(predicted.time, actual.time, epsilon = 1e-9);
+    assert_near(predicted.ships[0].position, actual.ships[0].position, 0.1);
+    assert!((predicted.ships[0].velocity - actual.ships[0].velocity).magnitude() < 0.1);
+}
+
+#[test]
+fn test_blend() {
+    let snapshots = accelerating_fighter();
+    let (from, to) = (&snapshots[0], &snapshots[2]);
+
+    let start = snapshot::blend(from, to, 0.0);
+    assert_near(start.ships[0].position, from.ships[0].position, 1e-9);
+    let end = snapshot::blend(from, to, 1.0);
+    approx::assert_abs_diff_eq!(end.time, to.time, epsilon = 1e-9);
+    assert_near(end.ships[0].position, to.ships[0].position, 1e-6);
+    assert!((end.ships[0].velocity - to.ships[0].velocity).magnitude() < 1e-6);
+    approx::assert_abs_diff_eq!(end.ships[0].heading, to.ships[0].heading, epsilon = 1e-6);
+
+    // Halfway lands close to the snapshot the viewer never received.
+    let middle = snapshot::blend(from, to, 0.5);
+    let skipped = &snapshots[1];
+    approx::assert_abs_diff_eq!(middle.time, skipped.time, epsilon = 1e-9);
+    assert_near(middle.ships[0].position, skipped.ships[0].position, 0.1);
+}
@@ -0,0 +1,85 @@
+use oort_simulator::scenario::{self, opponent_ai, Difficulty, DifficultySettings, OpponentTier};
+use oort_simulator::ship::ShipClass;
+use oort_simulator::simulation::{Code, Simulation};
+use oort_simulator::vm::builtin;
+use test_log::test;
+
+fn enemies(difficulty: Difficulty) -> usize {
+    let scenario_name = "station_defense";
+    let codes = scenario::load_with_difficulty(scenario_name, difficulty).initial_code();
+    let mut sim = Simulation::new_with_difficulty(scenario_name, 0, &codes, difficulty);
+    sim.step();
+    assert_eq!(sim.difficulty(), difficulty);
+    sim.ships
+        .iter()
+        .filter(|&&handle| {
+            let data = sim.ship(handle).data();
+            data.team == 1 && data.class == ShipClass::Fighter
+        })
+        .count()
+}
+
+#[test]
+fn test_enemy_count() {
+    assert_eq!(enemies(Difficulty::Easy), 2);
+    assert_eq!(enemies(Difficulty::Normal), 4);
+    assert_eq!(enemies(Difficulty::Hard), 6);
+}
+
+#[test]
+fn test_time_limit() {
+    let time_limit = |difficulty| {
+        let scenario_name = "station_defense";
+        let codes = scenario::load_with_difficulty(scenario_name, difficulty).initial_code();
+        Simulation::new_with_difficulty(scenario_name, 0, &codes, difficulty).time_limit()
+    };
+    let normal = time_limit(Difficulty::Normal);
+    assert_eq!(normal, scenario::TOURNAMENT_MAX_TICKS);
+    assert!(time_limit(Difficulty::Easy) > normal);
+    assert!(time_limit(Difficulty::Hard) < normal);
+}
+
+#[test]
+fn test_unsupported_difficulty() {
+    assert!(scenario::load_safe_with_difficulty("test", Difficulty::Normal).is_some());
+    assert!(scenario::load_safe_with_difficulty("test", Difficulty::Hard).is_none());
+    assert!(scenario::load_safe_with_difficulty("station_defense", Difficulty::Hard).is_some());
+}
+
+#[test]
+fn test_enemy_count_rounding() {
+    let settings = DifficultySettings::new(Difficulty::Easy);
+    assert_eq!(settings.enemy_count(0), 0);
+    assert_eq!(settings.enemy_count(1), 1);
+    assert_eq!(settings.enemy_count(4), 2);
+}
+
+#[test]
+fn test_opponent_tiers() {
+    assert_eq!(
+        opponent_ai(OpponentTier::Duelist),
+        Code::Builtin("opponent/duelist".to_string())
+    );
+    assert!(OpponentTier::Drone < OpponentTier::Duelist);
+    assert_eq!(
+        DifficultySettings::new(Difficulty::Easy).enemy_ai,
+        opponent_ai(OpponentTier::Gunner)
+    );
+}
+
+#[test]
+fn test_opponent_tiers_load() {
+    for tier in OpponentTier::ALL {
+        let name = format!("opponent/{}", tier.name());
+        assert!(
+            builtin::load_compiled(&name).is_ok(),
+            "missing builtin AI {}",
+            name
+        );
+        assert!(
+            builtin::load_source(&name).is_ok(),
+            "missing source for builtin AI {}",
+            name
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use nalgebra::vector;
+use oort_simulator::bullet;
+use oort_simulator::ship::{self, fighter};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn coast(drag: f64) -> (f64, f64) {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    sim.set_drag(drag);
+    let ship = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        fighter(0),
+    );
+    let bullet = bullet::create(
+        &mut sim,
+        vector![0.0, 1000.0],
+        vector![1000.0, 0.0],
+        bullet::BulletData {
+            mass: 0.1,
+            team: 0,
+            ttl: 5.0,
+            ..Default::default()
+        },
+    );
+
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    (
+        sim.ship(ship).velocity().x,
+        bullet::body(&sim, bullet).linvel().x,
+    )
+}
+
+#[test]
+fn test_no_drag() {
+    let (ship_speed, bullet_speed) = coast(0.0);
+    approx::assert_abs_diff_eq!(ship_speed, 100.0, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(bullet_speed, 1000.0, epsilon = 1e-9);
+}
+
+#[test]
+fn test_drag() {
+    let (ship_speed, bullet_speed) = coast(0.5);
+    approx::assert_abs_diff_eq!(ship_speed, 100.0 * (-0.5f64).exp(), epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(bullet_speed, 1000.0 * (-0.5f64).exp(), epsilon = 1e-6);
+}
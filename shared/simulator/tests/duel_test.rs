@@ -0,0 +1,52 @@
+use oort_api::prelude::angle_diff;
+use oort_simulator::scenario;
+use oort_simulator::ship::ShipClass;
+use oort_simulator::simulation::{Code, Simulation};
+use std::f64::consts::PI;
+use test_log::test;
+
+#[test]
+fn test_duel_name() {
+    let duel = scenario::duel(ShipClass::Fighter, ShipClass::Cruiser, 20000.0, 3);
+    assert_eq!(duel.name(), "duel-fighter-cruiser-20000-3");
+    assert!(duel.is_tournament());
+    assert_eq!(
+        scenario::load_safe(&duel.name()).map(|s| s.name()),
+        Some(duel.name())
+    );
+
+    assert!(scenario::load_safe("duel-fighter-station-20000-3").is_none());
+    assert!(scenario::load_safe("duel-fighter-cruiser-20000.0-3").is_none());
+    assert!(scenario::load_safe("duel-fighter-cruiser-1e9-3").is_none());
+    assert!(scenario::load_safe("duel-fighter-cruiser-20000").is_none());
+}
+
+#[test]
+fn test_duel_layout() {
+    for (class_a, class_b) in [
+        (ShipClass::Fighter, ShipClass::Fighter),
+        (ShipClass::Corvette, ShipClass::Frigate),
+        (ShipClass::Cruiser, ShipClass::Fighter),
+    ] {
+        for seed in 0..4 {
+            let name = scenario::duel(class_a, class_b, 15000.0, seed).name();
+            let sim = Simulation::new(&name, seed, &[Code::None, Code::None]);
+            let ships: Vec<_> = sim.ships.iter().map(|&handle| sim.ship(handle)).collect();
+            assert_eq!(ships.len(), 2);
+            assert_eq!(ships[0].data().team, 0);
+            assert_eq!(ships[0].data().class, class_a);
+            assert_eq!(ships[1].data().team, 1);
+            assert_eq!(ships[1].data().class, class_b);
+
+            // Mirrored about the center and facing each other.
+            let p0 = ships[0].position().vector;
+            let p1 = ships[1].position().vector;
+            assert!((p0 + p1).norm() < 1e-6);
+            assert!(((p1 - p0).norm() - 15000.0).abs() < 1e-6);
+            let dp = p1 - p0;
+            let bearing = dp.y.atan2(dp.x);
+            assert!(angle_diff(ships[0].heading(), bearing).abs() < 1e-6);
+            assert!(angle_diff(ships[1].heading(), bearing + PI).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::vm::ErrorKind;
+use test_log::test;
+
+#[test]
+fn test_compile_error() {
+    let sim = simulation::Simulation::new("test", 0, &[Code::Wasm(vec![0, 1, 2, 3])]);
+    let errors = &sim.events().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::Compile);
+    assert_eq!(errors[0].tick, Some(0));
+    assert_eq!(errors[0].ship, None);
+    assert!(errors[0].to_string().starts_with("Compile error: "));
+}
+
+#[test]
+fn test_budget_exceeded() {
+    let sim =
+        simulation::Simulation::new("code_limits_test", 0, &[Code::Builtin("reference".into())]);
+    let errors = &sim.events().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::BudgetExceeded);
+    assert!(errors[0].limit_exceeded.is_some());
+}
@@ -0,0 +1,83 @@
+use nalgebra::vector;
+use oort_api::events::{self, Events};
+use oort_api::prelude::{Message, ScanResult};
+use oort_api::sys::{write_system_state, write_system_state_u64};
+use oort_api::SystemState;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[derive(Default)]
+struct Recorder {
+    contacts: usize,
+    damage: f64,
+    messages: Vec<(usize, Message)>,
+    launches: Vec<usize>,
+}
+
+impl Events for Recorder {
+    fn on_radar_contact(&mut self, _contact: &ScanResult) {
+        self.contacts += 1;
+    }
+
+    fn on_damage(&mut self, amount: f64) {
+        self.damage += amount;
+    }
+
+    fn on_message(&mut self, radio: usize, message: Message) {
+        self.messages.push((radio, message));
+    }
+
+    fn on_launch(&mut self, index: usize) {
+        self.launches.push(index);
+    }
+}
+
+#[test]
+fn test_dispatch() {
+    let mut recorder = Recorder::default();
+    events::dispatch(&mut recorder);
+    assert_eq!(recorder.contacts, 0);
+    assert_eq!(recorder.damage, 0.0);
+    assert!(recorder.messages.is_empty());
+    assert!(recorder.launches.is_empty());
+
+    write_system_state(SystemState::RadarContactFound, 1.0);
+    write_system_state(SystemState::DamageTaken, 12.5);
+    write_system_state(SystemState::Radio1Receive, 1.0);
+    write_system_state(SystemState::Radio1Data2, 3.0);
+    write_system_state_u64(SystemState::LaunchedWeapons, 0b100);
+    events::dispatch(&mut recorder);
+    assert_eq!(recorder.contacts, 1);
+    assert_eq!(recorder.damage, 12.5);
+    assert_eq!(recorder.messages, vec![(1, [0.0, 0.0, 3.0, 0.0])]);
+    assert_eq!(recorder.launches, vec![2]);
+}
+
+#[test]
+fn test_simulator_records_events() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let target = ship::create(
+        &mut sim,
+        vector![300.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(1),
+    );
+
+    sim.ship_mut(fighter).fire(1);
+    sim.step();
+    assert_eq!(sim.ship(fighter).data().launched_weapons, 0b10);
+
+    for _ in 0..300 {
+        sim.step();
+    }
+    assert!(sim.ship(target).data().damage_taken > 0.0);
+}
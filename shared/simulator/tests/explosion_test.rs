@@ -0,0 +1,66 @@
+use nalgebra::vector;
+use oort_simulator::simulation::{self, Code, Simulation};
+use oort_simulator::{bullet, ship};
+use test_log::test;
+
+fn team_bullets(sim: &Simulation, team: i32) -> usize {
+    sim.bullets
+        .iter()
+        .filter(|&&handle| bullet::data(sim, handle).team == team)
+        .count()
+}
+
+#[test]
+fn test_chain_reaction() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let torpedo = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::torpedo(0),
+    );
+    let mut data = ship::fighter(1);
+    data.health = 1.0;
+    let fighter = ship::create(&mut sim, vector![50.0, 0.0], vector![0.0, 0.0], 0.0, data);
+
+    sim.ship_mut(torpedo).explode();
+    let mut secondary = 0;
+    for _ in 0..10 {
+        sim.step();
+        secondary = secondary.max(team_bullets(&sim, 1));
+    }
+    assert!(!sim.ships.contains(fighter));
+    assert_eq!(secondary, ship::fighter(1).warhead.chain_count() as usize);
+}
+
+#[test]
+fn test_no_chain_reaction_from_guns() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let missile = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::missile(1),
+    );
+    sim.ship_mut(missile).data_mut().destroyed = true;
+    sim.step();
+    assert!(!sim.ships.contains(missile));
+    assert_eq!(team_bullets(&sim, 1), 0);
+
+    let missile = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::missile(1),
+    );
+    sim.ship_mut(missile).data_mut().destroyed = true;
+    sim.ship_mut(missile).data_mut().chain_reaction = true;
+    sim.step();
+    assert_eq!(
+        team_bullets(&sim, 1),
+        ship::missile(1).warhead.chain_count() as usize
+    );
+}
@@ -0,0 +1,75 @@
+use nalgebra::{vector, Vector2};
+use oort_simulator::bullet;
+use oort_simulator::radar::ScanResult;
+use oort_simulator::ship::{self, ShipHandle};
+use oort_simulator::simulation::{self, Code, Simulation};
+use std::f64::consts::PI;
+use test_log::test;
+
+fn create(sim: &mut Simulation, team: i32, x: f64) -> ShipHandle {
+    let heading = if x < 0.0 { 0.0 } else { PI };
+    let handle = ship::create(
+        sim,
+        vector![x, 0.0],
+        vector![0.0, 0.0],
+        heading,
+        ship::fighter(team),
+    );
+    sim.ship_mut(handle)
+        .data_mut()
+        .radar
+        .as_mut()
+        .unwrap()
+        .set_heading(heading);
+    handle
+}
+
+// Places fighters from each team facing each other and has both fire their
+// guns. Returns the velocities of bullets fired by the fighter on the left and
+// its final radar contact. Team 0 is on the left unless `swap` is set.
+fn run_left(seed: u32, swap: bool) -> (Vec<Vector2<f64>>, Option<ScanResult>) {
+    let mut sim = Simulation::new("test", seed, &[Code::None, Code::None]);
+    let (x0, x1) = if swap { (1e3, -1e3) } else { (-1e3, 1e3) };
+    let team0 = create(&mut sim, 0, x0);
+    let team1 = create(&mut sim, 1, x1);
+    let left = if swap { team1 } else { team0 };
+
+    let mut velocities = vec![];
+    for _ in 0..20 {
+        let before: Vec<_> = sim.bullets.iter().cloned().collect();
+        sim.ship_mut(team0).fire_gun(0);
+        sim.ship_mut(team1).fire_gun(0);
+        let bullets: Vec<_> = sim.bullets.iter().cloned().collect();
+        for handle in bullets {
+            let body = bullet::body(&sim, handle);
+            if !before.contains(&handle) && body.translation().x < 0.0 {
+                velocities.push(*body.linvel());
+            }
+        }
+        sim.step();
+    }
+
+    (velocities, sim.ship(left).radar().unwrap().scan())
+}
+
+#[test]
+fn test_swapped_sides() {
+    let (velocities, contact) = run_left(0, false);
+    let (swapped_velocities, swapped_contact) = run_left(0, true);
+    assert!(!velocities.is_empty());
+    assert_eq!(velocities, swapped_velocities);
+
+    let contact = contact.unwrap();
+    let swapped_contact = swapped_contact.unwrap();
+    assert_eq!(contact.position, swapped_contact.position);
+    assert_eq!(contact.velocity, swapped_contact.velocity);
+    assert_eq!(contact.rssi, swapped_contact.rssi);
+}
+
+#[test]
+fn test_noise_depends_on_seed() {
+    let (velocities, contact) = run_left(0, false);
+    let (other_velocities, other_contact) = run_left(1, false);
+    assert_ne!(velocities, other_velocities);
+    assert_ne!(contact.unwrap().position, other_contact.unwrap().position);
+}
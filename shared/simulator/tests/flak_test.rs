@@ -0,0 +1,70 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn run(flak_mode: bool) -> bool {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    sim.ship_mut(cruiser).data_mut().flak_mode = flak_mode;
+    let missile = ship::create(
+        &mut sim,
+        vector![1500.0, 500.0],
+        vector![-300.0, -100.0],
+        0.0,
+        ship::missile(1),
+    );
+
+    // Returns whether the missile was destroyed before getting close.
+    for _ in 0..300 {
+        sim.step();
+        if !sim.ships.contains(missile) {
+            return true;
+        }
+        if sim.ship(missile).position().vector.norm() < 200.0 {
+            return false;
+        }
+    }
+    false
+}
+
+#[test]
+fn test_flak_intercepts_missile() {
+    assert!(run(true));
+}
+
+#[test]
+fn test_flak_disabled() {
+    assert!(!run(false));
+}
+
+#[test]
+fn test_flak_ignores_friendly_missiles() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    sim.ship_mut(cruiser).data_mut().flak_mode = true;
+    ship::create(
+        &mut sim,
+        vector![800.0, 0.0],
+        vector![-100.0, 0.0],
+        0.0,
+        ship::missile(0),
+    );
+
+    for _ in 0..10 {
+        sim.step();
+    }
+    assert_eq!(sim.bullets.len(), 0);
+}
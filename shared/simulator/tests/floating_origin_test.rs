@@ -0,0 +1,37 @@
+use oort_simulator::scenario;
+use oort_simulator::ship::ShipClass;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_floating_origin() {
+    let mut sim = simulation::Simulation::new("floating_origin_test", 0, &[Code::None]);
+    let handle = *sim.ships.iter().next().unwrap();
+
+    for _ in 0..600 {
+        sim.step();
+        let position = sim.ship(handle).position().vector;
+        assert!(position.magnitude() < 3e3, "position={position:?}");
+    }
+
+    let world_position = sim.ship(handle).position().vector + sim.origin();
+    approx::assert_abs_diff_eq!(world_position.x, -40e3, epsilon = 50.0);
+    approx::assert_abs_diff_eq!(world_position.y, 0.0, epsilon = 1e-6);
+    assert_eq!(sim.snapshot(0).origin, sim.origin());
+}
+
+#[test]
+fn test_fixed_origin() {
+    let mut sim = simulation::Simulation::new("basic", 0, &[Code::None, Code::None]);
+    for _ in 0..600 {
+        sim.step();
+    }
+    assert_eq!(sim.origin(), nalgebra::vector![0.0, 0.0]);
+}
+
+#[test]
+fn test_long_range_duel() {
+    use ShipClass::*;
+    assert!(scenario::duel(Fighter, Cruiser, 90e3, 0).floating_origin());
+    assert!(!scenario::duel(Fighter, Cruiser, 20e3, 0).floating_origin());
+}
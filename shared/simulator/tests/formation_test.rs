@@ -0,0 +1,84 @@
+use nalgebra::{distance, vector, Point2};
+use oort_simulator::scenario::{Formation, FormationShape, Placement};
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use std::f64::consts::{FRAC_PI_2, PI};
+use test_log::test;
+
+fn neighbour_distances(formation: &Formation, n: usize) -> Vec<f64> {
+    let offsets = formation.offsets(n);
+    offsets
+        .windows(2)
+        .map(|pair| distance(&Point2::from(pair[0]), &Point2::from(pair[1])))
+        .collect()
+}
+
+#[test]
+fn test_line_abreast() {
+    let formation = Formation::new(FormationShape::LineAbreast, 50.0);
+    let offsets = formation.offsets(3);
+    assert_eq!(offsets[1], vector![0.0, 0.0]);
+    for d in neighbour_distances(&formation, 3) {
+        approx::assert_abs_diff_eq!(d, 50.0, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_wedge() {
+    let formation = Formation::new(FormationShape::Wedge, 100.0);
+    assert_eq!(
+        formation.offsets(3),
+        vec![
+            vector![0.0, 0.0],
+            vector![-100.0, 100.0],
+            vector![-100.0, -100.0]
+        ]
+    );
+}
+
+#[test]
+fn test_ring() {
+    let formation = Formation::new(FormationShape::Ring, 200.0);
+    let offsets = formation.offsets(6);
+    for d in neighbour_distances(&formation, 6) {
+        approx::assert_abs_diff_eq!(d, 200.0, epsilon = 1e-9);
+    }
+    // Six ships around a ring sit as far from the center as from each other.
+    approx::assert_abs_diff_eq!(offsets[3].magnitude(), 200.0, epsilon = 1e-9);
+    assert_eq!(formation.offsets(1), vec![vector![0.0, 0.0]]);
+}
+
+#[test]
+fn test_place_and_create() {
+    let formation = Formation::new(FormationShape::LineAbreast, 100.0).with_facing(PI);
+    let placement = Placement {
+        position: vector![1000.0, 0.0],
+        heading: FRAC_PI_2,
+    };
+
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handles = formation.create(&mut sim, &placement, vec![ship::fighter(0); 2]);
+    assert_eq!(handles.len(), 2);
+    for (handle, expected) in handles.iter().zip(formation.place(&placement, 2)) {
+        let ship = sim.ship(*handle);
+        approx::assert_abs_diff_eq!(ship.position().x, expected.position.x, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(ship.position().y, expected.position.y, epsilon = 1e-9);
+    }
+    // Heading north, the line runs east to west, with every ship facing south.
+    let places = formation.place(&placement, 2);
+    approx::assert_abs_diff_eq!(places[0].position.x, 1050.0, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(places[1].position.x, 950.0, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(places[0].heading, 1.5 * PI, epsilon = 1e-9);
+}
+
+#[test]
+fn test_serialization() {
+    let formation = Formation::new(FormationShape::Wedge, 100.0).with_facing(0.5);
+    let json = serde_json::to_string(&formation).unwrap();
+    assert_eq!(serde_json::from_str::<Formation>(&json).unwrap(), formation);
+
+    // The facing can be left out.
+    let formation: Formation =
+        serde_json::from_str(r#"{"shape": "Ring", "spacing": 50.0}"#).unwrap();
+    assert_eq!(formation, Formation::new(FormationShape::Ring, 50.0));
+}
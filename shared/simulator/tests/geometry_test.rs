@@ -0,0 +1,143 @@
+use oort_api::prelude::*;
+use test_log::test;
+
+#[test]
+fn test_in_arc() {
+    let origin = vec2(100.0, 100.0);
+    assert!(in_arc(origin, 0.0, 0.5, origin + vec2(1000.0, 100.0)));
+    assert!(!in_arc(origin, 0.0, 0.5, origin + vec2(1000.0, 300.0)));
+    assert!(!in_arc(origin, 0.0, 0.5, origin + vec2(-1000.0, 0.0)));
+
+    // Wraps around at PI.
+    assert!(in_arc(origin, PI, 0.5, origin + vec2(-1000.0, 100.0)));
+    assert!(in_arc(origin, PI, 0.5, origin + vec2(-1000.0, -100.0)));
+    assert!(!in_arc(origin, PI, 0.5, origin + vec2(1000.0, 0.0)));
+
+    // Wider than a half circle.
+    assert!(in_arc(origin, 0.0, 1.5 * PI, origin + vec2(0.0, 1000.0)));
+    assert!(!in_arc(origin, 0.0, 1.5 * PI, origin + vec2(-1000.0, 0.0)));
+
+    assert!(in_arc(origin, 0.0, TAU, origin + vec2(-1000.0, 0.0)));
+}
+
+#[test]
+fn test_arcs_overlap() {
+    assert!(arcs_overlap(0.0, 0.5, 0.4, 0.5));
+    assert!(!arcs_overlap(0.0, 0.5, 0.6, 0.5));
+    assert!(arcs_overlap(PI - 0.1, 0.5, -PI + 0.1, 0.5));
+    assert!(!arcs_overlap(0.0, 1.0, PI, 1.0));
+}
+
+#[test]
+fn test_ray_circle_intersection() {
+    let check = |origin: Vec2, direction: Vec2, expected: Option<f64>| {
+        let actual = ray_circle_intersection(origin, direction, vec2(1000.0, 0.0), 100.0);
+        match (actual, expected) {
+            (Some(actual), Some(expected)) => {
+                approx::assert_abs_diff_eq!(actual, expected, epsilon = 1e-6)
+            }
+            _ => assert_eq!(actual, expected),
+        }
+    };
+    check(vec2(0.0, 0.0), vec2(1.0, 0.0), Some(900.0));
+    check(vec2(0.0, 0.0), vec2(10.0, 0.0), Some(900.0));
+    check(vec2(0.0, 0.0), vec2(-1.0, 0.0), None);
+    check(vec2(0.0, 0.0), vec2(0.0, 1.0), None);
+    check(vec2(0.0, 100.0), vec2(1.0, 0.0), Some(1000.0));
+    check(vec2(0.0, 101.0), vec2(1.0, 0.0), None);
+    check(vec2(0.0, 60.0), vec2(1.0, 0.0), Some(920.0));
+    check(vec2(1050.0, 0.0), vec2(1.0, 0.0), Some(0.0));
+}
+
+#[test]
+fn test_segment_intersects_circle() {
+    let center = vec2(1000.0, 0.0);
+    assert!(segment_intersects_circle(
+        vec2(0.0, 0.0),
+        vec2(2000.0, 0.0),
+        center,
+        100.0
+    ));
+    assert!(segment_intersects_circle(
+        vec2(0.0, 0.0),
+        vec2(950.0, 0.0),
+        center,
+        100.0
+    ));
+    assert!(!segment_intersects_circle(
+        vec2(0.0, 0.0),
+        vec2(850.0, 0.0),
+        center,
+        100.0
+    ));
+    assert!(!segment_intersects_circle(
+        vec2(0.0, 150.0),
+        vec2(2000.0, 150.0),
+        center,
+        100.0
+    ));
+    assert!(segment_intersects_circle(center, center, center, 100.0));
+}
+
+#[test]
+fn test_tangent_points() {
+    let point = vec2(0.0, 0.0);
+    let center = vec2(100.0, 0.0);
+    let (left, right) = tangent_points(point, center, 50.0).unwrap();
+    assert!(left.y > 0.0);
+    assert!(right.y < 0.0);
+    for tangent in [left, right] {
+        approx::assert_abs_diff_eq!(tangent.distance(center), 50.0, epsilon = 1e-6);
+        approx::assert_abs_diff_eq!((tangent - point).dot(tangent - center), 0.0, epsilon = 1e-6);
+    }
+    approx::assert_abs_diff_eq!(left.x, 75.0, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(left.y, 50.0 * 0.75f64.sqrt(), epsilon = 1e-6);
+
+    assert_eq!(tangent_points(vec2(120.0, 0.0), center, 50.0), None);
+}
+
+#[test]
+fn test_sector_contains() {
+    let sector = Sector::new(vec2(100.0, 0.0), PI / 2.0, PI / 2.0, 1000.0);
+    assert!(sector.contains(vec2(100.0, 0.0)));
+    assert!(sector.contains(vec2(100.0, 999.0)));
+    assert!(!sector.contains(vec2(100.0, 1001.0)));
+    assert!(sector.contains(vec2(400.0, 500.0)));
+    assert!(!sector.contains(vec2(700.0, 500.0)));
+    assert!(!sector.contains(vec2(100.0, -10.0)));
+}
+
+#[test]
+fn test_sector_overlaps() {
+    let a = Sector::new(vec2(0.0, 0.0), 0.0, 0.2, 1000.0);
+
+    // Facing each other with the tips crossing.
+    let b = Sector::new(vec2(1500.0, 0.0), PI, 0.2, 1000.0);
+    assert!(a.overlaps(&b));
+    assert!(b.overlaps(&a));
+
+    // Facing each other out of reach.
+    let b = Sector::new(vec2(2500.0, 0.0), PI, 0.2, 1000.0);
+    assert!(!a.overlaps(&b));
+
+    // Facing away.
+    let b = Sector::new(vec2(1500.0, 0.0), 0.0, 0.2, 1000.0);
+    assert!(!a.overlaps(&b));
+
+    // Crossing beams, neither containing the other's center.
+    let b = Sector::new(vec2(500.0, -500.0), PI / 2.0, 0.2, 1000.0);
+    assert!(!a.contains(b.center));
+    assert!(!b.contains(a.center));
+    assert!(a.overlaps(&b));
+    assert!(b.overlaps(&a));
+
+    // Parallel beams.
+    let b = Sector::new(vec2(0.0, 500.0), 0.0, 0.2, 1000.0);
+    assert!(!a.overlaps(&b));
+
+    // A circle whose edge cuts across the beam.
+    let b = Sector::new(vec2(500.0, 600.0), 0.0, TAU, 560.0);
+    assert!(a.overlaps(&b));
+    let b = Sector::new(vec2(500.0, 700.0), 0.0, TAU, 560.0);
+    assert!(!a.overlaps(&b));
+}
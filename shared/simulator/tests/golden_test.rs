@@ -1,5 +1,7 @@
+use nalgebra::vector;
 use oort_simulator::scenario;
-use oort_simulator::simulation;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
 use test_log::test;
 
 fn run(scenario_name: &str) -> u64 {
@@ -17,5 +19,40 @@ fn run(scenario_name: &str) -> u64 {
 
 #[test]
 fn test_frigate_vs_cruiser() {
-    assert_eq!(run("frigate_vs_cruiser"), 12731786239136837248);
+    assert_eq!(run("frigate_vs_cruiser"), 16463560253376968288);
+}
+
+#[test]
+fn test_state_hash() {
+    let scenario_name = "frigate_vs_cruiser";
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut sim0 = simulation::Simulation::new(scenario_name, 0, &codes);
+    let mut sim1 = simulation::Simulation::new(scenario_name, 0, &codes);
+    let sim2 = simulation::Simulation::new(scenario_name, 1, &codes);
+    assert_eq!(sim0.state_hash(), sim1.state_hash());
+    assert_ne!(sim0.state_hash(), sim2.state_hash());
+
+    let initial_hash = sim0.state_hash();
+    for _ in 0..60 {
+        sim0.step();
+        sim1.step();
+    }
+    assert_ne!(sim0.state_hash(), initial_hash);
+    assert_eq!(sim0.state_hash(), sim1.state_hash());
+    assert_eq!(sim0.state_hash(), 2468981286912962784);
+}
+
+#[test]
+fn test_state_hash_includes_radar() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let initial_hash = sim.state_hash();
+    sim.ship_mut(handle).radar_mut().unwrap().heading += 1.0;
+    assert_ne!(sim.state_hash(), initial_hash);
 }
@@ -0,0 +1,54 @@
+use oort_simulator::grader::Hint;
+use oort_simulator::scenario::{self, Status};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn run(sim: &mut simulation::Simulation) {
+    while sim.status() == Status::Running {
+        sim.step();
+    }
+}
+
+fn hints(sim: &simulation::Simulation) -> Vec<Hint> {
+    sim.hints().iter().map(|event| event.hint).collect()
+}
+
+#[test]
+fn test_never_fired() {
+    let mut sim = simulation::Simulation::new("tutorial_guns", 0, &[Code::None, Code::None]);
+    run(&mut sim);
+    assert_eq!(hints(&sim), vec![Hint::NeverFired]);
+    assert_eq!(sim.snapshot(0).hints, sim.hints());
+}
+
+#[test]
+fn test_fired_before_aiming() {
+    // The initial code fires while spinning.
+    let scenario = scenario::load("tutorial_rotation");
+    let mut sim = simulation::Simulation::new("tutorial_rotation", 0, &scenario.initial_code());
+    sim.step();
+    assert_eq!(hints(&sim), vec![Hint::FiredBeforeAiming]);
+    assert_eq!(sim.hints()[0].tick, 0);
+
+    run(&mut sim);
+    assert!(!hints(&sim).contains(&Hint::NeverRotated));
+    assert!(!hints(&sim).contains(&Hint::NeverFired));
+}
+
+#[test]
+fn test_no_hints_on_victory() {
+    let scenario = scenario::load("tutorial_guns");
+    let mut sim = simulation::Simulation::new("tutorial_guns", 0, &scenario.solution_codes());
+    run(&mut sim);
+    assert_eq!(sim.status(), Status::Victory { team: 0 });
+    assert!(sim.hints().is_empty());
+}
+
+#[test]
+fn test_no_grader_outside_tutorials() {
+    let mut sim = simulation::Simulation::new("basic", 0, &[Code::None, Code::None]);
+    for _ in 0..100 {
+        sim.step();
+    }
+    assert!(sim.hints().is_empty());
+}
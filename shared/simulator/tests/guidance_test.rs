@@ -0,0 +1,76 @@
+use nalgebra::{vector, Rotation2};
+use oort_api::prelude::{angle_diff, pro_nav_relative, vec2};
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::waypoint::{ANGULAR_VELOCITY_GAIN, HEADING_GAIN};
+use test_log::test;
+
+#[test]
+fn test_pro_nav_relative() {
+    // No rotation of the line of sight.
+    let a = pro_nav_relative(vec2(1000.0, 0.0), vec2(-100.0, 0.0), 4.0);
+    assert_eq!(a, vec2(0.0, 0.0));
+
+    // Target crossing to the left while closing.
+    let a = pro_nav_relative(vec2(1000.0, 0.0), vec2(-100.0, 100.0), 4.0);
+    approx::assert_abs_diff_eq!(a.x, 0.0);
+    approx::assert_abs_diff_eq!(a.y, 4.0 * 100.0 * 0.1);
+
+    assert_eq!(
+        pro_nav_relative(vec2(0.0, 0.0), vec2(1.0, 0.0), 4.0),
+        vec2(0.0, 0.0)
+    );
+}
+
+#[test]
+fn test_pro_nav_intercept() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+
+    let mut missile_data = ship::missile(0);
+    missile_data.fuel = None;
+    missile_data.ttl = None;
+    let missile = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        missile_data,
+    );
+    let target = ship::create(
+        &mut sim,
+        vector![4000.0, 0.0],
+        vector![0.0, 200.0],
+        0.0,
+        ship::fighter(1),
+    );
+
+    let max_acceleration = sim.ship(missile).data().max_forward_acceleration;
+    let mut min_distance = f64::INFINITY;
+    for _ in 0..(20 * 60) {
+        if !sim.ships.contains(missile) || !sim.ships.contains(target) {
+            break;
+        }
+        let dp = sim.ship(target).position().vector - sim.ship(missile).position().vector;
+        let dv = sim.ship(target).velocity() - sim.ship(missile).velocity();
+        min_distance = min_distance.min(dp.magnitude());
+
+        let lateral = pro_nav_relative(vec2(dp.x, dp.y), vec2(dv.x, dv.y), 4.0);
+        let lateral = vector![lateral.x, lateral.y].cap_magnitude(max_acceleration);
+        let forward = (max_acceleration.powi(2) - lateral.norm_squared()).sqrt();
+        let a = lateral + dp.normalize() * forward;
+
+        // Thrust in the ship's frame and turn toward the commanded
+        // acceleration, like an AI would.
+        let heading = sim.ship(missile).heading();
+        let angular_velocity = sim.ship(missile).angular_velocity();
+        let mut ship = sim.ship_mut(missile);
+        ship.accelerate(Rotation2::new(-heading) * a);
+        ship.torque(
+            HEADING_GAIN * angle_diff(heading, a.y.atan2(a.x))
+                - ANGULAR_VELOCITY_GAIN * angular_velocity,
+        );
+        sim.step();
+    }
+
+    assert!(min_distance < 50.0, "min_distance={min_distance}");
+}
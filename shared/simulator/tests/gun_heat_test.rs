@@ -0,0 +1,61 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_sustained_fire_overheats() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+
+    let mut overheat_tick = None;
+    for tick in 0..3000 {
+        sim.ship_mut(handle).fire_gun(0);
+        sim.step();
+        if sim.ship(handle).data().guns[0].overheated {
+            overheat_tick = Some(tick);
+            break;
+        }
+    }
+    let overheat_tick = overheat_tick.expect("gun never overheated");
+    assert!(overheat_tick > 600, "overheat_tick={overheat_tick}");
+
+    // Locked out until cooled down.
+    let mut lockout_ticks = 0;
+    while sim.ship(handle).data().guns[0].overheated {
+        let num_bullets = sim.bullets.len();
+        sim.ship_mut(handle).fire_gun(0);
+        assert_eq!(sim.bullets.len(), num_bullets);
+        sim.step();
+        lockout_ticks += 1;
+    }
+    assert!(lockout_ticks > 100, "lockout_ticks={lockout_ticks}");
+    assert!(sim.ship(handle).data().guns[0].heat <= ship::OVERHEAT_RECOVERY_HEAT);
+}
+
+#[test]
+fn test_burst_fire_does_not_overheat() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+
+    for tick in 0..3000 {
+        // Fire for one second out of every three.
+        if tick % 180 < 60 {
+            sim.ship_mut(handle).fire_gun(0);
+        }
+        sim.step();
+        assert!(!sim.ship(handle).data().guns[0].overheated);
+    }
+}
@@ -0,0 +1,82 @@
+use nalgebra::vector;
+use oort_simulator::hardpoint::{RefitError, Weapon};
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_refit_between_waves() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let frigate = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+
+    sim.step();
+    assert_eq!(
+        sim.refit_ship(frigate, &[Some(Weapon::Flak), Some(Weapon::Flak)]),
+        Err(RefitError::TooLarge { index: 1 })
+    );
+    assert_eq!(sim.ship(frigate).data().guns.len(), 3);
+
+    sim.refit_ship(
+        frigate,
+        &[
+            Some(Weapon::Flak),
+            None,
+            None,
+            Some(Weapon::MissileLauncher),
+        ],
+    )
+    .unwrap();
+    assert_eq!(sim.ship(frigate).data().guns.len(), 1);
+
+    sim.ship_mut(frigate).fire(0);
+    sim.step();
+    assert_eq!(sim.bullets.iter().count(), 6);
+
+    sim.ship_mut(frigate).launch_missile(0);
+    sim.step();
+    assert!(sim
+        .ships
+        .iter()
+        .any(|&handle| sim.ship(handle).data().class == ShipClass::Missile));
+}
+
+#[test]
+fn test_turret_arc() {
+    use oort_simulator::bullet;
+    use oort_simulator::hardpoint::CRUISER_MAIN_BATTERY_ARC;
+    use std::f64::consts::TAU;
+
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    sim.refit_ship(cruiser, &[Some(Weapon::Railgun)]).unwrap();
+    assert_eq!(
+        sim.ship(cruiser).get_gun_arc(0),
+        Some((
+            -CRUISER_MAIN_BATTERY_ARC / 2.0,
+            CRUISER_MAIN_BATTERY_ARC / 2.0
+        ))
+    );
+
+    // Aiming nearly aft fires along the closest edge of the arc instead.
+    sim.ship_mut(cruiser).aim(0, TAU * 0.45);
+    sim.ship_mut(cruiser).fire(0);
+    let handle = *sim.bullets.iter().next().unwrap();
+    let v = bullet::body(&sim, handle).linvel();
+    approx::assert_abs_diff_eq!(
+        v.y.atan2(v.x),
+        CRUISER_MAIN_BATTERY_ARC / 2.0,
+        epsilon = 1e-6
+    );
+}
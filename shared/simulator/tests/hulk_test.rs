@@ -0,0 +1,58 @@
+use nalgebra::vector;
+use oort_simulator::ship::{self, fighter, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_hulk() {
+    let mut sim = simulation::Simulation::new("hulk_test", 0, &[Code::None, Code::None]);
+
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        fighter(1),
+    );
+    sim.step();
+    sim.ship_mut(ship0).data_mut().destroyed = true;
+    sim.step();
+
+    assert!(!sim.ships.contains(ship0));
+    assert_eq!(sim.ships.len(), 1);
+    let hulk = *sim.ships.iter().next().unwrap();
+    {
+        let ship = sim.ship(hulk);
+        assert!(ship.data().hulk);
+        assert_eq!(ship.data().class, ShipClass::Fighter);
+        assert_eq!(ship.data().team, ship::NEUTRAL_TEAM);
+        approx::assert_abs_diff_eq!(ship.velocity().x, 100.0, epsilon = 1e-6);
+    }
+    let snapshot = sim.snapshot(0);
+    assert_eq!(snapshot.ships.len(), 1);
+    assert!(snapshot.ships[0].hulk);
+
+    for _ in 0..ship::HULK_TTL {
+        sim.step();
+    }
+    assert!(sim.ships.is_empty());
+    assert!(sim.bullets.is_empty());
+}
+
+#[test]
+fn test_no_hulk() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        fighter(1),
+    );
+    sim.step();
+    sim.ship_mut(ship0).data_mut().destroyed = true;
+    sim.step();
+
+    assert!(sim.ships.is_empty());
+}
@@ -0,0 +1,49 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_imu_clamped_acceleration() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        std::f64::consts::FRAC_PI_2,
+        ship::fighter(0),
+    );
+    for _ in 0..10 {
+        // Far beyond the fighter's forward acceleration.
+        sim.ship_mut(fighter).accelerate(vector![1000.0, 0.0]);
+        sim.ship_mut(fighter).torque(1.0);
+        sim.step();
+    }
+
+    let imu = &sim.ship(fighter).data().imu;
+    let max_acceleration = sim.ship(fighter).data().max_forward_acceleration;
+    approx::assert_abs_diff_eq!(imu.acceleration.x, 0.0, epsilon = 1e-3);
+    approx::assert_abs_diff_eq!(imu.acceleration.y, max_acceleration, epsilon = 1e-3);
+    approx::assert_abs_diff_eq!(imu.angular_acceleration, 1.0, epsilon = 1e-3);
+}
+
+#[test]
+fn test_imu_noise() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.step();
+    assert_eq!(sim.ship(fighter).data().imu.acceleration, vector![0.0, 0.0]);
+
+    sim.ship_mut(fighter).data_mut().imu.acceleration_noise = 1.0;
+    sim.step();
+    let imu = &sim.ship(fighter).data().imu;
+    assert_ne!(imu.acceleration, vector![0.0, 0.0]);
+    assert!(imu.acceleration.norm() < 10.0);
+    assert_eq!(imu.angular_acceleration, 0.0);
+}
@@ -0,0 +1,244 @@
+use nalgebra::vector;
+use oort_api::constants;
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::snapshot::Effect;
+use test_log::test;
+
+fn missiles(sim: &simulation::Simulation) -> Vec<ship::ShipHandle> {
+    sim.ships
+        .iter()
+        .cloned()
+        .filter(|&handle| sim.ship(handle).data().class == ShipClass::Missile)
+        .collect()
+}
+
+#[test]
+fn test_launch() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(fighter).launch_missile(0);
+    assert!(sim.events().blocked_launches.is_empty());
+    assert_eq!(missiles(&sim).len(), 1);
+}
+
+#[test]
+fn test_launch_blocked_by_hull() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let mut data = ship::fighter(0);
+    data.missile_launchers[0].offset = vector![0.0, 0.0];
+    data.missile_launchers[0].angle = std::f64::consts::PI;
+    let fighter = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+    sim.ship_mut(fighter).launch_missile(0);
+    assert_eq!(sim.events().blocked_launches, vec![(fighter, 0)]);
+    assert!(missiles(&sim).is_empty());
+    assert_eq!(
+        sim.ship(fighter).data().missile_launchers[0].reload_ticks_remaining,
+        0
+    );
+}
+
+#[test]
+fn test_ignition_delay() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let mut data = ship::fighter(0);
+    data.missile_launchers[0].ignition_ticks = 10;
+    let fighter = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+    sim.ship_mut(fighter).launch_missile(0);
+    let missile = missiles(&sim)[0];
+    let initial_velocity = sim.ship(missile).velocity();
+
+    for _ in 0..5 {
+        sim.ship_mut(missile).accelerate(vector![100.0, 0.0]);
+        sim.step();
+    }
+    assert_eq!(sim.ship(missile).velocity(), initial_velocity);
+
+    for _ in 0..10 {
+        sim.ship_mut(missile).accelerate(vector![100.0, 0.0]);
+        sim.step();
+    }
+    assert!(sim.ship(missile).velocity().x > initial_velocity.x);
+}
+
+#[test]
+fn test_burn_profile() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let mut data = ship::fighter(0);
+    let profile = ship::missile_burn_profile();
+    data.missile_launchers[0].burn_profile = Some(profile);
+    let fighter = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+    sim.ship_mut(fighter).launch_missile(0);
+    let missile = missiles(&sim)[0];
+
+    let mut accelerations = vec![];
+    for _ in 0..profile.burnout_ticks() + 10 {
+        sim.ship_mut(missile).accelerate(vector![1000.0, 0.0]);
+        sim.step();
+        accelerations.push(sim.ship(missile).data().last_acceleration.norm());
+    }
+    let boost = &accelerations[..profile.boost_ticks as usize];
+    let sustain = &accelerations[profile.boost_ticks as usize..profile.burnout_ticks() as usize];
+    let coast = &accelerations[profile.burnout_ticks() as usize..];
+    assert!(boost
+        .iter()
+        .all(|&a| (a - profile.boost_acceleration).abs() < 1e-9));
+    assert!(sustain
+        .iter()
+        .all(|&a| (a - profile.sustain_acceleration).abs() < 1e-9));
+    assert!(coast.iter().all(|&a| a == 0.0));
+    assert_eq!(sim.ship(missile).data().max_forward_acceleration, 0.0);
+
+    // Lateral thrust still works while coasting.
+    sim.ship_mut(missile).accelerate(vector![0.0, 50.0]);
+    sim.step();
+    approx::assert_abs_diff_eq!(
+        sim.ship(missile).data().last_acceleration.norm(),
+        50.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_corvette_launchers_clear_hull() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let corvette = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::corvette(0),
+    );
+    sim.ship_mut(corvette).launch_missile(0);
+    sim.ship_mut(corvette).launch_missile(1);
+    assert!(sim.events().blocked_launches.is_empty());
+    assert_eq!(missiles(&sim).len(), 2);
+}
+
+#[test]
+fn test_launch_ttl() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let default_ttl = ship::missile(0).ttl;
+    sim.ship_mut(fighter).launch_missile(0);
+    assert_eq!(sim.ship(missiles(&sim)[0]).data().ttl, default_ttl);
+
+    let mut launch = |launch_ttl| {
+        sim.ship_mut(fighter).data_mut().launch_ttl = Some(launch_ttl);
+        sim.ship_mut(fighter).data_mut().missile_launchers[0].reload_ticks_remaining = 0;
+        let before = missiles(&sim);
+        sim.ship_mut(fighter).launch_missile(0);
+        let missile = missiles(&sim)
+            .into_iter()
+            .find(|handle| !before.contains(handle))
+            .unwrap();
+        sim.ship(missile).data().ttl
+    };
+    assert_eq!(launch(120), Some(120));
+    assert_eq!(launch(1), Some(constants::MIN_LAUNCH_TTL as u64));
+    assert_eq!(launch(u64::MAX), default_ttl);
+}
+
+#[test]
+fn test_disarm() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let missile = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::missile(0),
+    );
+    sim.ship_mut(missile).disarm();
+    sim.ship_mut(missile).explode();
+    assert_eq!(sim.bullets.iter().count(), 0);
+    sim.step();
+    assert!(!sim.ships.contains(missile));
+}
+
+#[test]
+fn test_reload_state() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let weapon_index = sim.ship(fighter).data().guns.len();
+    let reload_ticks = sim.ship(fighter).data().missile_launchers[0].reload_ticks;
+    assert_ne!(sim.ship(fighter).ready_weapons() & (1 << weapon_index), 0);
+
+    sim.ship_mut(fighter).launch_missile(0);
+    assert_eq!(sim.ship(fighter).ready_weapons() & (1 << weapon_index), 0);
+
+    let launcher = |sim: &simulation::Simulation| {
+        sim.snapshot(0)
+            .launchers
+            .into_iter()
+            .find(|launcher| launcher.ship == u64::from(fighter) && launcher.index == 0)
+            .unwrap()
+    };
+    assert!(!launcher(&sim).ready());
+    assert_eq!(launcher(&sim).reload_fraction(), 0.0);
+
+    let reloaded = |sim: &simulation::Simulation| {
+        sim.events().effects.contains(&Effect::LauncherReloaded {
+            ship: fighter.into(),
+            launcher: 0,
+        })
+    };
+    for _ in 0..reload_ticks - 1 {
+        sim.step();
+        assert!(!reloaded(&sim));
+    }
+    sim.step();
+    assert!(reloaded(&sim));
+    assert!(launcher(&sim).ready());
+    assert_ne!(sim.ship(fighter).ready_weapons() & (1 << weapon_index), 0);
+}
+
+#[test]
+fn test_launch_designation() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(fighter).data_mut().launch_designation = Some(ship::Designation {
+        class: oort_api::Class::Fighter,
+        position: vector![0.0, 5000.0],
+        velocity: vector![100.0, 0.0],
+    });
+    sim.ship_mut(fighter).launch_missile(0);
+    let missile = missiles(&sim)[0];
+
+    let heading = sim.ship(missile).data().radar.as_ref().unwrap().heading;
+    assert!((heading - std::f64::consts::FRAC_PI_2).abs() < 0.01);
+    let contact = sim.ship(missile).data().designated_contact.clone().unwrap();
+    assert_eq!(contact.position, vector![0.0, 5000.0]);
+    assert_eq!(contact.velocity, vector![100.0, 0.0]);
+
+    // Missiles launched without a designation start blind.
+    sim.ship_mut(fighter).data_mut().launch_designation = None;
+    sim.ship_mut(fighter).data_mut().missile_launchers[0].reload_ticks_remaining = 0;
+    sim.ship_mut(fighter).launch_missile(0);
+    let missile = *missiles(&sim).iter().find(|&&m| m != missile).unwrap();
+    assert!(sim.ship(missile).data().designated_contact.is_none());
+}
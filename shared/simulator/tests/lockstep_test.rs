@@ -0,0 +1,105 @@
+use oort_simulator::ship::ShipClass;
+use oort_simulator::simulation::lockstep::{Command, Lockstep, LockstepError, TickMessage};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn client(seed: u32, team: i32) -> Lockstep {
+    let sim = simulation::Simulation::new("reinforcement_test", seed, &[Code::None, Code::None]);
+    Lockstep::new(sim, team, 1 - team, 3)
+}
+
+// Exchanges messages and steps both clients until they reach `tick`.
+fn run_until(a: &mut Lockstep, b: &mut Lockstep, tick: u32) -> Result<(), LockstepError> {
+    while a.sim().tick() < tick {
+        if let Some(message) = a.send()? {
+            b.receive(message)?;
+        }
+        if let Some(message) = b.send()? {
+            a.receive(message)?;
+        }
+        assert!(a.advance());
+        assert!(b.advance());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_lockstep() {
+    let mut a = client(0, 0);
+    let mut b = client(0, 1);
+    run_until(&mut a, &mut b, 100).unwrap();
+
+    a.queue(Command::RequestReinforcement(ShipClass::Fighter));
+    run_until(&mut a, &mut b, 110).unwrap();
+    for sim in [a.sim(), b.sim()] {
+        assert_eq!(sim.snapshot(0).ships.len(), 2);
+        assert_eq!(sim.team_resources(0), 10.0);
+    }
+    assert_eq!(a.sim().state_hash(), b.sim().state_hash());
+}
+
+#[test]
+fn test_waits_for_remote() {
+    let mut a = client(0, 0);
+    let mut b = client(0, 1);
+
+    // The input delay lets a client run ahead until it needs the other's
+    // commands.
+    for _ in 0..3 {
+        a.send().unwrap();
+        assert!(a.advance());
+    }
+    a.send().unwrap();
+    assert!(!a.advance());
+
+    let message = b.send().unwrap().unwrap();
+    a.receive(message).unwrap();
+    assert!(a.advance());
+}
+
+#[test]
+fn test_desync() {
+    let mut a = client(0, 0);
+    let mut b = client(1, 1);
+    assert!(matches!(
+        run_until(&mut a, &mut b, 10),
+        Err(LockstepError::Desync { tick: 0, .. })
+    ));
+}
+
+#[test]
+fn test_stale_message() {
+    let mut a = client(0, 0);
+    let mut b = client(0, 1);
+    run_until(&mut a, &mut b, 10).unwrap();
+
+    let message = TickMessage {
+        team: 1,
+        tick: 5,
+        commands: vec![Command::RequestReinforcement(ShipClass::Fighter)],
+        state_hash: None,
+    };
+    assert_eq!(
+        a.receive(message),
+        Err(LockstepError::StaleMessage {
+            tick: 5,
+            current: 10
+        })
+    );
+}
+
+#[test]
+fn test_desync_check_interval() {
+    let mut a = client(0, 0);
+    let mut b = client(0, 1);
+    a.set_desync_check_interval(10);
+    b.set_desync_check_interval(10);
+    for tick in 0..20 {
+        let message = a.send().unwrap().unwrap();
+        assert_eq!(message.state_hash.is_some(), tick % 10 == 0, "tick {tick}");
+        b.receive(message).unwrap();
+        a.receive(b.send().unwrap().unwrap()).unwrap();
+        assert!(a.advance());
+        assert!(b.advance());
+    }
+}
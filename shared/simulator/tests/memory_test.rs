@@ -0,0 +1,43 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_memory_usage() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.step();
+    assert!(sim.snapshot(0).memory.is_none());
+
+    sim.set_memory_accounting(true);
+    sim.step();
+    let initial = sim.snapshot(0).memory.unwrap();
+    assert_eq!(initial.ships, 1);
+    assert_eq!(initial.bullets, 0);
+    assert_eq!(initial.bodies, 1);
+    assert!(initial.snapshot_bytes > 0);
+    assert!(initial.total_bytes() > initial.snapshot_bytes);
+
+    sim.ship_mut(fighter).fire_gun(0);
+    sim.step();
+    let firing = sim.snapshot(0).memory.unwrap();
+    assert_eq!(firing.bullets, 1);
+    assert_eq!(firing.bodies, 2);
+    assert!(firing.bullet_bytes > 0);
+
+    // Expired bullets leave no bodies or colliders behind.
+    for _ in 0..700 {
+        sim.step();
+    }
+    let after = sim.snapshot(0).memory.unwrap();
+    assert_eq!(after.bullets, 0);
+    assert_eq!(after.bodies, initial.bodies);
+    assert_eq!(after.colliders, initial.colliders);
+}
@@ -0,0 +1,91 @@
+use nalgebra::vector;
+use oort_simulator::model;
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use std::rc::Rc;
+use test_log::test;
+
+const CLASSES: &[ShipClass] = &[
+    ShipClass::Fighter,
+    ShipClass::Frigate,
+    ShipClass::Cruiser,
+    ShipClass::Asteroid { variant: 0 },
+    ShipClass::Asteroid { variant: 1 },
+    ShipClass::Target,
+    ShipClass::Missile,
+    ShipClass::Torpedo,
+    ShipClass::Planet,
+    ShipClass::Station,
+    ShipClass::Turret,
+    ShipClass::Corvette,
+    ShipClass::Pod,
+];
+
+#[test]
+fn test_registry() {
+    for &class in CLASSES {
+        let model = model::get(class);
+        assert!(Rc::ptr_eq(&model, &model::get(class)));
+        assert!(model.hull.len() >= 3, "{class:?}");
+        assert_eq!(model.mesh, model::load(class));
+        for v in model.mesh.iter().chain(model.hull.iter()) {
+            assert!(v.norm() <= model.radius, "{class:?}");
+        }
+        assert_eq!(model::radius(class), model.radius);
+    }
+
+    assert_ne!(
+        model::get(ShipClass::Asteroid { variant: 0 }).mesh,
+        model::get(ShipClass::Asteroid { variant: 1 }).mesh
+    );
+}
+
+#[test]
+fn test_mass_from_area() {
+    for &class in CLASSES {
+        assert!(model::area(class) > 0.0, "{class:?}");
+    }
+
+    // A fighter's hull is a triangle with base 14.2 m and height 17 m.
+    assert!((model::area(ShipClass::Fighter) - 120.7).abs() < 0.1);
+
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    for data in [ship::fighter(0), ship::missile(0), ship::asteroid(3)] {
+        let class = data.class;
+        let handle = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+        let expected = model::area(class) * class.density();
+        assert!((sim.ship(handle).data().mass - expected).abs() < 1e-6 * expected);
+        assert!((sim.ship(handle).body().mass() - expected).abs() < 1e-6 * expected);
+    }
+}
+
+#[test]
+fn test_moment_of_inertia() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 2000.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    let fighter_inertia = sim.ship(fighter).moment_of_inertia();
+    let cruiser_inertia = sim.ship(cruiser).moment_of_inertia();
+    assert!(fighter_inertia > 0.0);
+    assert!(cruiser_inertia > fighter_inertia);
+
+    // Every point of the hull is within the model's radius of the center.
+    for handle in [fighter, cruiser] {
+        let class = sim.ship(handle).data().class;
+        let mass = sim.ship(handle).body().mass();
+        let radius = model::radius(class) as f64;
+        assert!(sim.ship(handle).moment_of_inertia() < mass * radius * radius);
+    }
+}
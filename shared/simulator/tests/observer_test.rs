@@ -0,0 +1,64 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code, PHYSICS_TICK_LENGTH};
+use test_log::test;
+
+#[test]
+fn test_observer_picture() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    ship::create(
+        &mut sim,
+        vector![1000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::target(1),
+    );
+    assert!(sim.observer_picture().is_none());
+
+    sim.add_observer(10);
+    for _ in 0..10 {
+        sim.step();
+        assert!(sim.observer_picture().is_none());
+    }
+    sim.step();
+
+    let picture = sim.observer_picture().unwrap();
+    approx::assert_abs_diff_eq!(
+        picture.time,
+        sim.time() - 11.0 * PHYSICS_TICK_LENGTH,
+        epsilon = 1e-9
+    );
+    assert_eq!(picture.ships.len(), 2);
+    assert_eq!(picture.contacts.len(), 1);
+    assert_eq!(picture.contacts[0].team, 0);
+    assert_eq!(picture.contacts[0].tracker, u64::from(ship0));
+}
+
+#[test]
+fn test_observer_delay() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.add_observer(60);
+    for _ in 0..120 {
+        sim.step();
+    }
+
+    // The observer sees where the ship was a second ago.
+    let picture = sim.observer_picture().unwrap();
+    let observed = picture.ships[0].position.x;
+    let actual = sim.ship(*sim.ships.iter().next().unwrap()).position().x;
+    approx::assert_abs_diff_eq!(actual - observed, 100.0, epsilon = 1.0);
+}
@@ -0,0 +1,58 @@
+use nalgebra::{vector, Vector2};
+use oort_simulator::orbit::{Orbit, OrbitDirection};
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code, Simulation};
+use test_log::test;
+
+fn fly(
+    direction: OrbitDirection,
+    target_velocity: Vector2<f64>,
+) -> (Box<Simulation>, ship::ShipHandle, ship::ShipHandle) {
+    let mut sim = Simulation::new("test", 0, &[Code::None, Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![-1000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let target = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        target_velocity,
+        0.0,
+        ship::target(1),
+    );
+    sim.ship_mut(fighter).data_mut().orbit = Some(Orbit::new(
+        vector![0.0, 0.0],
+        target_velocity,
+        500.0,
+        direction,
+    ));
+    for _ in 0..(simulation::PHYSICS_TICK_LENGTH.recip() as usize * 60) {
+        sim.step();
+    }
+    (sim, fighter, target)
+}
+
+#[test]
+fn test_orbit() {
+    for (direction, sign) in [
+        (OrbitDirection::Counterclockwise, 1.0),
+        (OrbitDirection::Clockwise, -1.0),
+    ] {
+        let (sim, fighter, target) = fly(direction, vector![0.0, 0.0]);
+        let dp = sim.ship(fighter).position().vector - sim.ship(target).position().vector;
+        let dv = sim.ship(fighter).velocity() - sim.ship(target).velocity();
+        log::info!("{direction:?}: distance={} speed={}", dp.norm(), dv.norm());
+        approx::assert_abs_diff_eq!(dp.norm(), 500.0, epsilon = 25.0);
+        assert!(dp.perp(&dv) * sign > 0.0);
+    }
+}
+
+#[test]
+fn test_orbit_moving_contact() {
+    let (sim, fighter, target) = fly(OrbitDirection::Counterclockwise, vector![20.0, 0.0]);
+    let dp = sim.ship(fighter).position().vector - sim.ship(target).position().vector;
+    approx::assert_abs_diff_eq!(dp.norm(), 500.0, epsilon = 25.0);
+}
@@ -0,0 +1,50 @@
+use oort_simulator::scenario::{self, Status};
+use oort_simulator::simulation::pool::SimulationPool;
+use oort_simulator::simulation::Code;
+use test_log::test;
+
+#[test]
+fn test_workspaces_are_isolated() {
+    let scenario_name = "tutorial_guns";
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut pool = SimulationPool::new();
+    let main = pool.create(scenario_name, 0, &codes);
+    let quick_test = pool.create(scenario_name, 0, &codes);
+    assert_eq!(pool.len(), 2);
+
+    // Leave the main workspace paused while the other one runs.
+    pool.step(main, 10);
+    assert_eq!(pool.run(quick_test), Some(Status::Victory { team: 0 }));
+    assert_eq!(pool.get(main).unwrap().tick(), 10);
+
+    // Running the other workspace didn't disturb the main one's RNG.
+    pool.run(main);
+    assert_eq!(
+        pool.get(main).unwrap().hash(),
+        pool.get(quick_test).unwrap().hash()
+    );
+
+    assert!(pool.remove(quick_test).is_some());
+    assert!(pool.get(quick_test).is_none());
+    assert_eq!(pool.step(quick_test, 1), None);
+    assert_eq!(pool.ids().collect::<Vec<_>>(), vec![main]);
+}
+
+#[test]
+fn test_errored_workspace() {
+    let mut pool = SimulationPool::new();
+    // Not a valid WASM module.
+    let id = pool.create("tutorial_guns", 0, &[Code::Wasm(vec![0, 1, 2, 3])]);
+    assert!(pool.errored(id));
+    assert_eq!(pool.step(id, 10), Some(0));
+}
+
+#[test]
+fn test_step_publishes_last_tick() {
+    let mut pool = SimulationPool::new();
+    let id = pool.create("tutorial_guns", 0, &[Code::None]);
+    pool.get_mut(id).unwrap().set_publishing(true);
+    assert_eq!(pool.step(id, 5), Some(5));
+    let sim = pool.get(id).unwrap();
+    assert_eq!(sim.published().unwrap().time, sim.time());
+}
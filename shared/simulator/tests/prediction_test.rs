@@ -0,0 +1,67 @@
+use oort_api::prelude::{vec2, Class, ScanResult, Track, TICK_LENGTH};
+use test_log::test;
+
+fn contact(position: (f64, f64), velocity: (f64, f64)) -> ScanResult {
+    ScanResult {
+        class: Class::Fighter,
+        position: vec2(position.0, position.1),
+        velocity: vec2(velocity.0, velocity.1),
+        rssi: 0.0,
+        snr: 0.0,
+        age: 0.0,
+    }
+}
+
+#[test]
+fn test_predict() {
+    let c = contact((100.0, 0.0), (10.0, -20.0));
+    assert_eq!(c.predict(0.0), vec2(100.0, 0.0));
+    assert_eq!(c.predict(0.5), vec2(105.0, -10.0));
+    assert_eq!(
+        c.predict_with_acceleration(vec2(4.0, 0.0), 2.0),
+        vec2(128.0, -40.0)
+    );
+}
+
+#[test]
+fn test_track_acceleration() {
+    let mut track = Track::new();
+    assert!(track.predict(1.0).is_none());
+
+    // Constant acceleration of 6 m/s² along y.
+    let acceleration = vec2(0.0, 6.0);
+    for tick in 0..120 {
+        let t = tick as f64 * TICK_LENGTH;
+        let velocity = acceleration * t;
+        let position = acceleration * (0.5 * t * t);
+        track.update_at(
+            t,
+            &contact((position.x, position.y), (velocity.x, velocity.y)),
+        );
+    }
+    approx::assert_abs_diff_eq!(track.acceleration().x, 0.0, epsilon = 1e-6);
+    approx::assert_abs_diff_eq!(track.acceleration().y, 6.0, epsilon = 1e-6);
+
+    let t = 119.0 * TICK_LENGTH + 1.0;
+    let predicted = track.predict(1.0).unwrap();
+    approx::assert_abs_diff_eq!(predicted.y, 0.5 * 6.0 * t * t, epsilon = 1e-3);
+}
+
+#[test]
+fn test_track_reset() {
+    let mut track = Track::new();
+    track.update_at(0.0, &contact((0.0, 0.0), (0.0, 0.0)));
+    track.update_at(0.1, &contact((0.0, 0.0), (10.0, 0.0)));
+    assert!(track.acceleration().x > 0.0);
+
+    // A long gap starts over.
+    track.update_at(5.0, &contact((0.0, 0.0), (0.0, 0.0)));
+    assert_eq!(track.acceleration(), vec2(0.0, 0.0));
+
+    // So does a different class.
+    track.update_at(5.1, &contact((0.0, 0.0), (10.0, 0.0)));
+    let mut missile = contact((0.0, 0.0), (20.0, 0.0));
+    missile.class = Class::Missile;
+    track.update_at(5.2, &missile);
+    assert_eq!(track.acceleration(), vec2(0.0, 0.0));
+}
@@ -0,0 +1,89 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code, PHYSICS_TICK_LENGTH};
+use oort_simulator::snapshot::SnapshotFilter;
+use std::sync::Arc;
+use test_log::test;
+
+#[test]
+fn test_publish_disabled_by_default() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    sim.step();
+    assert!(sim.published().is_none());
+}
+
+#[test]
+fn test_published_state() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![100.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.set_publishing(true);
+    assert_eq!(sim.published().unwrap().time, 0.0);
+
+    sim.step();
+    let first = sim.published().unwrap();
+    assert_eq!(first.time, PHYSICS_TICK_LENGTH);
+    assert_eq!(first.ships.len(), 1);
+    let position = first.ships[0].position;
+    assert_eq!(position.coords, sim.ship(handle).position().vector);
+
+    // A reader holding the published state keeps seeing the same tick.
+    for _ in 0..3 {
+        sim.step();
+    }
+    assert_eq!(first.time, PHYSICS_TICK_LENGTH);
+    assert_eq!(first.ships[0].position, position);
+
+    let latest = sim.published().unwrap();
+    assert!(!Arc::ptr_eq(&first, &latest));
+    assert_eq!(latest.time, sim.time());
+    assert_eq!(
+        latest.ships[0].position.coords,
+        sim.ship(handle).position().vector
+    );
+
+    sim.set_publishing(false);
+    assert!(sim.published().is_none());
+}
+
+#[test]
+fn test_subscriptions() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let renderer = sim.subscribe(SnapshotFilter::default());
+    let recorder = sim.subscribe(SnapshotFilter::default());
+    let stats = sim.subscribe(SnapshotFilter::every(10).without_bullets());
+    assert_eq!(sim.subscription(stats).unwrap().time, 0.0);
+
+    sim.ship_mut(handle).fire_gun(0);
+    sim.step();
+    let rendered = sim.subscription(renderer).unwrap();
+    assert_eq!(rendered.time, sim.time());
+    assert_eq!(rendered.bullets.len(), 1);
+    assert!(Arc::ptr_eq(&rendered, &sim.subscription(recorder).unwrap()));
+    assert_eq!(sim.subscription(stats).unwrap().time, 0.0);
+
+    for _ in 0..9 {
+        sim.step();
+    }
+    let counted = sim.subscription(stats).unwrap();
+    assert_eq!(counted.time, sim.time());
+    assert_eq!(counted.ships.len(), 1);
+    assert!(counted.bullets.is_empty());
+    assert_eq!(sim.subscription(renderer).unwrap().bullets.len(), 1);
+
+    sim.unsubscribe(recorder);
+    sim.step();
+    assert!(sim.subscription(recorder).is_none());
+}
@@ -0,0 +1,242 @@
+use nalgebra::vector;
+use oort_api::EcmMode;
+use oort_simulator::radar::{RadarWarning, MAX_RADAR_MEMORY, MIN_RADAR_POWER};
+use oort_simulator::ship::{self, ShipHandle};
+use oort_simulator::simulation::{self, Code};
+use std::f64::consts::{PI, TAU};
+use test_log::test;
+
+fn set_power(sim: &mut simulation::Simulation, handle: ShipHandle, fraction: f64) {
+    sim.ship_mut(handle)
+        .data_mut()
+        .radar
+        .as_mut()
+        .unwrap()
+        .set_power_fraction(fraction);
+}
+
+#[test]
+fn test_radar_power() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    ship::create(
+        &mut sim,
+        vector![20e3, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(1),
+    );
+
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().scan().is_some());
+
+    set_power(&mut sim, ship0, 0.0);
+    assert_eq!(
+        sim.ship(ship0).radar().unwrap().get_power_fraction(),
+        MIN_RADAR_POWER
+    );
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().scan().is_none());
+}
+
+fn warning_test(fraction: f64) -> (bool, Option<RadarWarning>) {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let ship1 = ship::create(
+        &mut sim,
+        vector![12e3, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(1),
+    );
+    set_power(&mut sim, ship0, fraction);
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().warning.is_none());
+    (
+        sim.ship(ship0).radar().unwrap().scan().is_some(),
+        sim.ship(ship1).radar().unwrap().warning,
+    )
+}
+
+#[test]
+fn test_radar_warning() {
+    let (found, warning) = warning_test(1.0);
+    assert!(found);
+    assert!((warning.unwrap().bearing - PI).abs() < 1e-3);
+
+    // At reduced power the radar still sees the target but goes unnoticed.
+    let (found, warning) = warning_test(0.1);
+    assert!(found);
+    assert!(warning.is_none());
+}
+
+fn jam_test(
+    data: ship::ShipData,
+    home_on_jam: bool,
+) -> (Option<f64>, simulation::Simulation, ShipHandle) {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, data);
+    let ship1 = ship::create(
+        &mut sim,
+        vector![0.0, 55e3],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(1),
+    );
+    sim.ship_mut(ship0).data_mut().home_on_jam = home_on_jam;
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = PI / 2.0;
+    sim.ship_mut(ship0).radar_mut().unwrap().width = TAU / 360.0;
+    sim.ship_mut(ship1).radar_mut().unwrap().heading = -PI / 2.0;
+    sim.ship_mut(ship1).radar_mut().unwrap().width = TAU / 360.0;
+    sim.ship_mut(ship1).radar_mut().unwrap().ecm_mode = EcmMode::Noise;
+    sim.step();
+    let jam_bearing = sim.ship(ship0).radar().unwrap().jam_bearing;
+    (jam_bearing, sim, ship0)
+}
+
+#[test]
+fn test_jam_bearing() {
+    let (jam_bearing, sim, torpedo) = jam_test(ship::torpedo(0), false);
+    assert!(sim.ship(torpedo).radar().unwrap().scan().is_none());
+    assert!((jam_bearing.unwrap() - PI / 2.0).abs() < 1e-6);
+
+    // Only torpedoes can find the jammer.
+    let (jam_bearing, _, _) = jam_test(ship::fighter(0), false);
+    assert_eq!(jam_bearing, None);
+}
+
+#[test]
+fn test_home_on_jam() {
+    let (_, mut sim, torpedo) = jam_test(ship::torpedo(0), true);
+    for _ in 0..180 {
+        sim.step();
+    }
+    let ship = sim.ship(torpedo);
+    assert!((ship.heading() - PI / 2.0).abs() < 0.1);
+    assert!(ship.velocity().y > 0.0);
+    assert!(ship.velocity().x.abs() < 1.0);
+
+    // Without home-on-jam the torpedo is left to its AI.
+    let (_, mut sim, torpedo) = jam_test(ship::torpedo(0), false);
+    for _ in 0..180 {
+        sim.step();
+    }
+    assert_eq!(sim.ship(torpedo).velocity(), vector![0.0, 0.0]);
+}
+
+#[test]
+fn test_radar_memory() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    ship::create(
+        &mut sim,
+        vector![20e3, 0.0],
+        vector![0.0, 100.0],
+        0.0,
+        ship::fighter(1),
+    );
+
+    // Off by default.
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().contact().is_some());
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = PI;
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().contact().is_none());
+
+    sim.ship_mut(ship0)
+        .radar_mut()
+        .unwrap()
+        .set_memory(MAX_RADAR_MEMORY * 2.0);
+    assert_eq!(
+        sim.ship(ship0).radar().unwrap().get_memory(),
+        MAX_RADAR_MEMORY
+    );
+    sim.ship_mut(ship0).radar_mut().unwrap().set_memory(1.0);
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+    sim.step();
+    let (seen, age) = sim.ship(ship0).radar().unwrap().contact().unwrap();
+    assert_eq!(age, 0);
+
+    // The lost contact keeps moving along its last known velocity.
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = PI;
+    for _ in 0..30 {
+        sim.step();
+    }
+    assert!(sim.ship(ship0).radar().unwrap().scan().is_none());
+    let (stale, age) = sim.ship(ship0).radar().unwrap().contact().unwrap();
+    assert_eq!(age, 30);
+    let expected = seen.position + seen.velocity * 30.0 * simulation::PHYSICS_TICK_LENGTH;
+    assert!((stale.position - expected).norm() < 1e-6);
+
+    // And is forgotten after a second.
+    for _ in 0..31 {
+        sim.step();
+    }
+    assert!(sim.ship(ship0).radar().unwrap().contact().is_none());
+}
+
+#[test]
+fn test_passive_radar() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let ship1 = ship::create(
+        &mut sim,
+        vector![12e3, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(1),
+    );
+    sim.ship_mut(ship0).radar_mut().unwrap().passive = true;
+    sim.ship_mut(ship1).radar_mut().unwrap().heading = PI;
+    sim.step();
+
+    // The listener hears the enemy radar without being noticed itself.
+    let radar = sim.ship(ship0).radar().unwrap();
+    assert!(radar.scan().is_none());
+    let contact = radar.passive_contact.unwrap();
+    assert!(contact.bearing.min(TAU - contact.bearing) < 0.1);
+    assert!(sim.ship(ship1).radar().unwrap().warning.is_none());
+    assert!(sim.ship(ship1).radar().unwrap().scan().is_some());
+
+    // Only emitters within the beam are heard.
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = PI;
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().passive_contact.is_none());
+
+    // And only while they're pointed at the listener.
+    sim.ship_mut(ship0).radar_mut().unwrap().heading = 0.0;
+    sim.ship_mut(ship1).radar_mut().unwrap().heading = 0.0;
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().passive_contact.is_none());
+
+    // Active radars don't listen.
+    sim.ship_mut(ship0).radar_mut().unwrap().passive = false;
+    sim.ship_mut(ship1).radar_mut().unwrap().heading = PI;
+    sim.step();
+    assert!(sim.ship(ship0).radar().unwrap().passive_contact.is_none());
+    assert!(sim.ship(ship1).radar().unwrap().warning.is_some());
+}
@@ -0,0 +1,79 @@
+use nalgebra::vector;
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_reinforcement() {
+    let mut sim = simulation::Simulation::new("reinforcement_test", 0, &[Code::None]);
+    assert_eq!(sim.team_resources(0), 0.0);
+    assert!(sim.request_reinforcement(0, ShipClass::Fighter).is_none());
+
+    for _ in 0..100 {
+        sim.step();
+    }
+    assert_eq!(sim.team_resources(0), 100.0);
+
+    let handle = sim.request_reinforcement(0, ShipClass::Fighter).unwrap();
+    assert_eq!(sim.team_resources(0), 0.0);
+    let ship = sim.ship(handle);
+    assert_eq!(ship.data().team, 0);
+    assert_eq!(ship.data().class, ShipClass::Fighter);
+    assert_eq!(ship.position().vector, vector![-1000.0, 0.0]);
+}
+
+#[test]
+fn test_reinforcement_spawn_points() {
+    let mut sim = simulation::Simulation::new("reinforcement_test", 0, &[Code::None]);
+    sim.credit_team(0, 1300.0);
+
+    assert!(sim.request_reinforcement(0, ShipClass::Cruiser).is_some());
+    let frigate = sim.request_reinforcement(0, ShipClass::Frigate);
+    assert!(frigate.is_none());
+    assert_eq!(sim.team_resources(0), 300.0);
+
+    let fighter = sim.request_reinforcement(0, ShipClass::Fighter).unwrap();
+    assert_eq!(sim.ship(fighter).position().vector, vector![1000.0, 0.0]);
+    let fighter = sim.request_reinforcement(0, ShipClass::Fighter).unwrap();
+    assert_eq!(sim.ship(fighter).position().vector, vector![-1000.0, 0.0]);
+}
+
+#[test]
+fn test_reinforcement_without_spawn_point() {
+    let mut sim = simulation::Simulation::new("reinforcement_test", 0, &[Code::None]);
+    sim.credit_team(1, 1000.0);
+    assert!(sim.request_reinforcement(1, ShipClass::Fighter).is_none());
+    assert!(sim.request_reinforcement(0, ShipClass::Missile).is_none());
+    assert_eq!(sim.team_resources(1), 1000.0);
+}
+
+#[test]
+fn test_reinforcement_spawn_grace() {
+    let mut sim = simulation::Simulation::new("reinforcement_test", 0, &[Code::None]);
+    sim.set_spawn_grace_ticks(30);
+    sim.credit_team(0, 100.0);
+
+    let blocker = ship::create(
+        &mut sim,
+        vector![-1000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::target(1),
+    );
+    let fighter = sim.request_reinforcement(0, ShipClass::Fighter).unwrap();
+
+    for _ in 0..10 {
+        sim.ship_mut(fighter).fire(0);
+        sim.step();
+    }
+    assert_eq!(sim.bullets.len(), 0);
+    assert_eq!(sim.ship(fighter).velocity(), vector![0.0, 0.0]);
+    assert_eq!(sim.ship(blocker).velocity(), vector![0.0, 0.0]);
+
+    for _ in 0..30 {
+        sim.step();
+    }
+    assert_ne!(sim.ship(fighter).velocity(), vector![0.0, 0.0]);
+    sim.ship_mut(fighter).fire(0);
+    assert!(!sim.bullets.is_empty());
+}
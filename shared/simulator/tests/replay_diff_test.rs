@@ -0,0 +1,87 @@
+use oort_simulator::replay_diff::{self, Divergence, Side};
+use oort_simulator::replay_export::{self, Record};
+use oort_simulator::scenario;
+use test_log::test;
+
+fn export(scenario_name: &str, seed: u32) -> Vec<Record> {
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut output = vec![];
+    replay_export::export(scenario_name, seed, &codes, &mut output).unwrap();
+    replay_export::read(output.as_slice()).unwrap()
+}
+
+fn tick_ships(records: &mut [Record], index: usize) -> &mut Vec<replay_export::Ship> {
+    match &mut records[index] {
+        Record::Tick { ships, .. } => ships,
+        _ => panic!("not a tick record"),
+    }
+}
+
+#[test]
+fn test_identical() {
+    let a = export("tutorial_guns", 0);
+    let b = export("tutorial_guns", 0);
+    let report = replay_diff::diff(&a, &b, 0.0, 10).unwrap();
+    assert!(report.is_identical());
+    assert_eq!(report.first_divergent_tick, None);
+    assert_eq!(report.ticks_compared as usize, a.len() - 2);
+}
+
+#[test]
+fn test_divergence() {
+    let a = export("tutorial_guns", 0);
+    let mut b = a.clone();
+    tick_ships(&mut b, 10)[0].position[0] += 1.0;
+    let id = tick_ships(&mut b, 20).remove(0).id;
+
+    let report = replay_diff::diff(&a, &b, 0.5, 10).unwrap();
+    assert_eq!(report.divergent_ticks, 2);
+    assert_eq!(report.first_divergent_tick, Some(10));
+    assert!(matches!(
+        report.divergences[0],
+        Divergence::ShipState {
+            tick: 10,
+            field: "position.x",
+            ..
+        }
+    ));
+    assert_eq!(
+        report.divergences[1],
+        Divergence::MissingShip {
+            tick: 20,
+            id,
+            missing_from: Side::B
+        }
+    );
+
+    // Within tolerance.
+    let report = replay_diff::diff(&a, &b, 2.0, 10).unwrap();
+    assert_eq!(report.first_divergent_tick, Some(20));
+}
+
+#[test]
+fn test_truncated() {
+    let a = export("tutorial_guns", 0);
+    let mut b = a.clone();
+    b.truncate(b.len() / 2);
+    let report = replay_diff::diff(&a, &b, 0.0, 10).unwrap();
+    assert_eq!(report.first_divergent_tick, None);
+    assert!(report.divergences.contains(&Divergence::Length {
+        a: (a.len() - 2) as u32,
+        b: (b.len() - 1) as u32,
+    }));
+    assert!(matches!(
+        report.divergences.last(),
+        Some(Divergence::Outcome { .. })
+    ));
+}
+
+#[test]
+fn test_incompatible() {
+    let a = export("tutorial_guns", 0);
+    let b = export("tutorial_guns", 1);
+    assert!(matches!(
+        replay_diff::diff(&a, &b, 0.0, 10),
+        Err(replay_diff::Error::Incompatible(_))
+    ));
+}
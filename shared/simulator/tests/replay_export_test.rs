@@ -0,0 +1,45 @@
+use oort_simulator::replay_export;
+use oort_simulator::scenario;
+use serde_json::Value;
+use test_log::test;
+
+#[test]
+fn test_export() {
+    let scenario_name = "tutorial_guns";
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut output = vec![];
+    replay_export::export(scenario_name, 0, &codes, &mut output).unwrap();
+
+    let records: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    let header = &records[0];
+    assert_eq!(header["type"], "header");
+    assert_eq!(header["format"], "oort-replay");
+    assert_eq!(header["scenario"], scenario_name);
+
+    let end = records.last().unwrap();
+    assert_eq!(end["type"], "end");
+    assert_eq!(end["status"], "victory");
+    assert_eq!(end["winner"], 0);
+
+    let ticks = &records[1..records.len() - 1];
+    assert_eq!(ticks.len() as u64, end["tick"].as_u64().unwrap());
+    assert!(ticks.iter().all(|record| record["type"] == "tick"));
+    assert!(ticks
+        .iter()
+        .any(|record| !record["bullets"].as_array().unwrap().is_empty()));
+
+    // The target is destroyed by the end.
+    let destroyed: Vec<&Value> = ticks
+        .iter()
+        .flat_map(|record| record["destroyed"].as_array().unwrap())
+        .collect();
+    assert!(!destroyed.is_empty());
+    let ship = &ticks[0]["ships"][0];
+    assert_eq!(ship["class"], "fighter");
+    assert_eq!(ship["position"].as_array().unwrap().len(), 2);
+}
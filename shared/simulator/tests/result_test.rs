@@ -0,0 +1,87 @@
+use oort_proto::ranked::{SeedGrant, SeedGrantError};
+use oort_simulator::result::{self, ValidationError};
+use oort_simulator::scenario::{self, Difficulty};
+use oort_simulator::simulation::Code;
+use test_log::test;
+
+// Stands in for the source the solution was compiled from.
+const SOURCE: &str = "// tutorial solution";
+
+#[test]
+fn test_validate_result() {
+    let scenario_name = "tutorial_guns";
+    let solution = scenario::load(scenario_name).solution();
+    let result = result::generate(scenario_name, 0, SOURCE, &solution);
+    assert_eq!(result.difficulty, Difficulty::Normal);
+    assert!(!result.stats.weapons.is_empty());
+    assert_eq!(result::validate(&result, SOURCE, &solution), Ok(()));
+
+    let mut tampered = result.clone();
+    tampered.ticks -= 1;
+    assert_eq!(
+        result::validate(&tampered, SOURCE, &solution),
+        Err(ValidationError::TicksMismatch {
+            expected: tampered.ticks,
+            actual: result.ticks,
+        })
+    );
+
+    let mut tampered = result.clone();
+    tampered.version += 1;
+    assert_eq!(
+        result::validate(&tampered, SOURCE, &solution),
+        Err(ValidationError::UnsupportedVersion(tampered.version))
+    );
+
+    assert_eq!(
+        result::validate(&result, SOURCE, &Code::None),
+        Err(ValidationError::NotVictorious)
+    );
+
+    assert_eq!(
+        result::validate(&result, "// other source", &solution),
+        Err(ValidationError::CodeHashMismatch {
+            expected: result.code_hash.clone(),
+            actual: oort_proto::code_hash("// other source"),
+        })
+    );
+
+    let mut tampered = result.clone();
+    tampered.stats.missiles.entry(0).or_default().launched += 1;
+    assert_eq!(
+        result::validate(&tampered, SOURCE, &solution),
+        Err(ValidationError::StatsMismatch)
+    );
+
+    let mut tampered = result.clone();
+    tampered.difficulty = Difficulty::Hard;
+    assert_eq!(
+        result::validate(&tampered, SOURCE, &solution),
+        Err(ValidationError::UnsupportedDifficulty(Difficulty::Hard))
+    );
+}
+
+#[test]
+fn test_validate_ranked_result() {
+    let scenario_name = "tutorial_guns";
+    let solution = scenario::load(scenario_name).solution();
+    let grant: SeedGrant = serde_json::from_value(serde_json::json!({
+        "scenario_name": scenario_name,
+        "userid": "user",
+        "seed": 7,
+        "issued_at": 0,
+        "signature": "",
+    }))
+    .unwrap();
+    let result = result::generate_ranked(&grant, SOURCE, &solution);
+    assert_eq!(result.seed, 7);
+    assert_eq!(result.seed_grant.as_ref(), Some(&grant));
+    assert_eq!(result::validate(&result, SOURCE, &solution), Ok(()));
+
+    let mut tampered = result.clone();
+    tampered.seed = 8;
+    assert_eq!(
+        result::validate(&tampered, SOURCE, &solution),
+        Err(ValidationError::SeedGrant(SeedGrantError::WrongSeed))
+    );
+}
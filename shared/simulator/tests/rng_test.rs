@@ -0,0 +1,39 @@
+use oort_api::prelude::*;
+use oort_api::testing::{Inputs, MockSim};
+use test_log::test;
+
+fn draw() -> Vec<i64> {
+    let mut sim = MockSim::new();
+    let mut values = vec![];
+    sim.tick(&Inputs::default(), || {
+        for _ in 0..100 {
+            values.push(rand_int(-3, 7));
+        }
+    });
+    values
+}
+
+#[test]
+fn test_rand_int() {
+    let values = draw();
+    assert!(values.iter().all(|v| (-3..7).contains(v)));
+    for v in -3..7 {
+        assert!(values.contains(&v), "never drew {v}");
+    }
+    assert_eq!(values, draw());
+
+    let _sim = MockSim::new();
+    assert_eq!(rand_int(5, 5), 5);
+    assert_eq!(rand_int(5, -5), 5);
+    assert_eq!(rand_int(i64::MIN, i64::MIN + 1), i64::MIN);
+    let _ = rand_int(i64::MIN, i64::MAX);
+}
+
+#[test]
+fn test_rand_bool() {
+    let _sim = MockSim::new();
+    assert!((0..100).all(|_| !rand_bool(0.0)));
+    assert!((0..100).all(|_| rand_bool(1.0)));
+    let count = (0..1000).filter(|_| rand_bool(0.25)).count();
+    assert!((150..350).contains(&count), "count = {count}");
+}
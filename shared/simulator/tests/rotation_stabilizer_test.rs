@@ -0,0 +1,31 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+fn spin(stabilizer: bool, torque: f64) -> f64 {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let handle = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(handle).data_mut().rotation_stabilizer = stabilizer;
+    sim.ship_mut(handle).body().set_angvel(5.0, true);
+    for _ in 0..60 {
+        sim.ship_mut(handle).torque(torque);
+        sim.step();
+    }
+    sim.ship(handle).angular_velocity()
+}
+
+#[test]
+fn test_rotation_stabilizer() {
+    approx::assert_abs_diff_eq!(spin(false, 0.0), 5.0, epsilon = 1e-9);
+    assert!(spin(true, 0.0).abs() < 1e-6);
+
+    // Commanded torque takes priority.
+    assert!(spin(true, 1.0) > 5.0);
+}
@@ -0,0 +1,78 @@
+use nalgebra::vector;
+use oort_simulator::section;
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_sections_follow_parent() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    let pods = section::attach_launcher_pods(&mut sim, cruiser);
+    assert_eq!(pods.len(), 2);
+    assert_eq!(section::sections(&sim, cruiser), pods);
+    assert_eq!(sim.ship(pods[0]).data().class, ShipClass::Pod);
+
+    for _ in 0..120 {
+        sim.ship_mut(cruiser).accelerate(vector![5.0, 0.0]);
+        sim.ship_mut(cruiser).torque(0.1);
+        sim.step();
+    }
+    let position = sim.ship(cruiser).position().vector;
+    assert!(position.x > 1.0);
+    let heading = sim.ship(cruiser).heading();
+    assert!(heading > 0.1);
+    for &pod in pods.iter() {
+        let distance = (sim.ship(pod).position().vector - position).norm();
+        approx::assert_abs_diff_eq!(distance, 42.0, epsilon = 1.0);
+        approx::assert_abs_diff_eq!(sim.ship(pod).heading(), heading, epsilon = 0.01);
+    }
+
+    let snapshot = sim.snapshot(0);
+    assert_eq!(snapshot.sections.len(), 2);
+    assert!(snapshot
+        .sections
+        .iter()
+        .all(|section| section.parent == u64::from(cruiser)));
+}
+
+#[test]
+fn test_section_breaks_off() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let cruiser = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::cruiser(0),
+    );
+    let pods = section::attach_launcher_pods(&mut sim, cruiser);
+    sim.step();
+
+    sim.ship_mut(pods[0]).data_mut().destroyed = true;
+    sim.step();
+    assert!(!sim.ships.contains(pods[0]));
+    assert!(sim.ships.contains(pods[1]));
+    assert!(sim.ship(cruiser).data().missile_launchers[0].lost);
+    assert!(!sim.ship(cruiser).data().missile_launchers[1].lost);
+    assert_eq!(section::sections(&sim, cruiser), vec![pods[1]]);
+
+    // The lost launcher can't fire, the others still can.
+    let count = sim.ships.len();
+    sim.ship_mut(cruiser).launch_missile(0);
+    assert_eq!(sim.ships.len(), count);
+    sim.ship_mut(cruiser).launch_missile(1);
+    assert_eq!(sim.ships.len(), count + 1);
+
+    // The remaining pod goes down with the ship.
+    sim.ship_mut(cruiser).data_mut().destroyed = true;
+    sim.step();
+    assert!(!sim.ships.contains(cruiser));
+    assert!(!sim.ships.contains(pods[1]));
+}
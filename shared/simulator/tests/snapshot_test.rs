@@ -0,0 +1,238 @@
+use nalgebra::vector;
+use oort_simulator::ship::{self, WeaponClass};
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::snapshot::{
+    self, v1, Effect, Snapshot, SnapshotMode, VersionedSnapshot, SNAPSHOT_VERSION,
+};
+use test_log::test;
+
+fn make_snapshot() -> Snapshot {
+    let mut sim = simulation::Simulation::new("floating_origin_test", 0, &[Code::None]);
+    for _ in 0..100 {
+        sim.step();
+    }
+    sim.snapshot(0)
+}
+
+// Converts `snapshot` to the layout used before versioning, the way an old
+// build would have produced it.
+fn downgrade(snapshot: Snapshot) -> v1::Snapshot {
+    v1::Snapshot {
+        nonce: snapshot.nonce,
+        time: snapshot.time,
+        score_time: snapshot.score_time,
+        status: snapshot.status,
+        ships: snapshot
+            .ships
+            .into_iter()
+            .map(|ship| v1::ShipSnapshot {
+                id: ship.id,
+                position: ship.position,
+                velocity: ship.velocity,
+                acceleration: ship.acceleration,
+                heading: ship.heading,
+                angular_velocity: ship.angular_velocity,
+                team: ship.team,
+                class: ship.class,
+                health: ship.health,
+                fuel: ship.fuel,
+                active_abilities: ship.active_abilities,
+            })
+            .collect(),
+        bullets: snapshot
+            .bullets
+            .into_iter()
+            .map(|bullet| v1::BulletSnapshot {
+                position: bullet.position,
+                velocity: bullet.velocity,
+                color: bullet.color,
+                ttl: bullet.ttl,
+            })
+            .collect(),
+        scenario_lines: snapshot.scenario_lines,
+        particles: snapshot.particles,
+        errors: snapshot
+            .errors
+            .into_iter()
+            .map(|error| v1::Error { msg: error.msg })
+            .collect(),
+        cheats: snapshot.cheats,
+        debug_lines: snapshot.debug_lines,
+        debug_text: snapshot.debug_text,
+        drawn_text: snapshot.drawn_text,
+        timing: snapshot.timing,
+        world_size: snapshot.world_size,
+    }
+}
+
+#[test]
+fn test_current_version() {
+    let snapshot = make_snapshot();
+    let versioned: VersionedSnapshot = snapshot.clone().into();
+    assert_eq!(versioned.version(), SNAPSHOT_VERSION);
+
+    let bytes = bincode::serialize(&versioned).unwrap();
+    let decoded = bincode::deserialize::<VersionedSnapshot>(&bytes)
+        .unwrap()
+        .into_latest();
+    assert_eq!(decoded.time, snapshot.time);
+    assert_eq!(decoded.origin, snapshot.origin);
+    assert_eq!(decoded.ships.len(), snapshot.ships.len());
+}
+
+#[test]
+fn test_decode_v1() {
+    let snapshot = make_snapshot();
+    let bytes = bincode::serialize(&VersionedSnapshot::V1(downgrade(snapshot.clone()))).unwrap();
+    let versioned = bincode::deserialize::<VersionedSnapshot>(&bytes).unwrap();
+    assert_eq!(versioned.version(), 1);
+
+    let decoded = versioned.into_latest();
+    assert_eq!(decoded.time, snapshot.time);
+    assert_eq!(decoded.ships.len(), snapshot.ships.len());
+    assert_eq!(decoded.bullets.len(), snapshot.bullets.len());
+    assert_eq!(decoded.origin, nalgebra::vector![0.0, 0.0]);
+    assert!(decoded.hints.is_empty());
+    assert!(decoded.stats.is_none());
+}
+
+#[test]
+fn test_decode() {
+    let snapshot = make_snapshot();
+    let bytes = bincode::serialize(&VersionedSnapshot::from(snapshot.clone())).unwrap();
+    let decoded = snapshot::decode(&bytes).unwrap();
+    assert_eq!(decoded.time, snapshot.time);
+    assert_eq!(decoded.origin, snapshot.origin);
+
+    // Saved before snapshots had a version header.
+    let bytes = bincode::serialize(&downgrade(snapshot.clone())).unwrap();
+    let decoded = snapshot::decode(&bytes).unwrap();
+    assert_eq!(decoded.time, snapshot.time);
+    assert_eq!(decoded.ships.len(), snapshot.ships.len());
+
+    assert!(snapshot::decode(&[]).is_err());
+}
+
+#[test]
+fn test_json_missing_fields() {
+    let snapshot = make_snapshot();
+    let json = serde_json::to_string(&downgrade(snapshot.clone())).unwrap();
+    let decoded: Snapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.time, snapshot.time);
+    assert_eq!(decoded.origin, nalgebra::vector![0.0, 0.0]);
+}
+
+#[test]
+fn test_effects() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let missile = ship::create(
+        &mut sim,
+        vector![1000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::missile(0),
+    );
+
+    sim.ship_mut(fighter).accelerate(vector![10.0, 0.0]);
+    sim.ship_mut(missile).explode();
+    sim.step();
+    sim.ship_mut(fighter).fire_gun(0);
+
+    let effects = sim.snapshot(0).effects;
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::Thruster { ship, magnitude, .. } if *ship == u64::from(fighter) && *magnitude == 10.0
+    )));
+    assert!(effects
+        .iter()
+        .any(|effect| matches!(effect, Effect::Explosion { radius, .. } if *radius > 0.0)));
+    assert!(effects.contains(&Effect::MuzzleFlash {
+        ship: fighter.into(),
+        gun: 0,
+        weapon_class: WeaponClass::Autocannon,
+    }));
+}
+
+#[test]
+fn test_weapon_classes() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let frigate = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::frigate(0),
+    );
+    sim.ship_mut(frigate).fire_gun(0);
+    sim.ship_mut(frigate).fire_gun(1);
+    sim.step();
+
+    let snapshot = sim.snapshot(0);
+    for (gun, class) in [(0, WeaponClass::HeavySlug), (1, WeaponClass::PointDefense)] {
+        assert!(snapshot.effects.contains(&Effect::MuzzleFlash {
+            ship: frigate.into(),
+            gun,
+            weapon_class: class,
+        }));
+        assert!(snapshot
+            .bullets
+            .iter()
+            .any(|bullet| bullet.weapon_class == class));
+    }
+}
+
+#[test]
+fn test_snapshot_modes() {
+    let setup = |mode| {
+        let mut sim = simulation::Simulation::new_with_snapshot_mode(
+            "test",
+            0,
+            &[Code::None, Code::None],
+            mode,
+        );
+        let ship0 = ship::create(
+            &mut sim,
+            vector![-1000.0, 0.0],
+            vector![0.0, 0.0],
+            0.0,
+            ship::fighter(0),
+        );
+        let ship1 = ship::create(
+            &mut sim,
+            vector![1000.0, 0.0],
+            vector![0.0, 0.0],
+            std::f64::consts::PI,
+            ship::fighter(1),
+        );
+        sim.step();
+        sim.ship_mut(ship0).fire_gun(0);
+        sim.ship_mut(ship1).fire_gun(0);
+        sim.step();
+        sim.snapshot(0)
+    };
+
+    let snapshot = setup(SnapshotMode::GroundTruth);
+    assert_eq!(snapshot.mode, SnapshotMode::GroundTruth);
+    assert_eq!(snapshot.ships.len(), 2);
+    assert_eq!(snapshot.bullets.len(), 2);
+    assert_eq!(snapshot.radar_beams.len(), 2);
+
+    let snapshot = setup(SnapshotMode::Team(1));
+    assert_eq!(snapshot.mode, SnapshotMode::Team(1));
+    assert_eq!(snapshot.ships.len(), 1);
+    assert_eq!(snapshot.ships[0].team, 1);
+    assert_eq!(snapshot.bullets.len(), 1);
+    assert_eq!(snapshot.radar_beams.len(), 1);
+    assert_eq!(snapshot.radar_beams[0].team, 1);
+    assert!(snapshot
+        .debug_lines
+        .iter()
+        .all(|(id, _)| *id == snapshot.ships[0].id));
+}
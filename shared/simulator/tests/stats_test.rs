@@ -0,0 +1,176 @@
+use nalgebra::vector;
+use oort_simulator::scenario::Status;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::snapshot::Effect;
+use test_log::test;
+
+#[test]
+fn test_gun_stats() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let mut fighter = ship::fighter(0);
+    fighter.guns[0].inaccuracy = 0.0;
+    let offset = fighter.guns[0].offset;
+    let attacker = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, fighter);
+    let mut target_data = ship::target(1);
+    target_data.health = 1e6;
+    let target = ship::create(
+        &mut sim,
+        vector![100.0, 0.0] + offset,
+        vector![0.0, 0.0],
+        0.0,
+        target_data,
+    );
+
+    sim.ship_mut(attacker).fire_gun(0);
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    let damage = 1e6 - sim.ship(target).data().health;
+    assert!(damage > 0.0);
+    let stats = sim.battle_stats();
+    assert_eq!(stats.team_damage(0, 1), damage);
+    assert_eq!(stats.team_damage(1, 0), 0.0);
+    assert_eq!(stats.class_damage["fighter"]["target"], damage);
+    let weapon = &stats.weapons["fighter gun 0"];
+    assert_eq!(weapon.shots, 1);
+    assert_eq!(weapon.hits, 1);
+    assert_eq!(weapon.accuracy(), 1.0);
+}
+
+#[test]
+fn test_ship_gun_stats() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let mut fighter = ship::fighter(0);
+    fighter.guns[0].inaccuracy = 0.0;
+    let offset = fighter.guns[0].offset;
+    let hitter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        fighter.clone(),
+    );
+    let misser = ship::create(
+        &mut sim,
+        vector![0.0, 500.0],
+        vector![0.0, 0.0],
+        std::f64::consts::PI,
+        fighter,
+    );
+    let mut target_data = ship::target(1);
+    target_data.health = 1e6;
+    ship::create(
+        &mut sim,
+        vector![100.0, 0.0] + offset,
+        vector![0.0, 0.0],
+        0.0,
+        target_data,
+    );
+
+    sim.ship_mut(hitter).fire_gun(0);
+    sim.ship_mut(misser).fire_gun(0);
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    let stats = sim.battle_stats();
+    assert_eq!(stats.weapons["fighter gun 0"].shots, 2);
+    assert_eq!(stats.weapons["fighter gun 0"].hits, 1);
+    let hits = stats.ship_gun(hitter.into(), 0).unwrap();
+    assert_eq!((hits.shots, hits.hits), (1, 1));
+    let misses = stats.ship_gun(misser.into(), 0).unwrap();
+    assert_eq!((misses.shots, misses.hits), (1, 0));
+    assert_eq!(misses.accuracy(), 0.0);
+    assert_eq!(stats.ship_guns[&u64::from(misser)].team, 0);
+    assert!(stats.ship_gun(hitter.into(), 1).is_none());
+}
+
+#[test]
+fn test_overkill() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let mut fighter = ship::fighter(0);
+    fighter.guns[0].inaccuracy = 0.0;
+    let offset = fighter.guns[0].offset;
+    let attacker = ship::create(&mut sim, vector![0.0, 0.0], vector![0.0, 0.0], 0.0, fighter);
+    let mut target_data = ship::target(1);
+    target_data.health = 1.0;
+    let target = ship::create(
+        &mut sim,
+        vector![100.0, 0.0] + offset,
+        vector![0.0, 0.0],
+        0.0,
+        target_data,
+    );
+
+    sim.ship_mut(attacker).fire_gun(0);
+    let mut hits = vec![];
+    for _ in 0..60 {
+        sim.step();
+        hits.extend(
+            sim.snapshot(0)
+                .effects
+                .into_iter()
+                .filter(|effect| matches!(effect, Effect::Hit { .. })),
+        );
+    }
+
+    assert_eq!(hits.len(), 1);
+    let Effect::Hit {
+        ship,
+        position,
+        normal,
+        damage,
+        overkill,
+    } = hits[0]
+    else {
+        unreachable!()
+    };
+    assert_eq!(ship, u64::from(target));
+    assert!(position.x < 100.0 + offset.x);
+    // The bullet came from the left, so it hit the target's left side.
+    assert!(normal.x < -0.9);
+    assert!((normal.norm() - 1.0).abs() < 1e-9);
+    assert!(damage > 1.0);
+    assert_eq!(overkill, damage - 1.0);
+
+    let weapon = &sim.battle_stats().weapons["fighter gun 0"];
+    assert_eq!(weapon.damage, damage);
+    assert_eq!(weapon.overkill, overkill);
+    assert_eq!(weapon.overkill_fraction(), overkill / damage);
+}
+
+#[test]
+fn test_missile_stats() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(fighter).launch_missile(0);
+    sim.step();
+    assert_eq!(sim.battle_stats().missiles[&0].launched, 1);
+    assert_eq!(sim.battle_stats().missiles[&0].intercepted, 0);
+}
+
+#[test]
+fn test_snapshot_stats() {
+    let mut sim = simulation::Simulation::new("basic", 0, &[Code::None, Code::None]);
+    sim.step();
+    assert_eq!(sim.status(), Status::Running);
+    assert!(sim.snapshot(0).stats.is_none());
+
+    let enemy = *sim
+        .ships
+        .iter()
+        .find(|&&h| sim.ship(h).data().team == 1)
+        .unwrap();
+    sim.ship_mut(enemy).explode();
+    sim.step();
+    assert_eq!(sim.status(), Status::Victory { team: 0 });
+    assert_eq!(sim.snapshot(0).stats.as_ref(), Some(sim.battle_stats()));
+}
@@ -0,0 +1,103 @@
+use nalgebra::vector;
+use oort_simulator::bullet;
+use oort_simulator::scenario::{self, Status};
+use oort_simulator::ship::{self, ShipClass};
+use oort_simulator::simulation::{self, Code};
+use std::f64::consts::FRAC_PI_2;
+use test_log::test;
+
+#[test]
+fn test_station_immobile() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let station = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::station(0),
+    );
+    let missile = ship::create(
+        &mut sim,
+        vector![-500.0, 0.0],
+        vector![500.0, 0.0],
+        0.0,
+        ship::missile(1),
+    );
+    let initial_health = sim.ship(station).data().health;
+
+    for _ in 0..120 {
+        sim.step();
+    }
+
+    assert!(!sim.ships.contains(missile));
+    assert!(sim.ship(station).data().health < initial_health);
+    assert_eq!(sim.ship(station).position().vector, vector![0.0, 0.0]);
+    assert_eq!(sim.ship(station).heading(), 0.0);
+}
+
+#[test]
+fn test_turret_fires() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None]);
+    let turret = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::turret(0),
+    );
+
+    sim.ship_mut(turret).aim(0, FRAC_PI_2);
+    sim.ship_mut(turret).fire(0);
+    let bullet = *sim.bullets.iter().next().unwrap();
+    let v = bullet::body(&sim, bullet).linvel();
+    approx::assert_abs_diff_eq!(v.x, 0.0, epsilon = 1e-6);
+    assert!(v.y > 0.0);
+}
+
+#[test]
+fn test_station_defense() {
+    let mut sim = simulation::Simulation::new("station_defense", 0, &[Code::None, Code::None]);
+    sim.step();
+    let count = |sim: &simulation::Simulation, team: i32| {
+        sim.ships
+            .iter()
+            .filter(|&&h| sim.ship(h).data().team == team)
+            .count()
+    };
+    assert_eq!(count(&sim, 0), 9);
+    assert_eq!(count(&sim, 1), 4);
+    assert_eq!(sim.status(), Status::Running);
+
+    let station = *sim
+        .ships
+        .iter()
+        .find(|&&h| sim.ship(h).data().class == ShipClass::Station)
+        .unwrap();
+    sim.ship_mut(station).explode();
+    sim.step();
+    assert_eq!(sim.status(), Status::Victory { team: 1 });
+}
+
+#[test]
+fn test_station_defense_solution() {
+    let scenario_name = "station_defense";
+    let scenario = scenario::load(scenario_name);
+    let mut codes = scenario.initial_code();
+    codes[0] = scenario.solution();
+    let mut sim = simulation::Simulation::new(scenario_name, 0, &codes);
+    for _ in 0..60 {
+        sim.step();
+    }
+
+    // The station and turrets are flown by the solution code too.
+    for &handle in sim.ships.iter() {
+        let data = sim.ship(handle).data();
+        if data.team == 0 {
+            assert_eq!(
+                data.crash_message, None,
+                "{:?} crashed running the solution",
+                data.class
+            );
+        }
+    }
+}
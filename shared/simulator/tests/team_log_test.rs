@@ -0,0 +1,40 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code};
+use test_log::test;
+
+#[test]
+fn test_team_log() {
+    let mut sim = simulation::Simulation::new("test", 0, &[Code::None, Code::None]);
+    let ship0 = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    let ship1 = ship::create(
+        &mut sim,
+        vector![1000.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(1),
+    );
+
+    sim.emit_team_log(ship0, "engaging\n");
+    sim.emit_team_log(ship1, "retreating\nout of ammo\n");
+    let entries = &sim.snapshot(0).team_log;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].team, 0);
+    assert_eq!(entries[0].ship, u64::from(ship0));
+    assert_eq!(entries[0].text, "engaging");
+    assert_eq!(entries[2].team, 1);
+    assert_eq!(entries[2].text, "out of ammo");
+
+    // Snapshots only carry new lines but the transcript keeps everything.
+    sim.step();
+    sim.emit_team_log(ship0, "done");
+    assert_eq!(sim.snapshot(0).team_log.len(), 1);
+    assert_eq!(sim.snapshot(0).team_log[0].tick, 1);
+    assert_eq!(sim.team_log().entries().len(), 4);
+}
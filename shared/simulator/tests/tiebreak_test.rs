@@ -0,0 +1,65 @@
+use oort_simulator::scenario::{Status, Tiebreak};
+use oort_simulator::ship::ShipHandle;
+use oort_simulator::simulation::{Code, Simulation};
+use test_log::test;
+
+fn run(tiebreak: Tiebreak, setup: impl FnOnce(&mut Simulation)) -> Box<Simulation> {
+    let mut sim = Simulation::new("fighter_duel", 0, &[Code::None, Code::None]);
+    sim.set_time_limit(100, tiebreak);
+    setup(&mut sim);
+    while sim.status() == Status::Running {
+        sim.step();
+    }
+    sim
+}
+
+fn team_ship(sim: &Simulation, team: i32) -> ShipHandle {
+    *sim.ships
+        .iter()
+        .find(|&&handle| sim.ship(handle).data().team == team)
+        .unwrap()
+}
+
+#[test]
+fn test_time_limit_draw() {
+    let sim = run(Tiebreak::Draw, |_| {});
+    assert_eq!(sim.status(), Status::Draw);
+    assert_eq!(sim.tick(), 99);
+    assert_eq!(sim.tiebreak_applied(), Some(Tiebreak::Draw));
+}
+
+#[test]
+fn test_surviving_health_tiebreak() {
+    let sim = run(Tiebreak::SurvivingHealth, |sim| {
+        let ship = team_ship(sim, 0);
+        sim.ship_mut(ship).data_mut().health -= 10.0;
+    });
+    assert_eq!(sim.status(), Status::Victory { team: 1 });
+    assert_eq!(sim.tiebreak_applied(), Some(Tiebreak::SurvivingHealth));
+}
+
+#[test]
+fn test_damage_dealt_tiebreak_without_damage() {
+    let sim = run(Tiebreak::DamageDealt, |_| {});
+    assert_eq!(sim.status(), Status::Draw);
+    assert_eq!(sim.damage_dealt(0), 0.0);
+}
+
+#[test]
+fn test_victory_on_last_tick_is_not_a_tiebreak() {
+    let sim = run(Tiebreak::SurvivingHealth, |sim| {
+        sim.set_time_limit(2, Tiebreak::SurvivingHealth);
+        let ship = team_ship(sim, 1);
+        sim.ship_mut(ship).explode();
+    });
+    assert_eq!(sim.status(), Status::Victory { team: 0 });
+    assert_eq!(sim.tick(), 1);
+    assert_eq!(sim.tiebreak_applied(), None);
+}
+
+#[test]
+fn test_no_tiebreak_before_time_limit() {
+    let sim = Simulation::new("fighter_duel", 0, &[Code::None, Code::None]);
+    assert_eq!(sim.status(), Status::Running);
+    assert_eq!(sim.tiebreak_applied(), None);
+}
@@ -0,0 +1,118 @@
+// Runs each tutorial's solution across many seeds to catch changes to ship
+// stats or sensor physics that make a tutorial unreliable or slow to finish
+// without outright breaking it.
+
+use oort_simulator::scenario::{self, DEFAULT_TUTORIAL_MAX_TICKS};
+use oort_simulator::simulation;
+use rayon::prelude::*;
+use test_log::test;
+
+// Disjoint from the seeds used by tutorial_test.
+const SEEDS: std::ops::Range<u32> = 1000..1032;
+
+struct Requirement {
+    min_win_rate: f64,
+    max_ticks: u32,
+}
+
+fn requirement(scenario_name: &str) -> Requirement {
+    let time_limit = match scenario_name {
+        "tutorial_radar" | "tutorial_radio" | "tutorial_missiles" | "tutorial_frigate"
+        | "tutorial_cruiser" => DEFAULT_TUTORIAL_MAX_TICKS * 2,
+        "tutorial_search" | "tutorial_squadron" => DEFAULT_TUTORIAL_MAX_TICKS * 3,
+        _ => DEFAULT_TUTORIAL_MAX_TICKS,
+    };
+    Requirement {
+        min_win_rate: 0.95,
+        max_ticks: time_limit,
+    }
+}
+
+#[derive(Default)]
+struct Stats {
+    runs: u32,
+    wins: u32,
+    // Ticks taken by the slowest and all winning runs.
+    max_ticks: u32,
+    total_ticks: u64,
+}
+
+impl Stats {
+    fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.runs as f64
+    }
+
+    fn mean_ticks(&self) -> f64 {
+        self.total_ticks as f64 / self.wins.max(1) as f64
+    }
+}
+
+// Returns the number of ticks to victory, or None if the solution failed.
+fn run_once(scenario_name: &str, seed: u32) -> Option<u32> {
+    let codes = scenario::load(scenario_name).solution_codes();
+    let mut sim = simulation::Simulation::new(scenario_name, seed, &codes);
+    while sim.status() == scenario::Status::Running && sim.tick() < scenario::MAX_TICKS {
+        sim.step();
+    }
+    if sim.status() == (scenario::Status::Victory { team: 0 }) {
+        Some(sim.tick())
+    } else {
+        None
+    }
+}
+
+fn run_solution(scenario_name: &str) -> Stats {
+    let results: Vec<Option<u32>> = SEEDS
+        .into_par_iter()
+        .map(|seed| run_once(scenario_name, seed))
+        .collect();
+    let mut stats = Stats::default();
+    for ticks in results {
+        stats.runs += 1;
+        if let Some(ticks) = ticks {
+            stats.wins += 1;
+            stats.max_ticks = stats.max_ticks.max(ticks);
+            stats.total_ticks += ticks as u64;
+        }
+    }
+    stats
+}
+
+fn check_requirement(scenario_name: &str) {
+    let requirement = requirement(scenario_name);
+    let stats = run_solution(scenario_name);
+    log::info!(
+        "{scenario_name}: won {}/{} runs, mean {:.0} ticks, max {} ticks",
+        stats.wins,
+        stats.runs,
+        stats.mean_ticks(),
+        stats.max_ticks
+    );
+    assert!(
+        stats.win_rate() >= requirement.min_win_rate,
+        "tutorial {scenario_name} solution won {}/{} runs, below the minimum win rate of {}",
+        stats.wins,
+        stats.runs,
+        requirement.min_win_rate
+    );
+    assert!(
+        stats.max_ticks <= requirement.max_ticks,
+        "tutorial {scenario_name} solution took {} ticks, over the maximum of {}",
+        stats.max_ticks,
+        requirement.max_ticks
+    );
+}
+
+#[test]
+fn test_tutorial_solutions() {
+    let categories = scenario::list();
+    let scenario_names: &Vec<String> = &categories
+        .iter()
+        .find(|(category, _)| category == "Tutorial")
+        .unwrap()
+        .1;
+    assert!(!scenario_names.is_empty());
+    scenario_names
+        .into_par_iter()
+        .for_each(|x| check_requirement(x));
+}
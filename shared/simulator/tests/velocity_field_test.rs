@@ -0,0 +1,63 @@
+use nalgebra::vector;
+use oort_simulator::ship::ShipClass;
+use oort_simulator::simulation::{self, Code};
+use oort_simulator::velocity_field::VelocityField;
+use test_log::test;
+
+#[test]
+fn test_keplerian_velocity() {
+    let field = VelocityField::Keplerian {
+        center: vector![0.0, 0.0],
+        mu: 1e6,
+    };
+    let v = field.velocity(vector![1000.0, 0.0]);
+    approx::assert_abs_diff_eq!(v.x, 0.0, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(v.y, 1e3f64.sqrt(), epsilon = 1e-9);
+    assert_eq!(
+        VelocityField::None.velocity(vector![1.0, 2.0]),
+        vector![0.0, 0.0]
+    );
+}
+
+#[test]
+fn test_keplerian_acceleration() {
+    let field = VelocityField::Keplerian {
+        center: vector![0.0, 0.0],
+        mu: 1e6,
+    };
+    let position = vector![0.0, 1000.0];
+    let a = field.acceleration(position);
+    let v = field.velocity(position);
+    approx::assert_abs_diff_eq!(a.x, 0.0, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(a.y, -v.norm_squared() / 1000.0, epsilon = 1e-9);
+    assert_eq!(
+        VelocityField::Uniform(vector![1.0, 2.0]).acceleration(position),
+        vector![0.0, 0.0]
+    );
+}
+
+#[test]
+fn test_asteroid_orbit() {
+    let mut sim = simulation::Simulation::new("velocity_field_test", 0, &[Code::None]);
+    let asteroid = *sim
+        .ships
+        .iter()
+        .find(|&&h| matches!(sim.ship(h).data().class, ShipClass::Asteroid { .. }))
+        .unwrap();
+    let fighter = *sim
+        .ships
+        .iter()
+        .find(|&&h| sim.ship(h).data().class == ShipClass::Fighter)
+        .unwrap();
+
+    for _ in 0..600 {
+        sim.step();
+    }
+
+    // Angular velocity is about 0.03 rad/s, so after 10 seconds the asteroid
+    // has moved about 0.3 radians.
+    let position = sim.ship(asteroid).position().vector;
+    approx::assert_abs_diff_eq!(position.magnitude(), 1000.0, epsilon = 10.0);
+    assert!(position.y > 250.0, "position={position:?}");
+    assert_eq!(sim.ship(fighter).position().vector, vector![-1000.0, 0.0]);
+}
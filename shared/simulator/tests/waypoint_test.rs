@@ -0,0 +1,75 @@
+use nalgebra::vector;
+use oort_simulator::ship;
+use oort_simulator::simulation::{self, Code, Simulation};
+use oort_simulator::waypoint::{Waypoint, WaypointPilot};
+use test_log::test;
+
+fn waypoint(x: f64, y: f64) -> Waypoint {
+    Waypoint {
+        position: vector![x, y],
+        speed: 200.0,
+    }
+}
+
+#[test]
+fn test_waypoints() {
+    let mut sim = Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(fighter).data_mut().waypoint_pilot = Some(WaypointPilot::new(
+        vec![waypoint(1000.0, 0.0), waypoint(1000.0, 1000.0)],
+        false,
+    ));
+    for _ in 0..(simulation::PHYSICS_TICK_LENGTH.recip() as usize * 60) {
+        sim.step();
+    }
+
+    let ship = sim.ship(fighter);
+    assert!(ship.data().waypoint_pilot.as_ref().unwrap().finished);
+    approx::assert_abs_diff_eq!(
+        ship.position().vector,
+        vector![1000.0, 1000.0],
+        epsilon = 10.0
+    );
+    assert!(ship.velocity().norm() < 5.0);
+    approx::assert_abs_diff_eq!(ship.heading(), std::f64::consts::FRAC_PI_2, epsilon = 0.1);
+}
+
+#[test]
+fn test_looping_waypoints() {
+    let mut sim = Simulation::new("test", 0, &[Code::None]);
+    let fighter = ship::create(
+        &mut sim,
+        vector![0.0, 0.0],
+        vector![0.0, 0.0],
+        0.0,
+        ship::fighter(0),
+    );
+    sim.ship_mut(fighter).data_mut().waypoint_pilot = Some(WaypointPilot::new(
+        vec![
+            waypoint(1000.0, 0.0),
+            waypoint(1000.0, 1000.0),
+            waypoint(0.0, 1000.0),
+            waypoint(0.0, 0.0),
+        ],
+        true,
+    ));
+
+    let mut laps = 0;
+    let mut last_next = 0;
+    for _ in 0..(simulation::PHYSICS_TICK_LENGTH.recip() as usize * 60) {
+        sim.step();
+        let pilot = sim.ship(fighter).data().waypoint_pilot.clone().unwrap();
+        assert!(!pilot.finished);
+        if pilot.next < last_next {
+            laps += 1;
+        }
+        last_next = pilot.next;
+    }
+    assert!(laps >= 1);
+}
@@ -46,7 +46,7 @@ async fn main() -> anyhow::Result<()> {
         .map(|_| simulation::Simulation::new(&args.scenario, args.seed, &codes))
         .collect::<Vec<_>>();
     while sims[0].status() == scenario::Status::Running && sims[0].tick() < scenario::MAX_TICKS {
-        let hashes = sims.iter().map(|sim| sim.hash()).collect::<Vec<_>>();
+        let hashes = sims.iter().map(|sim| sim.state_hash()).collect::<Vec<_>>();
         if hashes[0] != hashes[1] {
             println!("hashes differ at tick {}", sims[0].tick());
             diff_snapshots(&sims[0].snapshot(0), &sims[1].snapshot(0));
@@ -0,0 +1,49 @@
+use clap::Parser;
+use oort_simulator::{replay_export, scenario};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[clap()]
+struct Arguments {
+    scenario: String,
+    shortcodes: Vec<String>,
+
+    #[clap(short, long, default_value = "0")]
+    seed: u32,
+
+    #[clap(short, long)]
+    output: PathBuf,
+
+    #[clap(short, long)]
+    dev: bool,
+
+    #[clap(long, default_value = "/tmp/oort-wasm-cache")]
+    wasm_cache: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Arguments::parse();
+    scenario::load_safe(&args.scenario).expect("Unknown scenario");
+
+    log::info!("Compiling AIs");
+    let http = reqwest::Client::new();
+    let ais = oort_tools::fetch_and_compile_multiple(
+        &http,
+        &args.shortcodes,
+        args.dev,
+        args.wasm_cache.as_deref(),
+    )
+    .await?;
+    let codes: Vec<_> = ais.iter().map(|ai| ai.compiled_code.clone()).collect();
+
+    log::info!("Exporting replay to {}", args.output.display());
+    let mut writer = BufWriter::new(File::create(&args.output)?);
+    replay_export::export(&args.scenario, args.seed, &codes, &mut writer)?;
+
+    Ok(())
+}
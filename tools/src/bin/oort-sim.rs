@@ -0,0 +1,245 @@
+// Runs the simulator from the command line, without the compiler service or
+// the frontend. Results are printed to stdout as JSON.
+//
+// Code for each team is given as one of:
+//   builtin:NAME  a builtin AI, e.g. builtin:reference
+//   PATH.wasm     a compiled AI
+//   none          no code
+// With no code arguments, each team runs the scenario's solution code.
+use clap::{Parser, Subcommand};
+use oort_simulator::scenario::{self, Status};
+use oort_simulator::simulation::{Code, Simulation};
+use oort_simulator::snapshot::{CpuUsage, Timing};
+use oort_simulator::stats::BattleStats;
+use oort_simulator::{replay_export, vm};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Parser, Debug)]
+#[clap()]
+struct Arguments {
+    #[clap(subcommand)]
+    cmd: SubCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum SubCommand {
+    /// Runs the scenario to completion on each seed and prints the results.
+    Run {
+        scenario: String,
+        codes: Vec<String>,
+        #[clap(short, long, default_value_t = 0)]
+        seed: u32,
+        #[clap(short, long, default_value_t = 1)]
+        rounds: u32,
+        /// Include battle stats in the results.
+        #[clap(long)]
+        stats: bool,
+    },
+    /// Dumps every tick of a battle in the replay export format.
+    Replay {
+        scenario: String,
+        codes: Vec<String>,
+        #[clap(short, long, default_value_t = 0)]
+        seed: u32,
+        /// Written to stdout if not given.
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Measures simulation speed over several runs.
+    Bench {
+        scenario: String,
+        codes: Vec<String>,
+        #[clap(short, long, default_value_t = 10)]
+        rounds: u32,
+        /// Stops each run after this many ticks.
+        #[clap(short, long, default_value_t = scenario::MAX_TICKS)]
+        ticks: u32,
+    },
+    /// Breaks down where simulation time goes in a single run.
+    Profile {
+        scenario: String,
+        codes: Vec<String>,
+        #[clap(short, long, default_value_t = 0)]
+        seed: u32,
+        #[clap(short, long, default_value_t = scenario::MAX_TICKS)]
+        ticks: u32,
+    },
+}
+
+#[derive(Serialize, Debug)]
+struct RunResult {
+    scenario: String,
+    seed: u32,
+    status: Status,
+    ticks: u32,
+    hash: u64,
+    cpu: Vec<CpuUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<BattleStats>,
+}
+
+#[derive(Serialize, Debug)]
+struct BenchResult {
+    scenario: String,
+    rounds: u32,
+    ticks: u64,
+    seconds: f64,
+    ticks_per_second: f64,
+}
+
+#[derive(Serialize, Debug)]
+struct ProfileResult {
+    scenario: String,
+    seed: u32,
+    ticks: u32,
+    // Seconds spent in each phase, over the whole run.
+    timing: Timing,
+    total: f64,
+    // Largest number of ships and bullets alive at once.
+    max_ships: usize,
+    max_bullets: usize,
+    cpu: Vec<CpuUsage>,
+}
+
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Arguments::parse();
+    match args.cmd {
+        SubCommand::Run {
+            scenario,
+            codes,
+            seed,
+            rounds,
+            stats,
+        } => {
+            let codes = load_codes(&scenario, &codes)?;
+            let results: Vec<RunResult> = (seed..seed + rounds)
+                .into_par_iter()
+                .map(|seed| run(&scenario, seed, &codes, stats))
+                .collect();
+            print_json(&results)
+        }
+        SubCommand::Replay {
+            scenario,
+            codes,
+            seed,
+            output,
+        } => {
+            let codes = load_codes(&scenario, &codes)?;
+            let mut writer: Box<dyn Write> = match output {
+                Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+                None => Box::new(BufWriter::new(std::io::stdout().lock())),
+            };
+            replay_export::export(&scenario, seed, &codes, &mut writer)?;
+            Ok(())
+        }
+        SubCommand::Bench {
+            scenario,
+            codes,
+            rounds,
+            ticks,
+        } => {
+            let codes = load_codes(&scenario, &codes)?;
+            let start = Instant::now();
+            let mut total_ticks = 0;
+            for seed in 0..rounds {
+                let mut sim = Simulation::new(&scenario, seed, &codes);
+                run_until(&mut sim, ticks, |_| {});
+                total_ticks += sim.tick() as u64;
+            }
+            let seconds = start.elapsed().as_secs_f64();
+            print_json(&BenchResult {
+                scenario,
+                rounds,
+                ticks: total_ticks,
+                seconds,
+                ticks_per_second: total_ticks as f64 / seconds,
+            })
+        }
+        SubCommand::Profile {
+            scenario,
+            codes,
+            seed,
+            ticks,
+        } => {
+            let codes = load_codes(&scenario, &codes)?;
+            let mut sim = Simulation::new(&scenario, seed, &codes);
+            let mut timing = Timing::default();
+            let (mut max_ships, mut max_bullets) = (0, 0);
+            run_until(&mut sim, ticks, |sim| {
+                timing += sim.timing().clone();
+                max_ships = max_ships.max(sim.ships.len());
+                max_bullets = max_bullets.max(sim.bullets.len());
+            });
+            print_json(&ProfileResult {
+                scenario,
+                seed,
+                ticks: sim.tick(),
+                total: timing.total(),
+                timing,
+                max_ships,
+                max_bullets,
+                cpu: cpu_usage(&sim, codes.len()),
+            })
+        }
+    }
+}
+
+fn load_codes(scenario_name: &str, args: &[String]) -> anyhow::Result<Vec<Code>> {
+    let Some(scenario) = scenario::load_safe(scenario_name) else {
+        anyhow::bail!("Unknown scenario {:?}", scenario_name);
+    };
+    if args.is_empty() {
+        return Ok(scenario.solution_codes());
+    }
+    args.iter()
+        .map(|arg| -> anyhow::Result<Code> {
+            if arg == "none" {
+                Ok(Code::None)
+            } else if let Some(name) = arg.strip_prefix("builtin:") {
+                Ok(scenario::builtin(name))
+            } else {
+                let wasm = std::fs::read(arg)?;
+                vm::precompile(&wasm)
+                    .map_err(|e| anyhow::anyhow!("Failed to load {arg:?}: {}", e.msg))
+            }
+        })
+        .collect()
+}
+
+fn run(scenario_name: &str, seed: u32, codes: &[Code], stats: bool) -> RunResult {
+    let mut sim = Simulation::new(scenario_name, seed, codes);
+    run_until(&mut sim, scenario::MAX_TICKS, |_| {});
+    RunResult {
+        scenario: scenario_name.to_string(),
+        seed,
+        status: sim.status(),
+        ticks: sim.tick(),
+        hash: sim.hash(),
+        cpu: cpu_usage(&sim, codes.len()),
+        stats: stats.then(|| sim.battle_stats().clone()),
+    }
+}
+
+fn run_until(sim: &mut Simulation, ticks: u32, mut f: impl FnMut(&Simulation)) {
+    while sim.status() == Status::Running && sim.tick() < ticks {
+        sim.step();
+        f(sim);
+    }
+}
+
+// Indexed by team.
+fn cpu_usage(sim: &Simulation, teams: usize) -> Vec<CpuUsage> {
+    (0..teams).map(|team| sim.cpu_usage(team as i32)).collect()
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -19,6 +19,7 @@ struct Arguments {
 fn class_to_ship_data(class: ShipClass, team: i32) -> ShipData {
     match class {
         ShipClass::Fighter => ship::fighter(team),
+        ShipClass::Corvette => ship::corvette(team),
         ShipClass::Frigate => ship::frigate(team),
         ShipClass::Cruiser => ship::cruiser(team),
         ShipClass::Missile => ship::missile(team),
@@ -0,0 +1,64 @@
+use clap::Parser;
+use oort_simulator::{replay_diff, replay_export};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser, Debug)]
+#[clap()]
+struct Arguments {
+    a: PathBuf,
+    b: PathBuf,
+
+    // Largest difference in a floating point field that isn't reported.
+    #[clap(short, long, default_value = "0")]
+    tolerance: f64,
+
+    #[clap(short, long, default_value = "20")]
+    limit: usize,
+}
+
+fn read(path: &Path) -> anyhow::Result<Vec<replay_export::Record>> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(replay_export::read(reader)?)
+}
+
+fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Arguments::parse();
+    let a = read(&args.a)?;
+    let b = read(&args.b)?;
+    for (name, records) in [("a", &a), ("b", &b)] {
+        if let Some(replay_export::Record::Header {
+            simulator_version, ..
+        }) = records.first()
+        {
+            log::info!("{name}: simulator version {simulator_version}");
+        }
+    }
+
+    let report = replay_diff::diff(&a, &b, args.tolerance, args.limit)?;
+    for divergence in report.divergences.iter() {
+        println!("{divergence}");
+    }
+    if report.truncated {
+        println!("(more divergences omitted)");
+    }
+
+    match report.first_divergent_tick {
+        Some(tick) => println!(
+            "{} of {} ticks diverged, starting at tick {}",
+            report.divergent_ticks, report.ticks_compared, tick
+        ),
+        None if report.is_identical() => {
+            println!("Replays are identical over {} ticks", report.ticks_compared)
+        }
+        None => {}
+    }
+    if !report.is_identical() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
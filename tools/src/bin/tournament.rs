@@ -5,6 +5,8 @@ use comfy_table::Table;
 use firestore::*;
 use itertools::Itertools;
 use oort_proto::{ShortcodeUpload, TournamentCompetitor, TournamentResults, TournamentSubmission};
+use oort_simulator::ship::ShipClass;
+use oort_simulator::stats::{BattleStats, MissileStats, WeaponStats};
 use oort_simulator::{scenario, simulation};
 use oort_tools::AI;
 use rand::Rng;
@@ -51,6 +53,29 @@ enum SubCommand {
         #[clap(long, default_value = "/tmp/oort-wasm-cache")]
         wasm_cache: Option<PathBuf>,
     },
+    /// Runs a tournament in a generated duel for every pair of classes.
+    Sweep {
+        shortcodes: Vec<String>,
+
+        #[clap(
+            long,
+            value_delimiter = ',',
+            default_value = "fighter,corvette,frigate,cruiser"
+        )]
+        classes: Vec<String>,
+
+        #[clap(long, default_value_t = 20000.0)]
+        separation: f64,
+
+        #[clap(short, long, default_value_t = 100)]
+        rounds: i32,
+
+        #[clap(short, long)]
+        dev: bool,
+
+        #[clap(long, default_value = "/tmp/oort-wasm-cache")]
+        wasm_cache: Option<PathBuf>,
+    },
     Fetch {
         scenario: String,
         out_dir: String,
@@ -68,6 +93,31 @@ struct Entrant {
     source_code: String,
 }
 
+// Battle stats summed over every match an AI played.
+#[derive(Debug, Clone, Default)]
+struct CompetitorStats {
+    damage_dealt: f64,
+    damage_taken: f64,
+    guns: WeaponStats,
+    missiles: MissileStats,
+}
+
+impl CompetitorStats {
+    fn add(&mut self, stats: &BattleStats, team: i32) {
+        self.damage_dealt += stats.team_damage(team, 1 - team);
+        self.damage_taken += stats.damage_taken(team);
+        let guns = stats.team_guns(team);
+        self.guns.shots += guns.shots;
+        self.guns.hits += guns.hits;
+        self.guns.damage += guns.damage;
+        self.guns.overkill += guns.overkill;
+        if let Some(missiles) = stats.missiles.get(&team) {
+            self.missiles.launched += missiles.launched;
+            self.missiles.intercepted += missiles.intercepted;
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("tournament=info"))
@@ -88,6 +138,14 @@ async fn main() -> anyhow::Result<()> {
             dev,
             wasm_cache,
         } => cmd_run_unofficial(&scenario, &shortcodes, rounds, dev, wasm_cache).await,
+        SubCommand::Sweep {
+            shortcodes,
+            classes,
+            separation,
+            rounds,
+            dev,
+            wasm_cache,
+        } => cmd_sweep(&shortcodes, &classes, separation, rounds, dev, wasm_cache).await,
         SubCommand::Fetch { scenario, out_dir } => {
             cmd_fetch(&args.project_id, &scenario, &out_dir).await
         }
@@ -127,9 +185,9 @@ async fn cmd_run(
     let ais: Vec<AI> = results.into_iter().collect::<anyhow::Result<Vec<AI>>>()?;
 
     log::info!("Running tournament");
-    let results = run_tournament(scenario_name, &ais, rounds);
+    let (results, stats) = run_tournament(scenario_name, &ais, rounds);
 
-    display_results(&results);
+    display_results(&results, &stats);
 
     if !dry_run {
         upload_results(&db, project_id, &entrants, &results).await?;
@@ -152,22 +210,65 @@ async fn cmd_run_unofficial(
         .await?;
 
     log::info!("Running tournament");
-    let results = run_tournament(scenario_name, &ais, rounds);
+    let (results, stats) = run_tournament(scenario_name, &ais, rounds);
+
+    display_results(&results, &stats);
+
+    Ok(())
+}
+
+async fn cmd_sweep(
+    shortcodes: &[String],
+    classes: &[String],
+    separation: f64,
+    rounds: i32,
+    dev: bool,
+    wasm_cache: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let classes: Vec<ShipClass> = classes.iter().map(|name| parse_class(name)).collect();
 
-    display_results(&results);
+    let http = reqwest::Client::new();
+    let ais = oort_tools::fetch_and_compile_multiple(&http, shortcodes, dev, wasm_cache.as_deref())
+        .await?;
+
+    for &class_a in classes.iter() {
+        for &class_b in classes.iter() {
+            let scenario_name = scenario::duel(class_a, class_b, separation, 0).name();
+            log::info!("Running tournament in {}", scenario_name);
+            let (results, stats) = run_tournament(&scenario_name, &ais, rounds);
+            display_results(&results, &stats);
+        }
+    }
 
     Ok(())
 }
 
-fn run_tournament(scenario_name: &str, ais: &[AI], rounds: i32) -> TournamentResults {
+fn parse_class(name: &str) -> ShipClass {
+    [
+        ShipClass::Fighter,
+        ShipClass::Corvette,
+        ShipClass::Frigate,
+        ShipClass::Cruiser,
+    ]
+    .into_iter()
+    .find(|class| class.name() == name)
+    .unwrap_or_else(|| panic!("Unknown class {name:?}"))
+}
+
+fn run_tournament(
+    scenario_name: &str,
+    ais: &[AI],
+    rounds: i32,
+) -> (TournamentResults, HashMap<String, CompetitorStats>) {
     let mut pairings: HashMap<(String, String), f64> = HashMap::new();
+    let mut stats: HashMap<String, CompetitorStats> = HashMap::new();
     let config = Glicko2Config::new();
     let mut ratings: Vec<Glicko2Rating> = Vec::new();
     ratings.resize_with(ais.len(), Default::default);
     let pairs: Vec<(i32, Vec<_>)> = (0..rounds)
         .flat_map(|round| (0..(ais.len())).permutations(2).map(move |x| (round, x)))
         .collect();
-    let outcomes: Vec<(i32, Vec<_>, Outcomes)> = pairs
+    let outcomes: Vec<(i32, Vec<_>, (Outcomes, BattleStats))> = pairs
         .par_iter()
         .map(|(round, indices)| {
             let seed = *round as u32;
@@ -181,9 +282,15 @@ fn run_tournament(scenario_name: &str, ais: &[AI], rounds: i32) -> TournamentRes
         })
         .collect();
 
-    for (round, indices, outcome) in outcomes {
+    for (round, indices, (outcome, battle_stats)) in outcomes {
         let i0 = indices[0];
         let i1 = indices[1];
+        for (team, &i) in [i0, i1].iter().enumerate() {
+            stats
+                .entry(ais[i].name.clone())
+                .or_default()
+                .add(&battle_stats, team as i32);
+        }
         log::debug!(
             "{} vs {} seed {}: {:?}",
             ais[i0].name,
@@ -233,28 +340,32 @@ fn run_tournament(scenario_name: &str, ais: &[AI], rounds: i32) -> TournamentRes
         }
     }
 
-    TournamentResults {
-        scenario_name: scenario_name.to_string(),
-        competitors,
-        win_matrix,
-    }
+    (
+        TournamentResults {
+            scenario_name: scenario_name.to_string(),
+            competitors,
+            win_matrix,
+        },
+        stats,
+    )
 }
 
-fn run_simulation(scenario_name: &str, seed: u32, ais: &[&AI]) -> Outcomes {
+fn run_simulation(scenario_name: &str, seed: u32, ais: &[&AI]) -> (Outcomes, BattleStats) {
     let codes: Vec<_> = ais.iter().map(|x| x.compiled_code.clone()).collect();
     let mut sim = simulation::Simulation::new(scenario_name, seed, &codes);
     while sim.status() == scenario::Status::Running && sim.tick() < scenario::MAX_TICKS {
         sim.step();
     }
-    match sim.status() {
+    let outcome = match sim.status() {
         scenario::Status::Victory { team: 0 } => Outcomes::WIN,
         scenario::Status::Victory { team: 1 } => Outcomes::LOSS,
         scenario::Status::Draw => Outcomes::DRAW,
         _ => unreachable!(),
-    }
+    };
+    (outcome, sim.battle_stats().clone())
 }
 
-fn display_results(results: &TournamentResults) {
+fn display_results(results: &TournamentResults, stats: &HashMap<String, CompetitorStats>) {
     let mut table = Table::new();
     table.load_preset(UTF8_FULL);
     table.set_header(vec!["Name", "Rating"]);
@@ -293,6 +404,32 @@ fn display_results(results: &TournamentResults) {
         table.add_row(row);
     }
     println!("{table}");
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        "Name",
+        "Damage dealt",
+        "Damage taken",
+        "Gun accuracy",
+        "Missiles launched",
+        "Missiles intercepted",
+    ]);
+    for competitor in &results.competitors {
+        let Some(stats) = stats.get(&competitor.username) else {
+            continue;
+        };
+        table.add_row(vec![
+            competitor.username.clone(),
+            format!("{:.0}", stats.damage_dealt),
+            format!("{:.0}", stats.damage_taken),
+            format!("{:.1}%", stats.guns.accuracy() * 100.0),
+            format!("{}", stats.missiles.launched),
+            format!("{}", stats.missiles.intercepted),
+        ]);
+    }
+    println!("{table}");
 }
 
 async fn upload_results(